@@ -4,20 +4,41 @@
 
 pub use corebc_core::types::{Address, Network};
 
+use corebc_core::types::{Signature, SignatureError, H456};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::RwLock};
 
 const CONTRACTS_JSON: &str = include_str!("./contracts/contracts.json");
 
-static ADDRESSBOOK: Lazy<HashMap<String, Contract>> =
-    Lazy::new(|| serde_json::from_str(CONTRACTS_JSON).unwrap());
+static ADDRESSBOOK: Lazy<RwLock<HashMap<String, Contract>>> =
+    Lazy::new(|| RwLock::new(serde_json::from_str(CONTRACTS_JSON).unwrap()));
+
+/// Error thrown when updating the addressbook
+#[derive(Debug, thiserror::Error)]
+pub enum AddressbookError {
+    /// The registry was not signed by the trusted signing key
+    #[error("addressbook signed by untrusted key {0:?}")]
+    UntrustedKey(H456),
+    /// The signature does not match the registry
+    #[error(transparent)]
+    Signature(#[from] SignatureError),
+    /// The registry is not a valid addressbook
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    /// The registry could not be downloaded
+    #[cfg(feature = "remote")]
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
 
 /// Wrapper around a hash map that maps a [Network] to the contract's deployed address on that
 /// network.
 #[derive(Clone, Debug, Deserialize)]
 pub struct Contract {
     addresses: HashMap<Network, Address>,
+    #[serde(default)]
+    metadata: HashMap<Network, TokenMetadata>,
 }
 
 impl Contract {
@@ -26,17 +47,73 @@ impl Contract {
     pub fn address(&self, network: Network) -> Option<Address> {
         self.addresses.get(&network).cloned()
     }
+
+    /// Returns the token metadata of the contract on the specified network. If the contract is not
+    /// a token or its metadata is not found in the addressbook, the getter returns None.
+    pub fn metadata(&self, network: Network) -> Option<TokenMetadata> {
+        self.metadata.get(&network).cloned()
+    }
+}
+
+/// Metadata of a token contract, used to display the token without querying the contract.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct TokenMetadata {
+    /// The symbol of the token, like `CTN`.
+    pub symbol: String,
+    /// The number of decimals of the token's amounts.
+    pub decimals: u8,
+    /// The URI of the token's logo.
+    #[serde(default, rename = "logoURI")]
+    pub logo_uri: Option<String>,
 }
 
 /// Fetch the addressbook for a contract by its name. If the contract name is not a part of
 /// [corebc-addressbook](https://github.com/gakonst/ethers-rs/tree/master/corebc-addressbook) we return None.
 pub fn contract<S: Into<String>>(name: S) -> Option<Contract> {
-    ADDRESSBOOK.get(&name.into()).cloned()
+    ADDRESSBOOK.read().unwrap().get(&name.into()).cloned()
+}
+
+/// Replaces the addressbook with the `contracts_json` registry, if `signature` is a valid
+/// signature of the registry by `signing_key`.
+///
+/// The registry has the same format as the bundled `contracts.json` and is signed like a message,
+/// e.g. with `Signer::sign_message`. The addressbook is left untouched if the registry is invalid.
+pub fn update_signed(
+    contracts_json: &str,
+    signature: &Signature,
+    signing_key: H456,
+) -> Result<(), AddressbookError> {
+    if signature.public_key() != signing_key {
+        return Err(AddressbookError::UntrustedKey(signature.public_key()))
+    }
+    // verifies the Ed448 signature, the network only affects the recovered address
+    signature.recover(contracts_json, &Network::Mainnet)?;
+
+    let contracts = serde_json::from_str(contracts_json)?;
+    *ADDRESSBOOK.write().unwrap() = contracts;
+    Ok(())
+}
+
+/// Downloads the registry at `url` and its hex encoded detached signature at `<url>.sig`, and
+/// replaces the addressbook with it if the registry was signed by `signing_key`.
+///
+/// See [`update_signed`].
+#[cfg(feature = "remote")]
+#[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+pub async fn sync_remote(url: &str, signing_key: H456) -> Result<(), AddressbookError> {
+    let client = reqwest::Client::new();
+    let contracts_json = client.get(url).send().await?.error_for_status()?.text().await?;
+    let signature =
+        client.get(format!("{url}.sig")).send().await?.error_for_status()?.text().await?;
+    let signature: Signature = signature.trim().parse()?;
+    update_signed(&contracts_json, &signature, signing_key)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use corebc_core::utils::hash_message;
+    use corebc_signers::LocalWallet;
 
     #[test]
     fn test_tokens() {
@@ -49,4 +126,56 @@ mod tests {
         assert!(contract("ctn").unwrap().address(Network::Mainnet).is_some());
         assert!(contract("ctn").unwrap().address(Network::Devin).is_none());
     }
+
+    #[test]
+    fn test_metadata() {
+        let metadata = contract("ctn").unwrap().metadata(Network::Mainnet).unwrap();
+        assert_eq!(metadata.symbol, "CTN");
+        assert_eq!(metadata.decimals, 18);
+        assert!(contract("ctn").unwrap().metadata(Network::Devin).is_none());
+
+        let contract: Contract = serde_json::from_str(
+            r#"{"addresses":{},"metadata":{"devin":{"symbol":"TST","decimals":6,"logoURI":"https://example.com/tst.png"}}}"#,
+        )
+        .unwrap();
+        let metadata = contract.metadata(Network::Devin).unwrap();
+        assert_eq!(metadata.logo_uri.as_deref(), Some("https://example.com/tst.png"));
+    }
+
+    #[test]
+    fn test_update_signed() {
+        let wallet: LocalWallet = "0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        let registry = r#"{
+            "ctn": {
+                "addresses": { "mainnet": "0xcb19c7acc4c292d2943ba23c2eaa5d9c5a6652a8710c" },
+                "metadata": { "mainnet": { "symbol": "CTN", "decimals": 18 } }
+            },
+            "signed": { "addresses": { "devin": "0xab654efcf28707488885abbe9d1fc80cbe6d6036f250" } }
+        }"#;
+        let signature = wallet.sign_hash(hash_message(registry)).unwrap();
+        let signing_key = signature.public_key();
+
+        // tampered registries and untrusted keys are rejected
+        let tampered = registry.replace("devin", "mainnet");
+        assert!(update_signed(&tampered, &signature, signing_key).is_err());
+        assert!(matches!(
+            update_signed(registry, &signature, H456::zero()),
+            Err(AddressbookError::UntrustedKey(_))
+        ));
+        assert!(contract("signed").is_none());
+
+        update_signed(registry, &signature, signing_key).unwrap();
+        assert!(contract("signed").unwrap().address(Network::Devin).is_some());
+        assert!(contract("ctn").unwrap().address(Network::Mainnet).is_some());
+
+        update_signed(
+            CONTRACTS_JSON,
+            &wallet.sign_hash(hash_message(CONTRACTS_JSON)).unwrap(),
+            signing_key,
+        )
+        .unwrap();
+        assert!(contract("signed").is_none());
+    }
 }
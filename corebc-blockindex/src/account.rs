@@ -1,5 +1,6 @@
 use crate::{BlockindexError, Client, Result};
 use corebc_core::abi::Address;
+use futures_util::{future::try_join_all, stream, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
@@ -40,23 +41,81 @@ pub struct BalanceHistory {
 }
 
 /// The list sorting preference
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sort {
+    /// Oldest transactions first
+    Asc,
+    /// Newest transactions first, the order of the API
+    #[default]
+    Desc,
+}
+
 /// Common optional arguments for the transaction or event list API endpoints
 #[derive(Clone, Copy, Debug)]
 pub struct TxListParams {
     pub page: u64,
     pub page_size: u64,
+    /// The first block to include
     pub from: u64,
+    /// The last block to include
     pub to: u64,
+    /// The order of the transactions, see [`TxListParams::sort`]
+    sort: Sort,
 }
 
 impl TxListParams {
     pub fn new(page: u64, page_size: u64, from: u64, to: u64) -> Self {
-        Self { page, page_size, from, to }
+        Self { page, page_size, from, to, sort: Sort::Desc }
+    }
+
+    /// Sets the page to return, starting at 1
+    pub fn page(mut self, page: u64) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets the number of transactions per page
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sets the range of blocks to include
+    pub fn blocks(mut self, from: u64, to: u64) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// Sets the order of the transactions.
+    ///
+    /// The API lists the newest transactions first, so [`Sort::Asc`] numbers the pages from the
+    /// end: page 1 holds the oldest transactions and is the only page that may be partial.
+    pub fn sort(mut self, sort: Sort) -> Self {
+        self.sort = sort;
+        self
     }
 }
 impl Default for TxListParams {
     fn default() -> Self {
-        Self { from: 0, to: 99999999, page: 1, page_size: 1000 }
+        Self { from: 0, to: 99999999, page: 1, page_size: 1000, sort: Sort::Desc }
+    }
+}
+
+/// A page of the transactions of an address
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxPage {
+    /// The number of the page, starting at 1
+    pub page: u64,
+    pub total_pages: u64,
+    pub txids: Vec<String>,
+}
+
+impl TxPage {
+    /// Whether there are pages after this one
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
     }
 }
 
@@ -126,7 +185,7 @@ impl Client {
             self.create_query("address", addr_str.as_ref(), HashMap::from([("details", "basic")]));
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
         Ok(AccountBalance {
             account: *address,
@@ -134,6 +193,25 @@ impl Client {
         })
     }
 
+    /// Returns the Core balances of the given addresses, in the same order.
+    ///
+    /// The balances are requested concurrently, fails if any request fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let addresses = [
+    ///     "ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse()?,
+    ///     "ab57dde1a47041fc3c570c0318a713128ced55fd2ada".parse()?,
+    /// ];
+    /// let balances = client.get_balances(&addresses).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn get_balances(&self, addresses: &[Address]) -> Result<Vec<AccountBalance>> {
+        try_join_all(addresses.iter().map(|address| self.get_balance(address))).await
+    }
+
     /// Returns the list of transactions performed by an address, with optional pagination.
     ///
     /// # Examples
@@ -149,19 +227,120 @@ impl Client {
         address: &Address,
         params: Option<TxListParams>,
     ) -> Result<Vec<String>> {
+        Ok(self.get_transactions_page(address, params.unwrap_or_default()).await?.txids)
+    }
+
+    /// Returns a page of the transactions performed by an address, along with the total number
+    /// of pages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use corebc_blockindex::account::{Sort, TxListParams};
+    ///
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let address = "ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse()?;
+    /// let params = TxListParams::default().page_size(100).sort(Sort::Asc);
+    /// let first = client.get_transactions_page(&address, params).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn get_transactions_page(
+        &self,
+        address: &Address,
+        params: TxListParams,
+    ) -> Result<TxPage> {
+        self.get_transactions_page_with_total(address, params, None).await
+    }
+
+    /// Returns a page of the transactions of an address, `total_pages` is the number of pages
+    /// reported by an earlier request with the same params, which saves [`Sort::Asc`] requests
+    /// from looking it up again.
+    async fn get_transactions_page_with_total(
+        &self,
+        address: &Address,
+        params: TxListParams,
+        total_pages: Option<u64>,
+    ) -> Result<TxPage> {
+        if params.sort == Sort::Desc {
+            return self.get_transactions_page_desc(address, params).await
+        }
+
+        // the API only lists the newest transactions first, so read the pages from the end
+        let (total_pages, last) = match total_pages {
+            Some(total_pages) => (total_pages, None),
+            None => {
+                let last = self.get_transactions_page_desc(address, params.page(1)).await?;
+                (last.total_pages, Some(last))
+            }
+        };
+        if params.page > total_pages {
+            return Ok(TxPage { page: params.page, total_pages, txids: Vec::new() })
+        }
+        let desc_page = total_pages + 1 - params.page;
+        let mut page = match last {
+            Some(last) if desc_page == 1 => last,
+            _ => self.get_transactions_page_desc(address, params.page(desc_page)).await?,
+        };
+        page.total_pages = total_pages;
+        page.txids.reverse();
+        page.page = params.page;
+        Ok(page)
+    }
+
+    async fn get_transactions_page_desc(
+        &self,
+        address: &Address,
+        params: TxListParams,
+    ) -> Result<TxPage> {
         let addr_str = format!("{address:?}");
-        let tx_params: HashMap<&str, u64> = params.unwrap_or_default().into();
+        let tx_params: HashMap<&str, u64> = params.into();
         let query = self.create_query("address", addr_str.as_ref(), tx_params);
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
-        Ok(response["txids"]
-            .as_array()
-            .ok_or_else(|| BlockindexError::Builder("txids".to_string()))?
-            .iter()
-            .map(|x| x.to_string().replace('\"', ""))
-            .collect())
+        let total_pages = response["totalPages"].as_u64().unwrap_or_default();
+        let txids = match response["txids"].as_array() {
+            Some(txids) => txids.iter().map(|x| x.to_string().replace('\"', "")).collect(),
+            // the API omits the list if the page is empty
+            None if response["txs"].as_u64() == Some(0) || params.page > total_pages => Vec::new(),
+            None => return Err(BlockindexError::Builder("txids".to_string())),
+        };
+        Ok(TxPage { page: response["page"].as_u64().unwrap_or(params.page), total_pages, txids })
+    }
+
+    /// Returns a stream of all transactions performed by an address, starting at the page of
+    /// `params` and requesting the following pages as the stream is polled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use corebc_blockindex::account::{Sort, TxListParams};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let address = "ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse()?;
+    /// let params = TxListParams::default().blocks(1_000, 2_000).sort(Sort::Asc);
+    /// let txids: Vec<String> = client.transactions_stream(&address, params).try_collect().await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn transactions_stream<'a>(
+        &'a self,
+        address: &'a Address,
+        params: TxListParams,
+    ) -> impl Stream<Item = Result<String>> + 'a {
+        // the next params along with the number of pages reported by the previous page
+        stream::try_unfold(Some((params, None)), move |cursor| async move {
+            let (params, total_pages) = match cursor {
+                Some(cursor) => cursor,
+                None => return Result::Ok(None),
+            };
+            let page = self.get_transactions_page_with_total(address, params, total_pages).await?;
+            let next =
+                page.has_next().then(|| (params.page(page.page + 1), Some(page.total_pages)));
+            Ok(Some((stream::iter(page.txids.into_iter().map(Result::Ok)), next)))
+        })
+        .try_flatten()
     }
 
     /// Returns the list of tokens of an address.
@@ -186,7 +365,7 @@ impl Client {
         let query = self.create_query("address", addr_str.as_ref(), tx_params);
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
         response["tokens"]
             .as_array()
@@ -220,7 +399,7 @@ impl Client {
         let query = self.create_query("balancehistory", addr_str.as_ref(), tx_params);
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
         response
             .as_array()
@@ -1,7 +1,11 @@
-use crate::{BlockindexError, Client, Result, H256};
+use crate::{Address, BlockindexError, Client, Result, H256};
+use futures_util::{future, stream, Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeInclusive};
+
+/// The number of blocks that are requested concurrently when listing blocks
+const CONCURRENT_BLOCK_REQUESTS: usize = 8;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +22,84 @@ pub struct Block {
     pub tx_count: u64,
 }
 
+/// A summary of a block, see [`Client::blocks`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSummary {
+    pub number: u64,
+    pub hash: H256,
+    pub time: u64,
+    pub tx_count: u64,
+    /// The energy used by all transactions of the block, if reported by the index
+    pub energy_used: Option<u64>,
+    /// The energy limit of the block, if reported by the index
+    pub energy_limit: Option<u64>,
+    /// The miner of the block, if reported by the index
+    pub miner: Option<Address>,
+}
+
+impl BlockSummary {
+    /// Reads the summary from a block response
+    fn from_response(response: &Value) -> Result<Self> {
+        // chain specific fields are either reported at the top level or in `corecoinSpecific`
+        let field = |name: &str| {
+            Some(&response[name])
+                .filter(|value| !value.is_null())
+                .unwrap_or(&response["corecoinSpecific"][name])
+        };
+        let number = |name: &str| {
+            let value = field(name);
+            value.as_u64().or_else(|| value.as_str()?.parse().ok())
+        };
+        let required =
+            |name: &str| number(name).ok_or_else(|| BlockindexError::Builder(name.to_string()));
+
+        Ok(Self {
+            number: required("height")?,
+            hash: serde_json::from_value(field("hash").clone())?,
+            time: required("time")?,
+            tx_count: required("txCount")?,
+            energy_used: number("energyUsed"),
+            energy_limit: number("energyLimit"),
+            miner: [field("miner"), field("coinbase")]
+                .into_iter()
+                .find_map(|value| value.as_str()?.parse().ok()),
+        })
+    }
+}
+
+/// The page of a block listing, see [`Client::blocks`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    /// The page, starting at 1
+    pub page: u64,
+    pub page_size: u64,
+}
+
+impl Pagination {
+    pub fn new(page: u64, page_size: u64) -> Self {
+        Self { page, page_size }
+    }
+
+    /// Returns the part of `range` that is on this page, `None` if the page is past the end of
+    /// the range
+    pub fn slice(&self, range: &RangeInclusive<u64>) -> Option<RangeInclusive<u64>> {
+        let offset = self.page.saturating_sub(1).checked_mul(self.page_size)?;
+        let start = range.start().checked_add(offset)?;
+        if start > *range.end() || self.page_size == 0 {
+            return None
+        }
+        let end = start.saturating_add(self.page_size - 1).min(*range.end());
+        Some(start..=end)
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self { page: 1, page_size: 50 }
+    }
+}
+
 /// Options for querying blocks
 #[derive(Clone, Debug)]
 pub enum BlockQueryOption {
@@ -44,12 +126,152 @@ impl Client {
             BlockQueryOption::ByNumber(number) => format!("{number}"),
             BlockQueryOption::ByHash(block_hash) => block_hash,
         };
+        if let Some(block) = self.cache().and_then(|cache| cache.get_block(&query_param)) {
+            return Ok(block)
+        }
         let query =
             self.create_query("block", query_param.as_str(), HashMap::from([("details", "basic")]));
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
-        Ok(serde_json::from_value(response)?)
+        let block = serde_json::from_value(response)?;
+        if let Some(cache) = self.cache() {
+            cache.set_block(&query_param, &block);
+        }
+        Ok(block)
+    }
+
+    /// Returns the summaries of the blocks in `range` that are on the given page, in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use corebc_blockindex::block::Pagination;
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// // the second 10 blocks of the range
+    /// let blocks = client.blocks(4483900..=4483999, Some(Pagination::new(2, 10))).await?;
+    /// let energy_used: u64 = blocks.iter().filter_map(|block| block.energy_used).sum();
+    /// # Ok(()) }
+    /// ```
+    pub async fn blocks(
+        &self,
+        range: RangeInclusive<u64>,
+        pagination: Option<Pagination>,
+    ) -> Result<Vec<BlockSummary>> {
+        let page = match pagination.unwrap_or_default().slice(&range) {
+            Some(page) => page,
+            None => return Ok(Vec::new()),
+        };
+        self.block_summaries(page).try_collect().await
+    }
+
+    /// Returns the summaries of the blocks in `range` that were mined by `address`, on the given
+    /// page of the matching blocks.
+    ///
+    /// The blocks of `range` are fetched in ascending order until the page is full, so later
+    /// pages and sparse validators take more requests. Blocks whose miner is not reported by the
+    /// index are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let validator = "ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse()?;
+    /// let blocks = client.blocks_validated_by(&validator, 4483900..=4483999, None).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn blocks_validated_by(
+        &self,
+        address: &Address,
+        range: RangeInclusive<u64>,
+        pagination: Option<Pagination>,
+    ) -> Result<Vec<BlockSummary>> {
+        let Pagination { page, page_size } = pagination.unwrap_or_default();
+        let mut skip = page.saturating_sub(1).saturating_mul(page_size);
+        let summaries = self
+            .block_summaries(range)
+            .try_filter(|block| future::ready(block.miner.as_ref() == Some(address)));
+        futures_util::pin_mut!(summaries);
+
+        let mut blocks = Vec::new();
+        while (blocks.len() as u64) < page_size {
+            match summaries.try_next().await? {
+                Some(_) if skip > 0 => skip -= 1,
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Fetches the summaries of the blocks in `range` in order, with a bounded number of
+    /// requests in flight
+    fn block_summaries(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> impl Stream<Item = Result<BlockSummary>> + '_ {
+        stream::iter(range)
+            .map(move |number| self.get_block_summary(number))
+            .buffered(CONCURRENT_BLOCK_REQUESTS)
+    }
+
+    async fn get_block_summary(&self, number: u64) -> Result<BlockSummary> {
+        let number = number.to_string();
+        let query = self.create_query("block", &number, HashMap::from([("details", "basic")]));
+        let response: Value = self.get_json(&query).await?;
+        if response["error"].as_str().is_some() {
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
+        }
+        BlockSummary::from_response(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginates_ranges() {
+        let range = 100..=124;
+        assert_eq!(Pagination::new(1, 10).slice(&range), Some(100..=109));
+        assert_eq!(Pagination::new(3, 10).slice(&range), Some(120..=124));
+        assert_eq!(Pagination::new(4, 10).slice(&range), None);
+        assert_eq!(Pagination::new(1, 0).slice(&range), None);
+    }
+
+    #[test]
+    fn reads_block_summaries() {
+        let response = serde_json::json!({
+            "hash": "0xa9b9902f750ebde2179c4cec87c53a50eaf3ce6e8834570b8935e9e063e88303",
+            "height": 289632,
+            "time": 1680000000,
+            "txCount": 2,
+            "corecoinSpecific": {
+                "energyUsed": "42000",
+                "energyLimit": 8000000,
+                "miner": "ab654efcf28707488885abbe9d1fc80cbe6d6036f250"
+            }
+        });
+        let block = BlockSummary::from_response(&response).unwrap();
+        assert_eq!(block.number, 289632);
+        assert_eq!(block.tx_count, 2);
+        assert_eq!(block.energy_used, Some(42000));
+        assert_eq!(block.energy_limit, Some(8000000));
+        assert_eq!(
+            block.miner,
+            Some("ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse().unwrap())
+        );
+
+        let block = BlockSummary::from_response(&serde_json::json!({
+            "hash": "0xa9b9902f750ebde2179c4cec87c53a50eaf3ce6e8834570b8935e9e063e88303",
+            "height": 1,
+            "time": 1,
+            "txCount": 0
+        }))
+        .unwrap();
+        assert_eq!(block.energy_used, None);
+        assert_eq!(block.miner, None);
     }
 }
@@ -9,7 +9,7 @@ use corebc_core::{
 };
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 
 #[cfg(feature = "corebc-ylem")]
 use corebc_ylem::{artifacts::Settings, CvmVersion, Project, ProjectBuilder, YlemConfig};
@@ -66,6 +66,19 @@ impl SourceCodeMetadata {
         }
     }
 
+    /// Returns the remappings of the compiler settings, like `@openzeppelin/=lib/openzeppelin/`.
+    pub fn remappings(&self) -> Vec<String> {
+        match self {
+            Self::Metadata { settings: Some(settings), .. } => settings["remappings"]
+                .as_array()
+                .map(|remappings| {
+                    remappings.iter().filter_map(|r| r.as_str().map(str::to_string)).collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
     #[cfg(feature = "corebc-ylem")]
     pub fn settings(&self) -> Result<Option<Settings>> {
         match self {
@@ -186,16 +199,19 @@ impl Metadata {
         }
     }
 
-    /// Maps this contract's sources to a [SourceTreeEntry] vector.
+    /// Maps this contract's sources to a [SourceTreeEntry] vector, sorted by path.
     pub fn source_entries(&self) -> Vec<SourceTreeEntry> {
         let root = Path::new(&self.contract_name);
-        self.sources()
+        let mut entries: Vec<_> = self
+            .sources()
             .into_iter()
             .map(|(path, entry)| {
                 let path = root.join(path);
                 SourceTreeEntry { path, contents: entry.content }
             })
-            .collect()
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
     }
 
     /// Returns the source tree of this contract's sources.
@@ -203,6 +219,30 @@ impl Metadata {
         SourceTree { entries: self.source_entries() }
     }
 
+    /// Writes this contract's sources into `dir`, like [SourceTree::write_to].
+    ///
+    /// The sources are written to `dir/<contract name>/`. If the contract was verified with
+    /// remappings, they are rewritten to point into that directory and written to
+    /// `dir/remappings.txt`, so the sources can be compiled as a project rooted at `dir`.
+    pub fn write_project(&self, dir: &Path) -> Result<()> {
+        self.source_tree().write_to(dir)?;
+
+        let remappings: Vec<_> = self
+            .source_code
+            .remappings()
+            .into_iter()
+            .filter_map(|remapping| {
+                let (name, path) = remapping.split_once('=')?;
+                let path = path.trim_start_matches("./");
+                Some(format!("{name}={}/{path}", self.contract_name))
+            })
+            .collect();
+        if !remappings.is_empty() {
+            fs::write(dir.join("remappings.txt"), remappings.join("\n") + "\n")?;
+        }
+        Ok(())
+    }
+
     /// Returns the contract's compiler settings.
     #[cfg(feature = "corebc-ylem")]
     pub fn settings(&self) -> Result<Settings> {
@@ -313,3 +353,53 @@ impl Client {
         Ok(ContractMetadata { items: vec![] })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_multi_file_project() {
+        let source_code = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "src/Token.sol": { "content": "import \"@openzeppelin/ERC20.sol\";" },
+                "lib/openzeppelin/ERC20.sol": { "content": "contract ERC20 {}" }
+            },
+            "settings": { "remappings": ["@openzeppelin/=lib/openzeppelin/"] }
+        });
+        let metadata: Metadata = serde_json::from_value(serde_json::json!({
+            "SourceCode": format!("{{{source_code}}}"),
+            "ABI": "[]",
+            "ContractName": "Token",
+            "CompilerVersion": "v1.0.0",
+            "OptimizationUsed": "0",
+            "Runs": "200",
+            "ConstructorArguments": "0x",
+            "YVMVersion": "Default",
+            "Library": "",
+            "LicenseType": "",
+            "Proxy": "0",
+            "SwarmSource": ""
+        }))
+        .unwrap();
+
+        let paths: Vec<_> = metadata.source_entries().into_iter().map(|e| e.path).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("Token/lib/openzeppelin/ERC20.sol"), Path::new("Token/src/Token.sol")]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        metadata.write_project(dir.path()).unwrap();
+        assert!(dir.path().join("Token/src/Token.sol").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Token/lib/openzeppelin/ERC20.sol")).unwrap(),
+            "contract ERC20 {}"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("remappings.txt")).unwrap(),
+            "@openzeppelin/=Token/lib/openzeppelin/\n"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use corebc_core::types::{Address, Network};
-use std::env::VarError;
+use std::{env::VarError, time::Duration};
 
 #[derive(Debug, thiserror::Error)]
 pub enum BlockindexError {
@@ -43,6 +43,38 @@ pub enum BlockindexError {
     CloudFlareSecurityChallenge,
     #[error("Received `Page not found` response. API server is likely down")]
     PageNotFound,
+    #[error("Blockindex is under maintenance, try again later")]
+    Maintenance,
+    #[error("Rate limited by Blockindex{}", .retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+}
+
+impl BlockindexError {
+    /// Returns true if the request may succeed when retried later, e.g. because the explorer was
+    /// rate limiting or under maintenance
+    pub fn is_transient(&self) -> bool {
+        match self {
+            BlockindexError::RateLimitExceeded |
+            BlockindexError::RateLimited { .. } |
+            BlockindexError::Maintenance |
+            BlockindexError::PageNotFound |
+            BlockindexError::CloudFlareSecurityChallenge => true,
+            BlockindexError::Reqwest(err) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Converts the `error` message of a JSON response into the matching variant
+    pub(crate) fn from_response_error(error: String) -> Self {
+        let lower = error.to_lowercase();
+        if lower.contains("rate limit") || lower.contains("too many requests") {
+            BlockindexError::RateLimited { retry_after: None }
+        } else if is_maintenance_response(&lower) {
+            BlockindexError::Maintenance
+        } else {
+            BlockindexError::ErrorResponse { error }
+        }
+    }
 }
 
 /// etherscan/polyscan is protected by cloudflare, which can lead to html responses like `Sorry, you have been blocked` See also <https://community.cloudflare.com/t/sorry-you-have-been-blocked/110790>
@@ -59,6 +91,21 @@ pub(crate) fn is_cloudflare_security_challenge(txt: &str) -> bool {
         txt.to_lowercase().contains("checking if the site connection is secure")
 }
 
+/// The explorer answers with an html page or an error message while it is being maintained
+///
+/// This returns true if the `txt` is a maintenance response
+pub(crate) fn is_maintenance_response(txt: &str) -> bool {
+    let txt = txt.to_lowercase();
+    txt.contains("under maintenance") ||
+        txt.contains("down for maintenance") ||
+        txt.contains("maintenance mode")
+}
+
+/// Parses the delay in seconds of a `Retry-After` header
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +122,32 @@ mod tests {
 
         assert!(is_blocked_by_cloudflare_response(resp));
     }
+
+    #[test]
+    fn test_maintenance_response() {
+        let resp = "<!DOCTYPE html><html><head><title>Blockindex</title></head><body><h1>We are currently down for maintenance</h1><p>Please check back soon.</p></body></html>";
+        assert!(is_maintenance_response(resp));
+        assert!(!is_maintenance_response("Page not found"));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_response_error_kinds() {
+        let err = BlockindexError::from_response_error("Too many requests, slow down".to_string());
+        assert!(matches!(err, BlockindexError::RateLimited { retry_after: None }));
+        assert!(err.is_transient());
+
+        let err = BlockindexError::from_response_error("Backend under maintenance".to_string());
+        assert!(matches!(err, BlockindexError::Maintenance));
+        assert!(err.is_transient());
+
+        let err = BlockindexError::from_response_error("Invalid address".to_string());
+        assert!(matches!(err, BlockindexError::ErrorResponse { .. }));
+        assert!(!err.is_transient());
+    }
 }
@@ -2,15 +2,25 @@
 #![deny(unsafe_code, rustdoc::broken_intra_doc_links)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use crate::errors::{is_blocked_by_cloudflare_response, is_cloudflare_security_challenge};
+use crate::errors::{
+    is_blocked_by_cloudflare_response, is_cloudflare_security_challenge, is_maintenance_response,
+    parse_retry_after,
+};
 use corebc_core::{
     abi::Address,
     types::{Network, H256},
 };
 use errors::BlockindexError;
-use reqwest::{header, IntoUrl, Url};
+use reqwest::{header, IntoUrl, StatusCode, Url};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{borrow::Cow, fmt::Debug};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use tracing::{error, trace};
 pub mod account;
 pub mod block;
@@ -32,6 +42,8 @@ pub struct Client {
     blockindex_api_url: Url,
     /// Blockindex base endpoint like <https://blockindex.net/>
     blockindex_url: Url,
+    /// Optional cache for block responses
+    cache: Option<Cache>,
 }
 
 impl Client {
@@ -63,6 +75,11 @@ impl Client {
         &self.blockindex_url
     }
 
+    /// Returns the cache of the client, if configured
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
     /// Return the URL for the given block number
     pub fn block_url(&self, block: u64) -> String {
         format!("{}block/{block}", self.blockindex_url)
@@ -94,22 +111,56 @@ impl Client {
 
     /// Execute a GET request with parameters, without sanity checking the response.
     async fn get<'a, T: Serialize>(&self, query: &Query<'a, T>) -> Result<String> {
-        trace!(target: "blockindex", "GET {}", self.blockindex_api_url);
+        let url =
+            String::from(self.blockindex_api_url.as_str()) + query.module() + "/" + query.target();
+        self.get_url(url, query.other()).await
+    }
+
+    /// Execute a GET request to `url`, only checking the status of the response.
+    async fn get_url(&self, url: impl IntoUrl, query: &impl Serialize) -> Result<String> {
+        let url = url.into_url()?;
+        trace!(target: "blockindex", "GET {}", url);
         let response = self
             .client
-            .get(
-                String::from(self.blockindex_api_url.as_str()) +
-                    query.module() +
-                    "/" +
-                    query.target(),
-            )
+            .get(url)
             .header(header::ACCEPT, "application/json")
-            .query(query.other())
+            .query(query)
             .send()
-            .await?
-            .text()
             .await?;
-        Ok(response)
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+            return Err(BlockindexError::RateLimited { retry_after })
+        }
+        Ok(response.text().await?)
+    }
+
+    /// Checks that the Blockindex API is reachable and answering requests, returning the round
+    /// trip time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// match client.ping().await {
+    ///     Ok(latency) => println!("blockindex answered in {latency:?}"),
+    ///     Err(err) if err.is_transient() => println!("blockindex is unavailable: {err}"),
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn ping(&self) -> Result<Duration> {
+        let start = Instant::now();
+        let res =
+            self.get_url(self.blockindex_api_url.clone(), &HashMap::<&str, &str>::new()).await?;
+        let res: serde_json::Value = self.sanitize_response(res)?;
+        if let Some(error) = res["error"].as_str() {
+            return Err(BlockindexError::from_response_error(error.to_string()))
+        }
+        Ok(start.elapsed())
     }
 
     /// Perform sanity checks on a response and deserialize it into a [Result].
@@ -123,13 +174,15 @@ impl Client {
                 BlockindexError::BlockedByCloudflare
             } else if is_cloudflare_security_challenge(res) {
                 BlockindexError::CloudFlareSecurityChallenge
+            } else if is_maintenance_response(res) {
+                BlockindexError::Maintenance
             } else {
                 BlockindexError::Serde(err)
             }
         })?;
 
         match res {
-            ResponseData::Error { error } => Err(BlockindexError::ErrorResponse { error }),
+            ResponseData::Error { error } => Err(BlockindexError::from_response_error(error)),
             ResponseData::Success(res) => Ok(res),
         }
     }
@@ -152,6 +205,12 @@ pub struct ClientBuilder {
     blockindex_api_url: Option<Url>,
     /// Blockindex base endpoint like <https://blockindex.net/>
     blockindex_url: Option<Url>,
+    /// The network the client is configured for, used to separate cached blocks
+    network: Option<Network>,
+    /// Directory of the block cache
+    cache_root: Option<PathBuf>,
+    /// How long blocks are cached
+    cache_ttl: Option<Duration>,
 }
 
 // === impl ClientBuilder ===
@@ -159,6 +218,9 @@ pub struct ClientBuilder {
 impl ClientBuilder {
     /// Configures the blockindex url and api url for the given network
     ///
+    /// The urls can be overridden with environment variables, see
+    /// [`Network::blockindex_urls_from_env`].
+    ///
     /// # Errors
     ///
     /// Fails if the network is not supported by blockindex
@@ -170,10 +232,12 @@ impl ClientBuilder {
             (api.into_url(), url.into_url())
         }
         let (blockindex_api_url, blockindex_url) = network
-            .blockindex_urls()
+            .blockindex_urls_from_env()
             .map(|(api, base)| urls(api, base))
             .ok_or_else(|| BlockindexError::NetworkNotSupported(network))?;
-        self.with_api_url(blockindex_api_url?)?.with_url(blockindex_url?)
+        let mut builder = self.with_api_url(blockindex_api_url?)?.with_url(blockindex_url?)?;
+        builder.network = Some(network);
+        Ok(builder)
     }
 
     /// Configures the blockindex url
@@ -202,6 +266,16 @@ impl ClientBuilder {
         Ok(self)
     }
 
+    /// Caches the blocks returned by [`Client::get_block`] in the `root` directory for `ttl`.
+    ///
+    /// Blocks are stored per network, or per API host if the client is not configured with a
+    /// network. Other responses, e.g. contract ABIs and source code, are not cached.
+    pub fn with_cache(mut self, root: impl Into<PathBuf>, ttl: Duration) -> Self {
+        self.cache_root = Some(root.into());
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
     /// Returns a Client that uses this ClientBuilder configuration.
     ///
     /// # Errors
@@ -210,19 +284,110 @@ impl ClientBuilder {
     ///   - `blockindex_api_url`
     ///   - `blockindex_url`
     pub fn build(self) -> Result<Client> {
-        let ClientBuilder { client, blockindex_api_url, blockindex_url } = self;
+        let ClientBuilder {
+            client,
+            blockindex_api_url,
+            blockindex_url,
+            network,
+            cache_root,
+            cache_ttl,
+        } = self;
+
+        let blockindex_api_url = blockindex_api_url
+            .ok_or_else(|| BlockindexError::Builder("blockindex api url".to_string()))?;
+        let cache = cache_root.map(|root| {
+            let dir = match network {
+                Some(network) => network.to_string(),
+                None => blockindex_api_url.host_str().unwrap_or("default").to_string(),
+            };
+            Cache::new(root.join(dir), cache_ttl.unwrap_or_default())
+        });
 
         let client = Client {
             client: client.unwrap_or_default(),
-            blockindex_api_url: blockindex_api_url
-                .ok_or_else(|| BlockindexError::Builder("blockindex api url".to_string()))?,
+            blockindex_api_url,
             blockindex_url: blockindex_url
                 .ok_or_else(|| BlockindexError::Builder("blockindex url".to_string()))?,
+            cache,
         };
         Ok(client)
     }
 }
 
+/// A cache for the blocks returned by the API, stored as JSON files in a directory, see
+/// [`ClientBuilder::with_cache`]
+#[derive(Clone, Debug)]
+pub struct Cache {
+    root: PathBuf,
+    ttl: Duration,
+}
+
+/// A cached response
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CacheEnvelope<T> {
+    /// The unix timestamp the response expires at
+    expiry: u64,
+    data: T,
+}
+
+impl Cache {
+    /// Creates a cache in the `root` directory that keeps blocks for `ttl`
+    pub fn new(root: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self { root: root.into(), ttl }
+    }
+
+    /// The directory of the cache
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// How long blocks are cached
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached block, `key` is its number or hash
+    pub fn get_block(&self, key: &str) -> Option<block::Block> {
+        self.get("blocks", key)
+    }
+
+    /// Caches the block for the ttl of the cache, since its confirmations change
+    pub fn set_block(&self, key: &str, block: &block::Block) {
+        self.set("blocks", key, block)
+    }
+
+    fn path(&self, prefix: &str, key: &str) -> PathBuf {
+        self.root.join(prefix).join(format!("{key}.json"))
+    }
+
+    fn get<T: DeserializeOwned>(&self, prefix: &str, key: &str) -> Option<T> {
+        let content = fs::read_to_string(self.path(prefix, key)).ok()?;
+        let envelope: CacheEnvelope<T> = serde_json::from_str(&content).ok()?;
+        (envelope.expiry > now()).then_some(envelope.data)
+    }
+
+    /// Failing to write to the cache is not an error, the response is requested again next time
+    fn set<T: Serialize>(&self, prefix: &str, key: &str, data: T) {
+        let path = self.path(prefix, key);
+        let envelope = CacheEnvelope { expiry: now() + self.ttl.as_secs(), data };
+        if let Err(err) = write_json(&path, &envelope) {
+            error!(target: "blockindex", ?path, "Failed to write to cache: {}", err);
+        }
+    }
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec(value)?)
+}
+
+/// The current unix timestamp in seconds
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum ResponseData<T> {
@@ -272,8 +437,9 @@ fn into_url(url: impl IntoUrl) -> std::result::Result<Url, reqwest::Error> {
 
 #[cfg(test)]
 mod tests {
-    use crate::Client;
+    use crate::{block::Block, errors::BlockindexError, Cache, Client};
     use corebc_core::types::{Address, Network, H256};
+    use std::time::Duration;
 
     #[test]
     fn test_api_paths() {
@@ -283,6 +449,30 @@ mod tests {
         assert_eq!(client.block_url(100), "https://devin.blockindex.net/block/100");
     }
 
+    #[test]
+    fn detects_maintenance_responses() {
+        let client = Client::new(Network::Devin).unwrap();
+        let res = "<html><body><h1>Blockindex is under maintenance</h1></body></html>";
+        let err = client.sanitize_response::<serde_json::Value>(res).unwrap_err();
+        assert!(matches!(err, BlockindexError::Maintenance));
+
+        let res = r#"{"error":"Service under maintenance"}"#;
+        let err = client.sanitize_response::<Block>(res).unwrap_err();
+        assert!(matches!(err, BlockindexError::Maintenance));
+    }
+
+    #[test]
+    fn uses_env_urls() {
+        std::env::set_var(
+            "BLOCKINDEX_API_URL_PRIVATE_2152",
+            "https://staging.blockindex.net/api/v2",
+        );
+        std::env::set_var("BLOCKINDEX_URL_PRIVATE_2152", "https://staging.blockindex.net");
+        let client = Client::new(Network::Private(2152)).unwrap();
+        assert_eq!(client.blockindex_api_url().as_str(), "https://staging.blockindex.net/api/v2/");
+        assert_eq!(client.blockindex_url().as_str(), "https://staging.blockindex.net/");
+    }
+
     #[test]
     fn stringifies_block_url() {
         let blockindex = Client::new(Network::Mainnet).unwrap();
@@ -314,4 +504,37 @@ mod tests {
         let token_url: String = blockindex.token_url(token_hash);
         assert_eq!(token_url, format!("https://blockindex.net/address/{token_hash:?}"));
     }
+
+    #[test]
+    fn caches_responses_per_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = Client::builder()
+            .network(Network::Devin)
+            .unwrap()
+            .with_cache(dir.path(), Duration::ZERO)
+            .build()
+            .unwrap();
+        let cache = client.cache().unwrap();
+        assert_eq!(cache.root(), dir.path().join("devin"));
+
+        // blocks expire after the ttl
+        let block = Block {
+            hash: H256::zero(),
+            previous_block_hash: H256::zero(),
+            next_block_hash: H256::zero(),
+            height: 1,
+            confirmations: 1,
+            size: 0,
+            time: 0,
+            nonce: "0x0".to_string(),
+            difficulty: "1".to_string(),
+            tx_count: 0,
+        };
+        cache.set_block("1", &block);
+        assert!(dir.path().join("devin/blocks/1.json").exists());
+        assert!(cache.get_block("1").is_none());
+        let cache = Cache::new(cache.root(), Duration::from_secs(60));
+        cache.set_block("1", &block);
+        assert_eq!(cache.get_block("1").unwrap().height, 1);
+    }
 }
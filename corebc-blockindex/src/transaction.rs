@@ -1,5 +1,5 @@
 use crate::{BlockindexError, Client, Result};
-use corebc_core::types::H256;
+use corebc_core::types::{Address, Bytes, H256, U256};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -32,6 +32,129 @@ pub struct Transaction {
     pub data: String,
 }
 
+/// The execution status of a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxStatus {
+    /// The transaction is not mined yet
+    Pending,
+    /// The transaction was mined and executed successfully
+    Success,
+    /// The transaction was mined but failed
+    Failed,
+}
+
+impl TxStatus {
+    fn from_code(code: i64) -> Self {
+        match code {
+            1 => TxStatus::Success,
+            0 => TxStatus::Failed,
+            _ => TxStatus::Pending,
+        }
+    }
+}
+
+/// A transaction with typed fields, see [`Client::transaction`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetails {
+    pub hash: H256,
+    /// `None` while the transaction is pending
+    pub block_hash: Option<H256>,
+    /// `None` while the transaction is pending
+    pub block_number: Option<u64>,
+    pub confirmations: u64,
+    pub status: TxStatus,
+    /// The reason the transaction failed with, if reported by the index
+    pub revert_reason: Option<String>,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub fees: U256,
+    pub nonce: u64,
+    pub energy_limit: u64,
+    pub energy_used: Option<u64>,
+    pub energy_price: U256,
+    pub data: Bytes,
+}
+
+impl TransactionDetails {
+    /// Reads the transaction from a `tx` response
+    fn from_response(response: &Value) -> Result<Self> {
+        let specific = &response["corecoinSpecific"];
+        let missing = |name: &str| BlockindexError::Builder(name.to_string());
+        let u256 = |value: &Value| match value.as_str() {
+            Some(num) => U256::from_dec_str(num).ok(),
+            None => value.as_u64().map(U256::from),
+        };
+        let address = |value: &Value| value[0]["addresses"][0].as_str()?.parse().ok();
+
+        let block_number =
+            response["blockHeight"].as_i64().filter(|number| *number > 0).map(|n| n as u64);
+        let block_hash = match block_number {
+            Some(_) => response["blockHash"].as_str().and_then(|hash| hash.parse().ok()),
+            None => None,
+        };
+        let status = specific["status"].as_i64().ok_or_else(|| missing("status"))?;
+
+        Ok(Self {
+            hash: response["txid"]
+                .as_str()
+                .and_then(|hash| hash.parse().ok())
+                .ok_or_else(|| missing("txid"))?,
+            block_hash,
+            block_number,
+            confirmations: response["confirmations"].as_u64().unwrap_or_default(),
+            // the index can report a status before the transaction is mined
+            status: if block_number.is_some() {
+                TxStatus::from_code(status)
+            } else {
+                TxStatus::Pending
+            },
+            revert_reason: specific["error"].as_str().filter(|err| !err.is_empty()).map(Into::into),
+            from: address(&response["vin"]),
+            to: address(&response["vout"]),
+            value: u256(&response["value"]).ok_or_else(|| missing("value"))?,
+            fees: u256(&response["fees"]).unwrap_or_default(),
+            nonce: specific["nonce"].as_u64().ok_or_else(|| missing("nonce"))?,
+            energy_limit: specific["energyLimit"].as_u64().ok_or_else(|| missing("energyLimit"))?,
+            energy_used: specific["energyUsed"].as_u64(),
+            energy_price: u256(&specific["energyPrice"]).unwrap_or_default(),
+            data: specific["data"].as_str().and_then(|data| data.parse().ok()).unwrap_or_default(),
+        })
+    }
+}
+
+/// The status of a transaction, see [`Client::transaction_receipt_status`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceiptStatus {
+    pub status: TxStatus,
+    /// `None` while the transaction is pending
+    pub block_number: Option<u64>,
+    pub confirmations: u64,
+    /// The reason the transaction failed with, if reported by the index
+    pub revert_reason: Option<String>,
+}
+
+impl TransactionReceiptStatus {
+    /// Whether the transaction succeeded and has at least `confirmations` confirmations
+    pub fn is_confirmed(&self, confirmations: u64) -> bool {
+        self.status == TxStatus::Success && self.confirmations >= confirmations
+    }
+}
+
+impl From<TransactionDetails> for TransactionReceiptStatus {
+    fn from(tx: TransactionDetails) -> Self {
+        Self {
+            status: tx.status,
+            block_number: tx.block_number,
+            confirmations: tx.confirmations,
+            revert_reason: tx.revert_reason,
+        }
+    }
+}
+
 impl Client {
     /// Returns given transaction.
     ///
@@ -48,7 +171,7 @@ impl Client {
         let query = self.create_query("tx", hash.as_ref(), HashMap::from([("details", "basic")]));
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
         let mut res: Transaction = serde_json::from_value(response.clone())?;
         res.from = response["vin"][0]["addresses"][0].to_string().replace('\"', "");
@@ -72,6 +195,44 @@ impl Client {
         Ok(res)
     }
 
+    /// Returns given transaction with typed fields, including its execution status.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_hash = "0x9a0516515962331000ab0910b969b94cc63e3254ee36664595085af07815fa31".parse()?;
+    /// let tx = client.transaction(tx_hash).await?;
+    /// println!("{:?} paid {} with {} confirmations", tx.from, tx.value, tx.confirmations);
+    /// # Ok(()) }
+    /// ```
+    pub async fn transaction(&self, hash: H256) -> Result<TransactionDetails> {
+        let hash = format!("{hash:?}");
+        let query = self.create_query("tx", hash.as_ref(), HashMap::from([("details", "basic")]));
+        let response: Value = self.get_json(&query).await?;
+        if response["error"].as_str().is_some() {
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
+        }
+        TransactionDetails::from_response(&response)
+    }
+
+    /// Returns the execution status and the number of confirmations of given transaction.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo(client: corebc_blockindex::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let tx_hash = "0x9a0516515962331000ab0910b969b94cc63e3254ee36664595085af07815fa31".parse()?;
+    /// let status = client.transaction_receipt_status(tx_hash).await?;
+    /// if status.is_confirmed(12) {
+    ///     println!("payment received");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub async fn transaction_receipt_status(&self, hash: H256) -> Result<TransactionReceiptStatus> {
+        Ok(self.transaction(hash).await?.into())
+    }
+
     /// Sends raw signed transaction.
     ///
     /// # Examples
@@ -86,7 +247,7 @@ impl Client {
         let query = self.create_query("sendtx", tx.as_ref(), HashMap::from([("details", "basic")]));
         let response: Value = self.get_json(&query).await?;
         if response["error"].as_str().is_some() {
-            return Err(BlockindexError::ErrorResponse { error: response["error"].to_string() })
+            return Err(BlockindexError::from_response_error(response["error"].to_string()))
         }
         response["result"]
             .to_string()
@@ -95,3 +256,50 @@ impl Client {
             .map_err(|_| BlockindexError::Builder("result".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(block_height: i64, status: i64) -> Value {
+        serde_json::json!({
+            "txid": "0x9a0516515962331000ab0910b969b94cc63e3254ee36664595085af07815fa31",
+            "vin": [{ "addresses": ["ab654efcf28707488885abbe9d1fc80cbe6d6036f250"] }],
+            "vout": [{ "addresses": ["ab57dde1a47041fc3c570c0318a713128ced55fd2ada"] }],
+            "blockHash": "0xa9b9902f750ebde2179c4cec87c53a50eaf3ce6e8834570b8935e9e063e88303",
+            "blockHeight": block_height,
+            "confirmations": if block_height > 0 { 3 } else { 0 },
+            "blockTime": 1680000000,
+            "value": "1000000000000000000",
+            "fees": "21000000000000",
+            "corecoinSpecific": {
+                "status": status,
+                "error": if status == 0 { "execution reverted" } else { "" },
+                "nonce": 7,
+                "energyLimit": 21000,
+                "energyUsed": 21000,
+                "energyPrice": "1000000000",
+                "data": "0x"
+            }
+        })
+    }
+
+    #[test]
+    fn reads_transaction_status() {
+        let tx = TransactionDetails::from_response(&response(289632, 1)).unwrap();
+        assert_eq!(tx.block_number, Some(289632));
+        assert_eq!(tx.value, U256::exp10(18));
+        assert_eq!(tx.from, Some("ab654efcf28707488885abbe9d1fc80cbe6d6036f250".parse().unwrap()));
+        let status = TransactionReceiptStatus::from(tx);
+        assert!(status.is_confirmed(3));
+        assert!(!status.is_confirmed(4));
+
+        let tx = TransactionDetails::from_response(&response(289632, 0)).unwrap();
+        assert_eq!(tx.status, TxStatus::Failed);
+        assert_eq!(tx.revert_reason.as_deref(), Some("execution reverted"));
+
+        let tx = TransactionDetails::from_response(&response(-1, -1)).unwrap();
+        assert_eq!(tx.status, TxStatus::Pending);
+        assert_eq!((tx.block_number, tx.block_hash), (None, None));
+    }
+}
@@ -1,8 +1,11 @@
-use crate::{Client, Result};
-use corebc_core::types::Address;
+use crate::{BlockindexError, Client, Result};
+use corebc_core::types::{Address, Bytes};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[cfg(feature = "corebc-ylem")]
+use corebc_ylem::verify::{BoxFuture, ExplorerClient, VerificationRequest};
+
 /// Arguments for verifying contracts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerifyContract {
@@ -167,6 +170,16 @@ impl Client {
         self.get(&body).await
     }
 
+    /// Returns the GUID of a submission from the response of
+    /// [`Self::submit_contract_verification`], fails if the submission was rejected
+    pub fn parse_submission_guid(&self, response: &str) -> Result<String> {
+        let response: SubmissionResponse = self.sanitize_response(response)?;
+        if response.status != "1" {
+            return Err(BlockindexError::from_response_error(response.result))
+        }
+        Ok(response.result)
+    }
+
     /// Check Source Code Verification Status with receipt received from
     /// `[Self::submit_contract_verification]`
     pub async fn check_contract_verification_status(
@@ -203,4 +216,117 @@ impl Client {
         );
         self.get(&body).await
     }
+
+    /// Fetches the runtime bytecode deployed at `address` via the `xcb_getCode` proxy endpoint
+    pub async fn deployed_code(&self, address: Address) -> Result<Bytes> {
+        let address = format!("{address:?}");
+        let query = self.create_query(
+            "proxy",
+            "xcb_getCode",
+            HashMap::from([("address", address.as_str()), ("tag", "latest")]),
+        );
+        let code: ProxyResponse<Bytes> = self.get_json(&query).await?;
+        Ok(code.result)
+    }
+}
+
+/// The JSON-RPC response of a `proxy` endpoint
+#[derive(Deserialize)]
+struct ProxyResponse<T> {
+    result: T,
+}
+
+/// The response of the `verifysourcecode` endpoint, `result` is the GUID of the submission or the
+/// reason it was rejected
+#[derive(Deserialize)]
+struct SubmissionResponse {
+    status: String,
+    result: String,
+}
+
+/// Lets [`corebc_ylem::Project::verify_contract`] verify contracts on Blockindex
+#[cfg(feature = "corebc-ylem")]
+impl ExplorerClient for Client {
+    type Error = BlockindexError;
+
+    fn deployed_code(&self, address: Address) -> BoxFuture<'_, Result<Bytes>> {
+        Box::pin(Client::deployed_code(self, address))
+    }
+
+    fn submit_verification(&self, request: VerificationRequest) -> BoxFuture<'_, Result<String>> {
+        Box::pin(async move {
+            let contract = VerifyContract::new(
+                request.address,
+                request.contract_name,
+                serde_json::to_string(&request.input)?,
+                format!("v{}", request.compiler_version),
+            )
+            .code_format(CodeFormat::StandardJsonInput);
+            let response = self.submit_contract_verification(&contract).await?;
+            self.parse_submission_guid(&response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    /// Answers a single HTTP request with `body`, returns the client and the request line
+    fn serve_once(body: &'static str) -> (Client, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            BufReader::new(&stream).read_line(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        let client = Client::builder()
+            .with_api_url(format!("{url}api/"))
+            .unwrap()
+            .with_url(url)
+            .unwrap()
+            .build()
+            .unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn fetches_deployed_code() {
+        let (client, server) = serve_once(r#"{"jsonrpc":"2.0","id":1,"result":"0x6080"}"#);
+        let code = client.deployed_code(Address::zero()).await.unwrap();
+        assert_eq!(code, Bytes::from(vec![0x60, 0x80]));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /api/proxy/xcb_getCode?"), "{request}");
+        assert!(request.contains("tag=latest"), "{request}");
+    }
+
+    #[test]
+    fn parses_submission_guid() {
+        let client = Client::new(corebc_core::types::Network::Devin).unwrap();
+        let guid = client
+            .parse_submission_guid(
+                r#"{"status":"1","message":"OK","result":"ezxbftchadq3vrftcnevnzbfq"}"#,
+            )
+            .unwrap();
+        assert_eq!(guid, "ezxbftchadq3vrftcnevnzbfq");
+
+        let rejected =
+            r#"{"status":"0","message":"NOTOK","result":"Contract source code already verified"}"#;
+        let err = client.parse_submission_guid(rejected).unwrap_err();
+        assert!(err.to_string().contains("already verified"), "{err}");
+    }
 }
@@ -1,5 +1,7 @@
 use crate::*;
+use corebc_blockindex::account::{Sort, TxListParams};
 use corebc_core::types::Address;
+use futures_util::TryStreamExt;
 use serial_test::serial;
 
 #[tokio::test]
@@ -100,3 +102,43 @@ async fn get_balance_history_empty() {
     })
     .await
 }
+
+#[tokio::test]
+#[serial]
+async fn get_balances_success() {
+    run_with_client(Network::Devin, |client| async move {
+        let accounts: Vec<Address> = vec![
+            "ab77268cebda343475da4384139ad24a90e7afcb80c5".parse().unwrap(),
+            "ab720000000000000000000000000000000000000000".parse().unwrap(),
+        ];
+        let balances = client.get_balances(&accounts).await.unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].account, accounts[0]);
+        assert_eq!(balances[0].balance, "366406336387999999919");
+        assert_eq!(balances[1].balance, "0");
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+async fn get_transactions_pages() {
+    run_with_client(Network::Devin, |client: Client| async move {
+        let account: &Address = &"ab644ae44561a5a4c4d3011ee104ac8f0f848d84d4dd".parse().unwrap();
+        let params = TxListParams::default().page_size(4);
+        let page = client.get_transactions_page(account, params).await.unwrap();
+        assert_eq!((page.page, page.total_pages, page.txids.len()), (1, 2, 4));
+
+        let desc: Vec<String> =
+            client.transactions_stream(account, params).try_collect().await.unwrap();
+        assert_eq!(desc.len(), 6);
+        let mut asc: Vec<String> = client
+            .transactions_stream(account, params.sort(Sort::Asc))
+            .try_collect()
+            .await
+            .unwrap();
+        asc.reverse();
+        assert_eq!(asc, desc);
+    })
+    .await
+}
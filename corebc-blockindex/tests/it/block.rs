@@ -1,5 +1,5 @@
 use crate::*;
-use corebc_blockindex::block::BlockQueryOption;
+use corebc_blockindex::block::{BlockQueryOption, Pagination};
 use serial_test::serial;
 
 #[tokio::test]
@@ -38,3 +38,20 @@ async fn get_block_error() {
     })
     .await
 }
+
+#[tokio::test]
+#[serial]
+async fn get_blocks() {
+    run_with_client(Network::Devin, |client| async move {
+        let blocks = client.blocks(289630..=289639, Some(Pagination::new(1, 3))).await.unwrap();
+        assert_eq!(
+            blocks.iter().map(|block| block.number).collect::<Vec<_>>(),
+            [289630, 289631, 289632]
+        );
+        assert_eq!(
+            blocks[2].hash,
+            "0xa9b9902f750ebde2179c4cec87c53a50eaf3ce6e8834570b8935e9e063e88303".parse().unwrap()
+        );
+    })
+    .await
+}
@@ -30,3 +30,20 @@ async fn get_transaction_error() {
     })
     .await
 }
+
+#[tokio::test]
+#[serial]
+async fn get_transaction_receipt_status() {
+    run_with_client(Network::Devin, |client| async move {
+        let hash: H256 =
+            "0x9f41d62f5d0b680c1b1a21328c2e0359b65d08f65e2ddf4068920e6b459f6f76".parse().unwrap();
+        let tx = client.transaction(hash).await.unwrap();
+        assert_eq!(tx.hash, hash);
+        assert_eq!(tx.block_number, Some(289617));
+
+        let status = client.transaction_receipt_status(hash).await.unwrap();
+        assert_eq!(status.block_number, Some(289617));
+        assert!(status.confirmations > 0);
+    })
+    .await
+}
@@ -6,6 +6,8 @@ mod methods;
 pub(crate) mod structs;
 mod types;
 
+pub use methods::OverloadNaming;
+
 use super::{util, Abigen};
 use crate::contract::{methods::MethodAlias, structs::InternalStructs};
 use corebc_core::{
@@ -18,13 +20,15 @@ use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
 use serde::Deserialize;
 use std::collections::BTreeMap;
-use syn::Path;
+use syn::{Path, Visibility};
 
 /// The result of `Context::expand`
 #[derive(Debug)]
 pub struct ExpandedContract {
     /// The name of the contract module
     pub module: Ident,
+    /// The visibility of the contract module and its re-export
+    pub visibility: Visibility,
     /// The contract module's imports
     pub imports: TokenStream,
     /// Contract, Middle related implementations
@@ -44,6 +48,7 @@ impl ExpandedContract {
     pub fn into_tokens(self) -> TokenStream {
         let ExpandedContract {
             module,
+            visibility,
             imports,
             contract,
             events,
@@ -53,7 +58,7 @@ impl ExpandedContract {
         } = self;
 
         quote! {
-            pub use #module::*;
+            #visibility use #module::*;
 
             /// This module was auto-generated with ethers-rs Abigen.
             /// More information at: <https://github.com/gakonst/ethers-rs>
@@ -65,7 +70,7 @@ impl ExpandedContract {
                 dead_code,
                 non_camel_case_types,
             )]
-            pub mod #module {
+            #visibility mod #module {
                 #imports
                 #contract
                 #errors
@@ -109,6 +114,12 @@ pub struct Context {
     /// Derives added to event structs and enums.
     extra_derives: Vec<Path>,
 
+    /// How overloaded functions without a manual alias are named.
+    overload_naming: OverloadNaming,
+
+    /// The visibility of the generated module.
+    visibility: Visibility,
+
     /// Manually specified event aliases.
     event_aliases: BTreeMap<String, Ident>,
 
@@ -139,7 +150,9 @@ impl Context {
         let (contract_methods, call_structs) = self.methods_and_call_structs()?;
 
         // 5. The deploy method, only if the contract has a bytecode object
-        let deployment_methods = self.deployment_methods();
+        let deployment_methods = self.deployment_methods()?;
+
+        let forwarder_method = self.forwarder_method();
 
         // 6. Declare the structs parsed from the human readable abi
         let abi_structs_decl = self.abi_structs()?;
@@ -163,6 +176,8 @@ impl Context {
 
                     #deployment_methods
 
+                    #forwarder_method
+
                     #contract_methods
 
                     #contract_events
@@ -177,6 +192,7 @@ impl Context {
 
         Ok(ExpandedContract {
             module: name_mod,
+            visibility: self.visibility.clone(),
             imports: quote!(),
             contract,
             events: events_decl,
@@ -287,12 +303,19 @@ impl Context {
             internal_structs,
             contract_name: args.contract_name.to_string(),
             contract_ident: args.contract_name,
-            contract_bytecode,
+            contract_bytecode: args.bytecode.or(contract_bytecode),
             contract_deployed_bytecode,
             method_aliases,
             error_aliases: Default::default(),
             event_aliases,
-            extra_derives: args.derives,
+            // `Clone` is derived for all generated types, deriving it twice would not compile
+            extra_derives: args
+                .derives
+                .into_iter()
+                .filter(|derive| !util::is_builtin_derive(derive, "Clone"))
+                .collect(),
+            overload_naming: args.overload_naming,
+            visibility: args.visibility,
         })
     }
 
@@ -115,11 +115,13 @@ impl Context {
         util::ident(&format!("{}Events", self.contract_ident))
     }
 
-    /// Expands the `events` function that bundles all declared events of this contract
+    /// Expands the `events` function that bundles all declared events of this contract, and the
+    /// `decode_logs` function that decodes them from a transaction receipt
     fn expand_events_method(&self) -> Option<TokenStream> {
         let sorted_events: BTreeMap<_, _> = self.abi.events.clone().into_iter().collect();
 
         let mut iter = sorted_events.values().flatten();
+        let corebc_core = corebc_core_crate();
         let corebc_contract = corebc_contract_crate();
 
         if let Some(event) = iter.next() {
@@ -141,6 +143,20 @@ impl Context {
                 > {
                     self.0.event_with_filter(::core::default::Default::default())
                 }
+
+                /// Decodes the events this contract emitted in the transaction of the `receipt`.
+                ///
+                /// Logs of other contracts and logs that don't match any of the events of this
+                /// contract are skipped.
+                pub fn decode_logs(
+                    &self,
+                    receipt: &#corebc_core::types::TransactionReceipt,
+                ) -> ::std::vec::Vec<#ty> {
+                    #corebc_contract::decode_receipt_logs(
+                        receipt,
+                        ::core::option::Option::Some(self.0.address()),
+                    )
+                }
             })
         } else {
             None
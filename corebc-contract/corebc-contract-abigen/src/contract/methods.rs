@@ -11,7 +11,11 @@ use eyre::{Context as _, Result};
 use inflector::Inflector;
 use proc_macro2::{Literal, TokenStream};
 use quote::quote;
-use std::collections::{btree_map::Entry, BTreeMap, HashMap, HashSet};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
 use syn::Ident;
 
 /// The maximum amount of overloaded functions that are attempted to auto aliased with their param
@@ -19,6 +23,74 @@ use syn::Ident;
 /// all functions are aliased with their index, like `log0, log1, log2,....`
 const NAME_ALIASING_OVERLOADED_FUNCTIONS_CAP: usize = 3;
 
+/// How overloaded functions, which would otherwise expand to colliding Rust methods, are named.
+///
+/// Functions with a manual method alias are never renamed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverloadNaming {
+    /// Names overloads after the parameters they add to the overload with the fewest parameters,
+    /// like `transfer_with_data`, and falls back to their index if that is ambiguous.
+    #[default]
+    ParamNames,
+    /// Numbers all overloads by parameter count and then by signature, like `transfer0` and
+    /// `transfer1`.
+    Index,
+    /// Appends the parameter types to the name of every overload, like `transfer_address_uint256`.
+    /// An overload without parameters keeps its name.
+    Types,
+}
+
+impl OverloadNaming {
+    /// Returns the alias of the `idx`th overload, sorted by parameter count and signature
+    fn alias(&self, idx: usize, function: &Function) -> String {
+        let name = function.name.to_snake_case();
+        match self {
+            OverloadNaming::ParamNames | OverloadNaming::Index => format!("{name}{idx}"),
+            OverloadNaming::Types if function.inputs.is_empty() => name,
+            OverloadNaming::Types => {
+                let types = function.inputs.iter().map(|p| type_suffix(&p.kind));
+                format!("{name}_{}", types.collect::<Vec<_>>().join("_"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for OverloadNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            OverloadNaming::ParamNames => "param_names",
+            OverloadNaming::Index => "index",
+            OverloadNaming::Types => "types",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for OverloadNaming {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "param_names" | "params" => Ok(OverloadNaming::ParamNames),
+            "index" => Ok(OverloadNaming::Index),
+            "types" => Ok(OverloadNaming::Types),
+            _ => Err(format!(
+                "unknown overload naming `{s}`, expected one of `param_names`, `index` or `types`"
+            )),
+        }
+    }
+}
+
+/// Returns the part of an overload alias that stands for a parameter of type `kind`
+fn type_suffix(kind: &ParamType) -> String {
+    match kind {
+        ParamType::Array(ty) => format!("{}_array", type_suffix(ty)),
+        ParamType::FixedArray(ty, len) => format!("{}_array{len}", type_suffix(ty)),
+        ParamType::Tuple(_) => "tuple".to_string(),
+        kind => kind.to_string(),
+    }
+}
+
 /// Expands a context into a method struct containing all the generated bindings
 /// to the Solidity contract methods.
 impl Context {
@@ -48,12 +120,38 @@ impl Context {
         Ok((function_impls, all_structs))
     }
 
+    /// Returns the method creating a `Forwarder` for contracts that accept meta-transactions, i.e.
+    /// CIP-2771 recipients implementing `isTrustedForwarder(address)`
+    pub(crate) fn forwarder_method(&self) -> Option<TokenStream> {
+        let is_trusted_forwarder = self.abi.functions.get("isTrustedForwarder")?;
+        if !is_trusted_forwarder
+            .iter()
+            .any(|f| f.inputs.len() == 1 && f.inputs[0].kind == ParamType::Address)
+        {
+            return None
+        }
+
+        let corebc_core = corebc_core_crate();
+        let corebc_contract = corebc_contract_crate();
+        Some(quote! {
+            /// Returns a forwarder that wraps calls to this contract in meta-transactions. The
+            /// contract attributes the calls to their signer if it trusts the `forwarder`.
+            pub fn meta_tx_forwarder<T: Into<#corebc_core::types::Address>>(
+                &self,
+                forwarder: T,
+            ) -> #corebc_contract::Forwarder<M> {
+                #corebc_contract::Forwarder::new(forwarder, self.client())
+            }
+        })
+    }
+
     /// Returns all deploy (constructor) implementations
-    pub(crate) fn deployment_methods(&self) -> Option<TokenStream> {
+    pub(crate) fn deployment_methods(&self) -> Result<Option<TokenStream>> {
         // don't generate deploy if no bytecode
-        self.contract_bytecode.as_ref()?;
+        if self.contract_bytecode.is_none() {
+            return Ok(None)
+        }
 
-        let corebc_core = corebc_core_crate();
         let corebc_contract = corebc_contract_crate();
 
         let abi_name = self.inline_abi_ident();
@@ -66,11 +164,26 @@ impl Context {
             #bytecode_name.clone().into()
         };
 
-        Some(quote! {
-            /// Constructs the general purpose `Deployer` instance based on the provided constructor arguments and sends it.
+        let inputs = self.abi.constructor.as_ref().map(|c| &c.inputs[..]).unwrap_or_default();
+        let params = types::expand_params(inputs, |p| {
+            self.internal_structs.get_function_input_struct_type("constructor", &p.name)
+        })?;
+        let (args_type, args) =
+            expand_constructor_args(params.into_iter().map(|(_, ty)| ty).collect());
+        let signature = format!(
+            "constructor({})",
+            inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<_>>().join(",")
+        );
+        let doc_str = format!(
+            "Constructs the general purpose `Deployer` instance based on the arguments of `{signature}` and sends it."
+        );
+
+        Ok(Some(quote! {
+            #[doc = #doc_str]
             /// Returns a new instance of a deployer that returns an instance of this contract after sending the transaction
             ///
             /// Notes:
+            /// - The arguments are passed as a tuple, or as a single value if there is only one.
             /// - If there are no constructor arguments, you should pass `()` as the argument.
             /// - The default poll duration is 7 seconds.
             /// - The default number of confirmations is 1 block.
@@ -90,16 +203,16 @@ impl Context {
             ///    let msg = greeter_contract.greet().call().await.unwrap();
             /// # }
             /// ```
-            pub fn deploy<T: #corebc_core::abi::Tokenize>(
+            pub fn deploy(
                 client: ::std::sync::Arc<M>,
-                constructor_args: T,
+                constructor_args: #args_type,
             ) -> ::core::result::Result<#corebc_contract::builders::ContractDeployer<M, Self>, #corebc_contract::ContractError<M>> {
                 let factory = #corebc_contract::ContractFactory::new(#get_abi, #get_bytecode, client);
-                let deployer = factory.deploy(constructor_args)?;
+                let deployer = factory.deploy(#args)?;
                 let deployer = #corebc_contract::ContractDeployer::new(deployer);
                 Ok(deployer)
             }
-        })
+        }))
     }
 
     /// Expands to the corresponding struct type based on the inputs of the given function
@@ -393,6 +506,28 @@ impl Context {
                 continue
             }
 
+            if self.overload_naming != OverloadNaming::ParamNames {
+                let mut overloads = functions
+                    .iter()
+                    .filter(|f| !aliases.contains_key(&f.abi_signature()))
+                    .collect::<Vec<_>>();
+                overloads.sort_by_key(|f| (f.inputs.len(), f.abi_signature()));
+
+                let mut names = HashSet::new();
+                for (idx, function) in overloads.into_iter().enumerate() {
+                    let alias = MethodAlias::new(&self.overload_naming.alias(idx, function));
+                    eyre::ensure!(
+                        names.insert(alias.function_name.to_string()),
+                        "overloads of `{}` collide as `{}`, add a method alias for `{}`",
+                        function.name,
+                        alias.function_name,
+                        function.abi_signature()
+                    );
+                    aliases.insert(function.abi_signature(), alias);
+                }
+                continue
+            }
+
             let num_functions = functions.len();
             // sort functions by number of inputs asc
             let mut functions = functions.iter().enumerate().collect::<Vec<_>>();
@@ -572,6 +707,19 @@ impl Context {
     }
 }
 
+/// Expands the type of the constructor arguments taken by `deploy`, given the expanded `types` of
+/// the constructor's inputs, and the expression that tokenizes them
+///
+/// A single argument is taken as is, but wrapped in a tuple for tokenization so a struct argument
+/// is not flattened into its fields.
+fn expand_constructor_args(types: Vec<TokenStream>) -> (TokenStream, TokenStream) {
+    match &types[..] {
+        [] => (quote!(()), quote!(constructor_args)),
+        [ty] => (ty.clone(), quote!((constructor_args,))),
+        types => (quote!(( #( #types ),* )), quote!(constructor_args)),
+    }
+}
+
 fn expand_selector(selector: Selector) -> TokenStream {
     let bytes = selector.iter().copied().map(Literal::u8_unsuffixed);
     quote!([ #( #bytes ),* ])
@@ -698,6 +846,74 @@ mod tests {
         Ok(quote! { #( , #params )* })
     }
 
+    #[test]
+    fn test_overload_naming() {
+        let function = |inputs: Vec<ParamType>| {
+            #[allow(deprecated)]
+            Function {
+                name: "safeTransferFrom".to_string(),
+                inputs: inputs
+                    .into_iter()
+                    .map(|kind| Param { name: String::new(), kind, internal_type: None })
+                    .collect(),
+                outputs: vec![],
+                constant: None,
+                state_mutability: Default::default(),
+            }
+        };
+        let transfer = function(vec![ParamType::Address, ParamType::Uint(256)]);
+        let batch =
+            function(vec![ParamType::Array(Box::new(ParamType::Uint(256))), ParamType::Bytes]);
+
+        assert_eq!(OverloadNaming::Index.alias(1, &transfer), "safe_transfer_from1");
+        assert_eq!(OverloadNaming::Types.alias(1, &transfer), "safe_transfer_from_address_uint256");
+        assert_eq!(
+            OverloadNaming::Types.alias(0, &batch),
+            "safe_transfer_from_uint256_array_bytes"
+        );
+        assert_eq!(OverloadNaming::Types.alias(0, &function(vec![])), "safe_transfer_from");
+        assert_eq!("types".parse::<OverloadNaming>().unwrap(), OverloadNaming::Types);
+        assert!("suffix".parse::<OverloadNaming>().is_err());
+    }
+
+    #[test]
+    fn test_expand_constructor_args() {
+        let (ty, args) = expand_constructor_args(vec![]);
+        assert_eq!(ty.to_string(), "()");
+        assert_eq!(args.to_string(), "constructor_args");
+
+        let (ty, args) = expand_constructor_args(vec![quote!(::std::string::String)]);
+        assert_eq!(ty.to_string(), quote!(::std::string::String).to_string());
+        assert_eq!(args.to_string(), "(constructor_args ,)");
+
+        let corebc_core = corebc_core_crate();
+        let (ty, args) =
+            expand_constructor_args(vec![quote!(#corebc_core::types::Address), quote!(bool)]);
+        assert_eq!(ty.to_string(), quote!((#corebc_core::types::Address, bool)).to_string());
+        assert_eq!(args.to_string(), "constructor_args");
+    }
+
+    #[test]
+    fn test_expand_struct_constructor_args() {
+        let abi = r#"[{
+            "type": "constructor",
+            "stateMutability": "nonpayable",
+            "inputs": [{
+                "name": "config",
+                "type": "tuple",
+                "internalType": "struct Token.Config",
+                "components": [
+                    { "name": "owner", "type": "address", "internalType": "address" },
+                    { "name": "supply", "type": "uint256", "internalType": "uint256" }
+                ]
+            }]
+        }]"#;
+        let abigen = crate::Abigen::new("Token", abi).unwrap().bytecode(vec![0x60, 0x80]);
+        let deploy = Context::from_abigen(abigen).unwrap().deployment_methods().unwrap().unwrap();
+        let deploy = deploy.to_string();
+        assert!(deploy.contains(&quote!(constructor_args: Config).to_string()), "{deploy}");
+    }
+
     #[test]
     fn test_expand_inputs_call_arg() {
         // no inputs
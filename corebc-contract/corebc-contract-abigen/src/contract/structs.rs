@@ -234,7 +234,14 @@ impl InternalStructs {
         {
             let is_event = item.type_field == "event";
 
-            if let Some(name) = item.name {
+            // constructors are unnamed, their params are tracked like the human readable parser
+            // does
+            let name = match item.name {
+                Some(name) => Some(name),
+                None if item.type_field == "constructor" => Some("constructor".to_string()),
+                None => None,
+            };
+            if let Some(name) = name {
                 for (idx, input) in item.inputs.into_iter().enumerate() {
                     if let Some(ty) = input
                         .internal_type
@@ -382,6 +389,23 @@ impl InternalStructs {
     pub fn structs_types(&self) -> &HashMap<String, SolStruct> {
         &self.structs
     }
+
+    /// Returns the type identifiers of all structs the struct with the given identifier uses as
+    /// field types
+    pub fn struct_dependencies(&self, id: &str) -> Vec<String> {
+        self.structs
+            .get(id)
+            .map(|s| {
+                s.fields()
+                    .iter()
+                    .filter_map(|field| match field.r#type() {
+                        FieldType::Struct(ty) => Some(ty.identifier()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 /// This will determine the name of the rust type and will make sure that possible collisions are
@@ -631,4 +655,28 @@ mod tests {
 
         let _internal = InternalStructs::new(abi);
     }
+
+    #[test]
+    fn can_determine_constructor_structs() {
+        let abi = serde_json::from_value::<RawAbi>(serde_json::json!([{
+            "type": "constructor",
+            "stateMutability": "nonpayable",
+            "inputs": [{
+                "name": "config",
+                "type": "tuple",
+                "internalType": "struct Token.Config",
+                "components": [
+                    { "name": "owner", "type": "address", "internalType": "address" },
+                    { "name": "supply", "type": "uint256", "internalType": "uint256" }
+                ]
+            }]
+        }]))
+        .unwrap();
+
+        let internal = InternalStructs::new(abi);
+        assert_eq!(
+            internal.get_function_input_struct_type("constructor", "config"),
+            Some("Config")
+        );
+    }
 }
@@ -18,7 +18,7 @@
 mod test_macros;
 
 pub mod contract;
-pub use contract::structs::InternalStructs;
+pub use contract::{structs::InternalStructs, OverloadNaming};
 
 pub mod filter;
 pub use filter::{ContractFilter, ExcludeContracts, SelectContracts};
@@ -32,6 +32,7 @@ pub use source::Source;
 mod util;
 
 pub use corebc_core::types::Address;
+use corebc_core::types::Bytes;
 
 use contract::{Context, ExpandedContract};
 use eyre::{Context as _, Result};
@@ -83,6 +84,15 @@ pub struct Abigen {
 
     /// Manually specified `derive` macros added to all structs and enums.
     derives: Vec<syn::Path>,
+
+    /// The bytecode used by the generated `deploy` method, overrides the one in the ABI source.
+    bytecode: Option<Bytes>,
+
+    /// How overloaded functions without a method alias are named.
+    overload_naming: OverloadNaming,
+
+    /// The visibility of the generated module.
+    visibility: syn::Visibility,
 }
 
 impl Abigen {
@@ -101,6 +111,9 @@ impl Abigen {
             derives: Default::default(),
             event_aliases: Default::default(),
             error_aliases: Default::default(),
+            bytecode: None,
+            overload_naming: Default::default(),
+            visibility: syn::parse_quote!(pub),
         })
     }
 
@@ -114,9 +127,34 @@ impl Abigen {
             derives: Default::default(),
             event_aliases: Default::default(),
             error_aliases: Default::default(),
+            bytecode: None,
+            overload_naming: Default::default(),
+            visibility: syn::parse_quote!(pub),
         }
     }
 
+    /// Creates a new builder with the given contract name and in-memory ABI, e.g. to generate
+    /// bindings in the browser.
+    ///
+    /// The `abi` is a JSON ABI, a human-readable ABI or a JSON artifact that contains the ABI.
+    /// Unlike [`new`](Self::new), it is never read as a path or URL, so generating the bindings
+    /// touches neither the filesystem nor the network, which also works on `wasm32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_contract_abigen::Abigen;
+    ///
+    /// let abi = "[function transfer(address to, uint256 amount) external returns (bool)]";
+    /// let bindings = Abigen::from_abi_str("Token", abi)?.generate()?.to_string();
+    /// assert!(bindings.contains("pub fn transfer("));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_abi_str<T: AsRef<str>>(contract_name: T, abi: impl Into<String>) -> Result<Self> {
+        let contract_name = syn::parse_str(contract_name.as_ref())?;
+        Ok(Self::new_raw(contract_name, Source::String(abi.into())))
+    }
+
     /// Attempts to load a new builder from an ABI JSON file at the specific path.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let path = dunce::canonicalize(path).wrap_err("File does not exist")?;
@@ -184,6 +222,33 @@ impl Abigen {
         Ok(self)
     }
 
+    /// Sets the creation bytecode of the contract, e.g. from a `corebc-ylem` compile output.
+    ///
+    /// If the contract has a bytecode, the bindings include a typed `deploy` method. A bytecode
+    /// set here takes precedence over one found in the ABI source.
+    pub fn bytecode(mut self, bytecode: impl Into<Bytes>) -> Self {
+        self.bytecode = Some(bytecode.into());
+        self
+    }
+
+    /// Sets how overloaded functions without a method alias are named.
+    ///
+    /// The default [`OverloadNaming::ParamNames`] derives names from the parameter names, which
+    /// changes whenever an overload is added. [`OverloadNaming::Index`] and
+    /// [`OverloadNaming::Types`] only depend on the overloads' signatures.
+    pub fn overload_naming(mut self, naming: OverloadNaming) -> Self {
+        self.overload_naming = naming;
+        self
+    }
+
+    /// Sets the visibility of the generated module and its re-export, `pub` by default.
+    ///
+    /// For example, `pub(crate)` keeps the bindings private to the crate that generates them.
+    pub fn visibility<S: AsRef<str>>(mut self, visibility: S) -> Result<Self> {
+        self.visibility = syn::parse_str(visibility.as_ref())?;
+        Ok(self)
+    }
+
     #[deprecated = "Use format instead"]
     #[doc(hidden)]
     pub fn rustfmt(mut self, rustfmt: bool) -> Self {
@@ -266,6 +331,16 @@ impl Abigen {
     pub fn derives_mut(&mut self) -> &mut Vec<syn::Path> {
         &mut self.derives
     }
+
+    /// Returns a mutable reference to the naming of overloaded functions.
+    pub fn overload_naming_mut(&mut self) -> &mut OverloadNaming {
+        &mut self.overload_naming
+    }
+
+    /// Returns a mutable reference to the visibility of the generated module.
+    pub fn visibility_mut(&mut self) -> &mut syn::Visibility {
+        &mut self.visibility
+    }
 }
 
 /// Type-safe contract bindings generated by [Abigen].
@@ -380,6 +455,72 @@ mod tests {
         assert!(out.contains("pub struct Stuff"));
     }
 
+    #[test]
+    fn can_generate_from_abi_str() {
+        let abi = include_str!("../../tests/solidity-contracts/greeter_with_struct.json");
+        let out = Abigen::from_abi_str("Greeter", abi).unwrap().generate().unwrap().to_string();
+        assert!(out.contains("pub struct Stuff"));
+
+        // unlike `new`, a string is never resolved as a path
+        let err = Abigen::from_abi_str("Greeter", "greeter.json").unwrap().generate().unwrap_err();
+        assert!(err.to_string().contains("error parsing abi"), "{err}");
+    }
+
+    #[test]
+    fn can_generate_forwarder_method() {
+        let recipient = r#"[
+            function isTrustedForwarder(address forwarder) external view returns (bool)
+            function mint(uint256 amount) external
+        ]"#;
+        let out = Abigen::new("Token", recipient).unwrap().generate().unwrap().to_string();
+        assert!(out.contains("pub fn meta_tx_forwarder<"), "{out}");
+
+        let out = Abigen::new("Token", "[function mint(uint256 amount) external]")
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string();
+        assert!(!out.contains("meta_tx_forwarder"), "{out}");
+    }
+
+    #[test]
+    fn can_configure_overloads_visibility_and_derives() {
+        let abi = r#"[
+            struct Stuff { uint256 x; }
+            function transfer(address to) external
+            function transfer(address to, uint256 amount) external
+            function greet(Stuff stuff) external
+        ]"#;
+
+        let out = Abigen::new("Token", abi)
+            .unwrap()
+            .overload_naming(OverloadNaming::Types)
+            .visibility("pub(crate)")
+            .unwrap()
+            .add_derive("Hash")
+            .unwrap()
+            .add_derive("serde::Serialize")
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string();
+
+        assert!(out.contains("pub(crate) mod token"));
+        assert!(out.contains("pub(crate) use token::*"));
+        assert!(out.contains("pub fn transfer_address("));
+        assert!(out.contains("pub fn transfer_address_uint256("));
+        assert!(out.contains("serde::Serialize"));
+
+        let out = Abigen::new("Token", abi)
+            .unwrap()
+            .overload_naming(OverloadNaming::Index)
+            .generate()
+            .unwrap()
+            .to_string();
+        assert!(out.contains("pub fn transfer0("));
+        assert!(out.contains("pub fn transfer1("));
+    }
+
     #[test]
     fn can_compile_and_generate() {
         let tmp = TempProject::dapptools().unwrap();
@@ -194,19 +194,74 @@ impl MultiExpansion {
                 }
             }
 
-            // resolve type conflicts
+            // only types with identical definitions can be shared, group the contracts by the
+            // definition they expand to and share the most common one
+            let mut shared: BTreeMap<String, (TokenStream, BTreeSet<usize>)> = BTreeMap::new();
             for (id, contracts) in conflicts.iter().filter(|(_, c)| c.len() > 1) {
-                // extract the shared type once
-                shared_types.push(
-                    expansions[contracts[0]]
+                let mut definitions: BTreeMap<String, (TokenStream, BTreeSet<usize>)> =
+                    BTreeMap::new();
+                for contract in contracts.iter().copied() {
+                    let def = expansions[contract]
                         .1
                         .struct_definition(id)
-                        .expect("struct def succeeded previously"),
-                );
+                        .expect("struct def succeeded previously");
+                    definitions
+                        .entry(def.to_string())
+                        .or_insert_with(|| (def, BTreeSet::new()))
+                        .1
+                        .insert(contract);
+                }
+                if let Some(group) = definitions
+                    .into_values()
+                    .filter(|(_, contracts)| contracts.len() > 1)
+                    .max_by_key(|(_, contracts)| contracts.len())
+                {
+                    shared.insert(id.clone(), group);
+                }
+            }
+
+            // a shared type can only be shared by contracts that share all of its field types as
+            // well, otherwise it would refer to a type the shared module does not contain
+            loop {
+                let mut changed = false;
+                let ids = shared.keys().cloned().collect::<Vec<_>>();
+                for id in ids {
+                    let (_, contracts) = &shared[&id];
+                    let retained = contracts
+                        .iter()
+                        .copied()
+                        .filter(|contract| {
+                            expansions[*contract]
+                                .1
+                                .internal_structs()
+                                .struct_dependencies(&id)
+                                .iter()
+                                .all(|dep| {
+                                    shared.get(dep).map_or(false, |(_, c)| c.contains(contract))
+                                })
+                        })
+                        .collect::<BTreeSet<_>>();
+                    if retained.len() != contracts.len() {
+                        changed = true;
+                        if retained.len() > 1 {
+                            shared.get_mut(&id).unwrap().1 = retained;
+                        } else {
+                            shared.remove(&id);
+                        }
+                    }
+                }
+                if !changed {
+                    break
+                }
+            }
+
+            for (id, (def, contracts)) in shared {
+                // extract the shared type once
+                shared_types.push(def);
 
                 // remove the shared type from the contract's bindings
-                for contract in contracts.iter().copied() {
-                    expansions[contract].1.remove_struct(id);
+                for contract in contracts {
+                    expansions[contract].1.remove_struct(&id);
                     dirty_contracts.insert(contract);
                 }
             }
@@ -1150,6 +1205,59 @@ mod tests {
         assert!(!tokens.contains("mod __shared_types"));
     }
 
+    #[test]
+    fn shares_only_identical_types() {
+        fn abi(name: &str, point: &str) -> Abigen {
+            let abi = format!(
+                r#"[{{
+                    "type": "function",
+                    "name": "draw",
+                    "stateMutability": "view",
+                    "inputs": [{{
+                        "name": "line",
+                        "type": "tuple",
+                        "internalType": "struct Line",
+                        "components": [
+                            {{
+                                "name": "from",
+                                "type": "tuple",
+                                "internalType": "struct Point",
+                                "components": [{point}]
+                            }},
+                            {{ "name": "width", "type": "uint256", "internalType": "uint256" }}
+                        ]
+                    }}],
+                    "outputs": []
+                }}]"#
+            );
+            Abigen::new(name, abi).unwrap()
+        }
+        let xy = r#"{ "name": "x", "type": "uint256", "internalType": "uint256" },
+                    { "name": "y", "type": "uint256", "internalType": "uint256" }"#;
+        let xyz = r#"{ "name": "x", "type": "uint256", "internalType": "uint256" },
+                     { "name": "y", "type": "uint256", "internalType": "uint256" },
+                     { "name": "z", "type": "uint256", "internalType": "uint256" }"#;
+
+        let result = MultiExpansion::new(vec![
+            abi("First", xy).expand().unwrap(),
+            abi("Second", xy).expand().unwrap(),
+            abi("Third", xyz).expand().unwrap(),
+        ])
+        .expand();
+        assert_eq!(result.shared_types.len(), 2);
+        assert_eq!(result.dirty_contracts, BTreeSet::from([0, 1]));
+
+        let tokens = result.expand_inplace().to_string();
+        let shared = tokens.split("pub mod first").next().unwrap();
+        assert!(shared.contains("pub struct Point"));
+        assert!(shared.contains("pub struct Line"));
+        // the third contract keeps its own definitions of both structs
+        let third = tokens.split("pub mod third").nth(1).unwrap();
+        assert!(third.contains("pub struct Point"));
+        assert!(third.contains("pub struct Line"));
+        assert!(third.contains("pub z"));
+    }
+
     #[test]
     fn can_filter_abigen() {
         let abi = Abigen::new(
@@ -58,12 +58,19 @@ impl Source {
     ///
     /// - `http://...`: an HTTP URL to a contract ABI. <br> Note: either the `rustls` or `openssl`
     ///   feature must be enabled to support *HTTPS* URLs.
+    ///
+    /// On `wasm32` only raw ABIs are accepted, there is no filesystem to read ABI files from.
     pub fn parse(source: impl AsRef<str>) -> Result<Self> {
         let source = source.as_ref().trim();
         match source.chars().next() {
             Some('[' | '{') => Ok(Self::String(source.to_string())),
 
-            #[cfg(any(not(feature = "online"), target_arch = "wasm32"))]
+            #[cfg(target_arch = "wasm32")]
+            _ => Err(eyre::eyre!(
+                "ABI files can't be read on wasm32, pass the ABI instead: {source}"
+            )),
+
+            #[cfg(all(not(feature = "online"), not(target_arch = "wasm32")))]
             _ => Ok(Self::local(source)?),
 
             #[cfg(all(feature = "online", not(target_arch = "wasm32")))]
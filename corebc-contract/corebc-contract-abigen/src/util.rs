@@ -8,6 +8,7 @@ use inflector::Inflector;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::path::{Path, PathBuf};
+use syn::{parse::Parser, punctuated::Punctuated, Token};
 
 /// Creates a new Ident with the given string at [`Span::call_site`].
 ///
@@ -276,12 +277,30 @@ fn get_struct_params<'a>(s_ty: &StructFieldType, ty: &'a ParamType) -> &'a [Para
     }
 }
 
+/// Whether `derive` is the standard trait `name`, e.g. `Hash` or `std::hash::Hash`.
+pub(crate) fn is_builtin_derive(derive: &syn::Path, name: &str) -> bool {
+    let is_std = derive.segments.len() == 1 ||
+        derive.segments.first().map_or(false, |s| s.ident == "std" || s.ident == "core");
+    is_std && derive.segments.last().map_or(false, |s| s.ident == name)
+}
+
+/// Adds the builtin derives to `stream`, skipping the ones that were already requested by the
+/// user since deriving a trait twice does not compile.
 fn extend_derives(stream: &mut TokenStream, def: bool, others: bool) {
+    let requested =
+        Parser::parse2(Punctuated::<syn::Path, Token![,]>::parse_terminated, stream.clone())
+            .unwrap_or_default();
+    let mut derive = |name: &str| {
+        if !requested.iter().any(|derive| is_builtin_derive(derive, name)) {
+            let name = ident(name);
+            stream.extend(quote!(#name,));
+        }
+    };
     if def {
-        stream.extend(quote!(Default,))
+        derive("Default");
     }
     if others {
-        stream.extend(quote!(Debug, PartialEq, Eq, Hash))
+        ["Debug", "PartialEq", "Eq", "Hash"].into_iter().for_each(derive);
     }
 }
 
@@ -346,6 +365,34 @@ pub(crate) fn abi_signature_types<'a, T: IntoIterator<Item = &'a ParamType>>(typ
 mod tests {
     use super::*;
 
+    #[test]
+    fn detects_builtin_derives() {
+        let path = |s: &str| syn::parse_str::<syn::Path>(s).unwrap();
+        assert!(is_builtin_derive(&path("Hash"), "Hash"));
+        assert!(is_builtin_derive(&path("std::hash::Hash"), "Hash"));
+        assert!(!is_builtin_derive(&path("Debug"), "Hash"));
+        assert!(!is_builtin_derive(&path("my_crate::Hash"), "Hash"));
+    }
+
+    #[test]
+    fn keeps_requested_builtin_derives() {
+        let derives = |params: &[ParamType]| {
+            let mut stream = quote!(Debug, serde::Serialize,);
+            derive_builtin_traits(params, &mut stream, true, true);
+            stream.to_string()
+        };
+
+        // `Debug` is derived once
+        assert_eq!(
+            derives(&[ParamType::Bool]),
+            quote!(Debug, serde::Serialize, Default, PartialEq, Eq, Hash,).to_string()
+        );
+
+        // the builtin traits can't be derived, but the requested `Debug` is kept
+        let param = ParamType::Tuple(vec![ParamType::Bool; MAX_SUPPORTED_TUPLE_LEN + 1]);
+        assert_eq!(derives(&[param]), quote!(Debug, serde::Serialize,).to_string());
+    }
+
     #[test]
     fn can_detect_derives() {
         // array
@@ -2,7 +2,7 @@
 //! contract.
 
 use crate::spanned::Spanned;
-use corebc_contract_abigen::{multi::MultiExpansion, Abigen};
+use corebc_contract_abigen::{multi::MultiExpansion, Abigen, OverloadNaming};
 use proc_macro2::TokenStream;
 use std::collections::HashSet;
 use syn::{
@@ -11,7 +11,7 @@ use syn::{
     parenthesized,
     parse::{Error, Parse, ParseStream, Result},
     punctuated::Punctuated,
-    Ident, LitStr, Path, Token,
+    Ident, LitStr, Path, Token, Visibility,
 };
 
 /// A series of `ContractArgs` separated by `;`
@@ -67,6 +67,8 @@ impl ContractArgs {
                     .method_aliases_mut()
                     .extend(methods.into_iter().map(|m| (m.signature, m.alias.to_string()))),
                 Parameter::Derives(derives) => builder.derives_mut().extend(derives),
+                Parameter::Overloads(naming) => *builder.overload_naming_mut() = naming,
+                Parameter::Visibility(visibility) => *builder.visibility_mut() = visibility,
             }
         }
 
@@ -112,6 +114,8 @@ impl Parse for ContractArgs {
 enum Parameter {
     Methods(Vec<Method>),
     Derives(Punctuated<Path, Token![,]>),
+    Overloads(OverloadNaming),
+    Visibility(Visibility),
 }
 
 impl Parse for Parameter {
@@ -143,6 +147,19 @@ impl Parse for Parameter {
                 let derives = content.parse_terminated(Path::parse, Token![,])?;
                 Ok(Parameter::Derives(derives))
             }
+            "overloads" => {
+                let content;
+                parenthesized!(content in input);
+                let naming = content.parse::<Ident>()?;
+                let naming =
+                    naming.to_string().parse().map_err(|err| Error::new(naming.span(), err))?;
+                Ok(Parameter::Overloads(naming))
+            }
+            "visibility" => {
+                let content;
+                parenthesized!(content in input);
+                Ok(Parameter::Visibility(content.parse()?))
+            }
             _ => Err(Error::new(name.span(), "unexpected named parameter")),
         }
     }
@@ -398,6 +415,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_contract_args_with_overloads_and_visibility() {
+        let args = contract_args!(
+            TestContract,
+            "abi.json",
+            overloads(types),
+            visibility(pub(crate))
+        );
+        assert_eq!(
+            *args.first().unwrap(),
+            arg(
+                "TestContract",
+                "abi.json",
+                [
+                    Parameter::Overloads(OverloadNaming::Types),
+                    Parameter::Visibility(syn::parse_quote!(pub(crate)))
+                ],
+                false
+            )
+        );
+
+        contract_args_err!(TestContract, "abi.json", overloads(suffix));
+    }
+
     #[test]
     fn duplicate_method_rename_error() {
         contract_args_err!(
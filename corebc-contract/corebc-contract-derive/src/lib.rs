@@ -90,6 +90,22 @@ pub(crate) mod utils;
 ///
 /// Aliases for overloaded functions with no aliases provided in the `method` section are derived
 /// automatically.
+/// By default they are named after the parameters that tell them apart, `overloads(index)` numbers
+/// them instead (`my_method0`, `my_method1`, ...) and `overloads(types)` appends the parameter
+/// types (`my_method_address_uint256`).
+///
+/// The generated module is public, this can be changed with the `visibility` parameter:
+///
+/// ```ignore
+/// abigen!(
+///     MyContract,
+///     "path/to/MyContract.json",
+///     overloads(types),
+///     visibility(pub(crate)),
+///     derives(serde::Deserialize, serde::Serialize),
+/// );
+/// ```
+///
 ///
 /// `abigen!` supports multiple abigen definitions separated by a semicolon `;`
 /// This is useful if the contracts use ABIEncoderV2 structs. In which case
@@ -0,0 +1,28 @@
+#![allow(missing_docs)]
+use corebc_contract_derive::abigen;
+
+abigen!(
+    Ownable,
+    r#"[
+        function owner() external view returns (address)
+        function transferOwnership(address newOwner) external
+        function renounceOwnership() external
+        event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)
+    ]"#
+);
+
+abigen!(
+    AccessControl,
+    r#"[
+        function hasRole(bytes32 role, address account) external view returns (bool)
+        function getRoleAdmin(bytes32 role) external view returns (bytes32)
+        function grantRole(bytes32 role, address account) external
+        function revokeRole(bytes32 role, address account) external
+        function renounceRole(bytes32 role, address account) external
+        function getRoleMemberCount(bytes32 role) external view returns (uint256)
+        function getRoleMember(bytes32 role, uint256 index) external view returns (address)
+        event RoleAdminChanged(bytes32 indexed role, bytes32 indexed previousAdminRole, bytes32 indexed newAdminRole)
+        event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender)
+        event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender)
+    ]"#
+);
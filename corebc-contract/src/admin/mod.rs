@@ -0,0 +1,287 @@
+//! Bindings for common admin patterns, and a scanner reporting which of them a contract uses
+
+use crate::{ContractCall, ContractError};
+use corebc_core::{abi::Detokenize, types::Address, utils::sha3};
+use corebc_providers::{Middleware, MiddlewareError};
+use std::sync::Arc;
+
+/// The `Ownable` and `AccessControl` bindings. Auto-generated with `abigen`.
+pub mod contract;
+pub use contract::{AccessControl, Ownable};
+
+/// The role administering all roles of an `AccessControl` contract unless configured otherwise
+pub const DEFAULT_ADMIN_ROLE: [u8; 32] = [0; 32];
+
+/// Roles commonly defined by `AccessControl` contracts, probed by default by an [`AdminScanner`]
+pub const WELL_KNOWN_ROLES: [&str; 5] =
+    ["MINTER_ROLE", "PAUSER_ROLE", "BURNER_ROLE", "UPGRADER_ROLE", "OPERATOR_ROLE"];
+
+/// The maximum amount of members of a single role listed via `getRoleMember`
+pub const MAX_ENUMERATED_MEMBERS: usize = 256;
+
+/// Returns the identifier of the role `name`, i.e. the sha3 hash of the name
+pub fn role_id(name: &str) -> [u8; 32] {
+    sha3(name)
+}
+
+/// A role of an `AccessControl` contract
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleInfo {
+    /// The name of the role, if known
+    pub name: Option<String>,
+    /// The identifier of the role
+    pub role: [u8; 32],
+    /// The role administering this role
+    pub admin_role: [u8; 32],
+    /// The accounts holding this role that were found
+    pub members: Vec<Address>,
+}
+
+/// The admin patterns detected on a contract by an [`AdminScanner`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdminReport {
+    /// The owner of the contract, if it implements `owner()`
+    pub owner: Option<Address>,
+    /// Whether the contract implements `AccessControl`
+    pub access_control: bool,
+    /// Whether the members of roles can be listed via `getRoleMember`
+    pub enumerable: bool,
+    /// The roles with at least one member found, the default admin role is always included if the
+    /// contract implements `AccessControl`
+    pub roles: Vec<RoleInfo>,
+}
+
+/// Detects the owners and admin roles of contracts by probing the selectors of `Ownable` and
+/// `AccessControl`.
+///
+/// Unless the contract implements `AccessControlEnumerable`, the members of a role can't be
+/// listed, so the scanner checks whether the owner and the given candidate accounts hold each
+/// role instead.
+///
+/// ```no_run
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use corebc_contract::admin::AdminScanner;
+/// use corebc_core::types::Address;
+/// use corebc_providers::{Http, Provider};
+/// use std::sync::Arc;
+///
+/// let provider = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+/// let token: Address = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse()?;
+///
+/// let report = AdminScanner::new(provider).with_role("ORACLE_ROLE").scan(token).await?;
+/// if let Some(owner) = report.owner {
+///     println!("owned by {owner:?}");
+/// }
+/// for role in report.roles {
+///     println!("{:?}: {:?}", role.name, role.members);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AdminScanner<M> {
+    client: Arc<M>,
+    candidates: Vec<Address>,
+    roles: Vec<(Option<String>, [u8; 32])>,
+}
+
+impl<M> Clone for AdminScanner<M> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            candidates: self.candidates.clone(),
+            roles: self.roles.clone(),
+        }
+    }
+}
+
+impl<M: Middleware> AdminScanner<M> {
+    /// Creates a new scanner probing the default admin role and the [`WELL_KNOWN_ROLES`]
+    pub fn new(client: Arc<M>) -> Self {
+        let roles = std::iter::once(("DEFAULT_ADMIN_ROLE", DEFAULT_ADMIN_ROLE))
+            .chain(WELL_KNOWN_ROLES.iter().map(|name| (*name, role_id(name))))
+            .map(|(name, role)| (Some(name.to_string()), role))
+            .collect();
+        Self { client, candidates: Vec::new(), roles }
+    }
+
+    /// Adds accounts to check for membership of roles that can't be enumerated
+    #[must_use]
+    pub fn with_candidates(mut self, candidates: impl IntoIterator<Item = Address>) -> Self {
+        self.candidates.extend(candidates);
+        self
+    }
+
+    /// Adds the role `name` to the probed roles
+    #[must_use]
+    pub fn with_role(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        let role = role_id(&name);
+        self.roles.push((Some(name), role));
+        self
+    }
+
+    /// Adds a role with an unknown name to the probed roles
+    #[must_use]
+    pub fn with_role_id(mut self, role: [u8; 32]) -> Self {
+        self.roles.push((None, role));
+        self
+    }
+
+    /// Probes the contract at `address` for an owner and `AccessControl` roles
+    pub async fn scan(&self, address: Address) -> Result<AdminReport, ContractError<M>> {
+        let mut report = AdminReport::default();
+
+        let ownable = Ownable::new(address, self.client.clone());
+        report.owner = probe(ownable.owner()).await?;
+
+        let access_control = AccessControl::new(address, self.client.clone());
+        if probe(access_control.get_role_admin(DEFAULT_ADMIN_ROLE)).await?.is_none() {
+            return Ok(report)
+        }
+        report.access_control = true;
+        report.enumerable =
+            probe(access_control.get_role_member_count(DEFAULT_ADMIN_ROLE)).await?.is_some();
+
+        let candidates = report.owner.iter().chain(&self.candidates).copied().collect::<Vec<_>>();
+        for (name, role) in &self.roles {
+            let role = *role;
+            let mut members = Vec::new();
+            if report.enumerable {
+                let count = probe(access_control.get_role_member_count(role)).await?;
+                let count = count.unwrap_or_default().min(MAX_ENUMERATED_MEMBERS.into());
+                for index in 0..count.as_usize() {
+                    if let Some(member) =
+                        probe(access_control.get_role_member(role, index.into())).await?
+                    {
+                        members.push(member);
+                    }
+                }
+            } else {
+                for candidate in &candidates {
+                    let has_role = probe(access_control.has_role(role, *candidate)).await?;
+                    if has_role.unwrap_or_default() && !members.contains(candidate) {
+                        members.push(*candidate);
+                    }
+                }
+            }
+
+            if members.is_empty() && role != DEFAULT_ADMIN_ROLE {
+                continue
+            }
+            let admin_role =
+                probe(access_control.get_role_admin(role)).await?.unwrap_or(DEFAULT_ADMIN_ROLE);
+            report.roles.push(RoleInfo { name: name.clone(), role, admin_role, members });
+        }
+
+        Ok(report)
+    }
+}
+
+/// Executes the call, returning `None` if the contract does not implement the function, i.e. the
+/// call reverts or returns data that can't be decoded
+async fn probe<M: Middleware, D: Detokenize>(
+    call: ContractCall<M, D>,
+) -> Result<Option<D>, ContractError<M>> {
+    match call.call().await {
+        Ok(value) => Ok(Some(value)),
+        Err(
+            ContractError::Revert(_) |
+            ContractError::AbiError(_) |
+            ContractError::DecodingError(_) |
+            ContractError::DetokenizationError(_),
+        ) => Ok(None),
+        Err(ContractError::MiddlewareError { e }) if e.as_error_response().is_some() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::{
+        abi::{self, Token},
+        types::Bytes,
+        utils::id,
+    };
+    use corebc_providers::{JsonRpcError, Provider};
+    use serde_json::{json, Value};
+
+    fn calls(selector: &str) -> impl Fn(&Value) -> bool + Send + Sync + 'static {
+        let selector = format!("0x{}", hex::encode(id(selector)));
+        move |params: &Value| {
+            params[0]["data"].as_str().map_or(false, |data| data.starts_with(&selector))
+        }
+    }
+
+    fn returns(token: Token) -> Bytes {
+        abi::encode(&[token]).into()
+    }
+
+    #[tokio::test]
+    async fn scans_owner_and_roles() {
+        let (provider, mock) = Provider::mocked();
+        let contract: Address = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse().unwrap();
+        let owner: Address = "0x0000fb6916095ca1df60bb79ce92ce3ea74c37c5d359".parse().unwrap();
+        let minter = Address::repeat_byte(7);
+        let not_supported =
+            JsonRpcError { code: -32000, message: "execution reverted".to_string(), data: None };
+
+        mock.expect("xcb_call")
+            .with_params_matching(calls("owner()"))
+            .returns(returns(Token::Address(owner)));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("getRoleAdmin(bytes32)"))
+            .returns(returns(Token::FixedBytes(DEFAULT_ADMIN_ROLE.to_vec())));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("getRoleMemberCount(bytes32)"))
+            .return_error(not_supported);
+        // the owner holds the default admin role, the candidate is the minter
+        let grants = [
+            hex::encode(abi::encode(&[
+                Token::FixedBytes(DEFAULT_ADMIN_ROLE.to_vec()),
+                Token::Address(owner),
+            ])),
+            hex::encode(abi::encode(&[
+                Token::FixedBytes(role_id("MINTER_ROLE").to_vec()),
+                Token::Address(minter),
+            ])),
+        ];
+        mock.expect("xcb_call")
+            .with_params_matching(move |params| {
+                calls("hasRole(bytes32,address)")(params) &&
+                    grants.iter().any(|args| params[0]["data"].as_str().unwrap().ends_with(args))
+            })
+            .returns(returns(Token::Bool(true)));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("hasRole(bytes32,address)"))
+            .returns(returns(Token::Bool(false)));
+
+        let report =
+            AdminScanner::new(Arc::new(provider)).with_candidates([minter]).scan(contract).await;
+        let report = report.unwrap();
+
+        assert_eq!(report.owner, Some(owner));
+        assert!(report.access_control);
+        assert!(!report.enumerable);
+        assert_eq!(report.roles.len(), 2);
+        assert_eq!(report.roles[0].name.as_deref(), Some("DEFAULT_ADMIN_ROLE"));
+        assert_eq!(report.roles[0].members, vec![owner]);
+        assert_eq!(report.roles[1].name.as_deref(), Some("MINTER_ROLE"));
+        assert_eq!(report.roles[1].role, role_id("MINTER_ROLE"));
+        assert_eq!(report.roles[1].admin_role, DEFAULT_ADMIN_ROLE);
+        assert_eq!(report.roles[1].members, vec![minter]);
+    }
+
+    #[tokio::test]
+    async fn reports_nothing_for_plain_contracts() {
+        let (provider, mock) = Provider::mocked();
+        let not_supported =
+            JsonRpcError { code: -32000, message: "execution reverted".to_string(), data: None };
+        mock.expect("xcb_call").return_error(not_supported);
+
+        let report = AdminScanner::new(Arc::new(provider)).scan(Address::zero()).await.unwrap();
+        assert_eq!(report, AdminReport::default());
+    }
+}
@@ -0,0 +1,248 @@
+//! Caching of the results of read-only contract calls
+
+use corebc_core::{
+    abi::{Function, StateMutability},
+    types::{transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes, U64},
+};
+use corebc_providers::Middleware;
+use futures_util::StreamExt;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+/// The functions whose results are cached forever by default
+const IMMUTABLE_FUNCTIONS: [&str; 3] = ["name", "symbol", "decimals"];
+
+/// A cache of the results of `view` and `pure` contract calls, see
+/// [`ContractInstance::with_cache`](crate::ContractInstance::with_cache)
+///
+/// Results of immutable functions, `name()`, `symbol()` and `decimals()` by default, are cached
+/// forever. Results of other calls against the latest block are cached for the current head and
+/// dropped once a new head is [observed](Self::observe_head), calls are not cached before the
+/// first head was observed. Calls against a specific block and calls of state mutating functions
+/// are never cached.
+///
+/// The cache can be shared between contracts, e.g. keep it up to date with a single
+/// [`watch_heads`](Self::watch_heads) task.
+#[derive(Debug)]
+pub struct CallCache {
+    immutable: HashSet<String>,
+    permanent: Mutex<HashMap<CallKey, Bytes>>,
+    latest: Mutex<LatestEntries>,
+}
+
+/// The results cached for the current head
+#[derive(Debug, Default)]
+struct LatestEntries {
+    head: Option<U64>,
+    results: HashMap<CallKey, Bytes>,
+}
+
+/// Identifies a call by its sender, contract and calldata
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CallKey {
+    from: Option<Address>,
+    to: Address,
+    data: Bytes,
+}
+
+/// Where the result of a call is cached
+#[derive(Clone, Debug)]
+pub(crate) enum CacheSlot {
+    /// Cached forever
+    Permanent(CallKey),
+    /// Cached until a head other than this one is observed
+    Latest(U64, CallKey),
+}
+
+impl Default for CallCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CallCache {
+    /// Creates an empty cache that caches `name()`, `symbol()` and `decimals()` forever
+    pub fn new() -> Self {
+        Self {
+            immutable: IMMUTABLE_FUNCTIONS.iter().map(ToString::to_string).collect(),
+            permanent: Default::default(),
+            latest: Default::default(),
+        }
+    }
+
+    /// Caches the results of the function named `name` forever
+    #[must_use]
+    pub fn immutable(mut self, name: impl Into<String>) -> Self {
+        self.immutable.insert(name.into());
+        self
+    }
+
+    /// Returns the head the cached results of latest calls belong to, if any head was observed
+    pub fn head(&self) -> Option<U64> {
+        self.latest.lock().unwrap().head
+    }
+
+    /// Records the block number of the current head, dropping the results of latest calls if it
+    /// changed
+    pub fn observe_head(&self, number: impl Into<U64>) {
+        let number = number.into();
+        let mut latest = self.latest.lock().unwrap();
+        if latest.head != Some(number) {
+            latest.head = Some(number);
+            latest.results.clear();
+        }
+    }
+
+    /// Drops all cached results, including those of immutable functions
+    pub fn clear(&self) {
+        self.permanent.lock().unwrap().clear();
+        self.latest.lock().unwrap().results.clear();
+    }
+
+    /// Observes every new head of `client` until its block stream ends.
+    ///
+    /// This is usually spawned as a background task next to the contracts sharing the cache.
+    pub async fn watch_heads<M: Middleware>(&self, client: &M) -> Result<(), M::Error> {
+        let mut blocks = client.watch_blocks().await?;
+        self.observe_head(client.get_block_number().await?);
+        while blocks.next().await.is_some() {
+            self.observe_head(client.get_block_number().await?);
+        }
+        Ok(())
+    }
+
+    /// Returns where the result of calling `function` with `tx` at `block` is cached, `None` if
+    /// it can't be cached
+    pub(crate) fn slot(
+        &self,
+        function: &Function,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Option<CacheSlot> {
+        if !matches!(function.state_mutability, StateMutability::View | StateMutability::Pure) {
+            return None
+        }
+        let key =
+            CallKey { from: tx.from().copied(), to: *tx.to_addr()?, data: tx.data()?.clone() };
+
+        if self.immutable.contains(&function.name) {
+            return Some(CacheSlot::Permanent(key))
+        }
+        match block {
+            None | Some(BlockId::Number(BlockNumber::Latest)) => {
+                self.head().map(|head| CacheSlot::Latest(head, key))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Returns the cached result of a call
+    pub(crate) fn get(&self, slot: &CacheSlot) -> Option<Bytes> {
+        match slot {
+            CacheSlot::Permanent(key) => self.permanent.lock().unwrap().get(key).cloned(),
+            CacheSlot::Latest(head, key) => {
+                let latest = self.latest.lock().unwrap();
+                latest.results.get(key).filter(|_| latest.head == Some(*head)).cloned()
+            }
+        }
+    }
+
+    /// Caches the result of a call, results of latest calls are discarded if a new head was
+    /// observed while the call was in flight
+    pub(crate) fn insert(&self, slot: CacheSlot, result: Bytes) {
+        match slot {
+            CacheSlot::Permanent(key) => {
+                self.permanent.lock().unwrap().insert(key, result);
+            }
+            CacheSlot::Latest(head, key) => {
+                let mut latest = self.latest.lock().unwrap();
+                if latest.head == Some(head) {
+                    latest.results.insert(key, result);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::Contract;
+    use corebc_core::{
+        abi::{self, parse_abi, Token},
+        types::U256,
+        utils::id,
+    };
+    use corebc_providers::Provider;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    fn calls(selector: &str) -> impl Fn(&Value) -> bool + Send + Sync + 'static {
+        let selector = format!("0x{}", hex::encode(id(selector)));
+        move |params: &Value| {
+            params[0]["data"].as_str().map_or(false, |data| data.starts_with(&selector))
+        }
+    }
+
+    fn returns(token: Token) -> Bytes {
+        abi::encode(&[token]).into()
+    }
+
+    #[tokio::test]
+    async fn caches_view_calls() {
+        let (provider, mock) = Provider::mocked();
+        let abi = parse_abi(&[
+            "function symbol() external view returns (string)",
+            "function totalSupply() external view returns (uint256)",
+            "function mint() external returns (uint256)",
+        ])
+        .unwrap();
+        let cache = Arc::new(CallCache::new());
+        let contract = Contract::new(Address::repeat_byte(1), abi, Arc::new(provider))
+            .with_cache(cache.clone());
+        mock.expect("xcb_call")
+            .with_params_matching(calls("symbol()"))
+            .times(1)
+            .returns(returns(Token::String("CTN".to_string())));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("totalSupply()"))
+            .times(2)
+            .returns(returns(Token::Uint(100.into())));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("totalSupply()"))
+            .times(1)
+            .returns(returns(Token::Uint(200.into())));
+        mock.expect("xcb_call")
+            .with_params_matching(calls("mint()"))
+            .times(2)
+            .returns(returns(Token::Uint(1.into())));
+
+        let symbol = contract.method::<_, String>("symbol", ()).unwrap();
+        let total_supply = contract.method::<_, U256>("totalSupply", ()).unwrap();
+        let mint = contract.method::<_, U256>("mint", ()).unwrap();
+
+        // immutable functions are cached forever
+        assert_eq!(symbol.call().await.unwrap(), "CTN");
+        assert_eq!(symbol.call().await.unwrap(), "CTN");
+
+        // nothing is cached before the first head is observed
+        assert_eq!(total_supply.call().await.unwrap(), U256::from(100));
+        cache.observe_head(10u64);
+        assert_eq!(total_supply.call().await.unwrap(), U256::from(100));
+        assert_eq!(total_supply.call().await.unwrap(), U256::from(100));
+        // the same head does not invalidate the results
+        cache.observe_head(10u64);
+        assert_eq!(total_supply.call().await.unwrap(), U256::from(100));
+        cache.observe_head(11u64);
+        assert_eq!(total_supply.call().await.unwrap(), U256::from(200));
+        assert_eq!(symbol.call().await.unwrap(), "CTN");
+
+        // state mutating functions are never cached
+        assert_eq!(mint.call().await.unwrap(), U256::one());
+        assert_eq!(mint.call().await.unwrap(), U256::one());
+        mock.verify().unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use crate::{error::ContractRevert, EthError};
+use crate::{error::ContractRevert, CallCache, EthError};
 
 use super::base::{decode_function_data, AbiError};
 use corebc_core::{
@@ -19,6 +19,7 @@ use std::{
     future::{Future, IntoFuture},
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
 };
 
 use thiserror::Error as ThisError;
@@ -185,6 +186,7 @@ pub struct FunctionCall<B, M, D> {
     /// Optional block number to be used when calculating the transaction's energy and nonce
     pub block: Option<BlockId>,
     pub(crate) client: B,
+    pub(crate) cache: Option<Arc<CallCache>>,
     pub(crate) datatype: PhantomData<D>,
     pub(crate) _m: PhantomData<M>,
 }
@@ -199,6 +201,7 @@ where
             function: self.function.clone(),
             block: self.block,
             client: self.client.clone(),
+            cache: self.cache.clone(),
             datatype: self.datatype,
             _m: self._m,
         }
@@ -241,6 +244,19 @@ where
         self.block = Some(block.into());
         self
     }
+
+    /// Sets the cache the result of [`call`](Self::call) is read from and written to, see
+    /// [`CallCache`]
+    pub fn cache(mut self, cache: Arc<CallCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Always queries the blockchain on [`call`](Self::call), even if the contract has a cache
+    pub fn no_cache(mut self) -> Self {
+        self.cache = None;
+        self
+    }
 }
 
 impl<B, M, D> FunctionCall<B, M, D>
@@ -271,14 +287,32 @@ where
     /// If executed on a mutating smart contract function, it will do a "dry run" of the call
     /// and return the return type of the transaction without mutating the state
     ///
+    /// If a [`CallCache`] is set, the result of `view` and `pure` functions is read from and
+    /// written to the cache.
+    ///
     /// Note: this function _does not_ send a transaction from your account
     pub async fn call(&self) -> Result<D, ContractError<M>> {
-        let bytes = self
-            .client
-            .borrow()
-            .call(&self.tx, self.block)
-            .await
-            .map_err(ContractError::from_middleware_error)?;
+        let slot =
+            self.cache.as_ref().and_then(|cache| cache.slot(&self.function, &self.tx, self.block));
+        let cached = match (&self.cache, &slot) {
+            (Some(cache), Some(slot)) => cache.get(slot),
+            _ => None,
+        };
+        let bytes = match cached {
+            Some(bytes) => bytes,
+            None => {
+                let bytes = self
+                    .client
+                    .borrow()
+                    .call(&self.tx, self.block)
+                    .await
+                    .map_err(ContractError::from_middleware_error)?;
+                if let (Some(cache), Some(slot)) = (&self.cache, slot) {
+                    cache.insert(slot, bytes.clone());
+                }
+                bytes
+            }
+        };
 
         // decode output
         let data = decode_function_data(&self.function, &bytes, false)?;
@@ -2,7 +2,7 @@ use crate::{
     base::{encode_function_data, AbiError, BaseContract},
     call::FunctionCall,
     event::{EthEvent, Event},
-    EthLogDecode,
+    CallCache, EthLogDecode,
 };
 use corebc_core::{
     abi::{Abi, Detokenize, Error, EventExt, Function, Tokenize},
@@ -171,6 +171,7 @@ pub struct ContractInstance<B, M> {
     address: Address,
     base_contract: BaseContract,
     client: B,
+    cache: Option<Arc<CallCache>>,
     _m: PhantomData<M>,
 }
 
@@ -194,6 +195,7 @@ where
             base_contract: self.base_contract.clone(),
             client: self.client.clone(),
             address: self.address,
+            cache: self.cache.clone(),
             _m: self._m,
         }
     }
@@ -225,6 +227,19 @@ where
     pub fn client_ref(&self) -> &M {
         self.client.borrow()
     }
+
+    /// Returns the cache of the contract's read calls, if any.
+    pub fn cache(&self) -> Option<&Arc<CallCache>> {
+        self.cache.as_ref()
+    }
+
+    /// Caches the results of the contract's `view` and `pure` calls in `cache`, see
+    /// [`CallCache`]
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<CallCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
 impl<B, M> ContractInstance<B, M>
@@ -252,7 +267,13 @@ where
 {
     /// Creates a new contract from the provided client, abi and address
     pub fn new(address: impl Into<Address>, abi: impl Into<BaseContract>, client: B) -> Self {
-        Self { base_contract: abi.into(), client, address: address.into(), _m: PhantomData }
+        Self {
+            base_contract: abi.into(),
+            client,
+            address: address.into(),
+            cache: None,
+            _m: PhantomData,
+        }
     }
 
     /// Returns a new contract instance using the provided client
@@ -267,6 +288,7 @@ where
             base_contract: self.base_contract.clone(),
             client,
             address: self.address,
+            cache: self.cache.clone(),
             _m: PhantomData,
         }
     }
@@ -283,6 +305,7 @@ where
             base_contract: self.base_contract.clone(),
             client,
             address: self.address,
+            cache: self.cache.clone(),
             _m: PhantomData,
         }
     }
@@ -333,6 +356,7 @@ where
         Ok(FunctionCall {
             tx,
             client: self.client.clone(),
+            cache: self.cache.clone(),
             block: None,
             function: function.to_owned(),
             datatype: PhantomData,
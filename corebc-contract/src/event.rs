@@ -152,6 +152,9 @@ where
     /// matching log is available, instead we have to actively ask for new logs using additional RPC
     /// requests, and this is done on an interval basis.
     ///
+    /// Logs removed by a chain reorganization are dropped, use [`Self::stream_with_meta`] to
+    /// handle them.
+    ///
     /// # Example
     // Ignore because `corebc-contract-derive` macros do not work in doctests in `corebc-contract`.
     /// ```ignore
@@ -197,6 +200,9 @@ where
     }
 
     /// As [`Self::stream`], but does not discard [`Log`] metadata.
+    ///
+    /// Unlike [`Self::stream`], events of logs removed by a chain reorganization are yielded as
+    /// well, with [`LogMeta::removed`] set, so previously handled events can be compensated for.
     pub async fn stream_with_meta(
         &self,
     ) -> Result<
@@ -217,7 +223,8 @@ where
                 let meta = LogMeta::from(&log);
                 Ok((parse_log(log)?, meta))
             }),
-        ))
+        )
+        .include_removed())
     }
 }
 
@@ -230,6 +237,9 @@ where
 {
     /// Returns a subscription for the event
     ///
+    /// Logs removed by a chain reorganization are dropped, use [`Self::subscribe_with_meta`] to
+    /// handle them.
+    ///
     /// See also [Self::stream()].
     pub async fn subscribe(
         &self,
@@ -247,7 +257,11 @@ where
         Ok(EventStream::new(filter.id, filter, Box::new(move |log| Ok(parse_log(log)?))))
     }
 
-    /// As [`Self::subscribe`], but includes event metadata
+    /// As [`Self::subscribe`], but includes event metadata.
+    ///
+    /// Unlike [`Self::subscribe`], events of logs removed by a chain reorganization are yielded
+    /// as well, with [`LogMeta::removed`] set, so previously handled events can be compensated
+    /// for.
     pub async fn subscribe_with_meta(
         &self,
     ) -> Result<
@@ -268,7 +282,8 @@ where
                 let meta = LogMeta::from(&log);
                 Ok((parse_log(log)?, meta))
             }),
-        ))
+        )
+        .include_removed())
     }
 }
 
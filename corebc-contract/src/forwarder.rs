@@ -0,0 +1,435 @@
+//! Gas-less meta-transactions, executed by a trusted forwarder on behalf of their signer
+
+use crate::{Contract, ContractCall, ContractError, FunctionCall};
+use corebc_core::{
+    abi::{self, parse_abi, Abi, Token},
+    types::{
+        transaction::cip712::{CIP712Domain, Cip712, Cip712Error},
+        Address, Bytes, TxHash, U256,
+    },
+    utils::sha3,
+};
+use corebc_providers::{Http, JsonRpcClient, Middleware, Provider, ProviderError};
+#[cfg(feature = "signers")]
+use corebc_signers::Signer;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The CIP-712 type of a [`ForwardRequest`]
+pub const FORWARD_REQUEST_TYPE: &str =
+    "ForwardRequest(address from,address to,uint256 value,uint256 energy,uint256 nonce,bytes data)";
+
+/// The JSON-RPC method a [`Relayer`] submits signed forward requests with by default
+pub const RELAY_METHOD: &str = "relay_sendForwardRequest";
+
+static FORWARDER_ABI: Lazy<Abi> = Lazy::new(|| {
+    parse_abi(&[
+        concat!(
+            "struct ForwardRequest { address from; address to; uint256 value; uint256 energy; ",
+            "uint256 nonce; bytes data; }"
+        ),
+        "function getNonce(address from) external view returns (uint256)",
+        "function verify(ForwardRequest req, bytes signature) external view returns (bool)",
+        concat!(
+            "function execute(ForwardRequest req, bytes signature) external payable ",
+            "returns (bool, bytes)"
+        ),
+    ])
+    .expect("forwarder ABI is valid")
+});
+
+/// A call the [`Forwarder`] executes on behalf of `from`, the `ForwardRequest` of a
+/// `MinimalForwarder`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardRequest {
+    /// The signer, the recipient sees it as `_msgSender()`
+    pub from: Address,
+    /// The called contract
+    pub to: Address,
+    /// The value the forwarder sends along, paid by the submitter of the meta-transaction
+    pub value: U256,
+    /// The energy the forwarder passes to the call
+    pub energy: U256,
+    /// The nonce of `from` at the forwarder
+    pub nonce: U256,
+    /// The input data of the call
+    pub data: Bytes,
+}
+
+impl ForwardRequest {
+    fn into_token(self) -> Token {
+        Token::Tuple(vec![
+            Token::Address(self.from),
+            Token::Address(self.to),
+            Token::Uint(self.value),
+            Token::Uint(self.energy),
+            Token::Uint(self.nonce),
+            Token::Bytes(self.data.to_vec()),
+        ])
+    }
+}
+
+/// A [`ForwardRequest`] in the CIP-712 domain of the forwarder that verifies it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedForwardRequest {
+    /// The domain of the forwarder
+    pub domain: CIP712Domain,
+    /// The request
+    pub request: ForwardRequest,
+}
+
+impl Cip712 for TypedForwardRequest {
+    type Error = Cip712Error;
+
+    fn domain(&self) -> Result<CIP712Domain, Self::Error> {
+        Ok(self.domain.clone())
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(sha3(FORWARD_REQUEST_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        let request = &self.request;
+        Ok(sha3(abi::encode(&[
+            Token::Uint(U256::from(Self::type_hash()?)),
+            Token::Address(request.from),
+            Token::Address(request.to),
+            Token::Uint(request.value),
+            Token::Uint(request.energy),
+            Token::Uint(request.nonce),
+            Token::Uint(U256::from(sha3(&request.data))),
+        ])))
+    }
+}
+
+/// A [`ForwardRequest`] signed by its `from`, ready to be executed by the forwarder
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedForwardRequest {
+    /// The forwarder that executes the request
+    pub forwarder: Address,
+    /// The request
+    pub request: ForwardRequest,
+    /// The CIP-712 signature of the request
+    pub signature: Bytes,
+}
+
+/// Error thrown when wrapping a call in a meta-transaction
+#[cfg(feature = "signers")]
+#[derive(Debug, thiserror::Error)]
+pub enum ForwarderError<M: Middleware, S: Signer> {
+    /// Thrown when the request could not be built
+    #[error(transparent)]
+    ContractError(#[from] ContractError<M>),
+
+    /// Thrown when the signer could not sign the request
+    #[error("{0}")]
+    SignerError(S::Error),
+}
+
+/// Wraps contract calls in meta-transactions of a trusted forwarder, a `MinimalForwarder` or a
+/// compatible contract, so that the signer of a call does not pay for its energy.
+///
+/// The forwarder executes a signed [`ForwardRequest`] on behalf of its signer. Contracts that
+/// trust the forwarder, CIP-2771 recipients, read the signer with `_msgSender()`. The signed
+/// request is either [executed](Self::execute) by this forwarder's client or submitted to a
+/// [`Relayer`].
+#[derive(Debug)]
+pub struct Forwarder<M> {
+    contract: Contract<M>,
+    name: String,
+    version: String,
+}
+
+impl<M> Clone for Forwarder<M> {
+    fn clone(&self) -> Self {
+        Self {
+            contract: self.contract.clone(),
+            name: self.name.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<M: Middleware> Forwarder<M> {
+    /// The CIP-712 domain name of a `MinimalForwarder`
+    pub const DEFAULT_NAME: &'static str = "MinimalForwarder";
+    /// The CIP-712 domain version of a `MinimalForwarder`
+    pub const DEFAULT_VERSION: &'static str = "0.0.1";
+
+    /// Creates a forwarder for the `MinimalForwarder` at `address`
+    pub fn new(address: impl Into<Address>, client: Arc<M>) -> Self {
+        Self {
+            contract: Contract::new(address, FORWARDER_ABI.clone(), client),
+            name: Self::DEFAULT_NAME.to_string(),
+            version: Self::DEFAULT_VERSION.to_string(),
+        }
+    }
+
+    /// Sets the name and version of the forwarder's CIP-712 domain
+    #[must_use]
+    pub fn domain_name(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.name = name.into();
+        self.version = version.into();
+        self
+    }
+
+    /// Returns the address of the forwarder
+    pub fn address(&self) -> Address {
+        self.contract.address()
+    }
+
+    /// Returns the CIP-712 domain of the forwarder on the network with `network_id`
+    pub fn domain(&self, network_id: u64) -> CIP712Domain {
+        CIP712Domain {
+            name: Some(self.name.clone()),
+            version: Some(self.version.clone()),
+            network_id: Some(network_id.into()),
+            verifying_contract: Some(self.address()),
+            salt: None,
+        }
+    }
+
+    /// Returns the nonce of the next request of `from`
+    pub async fn nonce(&self, from: Address) -> Result<U256, ContractError<M>> {
+        self.contract.method("getNonce", from)?.call().await
+    }
+
+    /// Builds the request that executes `call` on behalf of `from`.
+    ///
+    /// Unless the energy of the call is set, it is estimated as if `from` sent the call itself.
+    pub async fn request<B, C, D>(
+        &self,
+        from: Address,
+        call: &FunctionCall<B, C, D>,
+    ) -> Result<ForwardRequest, ContractError<M>> {
+        let to = *call.tx.to_addr().ok_or_else(|| ContractError::ProviderError {
+            e: ProviderError::CustomError("meta-transactions must call a contract address".into()),
+        })?;
+        let energy = match call.tx.energy() {
+            Some(energy) => *energy,
+            None => {
+                let mut tx = call.tx.clone();
+                tx.set_from(from);
+                self.contract
+                    .client_ref()
+                    .estimate_energy(&tx, call.block)
+                    .await
+                    .map_err(ContractError::from_middleware_error)?
+            }
+        };
+        Ok(ForwardRequest {
+            from,
+            to,
+            value: call.tx.value().copied().unwrap_or_default(),
+            energy,
+            nonce: self.nonce(from).await?,
+            data: call.tx.data().cloned().unwrap_or_default(),
+        })
+    }
+
+    /// Signs the `request` in the forwarder's domain on the network of the `signer`
+    #[cfg(feature = "signers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signers")))]
+    pub async fn sign<S: Signer>(
+        &self,
+        request: ForwardRequest,
+        signer: &S,
+    ) -> Result<SignedForwardRequest, S::Error> {
+        let typed = TypedForwardRequest { domain: self.domain(signer.network_id()), request };
+        let signature = signer.sign_typed_data(&typed).await?;
+        Ok(SignedForwardRequest {
+            forwarder: self.address(),
+            request: typed.request,
+            signature: signature.to_vec().into(),
+        })
+    }
+
+    /// Wraps `call` in a meta-transaction signed by the `signer`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use corebc_contract::{BaseContract, Contract, Forwarder, Relayer};
+    /// use corebc_core::{
+    ///     abi::parse_abi,
+    ///     rand::thread_rng,
+    ///     types::{Address, Network},
+    /// };
+    /// use corebc_providers::{Http, Provider};
+    /// use corebc_signers::LocalWallet;
+    /// use std::sync::Arc;
+    ///
+    /// let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+    /// let wallet = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+    /// let abi: BaseContract = parse_abi(&["function mint(uint256 amount) external"])?.into();
+    /// let token = Contract::new(Address::random(), abi, client.clone());
+    ///
+    /// let forwarder = Forwarder::new(Address::random(), client);
+    /// let signed = forwarder.wrap(&token.method::<_, ()>("mint", 100u64)?, &wallet).await?;
+    /// let tx_hash = Relayer::try_from("https://relayer.example.com")?.relay(&signed).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "signers")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signers")))]
+    pub async fn wrap<B, C, D, S: Signer>(
+        &self,
+        call: &FunctionCall<B, C, D>,
+        signer: &S,
+    ) -> Result<SignedForwardRequest, ForwarderError<M, S>> {
+        let request = self.request(signer.address(), call).await?;
+        self.sign(request, signer).await.map_err(ForwarderError::SignerError)
+    }
+
+    /// Returns whether the forwarder accepts the `signed` request
+    pub fn verify(&self, signed: &SignedForwardRequest) -> ContractCall<M, bool> {
+        self.forwarder_call("verify", signed)
+    }
+
+    /// Executes the `signed` request, the client of the forwarder pays for the energy
+    pub fn execute(&self, signed: &SignedForwardRequest) -> ContractCall<M, (bool, Bytes)> {
+        self.forwarder_call("execute", signed).value(signed.request.value)
+    }
+
+    fn forwarder_call<D: abi::Detokenize>(
+        &self,
+        name: &str,
+        signed: &SignedForwardRequest,
+    ) -> ContractCall<M, D> {
+        let args = (signed.request.clone().into_token(), signed.signature.clone());
+        self.contract.method(name, args).expect("forwarder ABI has the function")
+    }
+}
+
+/// Submits signed forward requests to a relayer, which executes them and pays for their energy.
+///
+/// The relayer is a JSON-RPC endpoint that takes a [`SignedForwardRequest`] with the
+/// [`RELAY_METHOD`], or the method set with [`Relayer::method`], and returns the hash of the
+/// transaction that executes it.
+#[derive(Clone, Debug)]
+pub struct Relayer<P = Http> {
+    provider: Provider<P>,
+    method: String,
+}
+
+impl<P: JsonRpcClient> Relayer<P> {
+    /// Creates a relayer that submits requests through the `provider` with the [`RELAY_METHOD`]
+    pub fn new(provider: Provider<P>) -> Self {
+        Self { provider, method: RELAY_METHOD.to_string() }
+    }
+
+    /// Sets the JSON-RPC method the requests are submitted with, for relayers that do not use
+    /// the [`RELAY_METHOD`]
+    #[must_use]
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = method.into();
+        self
+    }
+
+    /// Submits the `signed` request, returns the hash of the transaction that executes it
+    pub async fn relay(&self, signed: &SignedForwardRequest) -> Result<TxHash, ProviderError> {
+        self.provider.request(&self.method, [signed]).await
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Relayer<Http> {
+    type Error = <Provider<Http> as TryFrom<&'a str>>::Error;
+
+    fn try_from(url: &'a str) -> Result<Self, Self::Error> {
+        Provider::try_from(url).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::abi::FunctionExt;
+    use serde_json::json;
+
+    #[tokio::test]
+    #[cfg(feature = "signers")]
+    async fn wraps_calls_in_meta_transactions() {
+        use crate::BaseContract;
+        use corebc_core::{
+            rand::thread_rng,
+            types::{Network, Signature},
+        };
+        use corebc_signers::LocalWallet;
+
+        let (provider, mock) = Provider::mocked();
+        let client = Arc::new(provider);
+        let forwarder = Forwarder::new(Address::repeat_byte(1), client.clone());
+        let wallet = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+        let token = Address::repeat_byte(2);
+
+        let abi = parse_abi(&["function mint(uint256 amount) external"]).unwrap();
+        let abi = BaseContract::from(abi);
+        let call = Contract::new(token, abi, client.clone())
+            .method::<_, ()>("mint", U256::from(100))
+            .unwrap()
+            .energy(50_000u64);
+        mock.expect("xcb_call")
+            .with_params_matching(move |params| params[0]["to"] == json!(Address::repeat_byte(1)))
+            .returns(Bytes::from(abi::encode(&[Token::Uint(7.into())])));
+
+        let signed = forwarder.wrap(&call, &wallet).await.unwrap();
+        let request = &signed.request;
+        assert_eq!(signed.forwarder, forwarder.address());
+        assert_eq!((request.from, request.to), (wallet.address(), token));
+        assert_eq!((request.energy, request.nonce), (50_000.into(), 7.into()));
+        assert_eq!(request.data, call.calldata().unwrap());
+
+        let typed = TypedForwardRequest {
+            domain: forwarder.domain(wallet.network_id()),
+            request: request.clone(),
+        };
+        let digest = typed.encode_cip712().unwrap();
+        let signature = Signature::try_from(signed.signature.as_ref()).unwrap();
+        assert_eq!(signature.recover(digest, &wallet.network()).unwrap(), wallet.address());
+        mock.verify().unwrap();
+
+        let (provider, mock) = Provider::mocked();
+        mock.expect("relay_sendForwardRequest")
+            .with_params(json!([signed]))
+            .returns(TxHash::repeat_byte(3));
+        let relayer = Relayer::new(provider);
+        assert_eq!(relayer.relay(&signed).await.unwrap(), TxHash::repeat_byte(3));
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn executes_and_relays_signed_requests() {
+        let (provider, _) = Provider::mocked();
+        let forwarder = Forwarder::new(Address::repeat_byte(1), Arc::new(provider));
+        let signed = SignedForwardRequest {
+            forwarder: forwarder.address(),
+            request: ForwardRequest { value: 5.into(), ..Default::default() },
+            signature: vec![1; 171].into(),
+        };
+
+        let execute = forwarder.execute(&signed);
+        assert_eq!(execute.tx.to_addr(), Some(&forwarder.address()));
+        assert_eq!(execute.tx.value(), Some(&5.into()));
+        assert_eq!(execute.calldata().unwrap()[..4], forwarder_selector("execute"));
+        assert_eq!(
+            forwarder.verify(&signed).calldata().unwrap()[..4],
+            forwarder_selector("verify")
+        );
+
+        let (provider, mock) = Provider::mocked();
+        mock.expect("gsn_relayTransaction")
+            .with_params(json!([signed]))
+            .returns(TxHash::repeat_byte(3));
+        let relayer = Relayer::new(provider).method("gsn_relayTransaction");
+        assert_eq!(relayer.relay(&signed).await.unwrap(), TxHash::repeat_byte(3));
+        mock.verify().unwrap();
+    }
+
+    fn forwarder_selector(name: &str) -> [u8; 4] {
+        FORWARDER_ABI.function(name).unwrap().selector()
+    }
+}
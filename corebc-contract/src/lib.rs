@@ -10,12 +10,24 @@ pub use _contract::{Contract, ContractInstance};
 mod base;
 pub use base::{decode_function_data, encode_function_data, AbiError, BaseContract};
 
+mod cache;
+pub use cache::CallCache;
+
 mod call;
 pub use call::{ContractCall, ContractError, EthCall, FunctionCall};
 
 mod error;
 pub use error::{ContractRevert, EthError};
 
+mod forwarder;
+#[cfg(feature = "signers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signers")))]
+pub use forwarder::ForwarderError;
+pub use forwarder::{
+    ForwardRequest, Forwarder, Relayer, SignedForwardRequest, TypedForwardRequest,
+    FORWARD_REQUEST_TYPE, RELAY_METHOD,
+};
+
 mod factory;
 pub use factory::{ContractDeployer, ContractDeploymentTx, ContractFactory, DeploymentTxFactory};
 
@@ -23,10 +35,24 @@ mod event;
 pub use event::{parse_log, EthEvent, Event};
 
 mod log;
-pub use log::{decode_logs, EthLogDecode, LogMeta};
+pub use log::{decode_logs, decode_receipt_logs, EthLogDecode, LogMeta};
+
+mod selectors;
+pub use selectors::{DecodedCall, SelectorRegistry};
 
 pub mod stream;
 
+#[cfg(any(test, feature = "corebc-ylem"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "corebc-ylem")))]
+mod storage;
+#[cfg(any(test, feature = "corebc-ylem"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "corebc-ylem")))]
+pub use storage::{StorageError, StorageReader};
+
+#[cfg(any(test, feature = "abigen"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "abigen")))]
+pub mod admin;
+
 #[cfg(any(test, feature = "abigen"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "abigen")))]
 mod multicall;
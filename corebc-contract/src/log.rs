@@ -1,7 +1,7 @@
 //! Mod of types for ethereum logs
 use corebc_core::{
     abi::{Error, RawLog},
-    types::{Address, Log, TxHash, H256, U256, U64},
+    types::{Address, Log, TransactionReceipt, TxHash, H256, U256, U64},
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,22 @@ pub fn decode_logs<T: EthLogDecode>(logs: &[RawLog]) -> Result<Vec<T>, Error> {
     logs.iter().map(T::decode_log).collect()
 }
 
+/// Decodes the logs of a transaction receipt that match `T`, in the order they were emitted.
+///
+/// If `address` is set, only the logs emitted by that contract are decoded. Logs that can't be
+/// decoded as `T` are skipped.
+pub fn decode_receipt_logs<T: EthLogDecode>(
+    receipt: &TransactionReceipt,
+    address: Option<Address>,
+) -> Vec<T> {
+    receipt
+        .logs
+        .iter()
+        .filter(|log| address.map_or(true, |address| log.address == address))
+        .filter_map(|log| T::decode_log(&RawLog::from(log.clone())).ok())
+        .collect()
+}
+
 /// Metadata inside a log
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LogMeta {
@@ -38,6 +54,11 @@ pub struct LogMeta {
 
     /// Log index position in the block
     pub log_index: U256,
+
+    /// Whether the log was removed by a chain reorganization. Events with removed logs were
+    /// yielded before and need to be compensated for, e.g. by reverting their effects.
+    #[serde(default)]
+    pub removed: bool,
 }
 
 impl From<&Log> for LogMeta {
@@ -49,6 +70,7 @@ impl From<&Log> for LogMeta {
             transaction_hash: src.transaction_hash.expect("should have a tx hash"),
             transaction_index: src.transaction_index.expect("should have a tx index"),
             log_index: src.log_index.expect("should have a log index"),
+            removed: src.is_removed(),
         }
     }
 }
@@ -0,0 +1,188 @@
+//! Lookup of functions by their selector across several ABIs
+
+use corebc_core::{
+    abi::{Abi, AbiError, Function, FunctionExt, Token},
+    types::Selector,
+};
+use std::collections::HashMap;
+
+/// A function call decoded by a [`SelectorRegistry`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCall {
+    /// The name of the function
+    pub name: String,
+    /// The signature of the function, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    /// The selector of the function
+    pub selector: Selector,
+    /// The decoded arguments
+    pub args: Vec<Token>,
+}
+
+/// Maps 4-byte function selectors of one or more ABIs to their functions, e.g. to label the
+/// calls of pending transactions or traces.
+///
+/// ```
+/// use corebc_contract::SelectorRegistry;
+/// use corebc_core::abi::{parse_abi, Token};
+///
+/// let erc20 = parse_abi(&["function transfer(address to, uint256 amount) returns (bool)"])?;
+/// let registry = SelectorRegistry::new().with_abi(&erc20);
+///
+/// let function = &erc20.functions["transfer"][0];
+/// let args = [Token::Address(Default::default()), Token::Uint(5.into())];
+/// let calldata = function.encode_input(&args)?;
+/// let call = registry.decode_calldata(&calldata)?;
+/// assert_eq!(call.signature, "transfer(address,uint256)");
+/// assert_eq!(call.args[1], Token::Uint(5.into()));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SelectorRegistry {
+    /// The functions of each selector, distinct selector collisions are rare but possible
+    functions: HashMap<Selector, Vec<Function>>,
+}
+
+impl SelectorRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry of the functions of all `abis`
+    pub fn from_abis<'a>(abis: impl IntoIterator<Item = &'a Abi>) -> Self {
+        let mut registry = Self::new();
+        for abi in abis {
+            registry.add_abi(abi);
+        }
+        registry
+    }
+
+    /// Adds the functions of the `abi`, functions with a known signature are skipped
+    pub fn add_abi(&mut self, abi: &Abi) -> &mut Self {
+        for function in abi.functions() {
+            self.add_function(function.clone());
+        }
+        self
+    }
+
+    /// Adds the functions of the `abi`
+    #[must_use]
+    pub fn with_abi(mut self, abi: &Abi) -> Self {
+        self.add_abi(abi);
+        self
+    }
+
+    /// Adds the function, unless a function with the same signature is known already
+    pub fn add_function(&mut self, function: Function) -> &mut Self {
+        let signature = function.abi_signature();
+        let functions = self.functions.entry(function.selector()).or_default();
+        if !functions.iter().any(|known| known.abi_signature() == signature) {
+            functions.push(function);
+        }
+        self
+    }
+
+    /// Returns the function of the selector. If the selector is ambiguous, the function that was
+    /// added first is returned.
+    pub fn function(&self, selector: Selector) -> Option<&Function> {
+        self.functions.get(&selector)?.first()
+    }
+
+    /// Returns all functions of the selector
+    pub fn functions(&self, selector: Selector) -> &[Function] {
+        self.functions.get(&selector).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the signature of the selector's function, e.g. `transfer(address,uint256)`
+    pub fn signature(&self, selector: Selector) -> Option<String> {
+        self.function(selector).map(FunctionExt::abi_signature)
+    }
+
+    /// The number of distinct selectors
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Whether the registry has no functions
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Decodes the calldata of a call to any of the known functions.
+    ///
+    /// If several functions share the selector, the first one that can decode the arguments is
+    /// used. Fails with [`AbiError::WrongSelector`] if the selector is unknown.
+    pub fn decode_calldata(&self, calldata: impl AsRef<[u8]>) -> Result<DecodedCall, AbiError> {
+        let calldata = calldata.as_ref();
+        let selector: Selector =
+            calldata.get(..4).and_then(|s| s.try_into().ok()).ok_or(AbiError::WrongSelector)?;
+        let functions = self.functions(selector);
+        if functions.is_empty() {
+            return Err(AbiError::WrongSelector)
+        }
+
+        let mut last_err = None;
+        for function in functions {
+            match function.decode_input(&calldata[4..]) {
+                Ok(args) => {
+                    return Ok(DecodedCall {
+                        name: function.name.clone(),
+                        signature: function.abi_signature(),
+                        selector,
+                        args,
+                    })
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.expect("selector has functions").into())
+    }
+}
+
+impl<'a> FromIterator<&'a Abi> for SelectorRegistry {
+    fn from_iter<I: IntoIterator<Item = &'a Abi>>(abis: I) -> Self {
+        Self::from_abis(abis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::{abi::parse_abi, types::Address};
+
+    #[test]
+    fn decodes_calldata_of_several_abis() {
+        let erc20 = parse_abi(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function approve(address spender, uint256 amount) returns (bool)",
+        ])
+        .unwrap();
+        let router = parse_abi(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function swap(uint256 amountIn, address[] path)",
+        ])
+        .unwrap();
+        let registry = SelectorRegistry::from_abis([&erc20, &router]);
+        assert_eq!(registry.len(), 3);
+
+        let swap = &router.functions["swap"][0];
+        assert_eq!(registry.signature(swap.selector()).unwrap(), "swap(uint256,address[])");
+        assert_eq!(registry.functions(swap.selector()).len(), 1);
+
+        let args =
+            vec![Token::Uint(7.into()), Token::Array(vec![Token::Address(Address::repeat_byte(1))])];
+        let calldata = swap.encode_input(&args).unwrap();
+        let call = registry.decode_calldata(&calldata).unwrap();
+        assert_eq!(call.name, "swap");
+        assert_eq!(call.selector, swap.selector());
+        assert_eq!(call.args, args);
+
+        assert!(matches!(registry.decode_calldata([0u8; 4]), Err(AbiError::WrongSelector)));
+        assert!(matches!(registry.decode_calldata([1u8]), Err(AbiError::WrongSelector)));
+        assert!(matches!(
+            registry.decode_calldata(&calldata[..8]),
+            Err(AbiError::DecodingError(_))
+        ));
+    }
+}
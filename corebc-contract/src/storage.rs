@@ -0,0 +1,450 @@
+//! Typed access to the storage of deployed contracts
+
+use corebc_core::{
+    abi::{self, InvalidOutputType, Token, Tokenizable},
+    types::{Address, AddressExt, BlockId, H256, U256},
+    utils::sha3,
+};
+use corebc_providers::Middleware;
+use corebc_ylem::artifacts::{Storage, StorageLayout, StorageType};
+use futures_util::future::try_join_all;
+use std::sync::Arc;
+use thiserror::Error as ThisError;
+
+/// The maximum length of a `bytes` or `string` value that is read from storage, longer values
+/// take more than 2048 storage reads
+const MAX_BYTES_LEN: usize = 1 << 16;
+
+/// An Error which is thrown when reading the storage of a contract
+#[derive(ThisError, Debug)]
+pub enum StorageError<M: Middleware> {
+    /// Thrown when the layout has no variable with the requested label
+    #[error("storage variable `{0}` not found in layout")]
+    UnknownVariable(String),
+
+    /// Thrown when the layout does not describe a type it references
+    #[error("storage type `{0}` not found in layout")]
+    UnknownType(String),
+
+    /// Thrown when the variable can't be read as requested, e.g. a struct or a non-mapping
+    /// passed to [`StorageReader::read_mapping`]
+    #[error("unsupported storage type `{0}`")]
+    UnsupportedType(String),
+
+    /// Thrown when reading an array element past the end of the array
+    #[error("index {index} out of bounds for array of length {len}")]
+    IndexOutOfBounds {
+        /// The requested index
+        index: usize,
+        /// The length of the array
+        len: U256,
+    },
+
+    /// Thrown when a `bytes` or `string` value is longer than the reader is willing to read
+    #[error("storage value of {len} bytes exceeds the maximum of {max} bytes")]
+    ValueTooLarge {
+        /// The length stored in the slot of the value
+        len: U256,
+        /// The maximum length that is read
+        max: usize,
+    },
+
+    /// Thrown when converting the read value into the requested type
+    #[error(transparent)]
+    DetokenizationError(#[from] InvalidOutputType),
+
+    /// Thrown when a middleware call fails
+    #[error("{e}")]
+    MiddlewareError {
+        /// The underlying error
+        e: M::Error,
+    },
+}
+
+/// Reads the state variables of a deployed contract by name, using the storage layout emitted by
+/// the compiler, so that state can be read without the contract exposing a getter for it.
+///
+/// Slots of mapping entries and dynamic arrays are derived the same way the compiler does, i.e.
+/// by hashing the key and the slot of the variable with sha3.
+///
+/// ```no_run
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use corebc_contract::StorageReader;
+/// use corebc_core::types::{Address, U256};
+/// use corebc_providers::{Http, Provider};
+/// use corebc_ylem::artifacts::StorageLayout;
+/// use std::sync::Arc;
+///
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let layout: StorageLayout =
+///     serde_json::from_str(&std::fs::read_to_string("out/Token.storage-layout.json")?)?;
+/// let address: Address = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse()?;
+///
+/// let reader = StorageReader::new(address, layout, Arc::new(provider));
+/// let owner: Address = reader.read("owner").await?;
+/// let balance: U256 = reader.read_mapping("balances", owner).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StorageReader<M> {
+    client: Arc<M>,
+    address: Address,
+    layout: StorageLayout,
+    block: Option<BlockId>,
+}
+
+impl<M> Clone for StorageReader<M> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            address: self.address,
+            layout: self.layout.clone(),
+            block: self.block,
+        }
+    }
+}
+
+impl<M: Middleware> StorageReader<M> {
+    /// Creates a new reader for the contract deployed at `address` with the given storage layout
+    pub fn new(address: impl Into<Address>, layout: StorageLayout, client: Arc<M>) -> Self {
+        Self { client, address: address.into(), layout, block: None }
+    }
+
+    /// Sets the block at which storage is read, defaults to the latest block
+    #[must_use]
+    pub fn block<T: Into<BlockId>>(mut self, block: T) -> Self {
+        self.block = Some(block.into());
+        self
+    }
+
+    /// Returns the address of the contract
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the storage layout of the contract
+    pub fn layout(&self) -> &StorageLayout {
+        &self.layout
+    }
+
+    /// Reads the state variable `label`
+    ///
+    /// Value types, strings and `bytes` are supported.
+    pub async fn read<T: Tokenizable>(&self, label: &str) -> Result<T, StorageError<M>> {
+        let var = self.variable(label)?;
+        let ty = self.storage_type(&var.storage_type)?;
+        let token = self.read_token(Self::parse_slot(&var.slot)?, var.offset as usize, ty).await?;
+        Ok(T::from_token(token)?)
+    }
+
+    /// Reads the entry for `key` of the mapping `label`
+    pub async fn read_mapping<K: Tokenizable, V: Tokenizable>(
+        &self,
+        label: &str,
+        key: K,
+    ) -> Result<V, StorageError<M>> {
+        let var = self.variable(label)?;
+        let ty = self.storage_type(&var.storage_type)?;
+        let value = match (ty.encoding.as_str(), ty.value.as_deref()) {
+            ("mapping", Some(value)) => self.storage_type(value)?,
+            _ => return Err(StorageError::UnsupportedType(ty.label.clone())),
+        };
+
+        let key = match key.into_token() {
+            Token::String(s) => s.into_bytes(),
+            Token::Bytes(b) => b,
+            token => abi::encode(&[token]),
+        };
+        let slot = hash_slot([key, word(Self::parse_slot(&var.slot)?).to_vec()].concat());
+        Ok(V::from_token(self.read_token(slot, 0, value).await?)?)
+    }
+
+    /// Reads the element at `index` of the array `label`
+    pub async fn read_array<T: Tokenizable>(
+        &self,
+        label: &str,
+        index: usize,
+    ) -> Result<T, StorageError<M>> {
+        let var = self.variable(label)?;
+        let ty = self.storage_type(&var.storage_type)?;
+        let base = ty
+            .other
+            .get("base")
+            .and_then(|base| base.as_str())
+            .ok_or_else(|| StorageError::UnsupportedType(ty.label.clone()))?;
+        let base = self.storage_type(base)?;
+        let slot = Self::parse_slot(&var.slot)?;
+
+        let (data, len) = match ty.encoding.as_str() {
+            "dynamic_array" => {
+                let len = U256::from_big_endian(self.get_storage(slot).await?.as_bytes());
+                (hash_slot(word(slot)), len)
+            }
+            // fixed size arrays are labeled e.g. `uint256[3]`
+            "inplace" => {
+                let len = ty
+                    .label
+                    .rsplit_once('[')
+                    .and_then(|(_, len)| U256::from_dec_str(len.trim_end_matches(']')).ok())
+                    .ok_or_else(|| StorageError::UnsupportedType(ty.label.clone()))?;
+                (slot, len)
+            }
+            _ => return Err(StorageError::UnsupportedType(ty.label.clone())),
+        };
+        if U256::from(index) >= len {
+            return Err(StorageError::IndexOutOfBounds { index, len })
+        }
+
+        // elements of up to 16 bytes are packed into a single slot
+        let size = Self::number_of_bytes(base)?;
+        if size == 0 {
+            return Err(StorageError::UnsupportedType(base.label.clone()))
+        }
+        let (slot, offset) = if size <= 16 {
+            let per_slot = 32 / size;
+            (data + index / per_slot, (index % per_slot) * size)
+        } else {
+            (data + U256::from(index) * ((size + 31) / 32), 0)
+        };
+        Ok(T::from_token(self.read_token(slot, offset, base).await?)?)
+    }
+
+    fn variable(&self, label: &str) -> Result<&Storage, StorageError<M>> {
+        self.layout
+            .storage
+            .iter()
+            .find(|var| var.label == label)
+            .ok_or_else(|| StorageError::UnknownVariable(label.to_string()))
+    }
+
+    fn storage_type(&self, ty: &str) -> Result<&StorageType, StorageError<M>> {
+        self.layout.types.get(ty).ok_or_else(|| StorageError::UnknownType(ty.to_string()))
+    }
+
+    async fn get_storage(&self, slot: U256) -> Result<H256, StorageError<M>> {
+        self.client
+            .get_storage_at(self.address, H256(word(slot)), self.block)
+            .await
+            .map_err(|e| StorageError::MiddlewareError { e })
+    }
+
+    /// Reads the value of type `ty` stored at `offset` bytes from the right of `slot`
+    async fn read_token(
+        &self,
+        slot: U256,
+        offset: usize,
+        ty: &StorageType,
+    ) -> Result<Token, StorageError<M>> {
+        let value = self.get_storage(slot).await?;
+        match ty.encoding.as_str() {
+            "inplace" => Self::decode_inplace(value.as_bytes(), offset, ty),
+            "bytes" => {
+                let value = value.as_bytes();
+                let data = if value[31] & 1 == 0 {
+                    // short values are stored in the slot itself, along with twice their length
+                    value[..(value[31] / 2) as usize].to_vec()
+                } else {
+                    // long values are stored from the hash of the slot on
+                    let len = (U256::from_big_endian(value) - 1) / 2;
+                    let len = usize::try_from(len)
+                        .ok()
+                        .filter(|len| *len <= MAX_BYTES_LEN)
+                        .ok_or(StorageError::ValueTooLarge { len, max: MAX_BYTES_LEN })?;
+                    let data = hash_slot(word(slot));
+                    let words =
+                        try_join_all((0..(len + 31) / 32).map(|i| self.get_storage(data + i)))
+                            .await?;
+                    let mut data = words.iter().flat_map(|w| w.0).collect::<Vec<_>>();
+                    data.truncate(len);
+                    data
+                };
+                if ty.label == "string" {
+                    Ok(Token::String(String::from_utf8_lossy(&data).into_owned()))
+                } else {
+                    Ok(Token::Bytes(data))
+                }
+            }
+            _ => Err(StorageError::UnsupportedType(ty.label.clone())),
+        }
+    }
+
+    /// Decodes a value type occupying `numberOfBytes` bytes at `offset` bytes from the right of the
+    /// storage word
+    fn decode_inplace(
+        value: &[u8],
+        offset: usize,
+        ty: &StorageType,
+    ) -> Result<Token, StorageError<M>> {
+        let unsupported = || StorageError::UnsupportedType(ty.label.clone());
+        let size = Self::number_of_bytes(ty)?;
+        if size == 0 || offset + size > 32 {
+            return Err(unsupported())
+        }
+        let bytes = &value[32 - offset - size..32 - offset];
+
+        let label = ty.label.as_str();
+        let token = if label == "bool" {
+            Token::Bool(bytes.iter().any(|b| *b != 0))
+        } else if label.starts_with("address") || label.starts_with("contract ") {
+            if size < Address::len_bytes() {
+                return Err(unsupported())
+            }
+            let mut word = H256::zero();
+            word[32 - size..].copy_from_slice(bytes);
+            Token::Address(Address::from_h256_truncated(word))
+        } else if label.starts_with("uint") || label.starts_with("enum ") {
+            Token::Uint(U256::from_big_endian(bytes))
+        } else if label.starts_with("int") {
+            // sign extend to 256 bits
+            let mut buf = if bytes[0] & 0x80 != 0 { [0xff; 32] } else { [0; 32] };
+            buf[32 - size..].copy_from_slice(bytes);
+            Token::Int(U256::from_big_endian(&buf))
+        } else if label.starts_with("bytes") {
+            Token::FixedBytes(bytes.to_vec())
+        } else {
+            return Err(unsupported())
+        };
+        Ok(token)
+    }
+
+    fn number_of_bytes(ty: &StorageType) -> Result<usize, StorageError<M>> {
+        ty.number_of_bytes.parse().map_err(|_| StorageError::UnsupportedType(ty.label.clone()))
+    }
+
+    fn parse_slot(slot: &str) -> Result<U256, StorageError<M>> {
+        U256::from_dec_str(slot).map_err(|_| StorageError::UnsupportedType(format!("slot {slot}")))
+    }
+}
+
+fn word(value: U256) -> [u8; 32] {
+    let mut word = [0; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn hash_slot(data: impl AsRef<[u8]>) -> U256 {
+    U256::from_big_endian(&sha3(data))
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_providers::Provider;
+    use serde_json::json;
+
+    fn layout() -> StorageLayout {
+        let var = |label: &str, slot: &str, offset: i64, ty: &str| {
+            json!({
+                "astId": 1,
+                "contract": "Token.sol:Token",
+                "label": label,
+                "offset": offset,
+                "slot": slot,
+                "type": ty
+            })
+        };
+        serde_json::from_value(json!({
+            "storage": [
+                var("owner", "0", 0, "t_address"),
+                var("paused", "0", 22, "t_bool"),
+                var("balances", "1", 0, "t_mapping(t_address,t_uint256)"),
+                var("name", "2", 0, "t_string_storage"),
+                var("checkpoints", "3", 0, "t_array(t_uint64)dyn_storage"),
+            ],
+            "types": {
+                "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "22" },
+                "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+                "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+                "t_uint64": { "encoding": "inplace", "label": "uint64", "numberOfBytes": "8" },
+                "t_string_storage": { "encoding": "bytes", "label": "string", "numberOfBytes": "32" },
+                "t_mapping(t_address,t_uint256)": {
+                    "encoding": "mapping",
+                    "key": "t_address",
+                    "label": "mapping(address => uint256)",
+                    "numberOfBytes": "32",
+                    "value": "t_uint256"
+                },
+                "t_array(t_uint64)dyn_storage": {
+                    "base": "t_uint64",
+                    "encoding": "dynamic_array",
+                    "label": "uint64[]",
+                    "numberOfBytes": "32"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_typed_storage() {
+        let (provider, mock) = Provider::mocked();
+        let address: Address = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse().unwrap();
+        let owner: Address = "0x0000fb6916095ca1df60bb79ce92ce3ea74c37c5d359".parse().unwrap();
+        let reader = StorageReader::new(address, layout(), Arc::new(provider));
+
+        let expect_slot = |slot: U256, value: H256| {
+            mock.expect("xcb_getStorageAt")
+                .with_params(json!([address, slot, "latest"]))
+                .returns(value);
+        };
+
+        // owner and paused share slot 0
+        let mut slot0 = [0u8; 32];
+        slot0[9] = 1;
+        slot0[10..].copy_from_slice(owner.as_bytes());
+        mock.expect("xcb_getStorageAt")
+            .with_params(json!([address, U256::zero(), "latest"]))
+            .times(2)
+            .returns(H256(slot0));
+        assert_eq!(reader.read::<Address>("owner").await.unwrap(), owner);
+        assert!(reader.read::<bool>("paused").await.unwrap());
+
+        let balance_slot =
+            hash_slot([[0u8; 10].as_slice(), owner.as_bytes(), &word(1.into())].concat());
+        expect_slot(balance_slot, H256(word(1000.into())));
+        let balance: U256 = reader.read_mapping("balances", owner).await.unwrap();
+        assert_eq!(balance, 1000.into());
+
+        let mut name = [0u8; 32];
+        name[..5].copy_from_slice(b"Token");
+        name[31] = 10;
+        expect_slot(2.into(), H256(name));
+        assert_eq!(reader.read::<String>("name").await.unwrap(), "Token");
+
+        // five uint64s, the fifth is packed into the second slot of the data
+        expect_slot(3.into(), H256(word(5.into())));
+        let mut data = [0u8; 32];
+        data[24..].copy_from_slice(&7u64.to_be_bytes());
+        expect_slot(hash_slot(word(3.into())) + 1, H256(data));
+        assert_eq!(reader.read_array::<u64>("checkpoints", 4).await.unwrap(), 7);
+
+        assert!(matches!(
+            reader.read_array::<u64>("checkpoints", 5).await,
+            Err(StorageError::IndexOutOfBounds { index: 5, .. })
+        ));
+        assert!(matches!(
+            reader.read::<U256>("missing").await,
+            Err(StorageError::UnknownVariable(_))
+        ));
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_long_bytes() {
+        let (provider, mock) = Provider::mocked();
+        let address: Address = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse().unwrap();
+        let reader = StorageReader::new(address, layout(), Arc::new(provider));
+
+        // a long value whose length does not fit into a usize
+        mock.expect("xcb_getStorageAt")
+            .with_params(json!([address, U256::from(2), "latest"]))
+            .returns(H256([0xff; 32]));
+        assert!(matches!(
+            reader.read::<String>("name").await,
+            Err(StorageError::ValueTooLarge { max: MAX_BYTES_LEN, .. })
+        ));
+        mock.verify().unwrap();
+    }
+}
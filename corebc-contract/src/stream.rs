@@ -21,19 +21,34 @@ type MapEvent<'a, R, E> = Box<dyn Fn(Log) -> Result<R, E> + 'a + Send + Sync>;
 ///
 /// We use this wrapper type instead of `StreamExt::map` in order to preserve
 /// information about the filter/subscription's id.
+///
+/// Logs that were removed by a chain reorganization are dropped unless
+/// [`include_removed`](Self::include_removed) is set, use [`with_meta`](Self::with_meta) to tell
+/// them apart via [`LogMeta::removed`].
 pub struct EventStream<'a, T, R, E> {
     /// The stream ID, provided by the RPC server
     pub id: U256,
     #[pin]
     stream: T,
     parse: MapEvent<'a, R, E>,
+    include_removed: bool,
 }
 
 impl<'a, T, R, E> EventStream<'a, T, R, E> {
-    /// Turns this stream of events into a stream that also yields the event's metadata
+    /// Turns this stream of events into a stream that also yields the event's metadata.
+    ///
+    /// The returned stream yields the events of removed logs as well, flagged by
+    /// [`LogMeta::removed`].
     pub fn with_meta(self) -> EventStreamMeta<'a, T, R, E> {
         EventStreamMeta(self)
     }
+
+    /// Yields the events of logs that were removed by a chain reorganization as well, instead of
+    /// dropping them
+    pub fn include_removed(mut self) -> Self {
+        self.include_removed = true;
+        self
+    }
 }
 
 impl<'a, T, R, E> EventStream<'a, T, R, E> {
@@ -41,7 +56,7 @@ impl<'a, T, R, E> EventStream<'a, T, R, E> {
     ///
     /// Typically users should not call this directly
     pub fn new(id: U256, stream: T, parse: MapEvent<'a, R, E>) -> Self {
-        Self { id, stream, parse }
+        Self { id, stream, parse, include_removed: false }
     }
 }
 
@@ -53,9 +68,12 @@ where
 
     fn poll_next(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        match futures_util::ready!(this.stream.poll_next_unpin(ctx)) {
-            Some(item) => Poll::Ready(Some((this.parse)(item))),
-            None => Poll::Pending,
+        loop {
+            match futures_util::ready!(this.stream.poll_next_unpin(ctx)) {
+                Some(item) if item.is_removed() && !*this.include_removed => continue,
+                Some(item) => return Poll::Ready(Some((this.parse)(item))),
+                None => return Poll::Pending,
+            }
         }
     }
 }
@@ -216,3 +234,51 @@ where
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::types::{H256, U64};
+    use futures_util::stream;
+
+    fn log(block: u64, removed: bool) -> Log {
+        Log {
+            block_number: Some(U64::from(block)),
+            block_hash: Some(H256::zero()),
+            transaction_hash: Some(H256::zero()),
+            transaction_index: Some(U64::zero()),
+            log_index: Some(U256::zero()),
+            removed: Some(removed),
+            ..Default::default()
+        }
+    }
+
+    fn events(
+        logs: Vec<Log>,
+    ) -> EventStream<'static, stream::Iter<std::vec::IntoIter<Log>>, u64, ()> {
+        EventStream::new(
+            U256::zero(),
+            stream::iter(logs),
+            Box::new(|log| Ok(log.block_number.unwrap().as_u64())),
+        )
+    }
+
+    #[tokio::test]
+    async fn handles_removed_logs() {
+        let logs = vec![log(1, false), log(1, true), log(2, false)];
+
+        let blocks = events(logs.clone()).take(2).collect::<Vec<_>>().await;
+        assert_eq!(blocks, [Ok(1), Ok(2)]);
+
+        let blocks = events(logs.clone()).include_removed().take(3).collect::<Vec<_>>().await;
+        assert_eq!(blocks, [Ok(1), Ok(1), Ok(2)]);
+
+        let metas = events(logs)
+            .with_meta()
+            .map(|res| res.map(|(block, meta)| (block, meta.removed)))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(metas, [Ok((1, false)), Ok((1, true)), Ok((2, false))]);
+    }
+}
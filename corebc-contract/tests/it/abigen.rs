@@ -3,7 +3,7 @@
 use corebc_contract::{abigen, ContractError, EthCall, EthError, EthEvent};
 use corebc_core::{
     abi::{AbiDecode, AbiEncode, Address, Tokenizable},
-    types::{Bytes, U256},
+    types::{Bytes, Log, TransactionReceipt, H256, U256},
 };
 use corebc_providers::{MockProvider, Provider};
 use std::{fmt::Debug, hash::Hash, sync::Arc};
@@ -635,6 +635,45 @@ fn can_handle_overloaded_events() {
     let _ev2 = ActionPaused2Filter { action: "action".to_string(), pause_state: false };
 }
 
+#[test]
+fn can_decode_receipt_logs() {
+    abigen!(
+        SimpleContract,
+        r#"[
+            event ValueChanged(address indexed author, uint256 value)
+            event Paused()
+    ]"#
+    );
+
+    let address = Address::random();
+    let author = Address::random();
+    let provider = Arc::new(Provider::new(MockProvider::new()));
+    let contract = SimpleContract::new(address, provider);
+
+    let value_changed = Log {
+        address,
+        topics: vec![ValueChangedFilter::signature(), H256::from_slice(&author.encode())],
+        data: U256::from(42).encode().into(),
+        ..Default::default()
+    };
+    let paused = Log { address, topics: vec![PausedFilter::signature()], ..Default::default() };
+    // emitted by another contract
+    let other = Log { address: Address::random(), ..paused.clone() };
+    let receipt =
+        TransactionReceipt { logs: vec![value_changed, other, paused], ..Default::default() };
+
+    assert_eq!(
+        contract.decode_logs(&receipt),
+        vec![
+            SimpleContractEvents::ValueChangedFilter(ValueChangedFilter {
+                author,
+                value: 42.into()
+            }),
+            SimpleContractEvents::PausedFilter(PausedFilter),
+        ]
+    );
+}
+
 // CORETODO: Needs anvil
 // #[tokio::test]
 // #[cfg(not(feature = "celo"))]
@@ -13,6 +13,10 @@ pub mod utils;
 #[cfg(feature = "macros")]
 pub mod macros;
 
+/// Canonical test vectors shared with other implementations
+#[cfg(any(test, feature = "test_vectors"))]
+pub mod test_vectors;
+
 // re-export rand to avoid potential confusion when there's rand version mismatches
 pub use rand;
 
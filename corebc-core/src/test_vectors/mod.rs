@@ -0,0 +1,318 @@
+//! Canonical test vectors for keys, ICAN addresses, signed messages, signed transactions and
+//! CIP-712 hashes.
+//!
+//! The vectors are shipped as JSON in [`TEST_VECTORS_JSON`] so that other implementations can
+//! check their conformance against the exact same data. All byte strings, hashes, addresses and
+//! quantities are `0x`-prefixed hex strings, network ids of ICAN vectors are plain numbers.
+//!
+//! ```
+//! use corebc_core::test_vectors::TestVectors;
+//!
+//! let vectors = TestVectors::canonical();
+//! assert!(vectors.verify().is_empty());
+//! ```
+
+use crate::{
+    types::{
+        transaction::{
+            cip712::{Cip712, TypedData},
+            eip2718::TypedTransaction,
+        },
+        Address, Bytes, Network, Signature, TransactionRequest, H160, H256, U256, U64,
+    },
+    utils::{hash_message, secret_key_to_address, sha3, to_ican},
+};
+use libgoldilocks::{PrehashSigner, SigningKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The canonical test vectors as JSON
+pub const TEST_VECTORS_JSON: &str = include_str!("vectors.json");
+
+/// A set of test vectors
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVectors {
+    /// Private keys with their public keys and mainnet addresses
+    pub keys: Vec<KeyVector>,
+    /// ICAN addresses of raw addresses on each network
+    pub ican: Vec<IcanVector>,
+    /// Messages signed with the `\x19Core Signed Message:\n` prefix
+    pub messages: Vec<MessageVector>,
+    /// Signed legacy transactions
+    pub transactions: Vec<TransactionVector>,
+    /// CIP-712 typed data and its hashes
+    pub typed_data: Vec<TypedDataVector>,
+}
+
+/// An Ed448 private key and the values derived from it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyVector {
+    /// The 57 byte private key
+    pub private_key: Bytes,
+    /// The 57 byte public key
+    pub public_key: Bytes,
+    /// The mainnet address of the key
+    pub address: Address,
+}
+
+/// The ICAN address of a raw address on a network
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IcanVector {
+    /// The last 20 bytes of the hash of a public key
+    pub h160: H160,
+    /// The network id, determining the prefix of the address
+    pub network_id: u64,
+    /// The prefixed and checksummed address
+    pub address: Address,
+}
+
+/// A message signed with a private key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageVector {
+    /// The private key signing the message
+    pub private_key: Bytes,
+    /// The UTF-8 message
+    pub message: String,
+    /// The hash of the prefixed message
+    pub hash: H256,
+    /// The 114 byte signature followed by the 57 byte public key
+    pub signature: Bytes,
+}
+
+/// A legacy transaction signed with a private key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionVector {
+    /// The private key signing the transaction
+    pub private_key: Bytes,
+    /// The nonce of the transaction
+    pub nonce: U256,
+    /// The energy price of the transaction
+    pub energy_price: U256,
+    /// The energy limit of the transaction
+    pub energy: U256,
+    /// The network id of the transaction
+    pub network_id: U64,
+    /// The recipient, `None` for contract deployments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The value transferred
+    pub value: U256,
+    /// The input data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    /// The hash that is signed
+    pub sighash: H256,
+    /// The 114 byte signature followed by the 57 byte public key
+    pub signature: Bytes,
+    /// The RLP encoding of the signed transaction
+    pub signed: Bytes,
+    /// The hash of the signed transaction
+    pub hash: H256,
+}
+
+impl TransactionVector {
+    /// Returns the unsigned transaction
+    pub fn request(&self) -> TransactionRequest {
+        TransactionRequest {
+            from: None,
+            to: self.to.map(Into::into),
+            energy: Some(self.energy),
+            energy_price: Some(self.energy_price),
+            value: Some(self.value),
+            data: self.data.clone(),
+            nonce: Some(self.nonce),
+            network_id: Some(self.network_id),
+        }
+    }
+}
+
+/// CIP-712 typed data and its hashes
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedDataVector {
+    /// The typed data
+    pub data: TypedData,
+    /// The domain separator
+    pub domain_separator: H256,
+    /// The hash of the message, `None` if the primary type is the domain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub struct_hash: Option<H256>,
+    /// The hash that is signed
+    pub hash: H256,
+}
+
+/// A value computed by this crate that differs from the value in the vectors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The path of the value, e.g. `transactions[1].sighash`
+    pub path: String,
+    /// The value in the vectors
+    pub expected: String,
+    /// The value computed by this crate, or the error computing it
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, got {}", self.path, self.expected, self.actual)
+    }
+}
+
+impl TestVectors {
+    /// Returns the canonical test vectors
+    pub fn canonical() -> Self {
+        serde_json::from_str(TEST_VECTORS_JSON).expect("canonical test vectors are valid")
+    }
+
+    /// Computes every value of the vectors with this crate and returns the ones that differ.
+    pub fn verify(&self) -> Vec<Mismatch> {
+        let mut report = Report::default();
+
+        for (i, vector) in self.keys.iter().enumerate() {
+            let path = format!("keys[{i}]");
+            let key = match report.signing_key(&path, &vector.private_key) {
+                Some(key) => key,
+                None => continue,
+            };
+            let public_key = Bytes::from(key.verifying_key().as_bytes().to_vec());
+            report.check(format!("{path}.public_key"), &vector.public_key, &public_key);
+            let address = secret_key_to_address(&key, &Network::Mainnet);
+            report.check(format!("{path}.address"), &vector.address, &address);
+        }
+
+        for (i, vector) in self.ican.iter().enumerate() {
+            let address = to_ican(&vector.h160, &Network::from(vector.network_id));
+            report.check(format!("ican[{i}].address"), &vector.address, &address);
+        }
+
+        for (i, vector) in self.messages.iter().enumerate() {
+            let path = format!("messages[{i}]");
+            let hash = hash_message(&vector.message);
+            report.check(format!("{path}.hash"), &vector.hash, &hash);
+            report.check_signature(&path, &vector.private_key, hash, &vector.signature);
+        }
+
+        for (i, vector) in self.transactions.iter().enumerate() {
+            let path = format!("transactions[{i}]");
+            let tx = vector.request();
+            let sighash = tx.sighash();
+            report.check(format!("{path}.sighash"), &vector.sighash, &sighash);
+            report.check_signature(&path, &vector.private_key, sighash, &vector.signature);
+
+            match Signature::try_from(vector.signature.as_ref()) {
+                Ok(signature) => report.check(
+                    format!("{path}.signed"),
+                    &vector.signed,
+                    &tx.rlp_signed(&signature),
+                ),
+                Err(err) => report.fail(format!("{path}.signature"), &vector.signature, err),
+            }
+            report.check(format!("{path}.hash"), &vector.hash, &H256(sha3(&vector.signed)));
+            match TypedTransaction::decode_signed_bytes(&vector.signed) {
+                Ok((decoded, _)) => {
+                    report.check(format!("{path}.signed"), &vector.sighash, &decoded.sighash())
+                }
+                Err(err) => report.fail(format!("{path}.signed"), &vector.signed, err),
+            }
+        }
+
+        for (i, vector) in self.typed_data.iter().enumerate() {
+            let path = format!("typed_data[{i}]");
+            let separator = H256(vector.data.domain.separator());
+            report.check(format!("{path}.domain_separator"), &vector.domain_separator, &separator);
+            if let Some(expected) = &vector.struct_hash {
+                match vector.data.struct_hash() {
+                    Ok(hash) => report.check(format!("{path}.struct_hash"), expected, &H256(hash)),
+                    Err(err) => report.fail(format!("{path}.struct_hash"), expected, err),
+                }
+            }
+            match vector.data.encode_cip712() {
+                Ok(hash) => report.check(format!("{path}.hash"), &vector.hash, &H256(hash)),
+                Err(err) => report.fail(format!("{path}.hash"), &vector.hash, err),
+            }
+        }
+
+        report.0
+    }
+}
+
+#[derive(Default)]
+struct Report(Vec<Mismatch>);
+
+impl Report {
+    fn check<T: PartialEq + fmt::Debug>(&mut self, path: String, expected: &T, actual: &T) {
+        if expected != actual {
+            self.0.push(Mismatch {
+                path,
+                expected: format!("{expected:?}"),
+                actual: format!("{actual:?}"),
+            });
+        }
+    }
+
+    fn fail(&mut self, path: String, expected: &impl fmt::Debug, err: impl fmt::Display) {
+        self.0.push(Mismatch { path, expected: format!("{expected:?}"), actual: err.to_string() });
+    }
+
+    fn signing_key(&mut self, path: &str, private_key: &Bytes) -> Option<SigningKey> {
+        match SigningKey::from_bytes(private_key.as_ref()) {
+            Ok(key) => Some(key),
+            Err(err) => {
+                self.fail(format!("{path}.private_key"), private_key, format!("{err:?}"));
+                None
+            }
+        }
+    }
+
+    /// Signs `hash` with the private key, signatures are deterministic
+    fn check_signature(&mut self, path: &str, private_key: &Bytes, hash: H256, expected: &Bytes) {
+        let key = match self.signing_key(path, private_key) {
+            Some(key) => key,
+            None => return,
+        };
+        match key.sign_prehash(hash.as_ref()) {
+            Ok(signature) => {
+                let signature = Bytes::from(signature.as_slice().to_vec());
+                self.check(format!("{path}.signature"), expected, &signature)
+            }
+            Err(err) => self.fail(format!("{path}.signature"), expected, format!("{err:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_vectors_conform() {
+        let vectors = TestVectors::canonical();
+        assert!(!vectors.keys.is_empty());
+        assert!(!vectors.transactions.is_empty());
+
+        let mismatches = vectors.verify();
+        assert!(
+            mismatches.is_empty(),
+            "{}",
+            mismatches.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+        );
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let mut vectors = TestVectors::canonical();
+        vectors.ican[0].network_id = 3;
+        vectors.transactions[0].nonce += U256::one();
+
+        let paths = vectors.verify().into_iter().map(|m| m.path).collect::<Vec<_>>();
+        assert!(paths.contains(&"ican[0].address".to_string()));
+        assert!(paths.contains(&"transactions[0].sighash".to_string()));
+        assert!(paths.contains(&"transactions[0].signed".to_string()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let vectors = TestVectors::canonical();
+        let json = serde_json::to_string(&vectors).unwrap();
+        assert_eq!(serde_json::from_str::<TestVectors>(&json).unwrap(), vectors);
+    }
+}
@@ -0,0 +1,82 @@
+//! Conversions between 22 byte ICAN addresses and 32 byte words
+
+use crate::types::{Address, H256};
+use thiserror::Error;
+
+/// The number of zero bytes an [`Address`] is left padded with to fill a 32 byte word
+pub const ADDRESS_PADDING: usize = 32 - Address::len_bytes();
+
+/// Error returned by [`AddressExt::try_from_h256`] if the padding bytes of a word are not zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("{0:?} is not a left padded address")]
+pub struct InvalidAddressWord(pub H256);
+
+/// Conversions between [`Address`]es and 32 byte words such as log topics, storage values and ABI
+/// words.
+///
+/// A word holds an address the way the ABI encodes it: the 22 address bytes are right aligned and
+/// left padded with [`ADDRESS_PADDING`] zero bytes. `H256::from(address)` pads the same way, but
+/// `Address::from(word)` silently drops the padding, use [`try_from_h256`](Self::try_from_h256)
+/// to reject words that do not hold an address.
+///
+/// # Example
+///
+/// ```
+/// use corebc_core::types::{Address, AddressExt, H256};
+///
+/// let address = Address::repeat_byte(1);
+/// let topic = address.to_h256();
+/// assert_eq!(&topic[..10], &[0; 10]);
+/// assert_eq!(Address::try_from_h256(topic), Ok(address));
+/// assert!(Address::try_from_h256(H256::repeat_byte(1)).is_err());
+/// ```
+pub trait AddressExt: Sized {
+    /// Left pads the address with zeros to a word
+    fn to_h256(&self) -> H256;
+
+    /// Returns the address held by `word`, fails if any of the padding bytes is not zero
+    fn try_from_h256(word: H256) -> Result<Self, InvalidAddressWord>;
+
+    /// Returns the address in the low 22 bytes of `word` and ignores the padding bytes, e.g. for
+    /// storage slots that pack other values in front of an address
+    fn from_h256_truncated(word: H256) -> Self;
+}
+
+impl AddressExt for Address {
+    fn to_h256(&self) -> H256 {
+        let mut word = H256::zero();
+        word[ADDRESS_PADDING..].copy_from_slice(self.as_bytes());
+        word
+    }
+
+    fn try_from_h256(word: H256) -> Result<Self, InvalidAddressWord> {
+        if word[..ADDRESS_PADDING].iter().any(|byte| *byte != 0) {
+            return Err(InvalidAddressWord(word))
+        }
+        Ok(Self::from_h256_truncated(word))
+    }
+
+    fn from_h256_truncated(word: H256) -> Self {
+        Address::from_slice(&word[ADDRESS_PADDING..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_address_words() {
+        let address: Address = "0x00006fc21092da55b392b045ed78f4732bff3c580e2c".parse().unwrap();
+        let word = address.to_h256();
+        assert_eq!(word, H256::from(address));
+        assert_eq!(&word[..ADDRESS_PADDING], &[0; ADDRESS_PADDING]);
+        assert_eq!(&word[ADDRESS_PADDING..], address.as_bytes());
+        assert_eq!(Address::try_from_h256(word), Ok(address));
+
+        let mut packed = word;
+        packed[ADDRESS_PADDING - 1] = 1;
+        assert_eq!(Address::try_from_h256(packed), Err(InvalidAddressWord(packed)));
+        assert_eq!(Address::from_h256_truncated(packed), address);
+    }
+}
@@ -1,19 +1,30 @@
 // Modified from <https://github.com/tomusdrw/rust-web3/blob/master/src/types/block.rs>
 
 use crate::types::{Address, Bloom, Bytes, Transaction, TxHash, H256, U256, U64};
+#[cfg(feature = "chrono")]
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{
     de::{MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::{fmt, fmt::Formatter, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fmt::Formatter,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use thiserror::Error;
 
 /// The block type returned from RPC calls.
 ///
 /// This is generic over a `TX` type which will be either the hash or the full transaction,
 /// i.e. `Block<TxHash>` or `Block<Transaction>`.
+///
+/// Core blocks have no uncles, the uncle fields are kept for compatibility with Ethereum style
+/// responses and are empty for gocore blocks. Header fields that are not modeled here, like the
+/// seal data of a network's consensus engine, are kept in [`Block::other`].
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Block<TX> {
     /// Hash of the block
@@ -38,11 +49,11 @@ pub struct Block<TX> {
     pub receipts_root: H256,
     /// Block number. None if pending.
     pub number: Option<U64>,
-    /// Gas Used
-    #[serde(default, rename = "energyUsed")]
+    /// Energy Used
+    #[serde(default, rename = "energyUsed", alias = "gasUsed")]
     pub energy_used: U256,
-    /// Gas Limit
-    #[serde(default, rename = "energyLimit")]
+    /// Energy Limit
+    #[serde(default, rename = "energyLimit", alias = "gasLimit")]
     pub energy_limit: U256,
     /// Extra data
     #[serde(default, rename = "extraData")]
@@ -72,6 +83,9 @@ pub struct Block<TX> {
     pub size: Option<U256>,
     /// Nonce
     pub nonce: Option<crate::types::H64>,
+    /// Fields of the response that are not modeled by this type, e.g. validator signatures
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
@@ -83,19 +97,47 @@ where
     Ok(opt.unwrap_or_default())
 }
 
-/// Error returned by [`Block::time`].
+/// Error returned by [`Block::timestamp_secs`], [`Block::system_time`] and `Block::time`.
 #[derive(Clone, Copy, Debug, Error)]
 pub enum TimeError {
     /// Timestamp is zero.
     #[error("timestamp is zero")]
     TimestampZero,
 
-    /// Timestamp is too large for [`DateTime<Utc>`].
+    /// Timestamp is too large to be represented.
     #[error("timestamp is too large")]
     TimestampOverflow,
 }
 
 impl<TX> Block<TX> {
+    /// Returns [`Self::timestamp`] in seconds since the unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// * [`TimeError::TimestampZero`] if the timestamp is zero, or
+    /// * [`TimeError::TimestampOverflow`] if the timestamp does not fit into a `u64`.
+    pub fn timestamp_secs(&self) -> Result<u64, TimeError> {
+        if self.timestamp.is_zero() {
+            return Err(TimeError::TimestampZero)
+        }
+        if self.timestamp.bits() > 64 {
+            return Err(TimeError::TimestampOverflow)
+        }
+        Ok(self.timestamp.as_u64())
+    }
+
+    /// Parse [`Self::timestamp`] into a [`SystemTime`].
+    ///
+    /// # Errors
+    ///
+    /// * [`TimeError::TimestampZero`] if the timestamp is zero, or
+    /// * [`TimeError::TimestampOverflow`] if the timestamp is too large to be represented as a
+    ///   [`SystemTime`].
+    pub fn system_time(&self) -> Result<SystemTime, TimeError> {
+        let secs = self.timestamp_secs()?;
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs)).ok_or(TimeError::TimestampOverflow)
+    }
+
     /// Parse [`Self::timestamp`] into a [`DateTime<Utc>`].
     ///
     /// # Errors
@@ -103,6 +145,8 @@ impl<TX> Block<TX> {
     /// * [`TimeError::TimestampZero`] if the timestamp is zero, or
     /// * [`TimeError::TimestampOverflow`] if the timestamp is too large to be represented as a
     ///   [`DateTime<Utc>`].
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
     pub fn time(&self) -> Result<DateTime<Utc>, TimeError> {
         if self.timestamp.is_zero() {
             return Err(TimeError::TimestampZero)
@@ -115,6 +159,22 @@ impl<TX> Block<TX> {
         let secs = self.timestamp.as_u64() as i64;
         Ok(Utc.timestamp_opt(secs, 0).unwrap())
     }
+
+    /// Consumes the block and returns an iterator over its transactions
+    pub fn into_transactions(self) -> std::vec::IntoIter<TX> {
+        self.transactions.into_iter()
+    }
+}
+
+impl Block<Transaction> {
+    /// Returns an iterator over the `(from, to, value)` of the transactions that transfer a
+    /// non-zero value, `to` is `None` for contract deployments.
+    pub fn iter_transfers(&self) -> impl Iterator<Item = (Address, Option<Address>, U256)> + '_ {
+        self.transactions
+            .iter()
+            .filter(|tx| !tx.value.is_zero())
+            .map(|tx| (tx.from, tx.to, tx.value))
+    }
 }
 
 impl Block<TxHash> {
@@ -141,6 +201,7 @@ impl Block<TxHash> {
                 uncles,
                 size,
                 nonce,
+                other,
                 ..
             } = self;
             Block {
@@ -164,6 +225,7 @@ impl Block<TxHash> {
                 size,
                 nonce,
                 transactions,
+                other,
             }
         }
     }
@@ -193,6 +255,7 @@ impl From<Block<Transaction>> for Block<TxHash> {
                 transactions,
                 size,
                 nonce,
+                other,
             } = full;
             Block {
                 hash,
@@ -215,6 +278,7 @@ impl From<Block<Transaction>> for Block<TxHash> {
                 size,
                 nonce,
                 transactions: transactions.iter().map(|tx| tx.hash).collect(),
+                other,
             }
         }
     }
@@ -223,14 +287,43 @@ impl From<Block<Transaction>> for Block<TxHash> {
 /// A [block hash](H256) or [block number](BlockNumber).
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum BlockId {
-    // TODO: May want to expand this to include the requireCanonical field
-    // <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md>
     /// A block hash
     Hash(H256),
+    /// A block hash that must be part of the canonical chain, the
+    /// [EIP-1898](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md) `requireCanonical`
+    /// flag. Nodes reject requests for blocks that were reorged out.
+    CanonicalHash(H256),
     /// A block number
     Number(BlockNumber),
 }
 
+impl BlockId {
+    /// Returns the block hash, if the block is identified by its hash
+    pub fn as_hash(&self) -> Option<H256> {
+        match self {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => Some(*hash),
+            BlockId::Number(_) => None,
+        }
+    }
+
+    /// Returns the block number, if the block is identified by its number or tag
+    pub fn as_number(&self) -> Option<BlockNumber> {
+        match self {
+            BlockId::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    /// Requires the block to be part of the canonical chain, if it is identified by its hash
+    #[must_use]
+    pub fn require_canonical(self) -> Self {
+        match self {
+            BlockId::Hash(hash) => BlockId::CanonicalHash(hash),
+            id => id,
+        }
+    }
+}
+
 impl From<u64> for BlockId {
     fn from(num: u64) -> Self {
         BlockNumber::Number(num.into()).into()
@@ -266,6 +359,12 @@ impl Serialize for BlockId {
                 s.serialize_field("blockHash", &format!("{x:?}"))?;
                 s.end()
             }
+            BlockId::CanonicalHash(ref x) => {
+                let mut s = serializer.serialize_struct("BlockIdEip1898", 2)?;
+                s.serialize_field("blockHash", &format!("{x:?}"))?;
+                s.serialize_field("requireCanonical", &true)?;
+                s.end()
+            }
             BlockId::Number(ref num) => num.serialize(serializer),
         }
     }
@@ -298,6 +397,7 @@ impl<'de> Deserialize<'de> for BlockId {
             {
                 let mut number = None;
                 let mut hash = None;
+                let mut require_canonical = false;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -313,18 +413,26 @@ impl<'de> Deserialize<'de> for BlockId {
                             }
                             hash = Some(BlockId::Hash(map.next_value::<H256>()?))
                         }
+                        "requireCanonical" => {
+                            require_canonical = map.next_value::<bool>()?;
+                        }
                         key => {
                             return Err(serde::de::Error::unknown_field(
                                 key,
-                                &["blockNumber", "blockHash"],
+                                &["blockNumber", "blockHash", "requireCanonical"],
                             ))
                         }
                     }
                 }
 
-                number.or(hash).ok_or_else(|| {
-                    serde::de::Error::custom("Expected `blockNumber` or `blockHash`")
-                })
+                match (number, hash) {
+                    (Some(number), _) => Ok(number),
+                    (None, Some(hash)) if require_canonical => Ok(hash.require_canonical()),
+                    (None, Some(hash)) => Ok(hash),
+                    (None, None) => {
+                        Err(serde::de::Error::custom("Expected `blockNumber` or `blockHash`"))
+                    }
+                }
             }
         }
 
@@ -547,6 +655,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serde_block_id_require_canonical() {
+        let hash: H256 =
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3".parse().unwrap();
+        let id = BlockId::from(hash).require_canonical();
+        assert_eq!(id, BlockId::CanonicalHash(hash));
+        assert_eq!(id.as_hash(), Some(hash));
+        assert_eq!(id.as_number(), None);
+
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(json, serde_json::json!({ "blockHash": hash, "requireCanonical": true }));
+        assert_eq!(serde_json::from_value::<BlockId>(json).unwrap(), id);
+
+        let json = serde_json::json!({ "blockHash": hash, "requireCanonical": false });
+        assert_eq!(serde_json::from_value::<BlockId>(json).unwrap(), BlockId::Hash(hash));
+
+        // only hashes can be required to be canonical
+        let id = BlockId::from(BlockNumber::Latest).require_canonical();
+        assert_eq!(id, BlockId::Number(BlockNumber::Latest));
+    }
+
     #[test]
     fn serde_block_number() {
         for b in &[
@@ -574,6 +703,67 @@ mod tests {
         let _block: Block<TxHash> = serde_json::from_str(block).unwrap();
     }
 
+    #[test]
+    fn block_timestamps() {
+        let mut block = Block::<TxHash>::default();
+        assert!(matches!(block.timestamp_secs(), Err(TimeError::TimestampZero)));
+
+        block.timestamp = U256::from(1_700_000_000u64);
+        assert_eq!(block.timestamp_secs().unwrap(), 1_700_000_000);
+        assert_eq!(block.system_time().unwrap(), UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        #[cfg(feature = "chrono")]
+        assert_eq!(block.time().unwrap().timestamp(), 1_700_000_000);
+
+        block.timestamp = U256::MAX;
+        assert!(matches!(block.system_time(), Err(TimeError::TimestampOverflow)));
+    }
+
+    #[test]
+    fn iterates_transfers() {
+        let (a, b) = (Address::random(), Address::random());
+        let tx =
+            |to, value: u64| Transaction { from: a, to, value: value.into(), ..Default::default() };
+        let block = Block {
+            transactions: vec![tx(Some(b), 5), tx(Some(b), 0), tx(None, 7)],
+            ..Default::default()
+        };
+        let transfers: Vec<_> = block.iter_transfers().collect();
+        assert_eq!(transfers, vec![(a, Some(b), 5.into()), (a, None, 7.into())]);
+        assert_eq!(block.into_transactions().len(), 3);
+    }
+
+    #[test]
+    fn deserialize_blk_keeps_unknown_fields() {
+        let block = serde_json::json!({
+            "number": "0x3",
+            "hash": "0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972",
+            "parentHash": "0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90",
+            "miner": "0x00000000000000000000000000000000000000000000",
+            "gasLimit": "0x6691b7",
+            "energyUsed": "0x5208",
+            "timestamp": "0x5ecedbb9",
+            "logsBloom": null,
+            "totalDifficulty": null,
+            "size": "0x3e8",
+            "nonce": "0x0000000000000000",
+            "transactions": [],
+            "signature": "0x1234",
+            "validators": ["0x00000000000000000000000000000000000000000000"]
+        });
+        let block: Block<TxHash> = serde_json::from_value(block.clone()).unwrap();
+        assert_eq!(block.energy_limit, U256::from(0x6691b7));
+        assert_eq!(block.energy_used, U256::from(0x5208));
+        assert_eq!(block.other.len(), 2);
+        assert_eq!(block.other["signature"], "0x1234");
+
+        // the unknown fields survive a round trip, also when converting to a full block
+        let full = block.clone().into_full_block(vec![]);
+        assert_eq!(full.other, block.other);
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["signature"], "0x1234");
+        assert_eq!(serde_json::from_value::<Block<TxHash>>(json).unwrap(), block);
+    }
+
     #[test]
     fn deserialize_blk_with_txs() {
         let block = r#"{"number":"0x3","hash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","parentHash":"0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90", "nonce":"0x0000000000000000","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","transactionsRoot":"0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d","stateRoot":"0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b","receiptsRoot":"0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2","miner":"0x00000000000000000000000000000000000000000000","difficulty":"0x0","totalDifficulty":"0x0","extraData":"0x","size":"0x3e8","energyLimit":"0x6691b7","energyUsed":"0x5208","timestamp":"0x5ecedbb9","transactions":[{"hash":"0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067","nonce":"0x2","blockHash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","blockNumber":"0x3","transactionIndex":"0x0","from":"0xfdcedc3bfca10ecb0890337fbdd1977aba848000007a","to":"0xdca8ce283150ab773bcbeb8d38289bdb5661d0000e1e","value":"0x0","energy":"0x15f90","energyPrice":"0x4a817c800","input":"0x","v":"0x25","r":"0x19f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88","s":"0x73e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e"}],"uncles":[]}"#;
@@ -1,6 +1,6 @@
 use crate::{
     abi::ethereum_types::BloomInput,
-    types::{Address, BlockNumber, Bloom, Log, /* H160, */ H176, H256, U256, U64},
+    types::{Address, AddressExt, BlockNumber, Bloom, Log, /* H160, */ H176, H256, U256, U64},
     utils::sha3,
 };
 use serde::{
@@ -12,6 +12,29 @@ use std::ops::{Range, RangeFrom, RangeTo};
 
 pub type BloomFilter = Vec<Option<Bloom>>;
 
+/// Checks whether a [`Bloom`], e.g. the `logsBloom` of a block or receipt, may contain logs of an
+/// address or with a topic.
+///
+/// Blooms have false positives but no false negatives: `false` means that there certainly is no
+/// such log, `true` only that there may be one.
+pub trait BloomExt {
+    /// Whether the bloom may contain logs emitted by `address`
+    fn contains_address(&self, address: &Address) -> bool;
+
+    /// Whether the bloom may contain logs with `topic`
+    fn contains_topic(&self, topic: &H256) -> bool;
+}
+
+impl BloomExt for Bloom {
+    fn contains_address(&self, address: &Address) -> bool {
+        self.contains_input(BloomInput::Raw(address.as_ref()))
+    }
+
+    fn contains_topic(&self, topic: &H256) -> bool {
+        self.contains_input(BloomInput::Raw(topic.as_ref()))
+    }
+}
+
 /// A single topic
 pub type Topic = ValueOrArray<Option<H256>>;
 
@@ -296,6 +319,56 @@ impl Filter {
         self
     }
 
+    /// Sets the indexed topic at `index` to the address or any of the addresses, left-padded to
+    /// 32 bytes like ylem encodes indexed `address` parameters
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than 3
+    ///
+    /// ```
+    /// use corebc_core::types::{Address, Filter};
+    ///
+    /// // `Transfer(address indexed from, address indexed to, uint256 value)` to one of two
+    /// // accounts
+    /// let filter = Filter::new()
+    ///     .event("Transfer(address,address,uint256)")
+    ///     .topic_address(2, vec![Address::random(), Address::random()]);
+    /// ```
+    #[must_use]
+    pub fn topic_address<T: Into<ValueOrArray<Address>>>(
+        mut self,
+        index: usize,
+        address: T,
+    ) -> Self {
+        self.topics[index] = Some(address.into().into());
+        self
+    }
+
+    /// Sets the indexed topic at `index` to the hash or any of the hashes, e.g. the hashes of
+    /// indexed `bytes32`, `string` or `bytes` parameters
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than 3
+    #[must_use]
+    pub fn topic_hash<T: Into<ValueOrArray<H256>>>(mut self, index: usize, hash: T) -> Self {
+        self.topics[index] = Some(hash.into().into());
+        self
+    }
+
+    /// Sets the indexed topic at `index` to the big endian encoding of the integer or any of the
+    /// integers
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than 3
+    #[must_use]
+    pub fn topic_uint<T: Into<ValueOrArray<U256>>>(mut self, index: usize, value: T) -> Self {
+        self.topics[index] = Some(value.into().into());
+        self
+    }
+
     pub fn is_paginatable(&self) -> bool {
         self.get_from_block().is_some()
     }
@@ -371,6 +444,41 @@ impl Filter {
     pub fn has_topics(&self) -> bool {
         self.topics.iter().any(|t| t.is_some())
     }
+
+    /// Returns `false` if a block or receipt with the `logsBloom` `bloom` certainly has no logs
+    /// matching the filter, so that its logs need not be fetched.
+    ///
+    /// The block range of the filter is not checked. Blooms have false positives, a `true` only
+    /// means that there may be matching logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_core::types::{Address, Bloom, Filter};
+    ///
+    /// let filter = Filter::new()
+    ///     .address(Address::repeat_byte(1))
+    ///     .event("Transfer(address,address,uint256)");
+    /// assert!(!filter.matches_bloom(&Bloom::zero()));
+    /// ```
+    pub fn matches_bloom(&self, bloom: &Bloom) -> bool {
+        let address = match &self.address {
+            None => true,
+            Some(ValueOrArray::Value(address)) => bloom.contains_address(address),
+            Some(ValueOrArray::Array(addresses)) => {
+                addresses.is_empty() || addresses.iter().any(|a| bloom.contains_address(a))
+            }
+        };
+        address &&
+            self.topics.iter().all(|topic| match topic {
+                None | Some(ValueOrArray::Value(None)) => true,
+                Some(ValueOrArray::Value(Some(topic))) => bloom.contains_topic(topic),
+                Some(ValueOrArray::Array(topics)) => {
+                    topics.is_empty() ||
+                        topics.iter().any(|t| t.map_or(true, |t| bloom.contains_topic(&t)))
+                }
+            })
+    }
 }
 
 impl Serialize for Filter {
@@ -563,6 +671,12 @@ impl From<H256> for Topic {
     }
 }
 
+impl From<H256> for ValueOrArray<H256> {
+    fn from(src: H256) -> Self {
+        ValueOrArray::Value(src)
+    }
+}
+
 impl From<Vec<H256>> for ValueOrArray<H256> {
     fn from(src: Vec<H256>) -> Self {
         ValueOrArray::Array(src)
@@ -584,19 +698,56 @@ impl<I: Into<H256>> From<Vec<I>> for Topic {
     }
 }
 
+impl From<U256> for ValueOrArray<U256> {
+    fn from(src: U256) -> Self {
+        ValueOrArray::Value(src)
+    }
+}
+
+impl From<Vec<U256>> for ValueOrArray<U256> {
+    fn from(src: Vec<U256>) -> Self {
+        ValueOrArray::Array(src)
+    }
+}
+
+/// Encodes the integer as big endian 32 byte topic
+fn uint_topic(value: U256) -> H256 {
+    let mut bytes = [0; 32];
+    value.to_big_endian(&mut bytes);
+    H256::from(bytes)
+}
+
 impl From<Address> for Topic {
     fn from(src: Address) -> Self {
-        let mut bytes = [0; 32];
-        bytes[10..32].copy_from_slice(src.as_bytes());
-        ValueOrArray::Value(Some(H256::from(bytes)))
+        ValueOrArray::Value(Some(src.to_h256()))
+    }
+}
+
+impl From<ValueOrArray<Address>> for Topic {
+    fn from(src: ValueOrArray<Address>) -> Self {
+        match src {
+            ValueOrArray::Value(val) => val.into(),
+            ValueOrArray::Array(arr) => {
+                ValueOrArray::Array(arr.into_iter().map(|addr| Some(addr.to_h256())).collect())
+            }
+        }
     }
 }
 
 impl From<U256> for Topic {
     fn from(src: U256) -> Self {
-        let mut bytes = [0; 32];
-        src.to_big_endian(&mut bytes);
-        ValueOrArray::Value(Some(H256::from(bytes)))
+        ValueOrArray::Value(Some(uint_topic(src)))
+    }
+}
+
+impl From<ValueOrArray<U256>> for Topic {
+    fn from(src: ValueOrArray<U256>) -> Self {
+        match src {
+            ValueOrArray::Value(val) => val.into(),
+            ValueOrArray::Array(arr) => {
+                ValueOrArray::Array(arr.into_iter().map(|val| Some(uint_topic(val))).collect())
+            }
+        }
     }
 }
 
@@ -894,6 +1045,44 @@ mod tests {
     use crate::utils::serialize;
     use serde_json::json;
 
+    #[test]
+    fn typed_topics() {
+        let a1 = "cb270000000000000000000000000000000000000001".parse::<Address>().unwrap();
+        let a2 = "cb270000000000000000000000000000000000000002".parse::<Address>().unwrap();
+        let filter = Filter::new()
+            .events(["Transfer(address,address,uint256)", "Approval(address,address,uint256)"])
+            .topic_address(1, a1)
+            .topic_address(2, vec![a1, a2])
+            .topic_uint(3, U256::from(5))
+            .topic_hash(3, vec![H256::repeat_byte(1)]);
+
+        assert_eq!(
+            filter.topics[0],
+            Some(ValueOrArray::Array(vec![
+                Some(H256::from(sha3("Transfer(address,address,uint256)"))),
+                Some(H256::from(sha3("Approval(address,address,uint256)"))),
+            ]))
+        );
+        let padded = |addr: Address| {
+            let topic = H256::from(addr);
+            assert_eq!(&topic[..10], &[0; 10]);
+            assert_eq!(&topic[10..], addr.as_bytes());
+            Some(topic)
+        };
+        assert_eq!(filter.topics[1], Some(ValueOrArray::Value(padded(a1))));
+        assert_eq!(filter.topics[2], Some(ValueOrArray::Array(vec![padded(a1), padded(a2)])));
+        assert_eq!(filter.topics[3], Some(ValueOrArray::Array(vec![Some(H256::repeat_byte(1))])));
+
+        let filter = Filter::new().topic_uint(1, vec![U256::from(1), U256::MAX]);
+        assert_eq!(
+            filter.topics[1],
+            Some(ValueOrArray::Array(vec![
+                Some(H256::from_low_u64_be(1)),
+                Some(H256::repeat_byte(0xff))
+            ]))
+        );
+    }
+
     #[test]
     fn can_serde_value_or_array() {
         #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -1001,6 +1190,26 @@ mod tests {
         (filter, Some(filtered_params.flat_topics))
     }
 
+    #[test]
+    fn matches_blooms() {
+        let address = Address::random();
+        let topic1 = H256::random();
+        let topic2 = H256::random();
+        let bloom = build_bloom(address, topic1, topic2);
+        assert!(bloom.contains_address(&address));
+        assert!(bloom.contains_topic(&topic1));
+        assert!(!bloom.contains_topic(&H256::random()));
+
+        assert!(Filter::new().matches_bloom(&bloom));
+        assert!(Filter::new().address(address).topic0(topic1).topic2(topic2).matches_bloom(&bloom));
+        assert!(Filter::new()
+            .address(vec![Address::random(), address])
+            .topic1(vec![H256::random(), topic2])
+            .matches_bloom(&bloom));
+        assert!(!Filter::new().address(Address::random()).matches_bloom(&bloom));
+        assert!(!Filter::new().address(address).topic1(H256::random()).matches_bloom(&bloom));
+    }
+
     #[test]
     fn can_detect_different_topics() {
         let topic1 = H256::random();
@@ -205,6 +205,12 @@ impl I256 {
         self.0
     }
 
+    /// Creates an I256 from the 64-bit limbs of its two's complement, least significant first.
+    #[inline(always)]
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        Self(U256(limbs))
+    }
+
     /// Convert from a decimal string.
     pub fn from_dec_str(value: &str) -> Result<Self, ParseI256Error> {
         let (sign, value) = match value.as_bytes().first() {
@@ -1613,6 +1619,62 @@ fn handle_overflow((result, overflow): (I256, bool)) -> I256 {
     result
 }
 
+#[doc(hidden)]
+pub const fn __i256_from_literal(literal: &str) -> I256 {
+    let bytes = literal.as_bytes();
+    let mut start = 0;
+    while start < bytes.len() && bytes[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    let negative = start < bytes.len() && bytes[start] == b'-';
+    if negative {
+        start += 1;
+        while start < bytes.len() && bytes[start].is_ascii_whitespace() {
+            start += 1;
+        }
+    }
+
+    let mut limbs = super::u256::parse_literal_limbs(bytes, start);
+    // the absolute value of the minimum is 1 << 255, all other values are below it
+    let is_min = limbs[0] == 0 && limbs[1] == 0 && limbs[2] == 0 && limbs[3] == 1 << 63;
+    if limbs[3] >= 1 << 63 && !(negative && is_min) {
+        panic!("integer literal does not fit into 256 bits")
+    }
+    if negative {
+        // two's complement: invert and add one
+        let mut carry = 1;
+        let mut limb = 0;
+        while limb < 4 {
+            let (value, overflow) = (!limbs[limb]).overflowing_add(carry);
+            limbs[limb] = value;
+            carry = overflow as u64;
+            limb += 1;
+        }
+    }
+    I256::from_limbs(limbs)
+}
+
+/// Creates an [`I256`] constant from a decimal or `0x` prefixed hexadecimal literal with an
+/// optional `-` sign. Invalid and overflowing literals fail to compile.
+///
+/// ```
+/// use corebc_core::{i256, types::I256};
+///
+/// const MINUS_ONE: I256 = i256!(-1);
+/// assert_eq!(MINUS_ONE, I256::minus_one());
+/// assert_eq!(i256!(-0x10), I256::from(-16));
+/// const MIN: I256 =
+///     i256!(-57896044618658097711785492504343953926634992332820282019728792003956564819968);
+/// assert_eq!(MIN, I256::MIN);
+/// ```
+#[macro_export]
+macro_rules! i256 {
+    ($value:literal) => {{
+        const VALUE: $crate::types::I256 = $crate::types::__i256_from_literal(stringify!($value));
+        VALUE
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1623,6 +1685,21 @@ mod tests {
 
     static MIN_ABS: Lazy<U256> = Lazy::new(|| U256::from(1) << 255);
 
+    #[test]
+    fn parses_literals() {
+        assert_eq!(crate::i256!(0), I256::zero());
+        assert_eq!(crate::i256!(-0), I256::zero());
+        assert_eq!(crate::i256!(-1_000), I256::from(-1000));
+        assert_eq!(crate::i256!(0x7f), I256::from(127));
+        assert_eq!(
+            crate::i256!(
+                57896044618658097711785492504343953926634992332820282019728792003956564819967
+            ),
+            I256::MAX
+        );
+        assert_eq!(I256::from_limbs([1, 0, 0, 0]), I256::one());
+    }
+
     #[test]
     fn identities() {
         const ONE: I256 = I256::from_raw(U256([1, 0, 0, 0]));
@@ -60,6 +60,13 @@ pub struct Log {
     pub removed: Option<bool>,
 }
 
+impl Log {
+    /// Whether the log was removed by a chain reorganization
+    pub fn is_removed(&self) -> bool {
+        self.removed.unwrap_or(false)
+    }
+}
+
 impl rlp::Encodable for Log {
     fn rlp_append(&self, s: &mut rlp::RlpStream) {
         s.begin_list(3);
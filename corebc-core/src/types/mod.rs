@@ -18,6 +18,9 @@ pub use transaction::{
     response::{Transaction, TransactionReceipt},
 };
 
+mod address;
+pub use address::{AddressExt, InvalidAddressWord, ADDRESS_PADDING};
+
 mod address_or_bytes;
 pub use address_or_bytes::AddressOrBytes;
 
@@ -31,6 +34,8 @@ mod uint8;
 pub use uint8::*;
 
 mod i256;
+#[doc(hidden)]
+pub use i256::__i256_from_literal;
 pub use i256::{ParseI256Error, Sign, I256};
 
 mod bytes;
@@ -290,6 +290,24 @@ impl Network {
         Some(Duration::from_millis(ms))
     }
 
+    /// Returns the maximum size in bytes of the runtime code of a contract on the network,
+    /// deployments of larger contracts fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corebc_core::types::Network;
+    ///
+    /// assert_eq!(Network::Mainnet.max_code_size(), 24_576);
+    /// ```
+    pub const fn max_code_size(&self) -> usize {
+        use Network::*;
+
+        match self {
+            Mainnet | Devin | Private(_) => 24_576,
+        }
+    }
+
     /// Returns whether the network implements EIP-1559 (with the type 2 EIP-2718 transaction type).
     ///
     /// # Examples
@@ -338,6 +356,37 @@ impl Network {
 
         Some(urls)
     }
+
+    /// Returns the network's blockchain explorer and its API URLs like
+    /// [`blockindex_urls`](Self::blockindex_urls), unless they are overridden by the
+    /// `BLOCKINDEX_API_URL_<NETWORK>` and `BLOCKINDEX_URL_<NETWORK>` environment variables.
+    ///
+    /// `<NETWORK>` is the uppercased name of the network with dashes replaced by underscores, e.g.
+    /// `BLOCKINDEX_API_URL_DEVIN` or `BLOCKINDEX_API_URL_PRIVATE_1337`.
+    ///
+    /// Returns `(API_URL, BASE_URL)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corebc_core::types::Network;
+    ///
+    /// std::env::set_var("BLOCKINDEX_API_URL_PRIVATE_1337", "https://staging.blockindex.net/api/v2");
+    /// let (api, base) = Network::Private(1337).blockindex_urls_from_env().unwrap();
+    /// assert_eq!(api, "https://staging.blockindex.net/api/v2");
+    /// assert_eq!(base, "");
+    /// ```
+    pub fn blockindex_urls_from_env(&self) -> Option<(String, String)> {
+        let var = |prefix: &str| {
+            let name = format!("{prefix}_{}", self.to_string().to_uppercase().replace('-', "_"));
+            std::env::var(name).ok().filter(|url| !url.is_empty())
+        };
+        let (api, base) = self.blockindex_urls()?;
+        Some((
+            var("BLOCKINDEX_API_URL").unwrap_or_else(|| api.to_string()),
+            var("BLOCKINDEX_URL").unwrap_or_else(|| base.to_string()),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +394,22 @@ mod tests {
     use super::*;
     use strum::IntoEnumIterator;
 
+    #[test]
+    fn blockindex_urls_env_overrides() {
+        let network = Network::Private(2152);
+        assert_eq!(network.blockindex_urls_from_env(), Some((String::new(), String::new())));
+
+        std::env::set_var("BLOCKINDEX_API_URL_PRIVATE_2152", "https://staging.blockindex.net/api");
+        std::env::set_var("BLOCKINDEX_URL_PRIVATE_2152", "https://staging.blockindex.net");
+        assert_eq!(
+            network.blockindex_urls_from_env(),
+            Some((
+                "https://staging.blockindex.net/api".to_string(),
+                "https://staging.blockindex.net".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn default() {
         assert_eq!(serde_json::to_string(&Network::default()).unwrap(), "\"mainnet\"");
@@ -1,6 +1,6 @@
 //! Some convenient serde helpers
 
-use crate::types::{BlockNumber, U256};
+use crate::types::{BlockNumber, H1368, H176, H256, I256, U256, U64};
 use serde::{Deserialize, Deserializer};
 use std::{
     convert::{TryFrom, TryInto},
@@ -255,6 +255,269 @@ where
     Ok(num)
 }
 
+/// Integers that [`decimal`] and [`decimal_opt`] (de)serialize as decimal strings
+pub trait DecimalString: Sized {
+    /// Formats the integer as decimal string
+    fn to_decimal_string(&self) -> String;
+
+    /// Parses a decimal string, unsigned integers also accept `0x` prefixed hex strings
+    fn from_decimal_str(s: &str) -> Result<Self, String>;
+}
+
+macro_rules! impl_unsigned_decimal_string {
+    ($($t:ty),*) => {
+        $(
+            impl DecimalString for $t {
+                fn to_decimal_string(&self) -> String {
+                    self.to_string()
+                }
+
+                fn from_decimal_str(s: &str) -> Result<Self, String> {
+                    if s.starts_with("0x") {
+                        <$t>::from_str(s).map_err(|err| err.to_string())
+                    } else {
+                        <$t>::from_dec_str(s).map_err(|err| err.to_string())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_decimal_string!(U64, U256);
+
+impl DecimalString for I256 {
+    fn to_decimal_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_decimal_str(s: &str) -> Result<Self, String> {
+        I256::from_dec_str(s).map_err(|err| err.to_string())
+    }
+}
+
+/// A decimal string or a plain JSON number
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DecimalValue {
+    String(String),
+    Unsigned(u64),
+    Signed(i64),
+}
+
+impl DecimalValue {
+    fn parse<T: DecimalString>(self) -> Result<T, String> {
+        match self {
+            DecimalValue::String(s) => T::from_decimal_str(&s),
+            DecimalValue::Unsigned(n) => T::from_decimal_str(&n.to_string()),
+            DecimalValue::Signed(n) => T::from_decimal_str(&n.to_string()),
+        }
+    }
+}
+
+/// (De)serializes [`U64`], [`U256`] and [`I256`] as decimal strings instead of hex quantities,
+/// for APIs that reject the latter.
+///
+/// Numbers are deserialized from decimal strings and JSON numbers.
+///
+/// ```
+/// use corebc_core::types::{serde_helpers::decimal, U256};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Payment {
+///     #[serde(with = "decimal")]
+///     amount: U256,
+/// }
+///
+/// let payment = Payment { amount: U256::from(1_000_000) };
+/// assert_eq!(serde_json::to_string(&payment).unwrap(), r#"{"amount":"1000000"}"#);
+/// ```
+pub mod decimal {
+    use super::{DecimalString, DecimalValue};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the integer as decimal string
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: DecimalString,
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.to_decimal_string())
+    }
+
+    /// Deserializes the integer from a decimal string or number
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DecimalString,
+        D: Deserializer<'de>,
+    {
+        DecimalValue::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Like [`decimal`], for optional integers
+pub mod decimal_opt {
+    use super::{DecimalString, DecimalValue};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the integer as decimal string, or `None` as `null`
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: DecimalString,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(&value.to_decimal_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes the integer from a decimal string, number or `null`
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: DecimalString,
+        D: Deserializer<'de>,
+    {
+        match Option::<DecimalValue>::deserialize(deserializer)? {
+            Some(value) => value.parse().map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Fixed size hashes that [`hash_base64`] and [`hash_bytes`] (de)serialize
+pub trait FixedHash: Sized {
+    /// Returns the big-endian bytes of the hash
+    fn hash_bytes(&self) -> &[u8];
+
+    /// Creates the hash from its big-endian bytes, fails if their length does not match
+    fn from_hash_bytes(bytes: &[u8]) -> Result<Self, String>;
+}
+
+macro_rules! impl_fixed_hash {
+    ($($t:ty),*) => {
+        $(
+            impl FixedHash for $t {
+                fn hash_bytes(&self) -> &[u8] {
+                    self.as_bytes()
+                }
+
+                fn from_hash_bytes(bytes: &[u8]) -> Result<Self, String> {
+                    if bytes.len() != <$t>::len_bytes() {
+                        return Err(format!(
+                            "expected {} bytes, got {}",
+                            <$t>::len_bytes(),
+                            bytes.len()
+                        ))
+                    }
+                    Ok(<$t>::from_slice(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_hash!(H176, H256, H1368);
+
+/// A `0x` prefixed hex string, a base64 string or an array of bytes
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HashValue {
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl HashValue {
+    fn parse<T: FixedHash>(self) -> Result<T, String> {
+        use base64::{engine::general_purpose, Engine};
+
+        let bytes = match self {
+            HashValue::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => hex::decode(hex).map_err(|err| err.to_string())?,
+                None => general_purpose::STANDARD
+                    .decode(&s)
+                    .or_else(|_| general_purpose::URL_SAFE.decode(&s))
+                    .map_err(|err| err.to_string())?,
+            },
+            HashValue::Bytes(bytes) => bytes,
+        };
+        T::from_hash_bytes(&bytes)
+    }
+}
+
+/// (De)serializes [`H256`], [`H1368`] and [`Address`](crate::types::Address) as base64 strings
+/// instead of `0x` prefixed hex strings, e.g. for the JSON mapping of protobuf `bytes` used by
+/// gRPC gateways.
+///
+/// Hashes are deserialized from standard or URL-safe base64 strings, `0x` prefixed hex strings
+/// and arrays of bytes. Strings without `0x` prefix are always read as base64.
+///
+/// ```
+/// use corebc_core::types::{serde_helpers::hash_base64, H256};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Receipt {
+///     #[serde(with = "hash_base64")]
+///     hash: H256,
+/// }
+///
+/// let receipt = Receipt { hash: H256::repeat_byte(0xff) };
+/// assert_eq!(
+///     serde_json::to_string(&receipt).unwrap(),
+///     r#"{"hash":"//////////////////////////////////////////8="}"#
+/// );
+/// ```
+pub mod hash_base64 {
+    use super::{FixedHash, HashValue};
+    use base64::{engine::general_purpose, Engine};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the hash as standard base64 string
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: FixedHash,
+        S: Serializer,
+    {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(value.hash_bytes()))
+    }
+
+    /// Deserializes the hash from a base64 string, hex string or array of bytes
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FixedHash,
+        D: Deserializer<'de>,
+    {
+        HashValue::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Like [`hash_base64`], but serializes hashes as raw bytes, which JSON represents as an array of
+/// numbers
+pub mod hash_bytes {
+    use super::{FixedHash, HashValue};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the hash as raw bytes
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: FixedHash,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value.hash_bytes())
+    }
+
+    /// Deserializes the hash from an array of bytes, base64 string or hex string
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FixedHash,
+        D: Deserializer<'de>,
+    {
+        HashValue::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -275,4 +538,83 @@ mod tests {
         let domain: CIP712Domain = serde_json::from_value(val).unwrap();
         assert_eq!(domain.network_id, Some(137u64.into()));
     }
+
+    #[test]
+    fn decimal_strings() {
+        use super::{decimal, decimal_opt};
+        use crate::types::{I256, U256};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Amounts {
+            #[serde(with = "decimal")]
+            unsigned: U256,
+            #[serde(with = "decimal")]
+            signed: I256,
+            #[serde(with = "decimal_opt")]
+            optional: Option<U256>,
+        }
+
+        let amounts =
+            Amounts { unsigned: U256::exp10(20), signed: I256::from(-42), optional: None };
+        let json = serde_json::to_value(&amounts).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "unsigned": "100000000000000000000",
+                "signed": "-42",
+                "optional": null
+            })
+        );
+        assert_eq!(serde_json::from_value::<Amounts>(json).unwrap(), amounts);
+
+        let json = serde_json::json!({ "unsigned": "0x10", "signed": -1, "optional": 7 });
+        let amounts: Amounts = serde_json::from_value(json).unwrap();
+        assert_eq!(amounts.unsigned, U256::from(16));
+        assert_eq!(amounts.signed, I256::minus_one());
+        assert_eq!(amounts.optional, Some(U256::from(7)));
+
+        let json = serde_json::json!({ "unsigned": "-1", "signed": 0, "optional": null });
+        assert!(serde_json::from_value::<Amounts>(json).is_err());
+    }
+
+    #[test]
+    fn fixed_hash_encodings() {
+        use super::{hash_base64, hash_bytes};
+        use crate::types::{Address, H1368, H256};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Hashes {
+            #[serde(with = "hash_base64")]
+            hash: H256,
+            #[serde(with = "hash_base64")]
+            sig: H1368,
+            #[serde(with = "hash_bytes")]
+            address: Address,
+        }
+
+        let hashes = Hashes {
+            hash: H256::repeat_byte(0xfb),
+            sig: H1368::repeat_byte(1),
+            address: Address::from_low_u64_be(0x0102),
+        };
+        let json = serde_json::to_value(&hashes).unwrap();
+        assert_eq!(json["hash"], "+/v7+/v7+/v7+/v7+/v7+/v7+/v7+/v7+/v7+/v7+/s=");
+        let mut address = vec![0u8; 20];
+        address.extend([1, 2]);
+        assert_eq!(json["address"], serde_json::json!(address));
+        assert_eq!(serde_json::from_value::<Hashes>(json).unwrap(), hashes);
+
+        // every helper accepts hex, base64 and bytes
+        let json = serde_json::json!({
+            "hash": "-_v7-_v7-_v7-_v7-_v7-_v7-_v7-_v7-_v7-_v7-_s=",
+            "sig": format!("0x{}", "01".repeat(171)),
+            "address": "AAAAAAAAAAAAAAAAAAAAAAAAAAABAg==",
+        });
+        assert_eq!(serde_json::from_value::<Hashes>(json).unwrap(), hashes);
+
+        let json = serde_json::json!({ "hash": "AQI=", "sig": [1], "address": "0x0102" });
+        assert!(serde_json::from_value::<Hashes>(json).is_err());
+    }
 }
@@ -1,6 +1,6 @@
 // Code adapted from: https://github.com/tomusdrw/rust-web3/blob/master/src/api/accounts.rs
 use crate::{
-    types::{Address, Network, H1368, H256},
+    types::{Address, Network, H1368, H256, H456, U256},
     utils::{hash_message, to_ican},
 };
 use ethabi::ethereum_types::H160;
@@ -29,6 +29,12 @@ pub enum SignatureError {
     /// Error in recovering public key from signature
     #[error("Public key recovery error")]
     RecoveryError,
+    /// Thrown when recovering the sender of a transaction without a network id
+    #[error("missing network id")]
+    MissingNetworkId,
+    /// Thrown when the network id of a transaction does not fit into 64 bits
+    #[error("invalid network id {0}")]
+    InvalidNetworkId(U256),
 }
 
 /// Recovery message data.
@@ -112,9 +118,8 @@ impl Signature {
 
         let sig_pub_bytes = self.sig.to_fixed_bytes();
         let mut sig_bytes = [0u8; 114];
-        let mut pub_bytes = [0u8; 57];
         sig_bytes.copy_from_slice(&sig_pub_bytes[0..114]);
-        pub_bytes.copy_from_slice(&sig_pub_bytes[114..171]);
+        let pub_bytes = self.public_key().to_fixed_bytes();
 
         ed448_verify_with_error(&pub_bytes, &sig_bytes, message_hash.as_ref())?;
 
@@ -127,6 +132,86 @@ impl Signature {
         Ok(to_ican(&addr, network))
     }
 
+    /// Same as [`Self::recover`], but takes the numeric network id, which determines the ICAN
+    /// prefix of the address. Ids of unknown networks recover to private network addresses.
+    pub fn recover_with_network_id<M>(
+        &self,
+        message: M,
+        network_id: u64,
+    ) -> Result<Address, SignatureError>
+    where
+        M: Into<RecoveryMessage>,
+    {
+        self.recover(message, &Network::from(network_id))
+    }
+
+    /// Recovers the signers of many signatures at once, e.g. the senders of the transactions of a
+    /// block.
+    ///
+    /// Returns a result per item, in the order of `items`, so a single invalid signature does not
+    /// fail the whole batch. See [`Self::par_batch_recover`] to recover them in parallel.
+    pub fn batch_recover<I, M>(items: I, network: &Network) -> Vec<Result<Address, SignatureError>>
+    where
+        I: IntoIterator<Item = (Signature, M)>,
+        M: Into<RecoveryMessage>,
+    {
+        items.into_iter().map(|(signature, message)| signature.recover(message, network)).collect()
+    }
+
+    /// Verifies many signatures at once, returning a result per `(signature, message, address)`
+    /// item in the order of `items`.
+    pub fn batch_verify<I, M>(items: I, network: &Network) -> Vec<Result<(), SignatureError>>
+    where
+        I: IntoIterator<Item = (Signature, M, Address)>,
+        M: Into<RecoveryMessage>,
+    {
+        items
+            .into_iter()
+            .map(|(signature, message, address)| signature.verify(message, network, address))
+            .collect()
+    }
+
+    /// Same as [`Self::batch_recover`], but recovers the signers on the rayon thread pool
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_batch_recover<M>(
+        items: Vec<(Signature, M)>,
+        network: &Network,
+    ) -> Vec<Result<Address, SignatureError>>
+    where
+        M: Into<RecoveryMessage> + Send,
+    {
+        use rayon::prelude::*;
+
+        items
+            .into_par_iter()
+            .map(|(signature, message)| signature.recover(message, network))
+            .collect()
+    }
+
+    /// Same as [`Self::batch_verify`], but verifies the signatures on the rayon thread pool
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_batch_verify<M>(
+        items: Vec<(Signature, M, Address)>,
+        network: &Network,
+    ) -> Vec<Result<(), SignatureError>>
+    where
+        M: Into<RecoveryMessage> + Send,
+    {
+        use rayon::prelude::*;
+
+        items
+            .into_par_iter()
+            .map(|(signature, message, address)| signature.verify(message, network, address))
+            .collect()
+    }
+
+    /// Returns the Ed448 public key of the signer, which trails the signature bytes
+    pub fn public_key(&self) -> H456 {
+        H456::from_slice(&self.sig[114..])
+    }
+
     /// Copies and serializes `self` into a new `Vec` with the recovery id included
     #[allow(clippy::wrong_self_convention)]
     pub fn to_vec(&self) -> Vec<u8> {
@@ -246,6 +331,8 @@ impl From<H256> for RecoveryMessage {
 mod tests {
     use super::*;
 
+    const WEB3_SIGNATURE: &str = "0x611d178b128095022653965eb0ed3bc8bbea8e7891b5a121a102a5b29bb895770d204354dbbc67c5567186f92cdb58a601397dfe0022e0ce002c1333b6829c37c732fb909501f719df200ceaaa0e0a1533dc22e4c9c999406c071fee2858bc7c76c66d113ff1ac739564d465cd541b0d1e003761457fcdd53dba3dea5848c43aa54fe468284319f032945a3acb9bd4cd0fa7b7c901d978e9acd9eca43fa5b3c32b648c33dcc3f3169e8080";
+
     #[test]
     fn recover_web3_signature() {
         // https://web3js.readthedocs.io/en/v1.2.2/web3-eth-accounts.html#sign
@@ -259,6 +346,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn batch_recovers_signatures() {
+        let signature = Signature::from_str(WEB3_SIGNATURE).unwrap();
+        let signer = Address::from_str("ab76fc37a3b370a1f22e2fe2f819c210895e098845ed").unwrap();
+        let mut tampered = signature;
+        tampered.sig.0[0] ^= 1;
+
+        let items = vec![(signature, "Some data"), (tampered, "Some data"), (signature, "Other")];
+        let recovered = Signature::batch_recover(items.clone(), &Network::Devin);
+        assert_eq!(recovered.len(), 3);
+        assert_eq!(recovered[0].as_ref().unwrap(), &signer);
+        assert!(recovered[1].is_err());
+        assert!(recovered[2].is_err());
+
+        let verified = Signature::batch_verify(
+            items.into_iter().map(|(signature, message)| (signature, message, signer)),
+            &Network::Devin,
+        );
+        assert!(verified[0].is_ok());
+        assert!(verified[1].is_err());
+        assert!(verified[2].is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_batch_recovers_signatures() {
+        let signature = Signature::from_str(WEB3_SIGNATURE).unwrap();
+        let signer = Address::from_str("ab76fc37a3b370a1f22e2fe2f819c210895e098845ed").unwrap();
+        let mut items = vec![(signature, "Some data"); 64];
+        items[7].1 = "Other";
+
+        let recovered = Signature::par_batch_recover(items.clone(), &Network::Devin);
+        assert!(recovered.iter().enumerate().all(|(i, r)| r.is_ok() == (i != 7)));
+        assert!(recovered.iter().flatten().all(|address| *address == signer));
+
+        let items = items.into_iter().map(|(signature, message)| (signature, message, signer));
+        let verified = Signature::par_batch_verify(items.collect(), &Network::Devin);
+        assert!(verified.iter().enumerate().all(|(i, r)| r.is_ok() == (i != 7)));
+    }
+
     #[test]
     fn signature_from_str() {
         let s1 = Signature::from_str(
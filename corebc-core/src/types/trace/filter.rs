@@ -31,6 +31,11 @@ pub struct TraceFilter {
 }
 
 impl TraceFilter {
+    /// Creates a new, empty trace filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     /// Sets From block
     #[allow(clippy::wrong_self_convention)]
     #[must_use]
@@ -76,6 +81,16 @@ impl TraceFilter {
         self.count = Some(count);
         self
     }
+
+    /// Returns the after offset, if set
+    pub fn get_after(&self) -> Option<usize> {
+        self.after
+    }
+
+    /// Returns the amount of traces to display, if set
+    pub fn get_count(&self) -> Option<usize> {
+        self.count
+    }
 }
 
 // `LocalizedTrace` in Parity
@@ -469,6 +484,34 @@ mod tests {
         "type": "reward"
     }"#;
 
+    #[test]
+    fn test_trace_filter_builder() {
+        let from = "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb".parse::<Address>().unwrap();
+        let to = "0x0000fb6916095ca1df60bb79ce92ce3ea74c37c5d359".parse::<Address>().unwrap();
+        let filter = TraceFilter::new()
+            .from_block(1u64)
+            .to_block(BlockNumber::Latest)
+            .from_address(vec![from])
+            .to_address(vec![to])
+            .after(10)
+            .count(5);
+
+        assert_eq!(filter.get_after(), Some(10));
+        assert_eq!(filter.get_count(), Some(5));
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            serde_json::json!({
+                "fromBlock": "0x1",
+                "toBlock": "latest",
+                "fromAddress": ["0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb"],
+                "toAddress": ["0x0000fb6916095ca1df60bb79ce92ce3ea74c37c5d359"],
+                "after": 10,
+                "count": 5
+            })
+        );
+        assert_eq!(serde_json::to_value(TraceFilter::new()).unwrap(), serde_json::json!({}));
+    }
+
     #[test]
     fn test_deserialize_trace() {
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_CALL).unwrap();
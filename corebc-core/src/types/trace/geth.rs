@@ -110,6 +110,34 @@ impl From<Value> for GoCoreTrace {
     }
 }
 
+/// The trace of a single transaction, as returned by `debug_traceBlockByNumber` and
+/// `debug_traceBlockByHash`
+///
+/// See <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debugtraceblockbynumber>
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoCoreTxTrace {
+    /// Hash of the traced transaction, omitted by older nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<H256>,
+    /// The trace of the transaction, if tracing succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<GoCoreTrace>,
+    /// The reason tracing the transaction failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl GoCoreTxTrace {
+    /// Returns the frame of the `callTracer`, if the transaction was traced with it
+    pub fn call_frame(&self) -> Option<&CallFrame> {
+        match &self.result {
+            Some(GoCoreTrace::Known(GoCoreTraceFrame::CallTracer(frame))) => Some(frame),
+            _ => None,
+        }
+    }
+}
+
 /// Available built-in tracers
 ///
 /// See <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
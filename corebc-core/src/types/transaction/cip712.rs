@@ -2,7 +2,7 @@ use crate::{
     abi,
     abi::{HumanReadableParser, ParamType, Token},
     types::{serde_helpers::StringifiedNumeric, Address, Bytes, U256},
-    utils::sha3,
+    utils::{hash_structured_data, sha3},
 };
 use ethabi::encode;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -94,9 +94,7 @@ pub trait Cip712 {
         let domain_separator = self.domain_separator()?;
         let struct_hash = self.struct_hash()?;
 
-        let digest_input = [&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat();
-
-        Ok(sha3(digest_input))
+        Ok(hash_structured_data(domain_separator, struct_hash).0)
     }
 }
 
@@ -1,4 +1,4 @@
-use super::request::RequestError;
+use super::{envelope::ExtensionTransaction, request::RequestError};
 use crate::{
     types::{
         Address, Bytes, NameOrAddress, Signature, Transaction, TransactionRequest, H256, U256, U64,
@@ -12,6 +12,9 @@ use thiserror::Error;
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum TypedTransaction {
     Legacy(TransactionRequest),
+    /// A transaction type defined outside of this crate, see
+    /// [`TransactionEnvelope`](super::envelope::TransactionEnvelope)
+    Extension(ExtensionTransaction),
 }
 
 impl Serialize for TypedTransaction {
@@ -21,6 +24,7 @@ impl Serialize for TypedTransaction {
     {
         match self {
             Self::Legacy(tx) => tx.serialize(serializer),
+            Self::Extension(tx) => tx.to_json().serialize(serializer),
         }
     }
 }
@@ -47,6 +51,9 @@ pub enum TypedTransactionError {
     /// Missing transaction payload when decoding from RLP
     #[error("Missing transaction payload when decoding")]
     MissingTransactionPayload,
+    /// The transaction type byte of an enveloped transaction is not defined by this crate
+    #[error("Unknown transaction type {0:#x}")]
+    UnknownType(u8),
 }
 
 impl Default for TypedTransaction {
@@ -58,23 +65,32 @@ impl Default for TypedTransaction {
 use TypedTransaction::*;
 
 impl TypedTransaction {
-    pub fn from(&self) -> Option<&Address> {
+    /// The fields shared by all transaction types
+    fn base(&self) -> &TransactionRequest {
         match self {
-            Legacy(inner) => inner.from.as_ref(),
+            Legacy(inner) => inner,
+            Extension(inner) => inner.base(),
         }
     }
 
-    pub fn set_from(&mut self, from: Address) -> &mut Self {
+    fn base_mut(&mut self) -> &mut TransactionRequest {
         match self {
-            Legacy(inner) => inner.from = Some(from),
-        };
+            Legacy(inner) => inner,
+            Extension(inner) => inner.as_envelope_mut().base_mut(),
+        }
+    }
+
+    pub fn from(&self) -> Option<&Address> {
+        self.base().from.as_ref()
+    }
+
+    pub fn set_from(&mut self, from: Address) -> &mut Self {
+        self.base_mut().from = Some(from);
         self
     }
 
     pub fn to(&self) -> Option<&NameOrAddress> {
-        match self {
-            Legacy(inner) => inner.to.as_ref(),
-        }
+        self.base().to.as_ref()
     }
 
     pub fn to_addr(&self) -> Option<&Address> {
@@ -83,98 +99,70 @@ impl TypedTransaction {
 
     pub fn set_to<T: Into<NameOrAddress>>(&mut self, to: T) -> &mut Self {
         let to = to.into();
-        match self {
-            Legacy(inner) => inner.to = Some(to),
-        };
+        self.base_mut().to = Some(to);
         self
     }
 
     pub fn nonce(&self) -> Option<&U256> {
-        match self {
-            Legacy(inner) => inner.nonce.as_ref(),
-        }
+        self.base().nonce.as_ref()
     }
 
     pub fn set_nonce<T: Into<U256>>(&mut self, nonce: T) -> &mut Self {
         let nonce = nonce.into();
-        match self {
-            Legacy(inner) => inner.nonce = Some(nonce),
-        };
+        self.base_mut().nonce = Some(nonce);
         self
     }
 
     pub fn value(&self) -> Option<&U256> {
-        match self {
-            Legacy(inner) => inner.value.as_ref(),
-        }
+        self.base().value.as_ref()
     }
 
     pub fn set_value<T: Into<U256>>(&mut self, value: T) -> &mut Self {
         let value = value.into();
-        match self {
-            Legacy(inner) => inner.value = Some(value),
-        };
+        self.base_mut().value = Some(value);
         self
     }
 
     pub fn energy(&self) -> Option<&U256> {
-        match self {
-            Legacy(inner) => inner.energy.as_ref(),
-        }
+        self.base().energy.as_ref()
     }
 
     pub fn energy_mut(&mut self) -> &mut Option<U256> {
-        match self {
-            Legacy(inner) => &mut inner.energy,
-        }
+        &mut self.base_mut().energy
     }
 
     pub fn set_energy<T: Into<U256>>(&mut self, energy: T) -> &mut Self {
         let energy = energy.into();
-        match self {
-            Legacy(inner) => inner.energy = Some(energy),
-        };
+        self.base_mut().energy = Some(energy);
         self
     }
 
     pub fn energy_price(&self) -> Option<U256> {
-        match self {
-            Legacy(inner) => inner.energy_price,
-        }
+        self.base().energy_price
     }
 
     pub fn set_energy_price<T: Into<U256>>(&mut self, energy_price: T) -> &mut Self {
         let energy_price = energy_price.into();
-        match self {
-            Legacy(inner) => inner.energy_price = Some(energy_price),
-        };
+        self.base_mut().energy_price = Some(energy_price);
         self
     }
 
     pub fn network_id(&self) -> Option<U64> {
-        match self {
-            Legacy(inner) => inner.network_id,
-        }
+        self.base().network_id
     }
 
     pub fn set_network_id<T: Into<U64>>(&mut self, network_id: T) -> &mut Self {
         let network_id = network_id.into();
-        match self {
-            Legacy(inner) => inner.network_id = Some(network_id),
-        };
+        self.base_mut().network_id = Some(network_id);
         self
     }
 
     pub fn data(&self) -> Option<&Bytes> {
-        match self {
-            Legacy(inner) => inner.data.as_ref(),
-        }
+        self.base().data.as_ref()
     }
 
     pub fn set_data(&mut self, data: Bytes) -> &mut Self {
-        match self {
-            Legacy(inner) => inner.data = Some(data),
-        };
+        self.base_mut().data = Some(data);
         self
     }
 
@@ -184,6 +172,9 @@ impl TypedTransaction {
             Legacy(ref tx) => {
                 encoded.extend_from_slice(tx.rlp_signed(signature).as_ref());
             }
+            Extension(ref tx) => {
+                encoded.extend_from_slice(tx.rlp_signed(signature).as_ref());
+            }
         };
         encoded.into()
     }
@@ -194,6 +185,9 @@ impl TypedTransaction {
             Legacy(inner) => {
                 encoded.extend_from_slice(inner.rlp().as_ref());
             }
+            Extension(inner) => {
+                encoded.extend_from_slice(inner.rlp().as_ref());
+            }
         };
 
         encoded.into()
@@ -207,12 +201,18 @@ impl TypedTransaction {
             Legacy(inner) => {
                 encoded.extend_from_slice(inner.rlp_sighash().as_ref());
             }
+            Extension(inner) => {
+                encoded.extend_from_slice(inner.rlp_sighash().as_ref());
+            }
         };
 
         encoded.into()
     }
 
-    /// Hashes the transaction's data. Does not double-RLP encode
+    /// Hashes the transaction's data. Does not double-RLP encode.
+    ///
+    /// For extension transactions this is the hash of the type byte followed by their
+    /// [`rlp_sighash_payload`](super::envelope::TransactionEnvelope::rlp_sighash_payload).
     pub fn sighash(&self) -> H256 {
         let encoded = self.rlp_sighash();
         sha3(encoded).into()
@@ -242,6 +242,22 @@ impl TypedTransaction {
 
         Err(rlp::DecoderError::Custom("invalid tx type").into())
     }
+
+    /// Decodes a raw signed transaction, like the ones sent with `xcb_sendRawTransaction` or
+    /// received from the p2p network, into the transaction and its signature.
+    ///
+    /// The sender of the transaction is recovered from the signature. Enveloped transactions fail
+    /// with [`TypedTransactionError::UnknownType`], their types are defined outside of this crate.
+    pub fn decode_signed_bytes(
+        raw: impl AsRef<[u8]>,
+    ) -> Result<(Self, Signature), TypedTransactionError> {
+        let raw = raw.as_ref();
+        match raw.first() {
+            None => Err(TypedTransactionError::MissingTransactionPayload),
+            Some(&tx_type) if tx_type < 0x7f => Err(TypedTransactionError::UnknownType(tx_type)),
+            Some(_) => Self::decode_signed(&rlp::Rlp::new(raw)),
+        }
+    }
 }
 
 /// Get a TypedTransaction directly from a rlp encoded byte stream
@@ -251,6 +267,12 @@ impl Decodable for TypedTransaction {
     }
 }
 
+impl From<ExtensionTransaction> for TypedTransaction {
+    fn from(src: ExtensionTransaction) -> TypedTransaction {
+        TypedTransaction::Extension(src)
+    }
+}
+
 impl From<TransactionRequest> for TypedTransaction {
     fn from(src: TransactionRequest) -> TypedTransaction {
         TypedTransaction::Legacy(src)
@@ -268,20 +290,36 @@ impl TypedTransaction {
     pub fn as_legacy_ref(&self) -> Option<&TransactionRequest> {
         match self {
             Legacy(tx) => Some(tx),
+            Extension(_) => None,
         }
     }
 
     pub fn as_legacy_mut(&mut self) -> Option<&mut TransactionRequest> {
         match self {
             Legacy(tx) => Some(tx),
+            Extension(_) => None,
         }
     }
+
+    pub fn as_extension_ref(&self) -> Option<&ExtensionTransaction> {
+        match self {
+            Extension(tx) => Some(tx),
+            Legacy(_) => None,
+        }
+    }
+
+    /// The type byte of the transaction, `None` for legacy transactions
+    pub fn tx_type(&self) -> Option<u8> {
+        self.as_extension_ref().map(|tx| tx.tx_type())
+    }
 }
 
 impl TypedTransaction {
+    /// Returns the legacy transaction, or the fields an extension shares with it
     fn into_legacy(self) -> TransactionRequest {
         match self {
             Legacy(tx) => tx,
+            Extension(tx) => tx.base().clone(),
         }
     }
 }
@@ -0,0 +1,186 @@
+//! Transaction types that are not built into [`TypedTransaction`](super::eip2718::TypedTransaction)
+
+use crate::types::{Bytes, Signature, TransactionRequest};
+use std::{fmt, ops::Deref};
+
+/// A transaction type that is defined outside of this crate.
+///
+/// Extensions are encoded like EIP-2718 envelopes: the type byte followed by the payload. The
+/// fields they share with legacy transactions are read from and written to
+/// [`base`](Self::base), so providers, middlewares and signers handle them like any other
+/// transaction. In particular, signers sign the hash of [`rlp_sighash_payload`] prefixed with
+/// the type byte.
+///
+/// [`rlp_sighash_payload`]: Self::rlp_sighash_payload
+pub trait TransactionEnvelope: fmt::Debug + Send + Sync {
+    /// The type byte of the transaction, must be below `0x7f` to be distinguishable from a RLP
+    /// encoded legacy transaction
+    fn tx_type(&self) -> u8;
+
+    /// The fields shared with legacy transactions
+    fn base(&self) -> &TransactionRequest;
+
+    /// Mutable access to the fields shared with legacy transactions
+    fn base_mut(&mut self) -> &mut TransactionRequest;
+
+    /// The RLP encoded unsigned payload, without the type byte
+    fn rlp_payload(&self) -> Bytes;
+
+    /// The RLP encoded payload whose hash is signed, without the type byte
+    fn rlp_sighash_payload(&self) -> Bytes {
+        self.rlp_payload()
+    }
+
+    /// The RLP encoded signed payload, without the type byte
+    fn rlp_signed_payload(&self, signature: &Signature) -> Bytes;
+
+    /// The JSON-RPC representation of the transaction.
+    ///
+    /// Defaults to the base fields with an additional `type` field.
+    fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::to_value(self.base()).unwrap_or_default();
+        if let Some(fields) = json.as_object_mut() {
+            fields.insert("type".to_string(), format!("{:#x}", self.tx_type()).into());
+        }
+        json
+    }
+
+    /// Clones the transaction into a new box
+    fn clone_envelope(&self) -> Box<dyn TransactionEnvelope>;
+}
+
+/// An owned [`TransactionEnvelope`], see
+/// [`TypedTransaction::Extension`](super::eip2718::TypedTransaction::Extension)
+#[derive(Debug)]
+pub struct ExtensionTransaction(Box<dyn TransactionEnvelope>);
+
+impl ExtensionTransaction {
+    /// Wraps the transaction
+    pub fn new(tx: impl TransactionEnvelope + 'static) -> Self {
+        Self(Box::new(tx))
+    }
+
+    /// Returns mutable access to the wrapped transaction
+    pub fn as_envelope_mut(&mut self) -> &mut dyn TransactionEnvelope {
+        &mut *self.0
+    }
+
+    /// The type byte followed by the unsigned payload
+    pub fn rlp(&self) -> Bytes {
+        self.enveloped(self.rlp_payload())
+    }
+
+    /// The type byte followed by the payload whose hash is signed
+    pub fn rlp_sighash(&self) -> Bytes {
+        self.enveloped(self.rlp_sighash_payload())
+    }
+
+    /// The type byte followed by the signed payload
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        self.enveloped(self.rlp_signed_payload(signature))
+    }
+
+    fn enveloped(&self, payload: Bytes) -> Bytes {
+        [&[self.tx_type()][..], &payload].concat().into()
+    }
+}
+
+impl Deref for ExtensionTransaction {
+    type Target = dyn TransactionEnvelope;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl Clone for ExtensionTransaction {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_envelope())
+    }
+}
+
+impl PartialEq for ExtensionTransaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.rlp() == other.rlp() && self.base() == other.base()
+    }
+}
+
+impl Eq for ExtensionTransaction {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::{transaction::eip2718::TypedTransaction, Address, H256, U256},
+        utils::sha3,
+    };
+
+    /// A transaction that carries an extra commitment next to the legacy fields
+    #[derive(Clone, Debug, Default)]
+    struct CommitmentTx {
+        base: TransactionRequest,
+        commitment: U256,
+    }
+
+    impl CommitmentTx {
+        fn rlp_with(&self, signature: Option<&Signature>) -> Bytes {
+            let mut rlp = rlp::RlpStream::new();
+            rlp.begin_unbounded_list();
+            rlp.append_raw(&self.base.rlp(), 1);
+            rlp.append(&self.commitment);
+            if let Some(signature) = signature {
+                rlp.append(&signature.sig.as_bytes().to_vec());
+            }
+            rlp.finalize_unbounded_list();
+            rlp.out().freeze().into()
+        }
+    }
+
+    impl TransactionEnvelope for CommitmentTx {
+        fn tx_type(&self) -> u8 {
+            0x10
+        }
+
+        fn base(&self) -> &TransactionRequest {
+            &self.base
+        }
+
+        fn base_mut(&mut self) -> &mut TransactionRequest {
+            &mut self.base
+        }
+
+        fn rlp_payload(&self) -> Bytes {
+            self.rlp_with(None)
+        }
+
+        fn rlp_signed_payload(&self, signature: &Signature) -> Bytes {
+            self.rlp_with(Some(signature))
+        }
+
+        fn clone_envelope(&self) -> Box<dyn TransactionEnvelope> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn extension_transactions_are_enveloped() {
+        let ext = CommitmentTx { commitment: 7.into(), ..Default::default() };
+        let mut tx = TypedTransaction::from(ExtensionTransaction::new(ext.clone()));
+
+        let to = Address::random();
+        tx.set_to(to).set_nonce(1).set_network_id(3);
+        assert_eq!(tx.to_addr(), Some(&to));
+        assert_eq!(tx.tx_type(), Some(0x10));
+        assert!(tx.as_legacy_ref().is_none());
+
+        let payload = tx.as_extension_ref().unwrap().rlp_payload();
+        assert_eq!(tx.rlp()[0], 0x10);
+        assert_eq!(tx.rlp()[1..], payload[..]);
+        assert_eq!(tx.sighash(), H256::from(sha3([&[0x10][..], &payload].concat())));
+        assert_ne!(tx, TypedTransaction::from(ExtensionTransaction::new(ext)));
+
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["type"], "0x10");
+        assert_eq!(json["nonce"], "0x1");
+    }
+}
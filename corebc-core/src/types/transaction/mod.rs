@@ -3,6 +3,8 @@ pub mod response;
 
 pub mod eip2718;
 
+pub mod envelope;
+
 pub mod cip712;
 
 pub(crate) const BASE_NUM_TX_FIELDS: usize = 9;
@@ -5,7 +5,7 @@ use crate::{
         Address, Bytes, NameOrAddress, Network, Signature, SignatureError, Transaction, H256, U256,
         U64,
     },
-    utils::sha3,
+    utils::{get_contract_address, sha3},
 };
 
 use rlp::{Decodable, RlpStream};
@@ -73,6 +73,28 @@ impl TransactionRequest {
         TransactionRequest { to: Some(to.into()), value: Some(value.into()), ..Default::default() }
     }
 
+    /// Creates a contract creation transaction that deploys `bytecode`, the ABI encoded
+    /// constructor arguments are appended to the code.
+    ///
+    /// The address of the contract can be computed with
+    /// [`contract_address`](Self::contract_address) once the sender and nonce are set.
+    pub fn deploy<T: Into<Bytes>>(bytecode: T, constructor_args: impl AsRef<[u8]>) -> Self {
+        let bytecode = bytecode.into();
+        let data = [bytecode.as_ref(), constructor_args.as_ref()].concat();
+        TransactionRequest { data: Some(data.into()), ..Default::default() }
+    }
+
+    /// Returns the address of the contract this transaction creates on `network`.
+    ///
+    /// Returns `None` if this is not a contract creation, or if the sender or the nonce are not
+    /// set yet.
+    pub fn contract_address(&self, network: &Network) -> Option<Address> {
+        if self.to.is_some() {
+            return None
+        }
+        Some(get_contract_address(self.from?, self.nonce?, network))
+    }
+
     // Builder pattern helpers
 
     /// Sets the `from` field in the transaction to the provided value
@@ -298,9 +320,25 @@ impl From<&Transaction> for TransactionRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::transaction::eip2718::{TypedTransaction, TypedTransactionError};
     use ethabi::ethereum_types::H1368;
     use rlp::{Decodable, Rlp};
 
+    #[test]
+    fn deploy_contract() {
+        let from = "00006ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse::<Address>().unwrap();
+        let tx = TransactionRequest::deploy(vec![0x60, 0x80], [0u8, 1]).from(from);
+        assert_eq!(tx.data, Some(vec![0x60, 0x80, 0, 1].into()));
+        assert_eq!(tx.contract_address(&Network::Mainnet), None);
+
+        let tx = tx.nonce(1);
+        assert_eq!(
+            tx.contract_address(&Network::Mainnet),
+            Some("cb45936ded405892dc4f98cc5e04805d03c200c706a5".parse().unwrap())
+        );
+        assert_eq!(tx.to(from).contract_address(&Network::Mainnet), None);
+    }
+
     #[test]
     fn encode_decode_rlp() {
         let tx = TransactionRequest::new()
@@ -458,6 +496,28 @@ mod tests {
         assert_eq!(decoded_tx.network_id, Some(U64::from(3)));
     }
 
+    #[test]
+    fn test_decode_signed_bytes() {
+        let raw_tx = hex::decode(
+            "f8ce030a82c3500396ab33d3649d846a2bd426c0ceaca24fab50f7cba8f8390a821123b8ab9e73edbc2506ab6805794c3bc45509713493f34eab8e62d2e75ebf9af7346fa85ad0d86c5c116366667b853f63e143943195602bd3cce8078008f9e95d6febd1b3909be9e4e2b1d5090a295c7dccfa28a3a8cc242ec8da680a77901a75c3e97e8c4a73552d09504432157a9d18aa08052700ba277941fcb9ac8063a9b6ed64fbc86c51dd5ae6cf1f01f7bcf533cf0b0cfc5dc3fdc5bc7eaa99366ada5e7127331b862586a46c12a85f9580"
+        ).unwrap();
+
+        let (tx, sig) = TypedTransaction::decode_signed_bytes(&raw_tx).unwrap();
+        let expected: Address = "0xab0338748ee459bc0c1d86eab1d3f6d83bb433cdad9c".parse().unwrap();
+        assert_eq!(tx.from(), Some(&expected));
+        assert_eq!(tx.network_id(), Some(U64::from(3)));
+        assert_eq!(tx.rlp_signed(&sig).as_ref(), raw_tx.as_slice());
+
+        assert!(matches!(
+            TypedTransaction::decode_signed_bytes(b""),
+            Err(TypedTransactionError::MissingTransactionPayload)
+        ));
+        assert!(matches!(
+            TypedTransaction::decode_signed_bytes([0x10u8, 0xc0]),
+            Err(TypedTransactionError::UnknownType(0x10))
+        ));
+    }
+
     #[test]
     fn test_recover_legacy_tx() {
         let raw_tx =
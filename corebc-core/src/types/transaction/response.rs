@@ -2,7 +2,7 @@
 use super::{decode_signature, decode_to, eip2718::TypedTransaction, rlp_opt};
 use crate::{
     types::{
-        Address, Bloom, Bytes, Log, Network, Signature, SignatureError, H1368, H256, U256, U64,
+        Address, Bloom, Bytes, Log, Signature, SignatureError, H1368, H256, U256, U64,
     },
     utils::sha3,
 };
@@ -115,12 +115,17 @@ impl Transaction {
     }
 
     /// Recover the sender of the tx from signature
+    ///
+    /// Fails with [`SignatureError::MissingNetworkId`] if the transaction has no network id.
     pub fn recover_from(&self) -> Result<Address, SignatureError> {
+        let network_id = self.network_id.ok_or(SignatureError::MissingNetworkId)?;
+        if network_id > U256::from(u64::MAX) {
+            return Err(SignatureError::InvalidNetworkId(network_id))
+        }
+
         let signature = Signature { sig: self.sig };
         let typed_tx: TypedTransaction = self.into();
-        // CORETODO: Please find a way to unwrap it more naturally
-        let network = Network::try_from(typed_tx.network_id().unwrap()).unwrap();
-        signature.recover(typed_tx.sighash(), &network)
+        signature.recover_with_network_id(typed_tx.sighash(), network_id.as_u64())
     }
 
     /// Recover the sender of the tx from signature and set the from field
@@ -365,6 +370,14 @@ mod tests {
 
         assert_eq!(tx.from, tx.recover_from().unwrap());
         assert_eq!(tx.hash, tx.hash());
+
+        // unknown or missing network ids are errors instead of panics
+        let other_network = Transaction { network_id: Some(U256::from(1337)), ..tx.clone() };
+        assert!(other_network.recover_from().is_err());
+        let missing = Transaction { network_id: None, ..tx.clone() };
+        assert!(matches!(missing.recover_from(), Err(SignatureError::MissingNetworkId)));
+        let overflowing = Transaction { network_id: Some(U256::MAX), ..tx };
+        assert!(matches!(overflowing.recover_from(), Err(SignatureError::InvalidNetworkId(_))));
     }
 
     #[test]
@@ -41,36 +41,57 @@ impl<'de> Visitor<'de> for TxpoolInspectSummaryVisitor {
     where
         E: de::Error,
     {
-        let addr_split: Vec<&str> = value.split(": ").collect();
-        if addr_split.len() != 2 {
-            return Err(de::Error::custom("invalid format for TxpoolInspectSummary: to"))
-        }
-        let value_split: Vec<&str> = addr_split[1].split(" wei + ").collect();
-        if value_split.len() != 2 {
-            return Err(de::Error::custom("invalid format for TxpoolInspectSummary: energyLimit"))
-        }
-        let energy_split: Vec<&str> = value_split[1].split(" energy × ").collect();
-        if energy_split.len() != 2 {
-            return Err(de::Error::custom("invalid format for TxpoolInspectSummary: energy"))
-        }
-        let energy_price_split: Vec<&str> = energy_split[1].split(" wei").collect();
-        if energy_price_split.len() != 2 {
-            return Err(de::Error::custom("invalid format for TxpoolInspectSummary: energy_price"))
-        }
-        let addr = match addr_split[0] {
-            "" => None,
-            "0x" => None,
-            "contract creation" => None,
-            addr => {
-                Some(Address::from_str(addr.trim_start_matches("0x")).map_err(de::Error::custom)?)
-            }
-        };
-        let value = U256::from_dec_str(value_split[0]).map_err(de::Error::custom)?;
-        let energy = U256::from_dec_str(energy_split[0]).map_err(de::Error::custom)?;
-        let energy_price = U256::from_dec_str(energy_price_split[0]).map_err(de::Error::custom)?;
+        let (to, amounts) = value
+            .split_once(':')
+            .ok_or_else(|| de::Error::custom("invalid format for TxpoolInspectSummary: to"))?;
+        let to = parse_inspect_recipient(to).map_err(de::Error::custom)?;
+
+        // the amounts are the only numbers, whatever the units and wording around them
+        let amounts = amounts
+            .split(|c: char| c.is_whitespace() || matches!(c, '+' | '×' | '*'))
+            .filter_map(parse_inspect_amount)
+            .collect::<Vec<_>>();
+        let [value, energy, energy_price]: [U256; 3] =
+            amounts.try_into().map_err(|amounts: Vec<U256>| {
+                de::Error::custom(format!(
+                    "invalid format for TxpoolInspectSummary: expected value, energy and energy \
+                     price, got {} amounts",
+                    amounts.len()
+                ))
+            })?;
+
+        Ok(TxpoolInspectSummary { to, value, energy, energy_price })
+    }
+}
 
-        Ok(TxpoolInspectSummary { to: addr, value, energy, energy_price })
+/// Parses the recipient of a summary, `None` for contract creations. Accepts hex and ICAN
+/// addresses, with or without `0x` prefix.
+fn parse_inspect_recipient(to: &str) -> Result<Option<Address>, String> {
+    let to = to.trim();
+    let hex = to.strip_prefix("0x").or_else(|| to.strip_prefix("0X")).unwrap_or(to);
+    if hex.is_empty() || to.to_lowercase().contains("contract") {
+        return Ok(None)
+    }
+    Address::from_str(hex)
+        .map(Some)
+        .map_err(|err| format!("invalid format for TxpoolInspectSummary: to `{to}`: {err}"))
+}
+
+/// Parses a decimal or `0x`-prefixed hex amount, optionally directly followed by its unit (e.g.
+/// `21000energy`), `None` for any other word
+fn parse_inspect_amount(word: &str) -> Option<U256> {
+    if let Some(hex) = word.strip_prefix("0x") {
+        if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None
+        }
+        return U256::from_str_radix(hex, 16).ok()
+    }
+    let (digits, unit) =
+        word.split_at(word.find(|c: char| !c.is_ascii_digit()).unwrap_or(word.len()));
+    if digits.is_empty() || !unit.chars().all(char::is_alphabetic) {
+        return None
     }
+    U256::from_dec_str(digits).ok()
 }
 
 /// Implement the `Deserialize` trait for `TxpoolInspectSummary` struct.
@@ -339,6 +360,97 @@ mod tests {
         assert_eq!(txpool_status_json.trim(), serialized);
     }
 
+    #[test]
+    fn deserialize_gocore_inspect_summaries() {
+        let to: Address = "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87".parse().unwrap();
+        let expected = TxpoolInspectSummary {
+            to: Some(to),
+            value: U256::from(10),
+            energy: U256::from(21000),
+            energy_price: U256::from(1000000000),
+        };
+        for summary in [
+            "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87: 10 ore + 21000 energy × 1000000000 ore",
+            "0xCB08095E7BAEA6A6C7C4C2DFEB977EFAC326AF552D87: 10 wei + 21000 energy × 1000000000 wei",
+            "  cb08095e7baea6a6c7c4c2dfeb977efac326af552d87 :\t10 ore +  21000 energy\t×1000000000 ore ",
+            "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87: 10ore + 21000 energy * 1000000000ore",
+            "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87: value 10 + energy 21000 at 0x3b9aca00",
+        ] {
+            let parsed: TxpoolInspectSummary =
+                serde_json::from_value(serde_json::Value::String(summary.to_string())).unwrap();
+            assert_eq!(parsed, expected, "{summary}");
+        }
+
+        let creation: TxpoolInspectSummary =
+            serde_json::from_str(r#""Contract Creation: 0 ore + 612412 energy × 6000000000 ore""#)
+                .unwrap();
+        assert_eq!(creation.to, None);
+
+        for invalid in [
+            "",
+            "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87 10 ore + 21000 energy × 1 ore",
+            "cb08095e7baea6a6c7c4c2dfeb977efac326af552d87: 10 ore + 21000 energy",
+            "cb08: 10 ore + 21000 energy × 1 ore",
+        ] {
+            let parsed = serde_json::from_value::<TxpoolInspectSummary>(invalid.to_string().into());
+            assert!(parsed.is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn fuzz_inspect_summaries() {
+        use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(2171);
+        let space = |rng: &mut StdRng| [" ", "  ", "\t", " \t "].choose(rng).unwrap().to_string();
+        for _ in 0..1000 {
+            let expected = TxpoolInspectSummary {
+                to: rng.gen_bool(0.8).then(|| Address::random_using(&mut rng)),
+                value: U256::from(rng.gen::<u128>()),
+                energy: U256::from(rng.gen::<u64>()),
+                energy_price: U256::from(rng.gen::<u128>()),
+            };
+            let to = match expected.to {
+                Some(to) => {
+                    let to = hex::encode(to);
+                    let to = if rng.gen() { to.to_uppercase() } else { to };
+                    if rng.gen() {
+                        format!("0x{to}")
+                    } else {
+                        to
+                    }
+                }
+                None => ["contract creation", "Contract Creation", ""]
+                    .choose(&mut rng)
+                    .unwrap()
+                    .to_string(),
+            };
+            let unit = ["wei", "ore", ""].choose(&mut rng).unwrap();
+            let times = ["×", "*", "x"].choose(&mut rng).unwrap();
+            let words = [
+                to,
+                ":".to_string(),
+                format!("{}{unit}", expected.value),
+                "+".to_string(),
+                expected.energy.to_string(),
+                "energy".to_string(),
+                times.to_string(),
+                format!("{}{unit}", expected.energy_price),
+            ];
+            let summary =
+                words.iter().map(|word| format!("{}{word}", space(&mut rng))).collect::<String>();
+            let parsed: TxpoolInspectSummary =
+                serde_json::from_value(summary.clone().into()).unwrap();
+            assert_eq!(parsed, expected, "{summary}");
+
+            // arbitrary bytes never panic
+            let garbage = (0..rng.gen_range(0..64))
+                .map(|_| *b"0123456789abcdefx: +*".choose(&mut rng).unwrap() as char)
+                .collect::<String>();
+            let _ = serde_json::from_value::<TxpoolInspectSummary>(garbage.into());
+        }
+    }
+
     fn expected_txpool_inspect() -> TxpoolInspect {
         let mut pending_map = BTreeMap::new();
         let mut pending_map_inner = BTreeMap::new();
@@ -47,6 +47,107 @@ pub fn u256_from_f64_saturating(mut f: f64) -> U256 {
     }
 }
 
+/// Creates a [`U256`] from its 64-bit limbs, least significant first. Usable in constants.
+///
+/// `U256` is defined outside of this crate, which is why this is a function instead of an
+/// associated `U256::from_limbs`.
+///
+/// ```
+/// use corebc_core::types::{u256_from_limbs, U256};
+///
+/// const TWO_POW_64: U256 = u256_from_limbs([0, 1, 0, 0]);
+/// assert_eq!(TWO_POW_64, U256::from(u64::MAX) + 1);
+/// ```
+pub const fn u256_from_limbs(limbs: [u64; 4]) -> U256 {
+    U256(limbs)
+}
+
+/// Parses the limbs of an integer literal starting at `start`. Decimal and `0x` prefixed
+/// hexadecimal literals with `_` separators are supported, string literals are unquoted first.
+///
+/// Panics on invalid or overflowing literals, which is a compile error in constants.
+pub(crate) const fn parse_literal_limbs(literal: &[u8], mut start: usize) -> [u64; 4] {
+    let mut end = literal.len();
+    if end >= start + 2 && literal[start] == b'"' && literal[end - 1] == b'"' {
+        start += 1;
+        end -= 1;
+    }
+    let radix = if end >= start + 2 &&
+        literal[start] == b'0' &&
+        (literal[start + 1] == b'x' || literal[start + 1] == b'X')
+    {
+        start += 2;
+        16
+    } else {
+        10
+    };
+
+    let mut limbs = [0u64; 4];
+    let mut digits = 0;
+    let mut i = start;
+    while i < end {
+        let digit = match literal[i] {
+            b'_' => {
+                i += 1;
+                continue
+            }
+            b @ b'0'..=b'9' => b - b'0',
+            b @ b'a'..=b'f' if radix == 16 => b - b'a' + 10,
+            b @ b'A'..=b'F' if radix == 16 => b - b'A' + 10,
+            _ => panic!("invalid digit in integer literal"),
+        };
+        // limbs = limbs * radix + digit
+        let mut carry = digit as u128;
+        let mut limb = 0;
+        while limb < 4 {
+            let value = limbs[limb] as u128 * radix + carry;
+            limbs[limb] = value as u64;
+            carry = value >> 64;
+            limb += 1;
+        }
+        if carry != 0 {
+            panic!("integer literal does not fit into 256 bits")
+        }
+        digits += 1;
+        i += 1;
+    }
+    if digits == 0 {
+        panic!("integer literal has no digits")
+    }
+    limbs
+}
+
+#[doc(hidden)]
+pub const fn __u256_from_literal(literal: &str) -> U256 {
+    U256(parse_literal_limbs(literal.as_bytes(), 0))
+}
+
+/// Creates a [`U256`] constant from a decimal or `0x` prefixed hexadecimal literal. Invalid and
+/// overflowing literals fail to compile.
+///
+/// ```
+/// use corebc_core::{types::U256, u256};
+///
+/// const ONE_XCB: U256 = u256!(1_000_000_000_000_000_000);
+/// assert_eq!(ONE_XCB, U256::exp10(18));
+/// assert_eq!(u256!(0xff), U256::from(255));
+/// const MAX: U256 =
+///     u256!("115792089237316195423570985008687907853269984665640564039457584007913129639935");
+/// assert_eq!(MAX, U256::MAX);
+/// ```
+///
+/// ```compile_fail
+/// // 2^256
+/// let _ = corebc_core::u256!(0x1_0000000000000000000000000000000000000000000000000000000000000000);
+/// ```
+#[macro_export]
+macro_rules! u256 {
+    ($value:literal) => {{
+        const VALUE: $crate::types::U256 = $crate::types::__u256_from_literal(stringify!($value));
+        VALUE
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,4 +219,16 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn parses_literals() {
+        assert_eq!(crate::u256!(0), U256::zero());
+        assert_eq!(crate::u256!(1_000), U256::from(1000));
+        assert_eq!(crate::u256!(0xDEAD_beef), U256::from(0xdeadbeefu64));
+        assert_eq!(crate::u256!("18446744073709551616"), u256_from_limbs([0, 1, 0, 0]));
+        assert_eq!(
+            crate::u256!(0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff),
+            U256::MAX
+        );
+    }
 }
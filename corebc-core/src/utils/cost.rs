@@ -0,0 +1,165 @@
+//! Human readable formatting of energy prices and transaction costs
+
+use super::{format_units, Units};
+use crate::types::{transaction::eip2718::TypedTransaction, U256};
+use std::fmt;
+
+/// The units energy prices are formatted in, from the largest to the smallest
+const PRICE_UNITS: [(Units, &str); 7] = [
+    (Units::Core, "XCB"),
+    (Units::Moli, "moli"),
+    (Units::Atom, "atom"),
+    (Units::Nucle, "nucle"),
+    (Units::Grav, "grav"),
+    (Units::Wav, "wav"),
+    (Units::Ore, "ore"),
+];
+
+/// Formats the amount in the unit without trailing zeros, e.g. `0.0021` instead of
+/// `0.002100000000000000`
+fn format_trimmed(amount: U256, units: Units) -> String {
+    // units are at most 18 decimals wide, which never overflows
+    let formatted = format_units(amount, units.as_num()).expect("units do not overflow");
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Formats an amount of ore in XCB, e.g. `0.0021 XCB`
+pub fn format_xcb<T: Into<U256>>(amount: T) -> String {
+    format!("{} XCB", format_trimmed(amount.into(), Units::Core))
+}
+
+/// Formats an energy price in the largest unit it contains at least once of, e.g. `100 nucle`
+/// for `100_000_000_000` ore.
+///
+/// ```
+/// use corebc_core::utils::format_energy_price;
+///
+/// assert_eq!(format_energy_price(100_000_000_000u64), "100 nucle");
+/// assert_eq!(format_energy_price(1_500_000_000u64), "1.5 nucle");
+/// assert_eq!(format_energy_price(7u64), "7 ore");
+/// ```
+pub fn format_energy_price<T: Into<U256>>(price: T) -> String {
+    let price = price.into();
+    let (units, name) = PRICE_UNITS
+        .into_iter()
+        .find(|(units, _)| price >= U256::exp10(units.as_num() as usize))
+        .unwrap_or((Units::Ore, "ore"));
+    format!("{} {name}", format_trimmed(price, units))
+}
+
+/// The cost of a transaction, its value plus the fee for the energy it uses.
+///
+/// Its [`Display`](fmt::Display) implementation is meant for CLIs and wallets:
+///
+/// ```
+/// use corebc_core::utils::{parse_units, TxCostEstimate};
+///
+/// let price = parse_units(100, "nucle").unwrap();
+/// let cost = TxCostEstimate::new(0, 21000, price);
+/// assert_eq!(cost.to_string(), "0.0021 XCB (21000 × 100 nucle)");
+///
+/// let cost = TxCostEstimate::new(parse_units(1, "xcb").unwrap(), 21000, price);
+/// assert_eq!(cost.to_string(), "1.0021 XCB (1 XCB + 21000 × 100 nucle)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TxCostEstimate {
+    /// The value transferred, in ore
+    pub value: U256,
+    /// The energy used
+    pub energy: U256,
+    /// The price per unit of energy, in ore
+    pub energy_price: U256,
+}
+
+impl TxCostEstimate {
+    /// Creates a new estimate
+    pub fn new(
+        value: impl Into<U256>,
+        energy: impl Into<U256>,
+        energy_price: impl Into<U256>,
+    ) -> Self {
+        Self { value: value.into(), energy: energy.into(), energy_price: energy_price.into() }
+    }
+
+    /// Reads the cost of a transaction, returns `None` if its energy or energy price is not set
+    pub fn from_tx(tx: &TypedTransaction) -> Option<Self> {
+        Some(Self {
+            value: tx.value().copied().unwrap_or_default(),
+            energy: *tx.energy()?,
+            energy_price: tx.energy_price()?,
+        })
+    }
+
+    /// The fee paid for the energy, in ore
+    pub fn fee(&self) -> U256 {
+        self.energy.saturating_mul(self.energy_price)
+    }
+
+    /// The value plus the fee, in ore
+    pub fn total(&self) -> U256 {
+        self.value.saturating_add(self.fee())
+    }
+
+    /// The fee and how it is computed, e.g. `0.0021 XCB (21000 × 100 nucle)`
+    pub fn fee_string(&self) -> String {
+        format!(
+            "{} ({} × {})",
+            format_xcb(self.fee()),
+            self.energy,
+            format_energy_price(self.energy_price)
+        )
+    }
+
+    /// The total cost in XCB, e.g. `1.0021 XCB`
+    pub fn total_string(&self) -> String {
+        format_xcb(self.total())
+    }
+}
+
+impl fmt::Display for TxCostEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value.is_zero() {
+            return f.write_str(&self.fee_string())
+        }
+        write!(
+            f,
+            "{} ({} + {} × {})",
+            self.total_string(),
+            format_xcb(self.value),
+            self.energy,
+            format_energy_price(self.energy_price)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TransactionRequest;
+
+    #[test]
+    fn formats_energy_prices() {
+        assert_eq!(format_energy_price(0u64), "0 ore");
+        assert_eq!(format_energy_price(1_000u64), "1 wav");
+        assert_eq!(format_energy_price(999_999_999u64), "999.999999 grav");
+        assert_eq!(format_energy_price(U256::exp10(18) * 2), "2 XCB");
+        assert_eq!(format_xcb(U256::exp10(17) * 15), "1.5 XCB");
+        assert_eq!(format_xcb(0u64), "0 XCB");
+    }
+
+    #[test]
+    fn estimates_tx_costs() {
+        let tx: TypedTransaction = TransactionRequest::new().value(5).into();
+        assert_eq!(TxCostEstimate::from_tx(&tx), None);
+
+        let tx: TypedTransaction =
+            TransactionRequest::new().value(5).energy(21000).energy_price(100).into();
+        let cost = TxCostEstimate::from_tx(&tx).unwrap();
+        assert_eq!(cost.fee(), U256::from(2_100_000));
+        assert_eq!(cost.total(), U256::from(2_100_005));
+        assert_eq!(cost.fee_string(), "0.0000000000021 XCB (21000 × 100 ore)");
+
+        let cost = TxCostEstimate::new(U256::MAX, 1, 1);
+        assert_eq!(cost.total(), U256::MAX);
+    }
+}
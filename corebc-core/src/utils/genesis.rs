@@ -38,6 +38,10 @@ pub struct Genesis {
     #[serde(default)]
     pub coinbase: Address,
 
+    /// The genesis header mix hash.
+    #[serde(default)]
+    pub mix_hash: H256,
+
     /// The initial state of the genesis block.
     pub alloc: HashMap<Address, GenesisAccount>,
 
@@ -63,38 +67,158 @@ impl Genesis {
     /// Enables all hard forks up to London at genesis.
     pub fn new(network_id: u64, signer_addr: Address) -> Genesis {
         // set up a clique config with an instant sealing period and short (8 block) epoch
-        let clique_config = CliqueConfig { period: Some(0), epoch: Some(8) };
+        Genesis::builder(network_id)
+            .clique(0, 8, [signer_addr])
+            .fund(signer_addr, U256::MAX)
+            .build()
+    }
 
-        let config =
-            NetworkConfig { network_id, clique: Some(clique_config), ..Default::default() };
+    /// Returns a [`GenesisBuilder`] for a network with the given id
+    pub fn builder(network_id: u64) -> GenesisBuilder {
+        GenesisBuilder::new(network_id)
+    }
+}
 
-        // fund account
-        let mut alloc = HashMap::new();
-        alloc.insert(
-            signer_addr,
-            GenesisAccount { balance: U256::MAX, nonce: None, code: None, storage: None },
-        );
+/// A fluent builder for a [`Genesis`] in gocore's format.
+///
+/// # Example
+///
+/// ```
+/// use corebc_core::{types::{Address, H256, U256}, utils::Genesis};
+///
+/// let owner = Address::random();
+/// let genesis = Genesis::builder(3)
+///     .cryptore()
+///     .energy_limit(8_000_000)
+///     .fund(owner, U256::exp10(24))
+///     .code(Address::zero(), vec![0x60, 0x00])
+///     .storage(Address::zero(), H256::zero(), H256::from_low_u64_be(1))
+///     .build();
+/// assert_eq!(genesis.alloc[&owner].balance, U256::exp10(24));
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct GenesisBuilder {
+    genesis: Genesis,
+}
+
+impl GenesisBuilder {
+    /// Creates a genesis for the network with difficulty 1 and an energy limit of 5M
+    pub fn new(network_id: u64) -> Self {
+        Self {
+            genesis: Genesis {
+                config: NetworkConfig { network_id, ..Default::default() },
+                difficulty: U256::one(),
+                energy_limit: U64::from(5000000),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Uses the Cryptore proof-of-work consensus engine
+    pub fn cryptore(mut self) -> Self {
+        self.genesis.config.cryptore = Some(CryptoreConfig {});
+        self.genesis.config.clique = None;
+        self
+    }
 
-        // put signer address in the extra data, padded by the required amount of zeros
+    /// Uses the Clique proof-of-authority consensus engine with the given block `period` in
+    /// seconds, vote `epoch` length and initial `signers`
+    pub fn clique(
+        mut self,
+        period: u64,
+        epoch: u64,
+        signers: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        self.genesis.config.clique =
+            Some(CliqueConfig { period: Some(period), epoch: Some(epoch) });
+        self.genesis.config.cryptore = None;
+
+        // put the signer addresses in the extra data, padded by the required amount of zeros
         // Clique issue: https://github.com/ethereum/EIPs/issues/225
         // Clique EIP: https://eips.ethereum.org/EIPS/eip-225
         //
         // The first 32 bytes are vanity data, so we will populate it with zeros
-        // This is followed by the signer address, which is 20 bytes
-        // There are 65 bytes of zeros after the signer address, which is usually populated with the
-        // proposer signature. Because the genesis does not have a proposer signature, it will be
-        // populated with zeros.
-        let extra_data_bytes = [&[0u8; 32][..], signer_addr.as_bytes(), &[0u8; 65][..]].concat();
-        let extra_data = Bytes::from(extra_data_bytes);
-
-        Genesis {
-            config,
-            alloc,
-            difficulty: U256::one(),
-            energy_limit: U64::from(5000000),
-            extra_data,
-            ..Default::default()
+        // This is followed by the signer addresses
+        // There are 65 bytes of zeros after the signer addresses, which is usually populated with
+        // the proposer signature. Because the genesis does not have a proposer signature, it will
+        // be populated with zeros.
+        let mut extra_data = vec![0u8; 32];
+        for signer in signers {
+            extra_data.extend_from_slice(signer.as_bytes());
         }
+        extra_data.extend_from_slice(&[0u8; 65]);
+        self.genesis.extra_data = extra_data.into();
+        self
+    }
+
+    /// Sets the energy limit of the genesis block
+    pub fn energy_limit(mut self, energy_limit: u64) -> Self {
+        self.genesis.energy_limit = energy_limit.into();
+        self
+    }
+
+    /// Sets the difficulty of the genesis block
+    pub fn difficulty(mut self, difficulty: impl Into<U256>) -> Self {
+        self.genesis.difficulty = difficulty.into();
+        self
+    }
+
+    /// Sets the timestamp of the genesis block
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.genesis.timestamp = timestamp.into();
+        self
+    }
+
+    /// Sets the coinbase of the genesis block
+    pub fn coinbase(mut self, coinbase: Address) -> Self {
+        self.genesis.coinbase = coinbase;
+        self
+    }
+
+    /// Sets the extra data of the genesis block, this overwrites the Clique signers
+    pub fn extra_data(mut self, extra_data: impl Into<Bytes>) -> Self {
+        self.genesis.extra_data = extra_data.into();
+        self
+    }
+
+    /// Sets the balance of the account
+    pub fn fund(mut self, address: Address, balance: impl Into<U256>) -> Self {
+        self.account_mut(address).balance = balance.into();
+        self
+    }
+
+    /// Sets the nonce of the account
+    pub fn nonce(mut self, address: Address, nonce: u64) -> Self {
+        self.account_mut(address).nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the code of the account
+    pub fn code(mut self, address: Address, code: impl Into<Bytes>) -> Self {
+        self.account_mut(address).code = Some(code.into());
+        self
+    }
+
+    /// Sets a storage slot of the account
+    pub fn storage(mut self, address: Address, slot: H256, value: H256) -> Self {
+        self.account_mut(address).storage.get_or_insert_with(Default::default).insert(slot, value);
+        self
+    }
+
+    /// Replaces the account
+    pub fn account(mut self, address: Address, account: GenesisAccount) -> Self {
+        self.genesis.alloc.insert(address, account);
+        self
+    }
+
+    /// Returns the genesis
+    pub fn build(self) -> Genesis {
+        self.genesis
+    }
+
+    fn account_mut(&mut self, address: Address) -> &mut GenesisAccount {
+        self.genesis.alloc.entry(address).or_default()
     }
 }
 
@@ -138,6 +262,10 @@ pub struct NetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ethash: Option<EthashConfig>,
 
+    /// Cryptore parameters, Core's proof-of-work engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cryptore: Option<CryptoreConfig>,
+
     /// Clique parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clique: Option<CliqueConfig>,
@@ -153,6 +281,10 @@ const fn one() -> u64 {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct EthashConfig {}
 
+/// Empty consensus configuration for Cryptore proof-of-work networks.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CryptoreConfig {}
+
 /// Consensus configuration for Clique.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct CliqueConfig {
@@ -170,7 +302,7 @@ mod tests {
     use super::{Genesis, GenesisAccount, NetworkConfig, H256};
     use crate::{
         types::{Address, Bytes, H176, U256},
-        utils::EthashConfig,
+        utils::{CryptoreConfig, EthashConfig},
     };
     use std::{collections::HashMap, str::FromStr};
 
@@ -744,4 +876,79 @@ mod tests {
         let deserialized_genesis: Genesis = serde_json::from_str(hive_genesis).unwrap();
         assert_eq!(deserialized_genesis, expected_genesis, "deserialized genesis {deserialized_genesis:#?} does not match expected {expected_genesis:#?}");
     }
+
+    #[test]
+    fn builder_round_trips_gocore_json() {
+        let owner = Address::from_str("0x0000dbdbdb2cbd23b783741e8d7fcf51e459b497e4a6").unwrap();
+        let contract = Address::from_str("0x00000000000000000000000000000000000000000001").unwrap();
+        let slot = H256::from_low_u64_be(1);
+        let genesis = Genesis::builder(3)
+            .cryptore()
+            .energy_limit(0x2fefd8)
+            .difficulty(0x20000)
+            .timestamp(0x123456)
+            .fund(owner, 0x11)
+            .nonce(owner, 2)
+            .code(contract, vec![0x12])
+            .storage(contract, slot, H256::from_low_u64_be(0x22))
+            .build();
+
+        let json = serde_json::to_value(&genesis).unwrap();
+        assert_eq!(json["config"]["networkId"], 3);
+        assert_eq!(json["config"]["cryptore"], serde_json::json!({}));
+        assert!(json["config"].get("clique").is_none());
+        assert_eq!(json["energyLimit"], "0x2fefd8");
+        assert_eq!(json["difficulty"], "0x20000");
+
+        let parsed: Genesis = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, genesis);
+        assert_eq!(parsed.config.cryptore, Some(CryptoreConfig {}));
+        assert_eq!(parsed.alloc[&owner].nonce, Some(2));
+        assert_eq!(
+            parsed.alloc[&contract].storage.as_ref().unwrap()[&slot],
+            H256::from_low_u64_be(0x22)
+        );
+    }
+
+    #[test]
+    fn parse_cryptore_genesis() {
+        let gocore_genesis = r#"
+        {
+            "config": {
+                "networkId": 3,
+                "cryptore": {}
+            },
+            "nonce": "0x0",
+            "timestamp": "0x5f5e100",
+            "extraData": "0x",
+            "energyLimit": "0x7a1200",
+            "difficulty": "0x1",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "coinbase": "0x00000000000000000000000000000000000000000000",
+            "alloc": {
+                "0000dbdbdb2cbd23b783741e8d7fcf51e459b497e4a6": {
+                    "balance": "0x3635c9adc5dea00000"
+                }
+            }
+        }
+        "#;
+
+        let genesis: Genesis = serde_json::from_str(gocore_genesis).unwrap();
+        assert_eq!(genesis.config.cryptore, Some(CryptoreConfig {}));
+        assert_eq!(genesis.energy_limit, 0x7a1200.into());
+        assert_eq!(
+            genesis,
+            serde_json::from_value(serde_json::to_value(&genesis).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn clique_builder_matches_new() {
+        let signer = Address::from_str("0x0000dbdbdb2cbd23b783741e8d7fcf51e459b497e4a6").unwrap();
+        let genesis = Genesis::new(3, signer);
+        assert_eq!(genesis.extra_data.len(), 32 + 22 + 65);
+        assert_eq!(genesis.alloc[&signer].balance, U256::MAX);
+        assert_eq!(genesis.config.clique.as_ref().unwrap().epoch, Some(8));
+        assert!(genesis.config.cryptore.is_none());
+    }
 }
@@ -1,25 +1,57 @@
 //! Various utilities for manipulating Ethereum related data.
 
+use crate::types::Address;
 use ethabi::ethereum_types::H256;
 use tiny_keccak::{Hasher, Sha3};
 
+/// The prefix of personal messages signed by Core nodes, see [`hash_message`]
+pub const CORE_MESSAGE_PREFIX: &str = "\x19Core Signed Message:\n";
+
+/// The EIP-191 version byte of data with an intended validator
+pub const EIP191_VALIDATOR_VERSION: u8 = 0x00;
+
+/// The EIP-191 version byte of structured (CIP-712) data
+pub const EIP191_STRUCTURED_DATA_VERSION: u8 = 0x01;
+
 /// The final message is a UTF-8 string, encoded as follows:
 /// `"\x19Core Signed Message:\n" + message.length + message`
 pub fn hash_message<T: AsRef<[u8]>>(message: T) -> H256 {
-    const PREFIX: &str = "\x19Core Signed Message:\n";
+    hash_message_with_prefix(CORE_MESSAGE_PREFIX, message)
+}
 
+/// Same as [`hash_message`] but with a custom prefix, e.g. to verify signatures of clients that
+/// use another one: `prefix + message.length + message`
+pub fn hash_message_with_prefix<T: AsRef<[u8]>>(prefix: &str, message: T) -> H256 {
     let message = message.as_ref();
     let len = message.len();
     let len_string = len.to_string();
 
-    let mut eth_message = Vec::with_capacity(PREFIX.len() + len_string.len() + len);
-    eth_message.extend_from_slice(PREFIX.as_bytes());
+    let mut eth_message = Vec::with_capacity(prefix.len() + len_string.len() + len);
+    eth_message.extend_from_slice(prefix.as_bytes());
     eth_message.extend_from_slice(len_string.as_bytes());
     eth_message.extend_from_slice(message);
 
     H256(sha3(&eth_message))
 }
 
+/// Hashes EIP-191 version `0x00` data, which is only valid for the contract at `validator`:
+/// `0x19 0x00 + validator + data`
+pub fn hash_validator_message<T: AsRef<[u8]>>(validator: Address, data: T) -> H256 {
+    H256(sha3(
+        [&[0x19, EIP191_VALIDATOR_VERSION][..], validator.as_bytes(), data.as_ref()].concat(),
+    ))
+}
+
+/// Hashes EIP-191 version `0x01` structured data:
+/// `0x19 0x01 + domain_separator + struct_hash`
+///
+/// See also [`Cip712::encode_cip712`](crate::types::transaction::cip712::Cip712::encode_cip712)
+pub fn hash_structured_data(domain_separator: [u8; 32], struct_hash: [u8; 32]) -> H256 {
+    H256(sha3(
+        [&[0x19, EIP191_STRUCTURED_DATA_VERSION][..], &domain_separator, &struct_hash].concat(),
+    ))
+}
+
 /// Compute the Sha3-256 hash of input bytes.
 ///
 /// Note that strings are interpreted as UTF-8 bytes,
@@ -81,6 +113,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_message_with_prefix() {
+        assert_eq!(
+            hash_message_with_prefix(CORE_MESSAGE_PREFIX, "Hello World"),
+            hash_message("Hello World")
+        );
+
+        let hash = hash_message_with_prefix("\x19Other Signed Message:\n", "Hello World");
+        assert_eq!(hash.0, sha3(b"\x19Other Signed Message:\n11Hello World"));
+    }
+
+    #[test]
+    fn test_eip191_versions() {
+        let validator = Address::from_low_u64_be(1);
+        let hash = hash_validator_message(validator, [0xaa]);
+        let mut expected = vec![0x19, 0x00];
+        expected.extend_from_slice(validator.as_bytes());
+        expected.push(0xaa);
+        assert_eq!(hash.0, sha3(expected));
+
+        let hash = hash_structured_data([1u8; 32], [2u8; 32]);
+        assert_eq!(hash.0, sha3([&[0x19, 0x01][..], &[1u8; 32], &[2u8; 32]].concat()));
+    }
+
     #[test]
     fn simple_function_signature() {
         // test vector retrieved from
@@ -0,0 +1,320 @@
+//! Display formats of ICAN addresses and `core:` payment URIs
+
+use super::{is_valid_ican, MAINNET, PRIVATE, TESTNET};
+use crate::types::{Address, Bytes, Network, U256};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// The scheme of payment URIs
+pub const PAYMENT_URI_SCHEME: &str = "core";
+
+/// The ways an ICAN address can be displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IcanFormat {
+    /// Lowercase without a `0x` prefix, e.g. `cb57bbbb…`, the canonical form in URIs and APIs
+    #[default]
+    Lowercase,
+    /// Uppercase, e.g. `CB57BBBB…`, which encodes more compactly in alphanumeric QR codes
+    Uppercase,
+    /// Uppercase in groups of four characters, e.g. `CB57 BBBB …`, for reading out and printing
+    Grouped,
+}
+
+/// Error thrown when parsing an ICAN address or a payment URI
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IcanError {
+    /// The address is malformed, has an unknown prefix or an invalid checksum
+    #[error("invalid ICAN address `{0}`")]
+    InvalidAddress(String),
+    /// The URI does not start with `core:`
+    #[error("payment URI must start with `core:`: `{0}`")]
+    InvalidScheme(String),
+    /// A query parameter of the URI is malformed or given more than once
+    #[error("invalid payment URI parameter `{name}={value}`")]
+    InvalidParameter {
+        /// The name of the parameter
+        name: String,
+        /// The value of the parameter
+        value: String,
+    },
+}
+
+/// Formats `addr` for display, see [`IcanFormat`].
+///
+/// ```
+/// use corebc_core::{types::Address, utils::{format_ican, IcanFormat}};
+///
+/// let addr: Address = "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96".parse().unwrap();
+/// assert_eq!(
+///     format_ican(&addr, IcanFormat::Grouped),
+///     "CB36 80E5 927E C9AF 1EFC 619E E6D4 198E 97C9 1AB7 2A96"
+/// );
+/// ```
+pub fn format_ican(addr: &Address, format: IcanFormat) -> String {
+    let lowercase = format!("{addr:x}");
+    match format {
+        IcanFormat::Lowercase => lowercase,
+        IcanFormat::Uppercase => lowercase.to_ascii_uppercase(),
+        IcanFormat::Grouped => lowercase
+            .to_ascii_uppercase()
+            .as_bytes()
+            .chunks(4)
+            .map(|group| std::str::from_utf8(group).expect("hex is ascii"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Parses an ICAN address in any of the [`IcanFormat`]s, with or without a `0x` prefix, and
+/// checks its checksum against the network of its prefix.
+///
+/// ```
+/// use corebc_core::utils::{format_ican, parse_ican, IcanFormat};
+///
+/// let addr = parse_ican("CB36 80E5 927E C9AF 1EFC 619E E6D4 198E 97C9 1AB7 2A96").unwrap();
+/// assert_eq!(
+///     format_ican(&addr, IcanFormat::Lowercase),
+///     "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96"
+/// );
+/// assert!(parse_ican("cb3780e5927ec9af1efc619ee6d4198e97c91ab72a96").is_err());
+/// ```
+pub fn parse_ican(s: &str) -> Result<Address, IcanError> {
+    let invalid = || IcanError::InvalidAddress(s.to_string());
+    let compact: String = s.split_whitespace().collect();
+    let hex = compact.strip_prefix("0x").or_else(|| compact.strip_prefix("0X")).unwrap_or(&compact);
+    if hex.len() != Address::len_bytes() * 2 {
+        return Err(invalid())
+    }
+    let addr = Address::from_str(hex).map_err(|_| invalid())?;
+    let network = match &hex[..2].to_ascii_lowercase()[..] {
+        MAINNET => Network::Mainnet,
+        TESTNET => Network::Devin,
+        // the checksum only depends on the prefix, not on the id of the private network
+        PRIVATE => Network::Private(0),
+        _ => return Err(invalid()),
+    };
+    if !is_valid_ican(&addr, &network) {
+        return Err(invalid())
+    }
+    Ok(addr)
+}
+
+/// A payment request that wallets exchange as link or QR code, e.g.
+/// `core:cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96?value=1000000000000000000`.
+///
+/// The recipient is an ICAN address, its prefix determines the network. The query parameters
+/// are all optional:
+/// - `value`: the amount in ore, as integer or in scientific notation, e.g. `1.5e18`
+/// - `energy`: the energy limit
+/// - `data`: the `0x`-prefixed input data
+///
+/// Unknown parameters are ignored when parsing.
+///
+/// ```
+/// use corebc_core::utils::{parse_core, PaymentUri};
+///
+/// let uri = "core:cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96?value=1.5e18";
+/// let uri: PaymentUri = uri.parse().unwrap();
+/// assert_eq!(uri.value, Some(parse_core("1.5").unwrap()));
+/// assert_eq!(
+///     uri.to_string(),
+///     "core:cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96?value=1500000000000000000"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentUri {
+    /// The recipient
+    pub address: Address,
+    /// The amount to transfer in ore
+    pub value: Option<U256>,
+    /// The energy limit of the transaction
+    pub energy: Option<U256>,
+    /// The input data of the transaction
+    pub data: Option<Bytes>,
+}
+
+impl PaymentUri {
+    /// Creates a payment request to `address` without any parameters
+    pub fn new(address: Address) -> Self {
+        Self { address, value: None, energy: None, data: None }
+    }
+
+    /// Sets the amount to transfer in ore
+    #[must_use]
+    pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets the energy limit of the transaction
+    #[must_use]
+    pub fn energy<T: Into<U256>>(mut self, energy: T) -> Self {
+        self.energy = Some(energy.into());
+        self
+    }
+
+    /// Sets the input data of the transaction
+    #[must_use]
+    pub fn data<T: Into<Bytes>>(mut self, data: T) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Returns the URI in uppercase, which encodes more compactly in alphanumeric QR codes.
+    ///
+    /// Parsing is case insensitive, so the payload parses back to the same request.
+    pub fn to_qr_payload(&self) -> String {
+        self.to_string().to_ascii_uppercase()
+    }
+}
+
+impl fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{PAYMENT_URI_SCHEME}:{}", format_ican(&self.address, IcanFormat::Lowercase))?;
+        let mut separator = '?';
+        let mut param = |f: &mut fmt::Formatter<'_>, name: &str, value: String| {
+            let res = write!(f, "{separator}{name}={value}");
+            separator = '&';
+            res
+        };
+        if let Some(value) = self.value {
+            param(f, "value", value.to_string())?;
+        }
+        if let Some(energy) = self.energy {
+            param(f, "energy", energy.to_string())?;
+        }
+        if let Some(data) = &self.data {
+            param(f, "data", data.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = IcanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let rest = match s.split_once(':') {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case(PAYMENT_URI_SCHEME) => rest,
+            _ => return Err(IcanError::InvalidScheme(s.to_string())),
+        };
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let mut uri = PaymentUri::new(parse_ican(address)?);
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let invalid =
+                || IcanError::InvalidParameter { name: name.to_string(), value: value.to_string() };
+            match &name.to_ascii_lowercase()[..] {
+                "value" if uri.value.is_none() => {
+                    uri.value = Some(parse_amount(value).ok_or_else(invalid)?)
+                }
+                "energy" if uri.energy.is_none() => {
+                    uri.energy = Some(parse_amount(value).ok_or_else(invalid)?)
+                }
+                "data" if uri.data.is_none() => {
+                    let hex = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X"));
+                    let data = hex.and_then(|hex| hex::decode(hex).ok()).ok_or_else(invalid)?;
+                    uri.data = Some(data.into())
+                }
+                "value" | "energy" | "data" => return Err(invalid()),
+                _ => {}
+            }
+        }
+        Ok(uri)
+    }
+}
+
+/// Parses a non-negative integer, either plain or in scientific notation like `1.5e18`
+fn parse_amount(s: &str) -> Option<U256> {
+    let (mantissa, exponent) = match s.split_once(|c| c == 'e' || c == 'E') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<u32>().ok()?),
+        None => (s, 0),
+    };
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if int.is_empty() || !digits(int) || !digits(fraction) || fraction.len() > exponent as usize {
+        return None
+    }
+    let scale = U256::from(10).checked_pow(U256::from(exponent as usize - fraction.len()))?;
+    U256::from_dec_str(&format!("{int}{fraction}")).ok()?.checked_mul(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_ican;
+
+    const ADDRESS: &str = "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96";
+
+    #[test]
+    fn formats_and_parses_icans() {
+        let addr: Address = ADDRESS.parse().unwrap();
+        let formats = [IcanFormat::Lowercase, IcanFormat::Uppercase, IcanFormat::Grouped];
+        for format in formats {
+            assert_eq!(parse_ican(&format_ican(&addr, format)).unwrap(), addr);
+        }
+        assert_eq!(format_ican(&addr, IcanFormat::Uppercase), ADDRESS.to_ascii_uppercase());
+        assert_eq!(parse_ican(&format!("0x{ADDRESS}")).unwrap(), addr);
+
+        for network in [Network::Devin, Network::Private(1337)] {
+            let addr = to_ican(&Default::default(), &network);
+            assert_eq!(parse_ican(&format_ican(&addr, IcanFormat::Grouped)).unwrap(), addr);
+        }
+
+        for invalid in [
+            "",
+            "cb3780e5927ec9af1efc619ee6d4198e97c91ab72a96",
+            "dd3680e5927ec9af1efc619ee6d4198e97c91ab72a96",
+            "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a9",
+            "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a9z",
+        ] {
+            assert_eq!(parse_ican(invalid), Err(IcanError::InvalidAddress(invalid.to_string())));
+        }
+    }
+
+    #[test]
+    fn roundtrips_payment_uris() {
+        let addr: Address = ADDRESS.parse().unwrap();
+        let uri = PaymentUri::new(addr).value(1_000u64).energy(21_000u64).data(vec![0xde, 0xad]);
+        assert_eq!(uri.to_string(), format!("core:{ADDRESS}?value=1000&energy=21000&data=0xdead"));
+        assert_eq!(uri.to_string().parse::<PaymentUri>().unwrap(), uri);
+        assert_eq!(uri.to_qr_payload().parse::<PaymentUri>().unwrap(), uri);
+
+        let plain = PaymentUri::new(addr);
+        assert_eq!(plain.to_string(), format!("core:{ADDRESS}"));
+        assert_eq!(plain.to_string().parse::<PaymentUri>().unwrap(), plain);
+    }
+
+    #[test]
+    fn parses_payment_uris() {
+        let uri: PaymentUri =
+            format!("core:{ADDRESS}?value=2.5e3&label=coffee&energy=21000").parse().unwrap();
+        assert_eq!(uri.value, Some(U256::from(2500)));
+        assert_eq!(uri.energy, Some(U256::from(21000)));
+        assert_eq!(uri.data, None);
+
+        assert!(matches!(
+            format!("xcb:{ADDRESS}").parse::<PaymentUri>(),
+            Err(IcanError::InvalidScheme(_))
+        ));
+        for query in [
+            "value=1.5",
+            "value=-1",
+            "value=1e",
+            "value=1e99",
+            "value=1&value=2",
+            "data=dead",
+            "data=0xz",
+        ] {
+            assert!(
+                matches!(
+                    format!("core:{ADDRESS}?{query}").parse::<PaymentUri>(),
+                    Err(IcanError::InvalidParameter { .. })
+                ),
+                "{query}"
+            );
+        }
+    }
+}
@@ -6,7 +6,10 @@ pub use gocore::{GoCore, GoCoreInstance};
 
 /// Utilities for working with a `genesis.json` and other network config structs.
 mod genesis;
-pub use genesis::{CliqueConfig, EthashConfig, Genesis, GenesisAccount, NetworkConfig};
+pub use genesis::{
+    CliqueConfig, CryptoreConfig, EthashConfig, Genesis, GenesisAccount, GenesisBuilder,
+    NetworkConfig,
+};
 
 /// Utilities for launching an anvil instance
 #[cfg(not(target_arch = "wasm32"))]
@@ -15,12 +18,21 @@ mod anvil;
 pub use anvil::{Anvil, AnvilInstance};
 
 mod hash;
-pub use hash::{hash_message, id, serialize, sha3};
+pub use hash::{
+    hash_message, hash_message_with_prefix, hash_structured_data, hash_validator_message, id,
+    serialize, sha3, CORE_MESSAGE_PREFIX, EIP191_STRUCTURED_DATA_VERSION, EIP191_VALIDATOR_VERSION,
+};
 
 mod units;
 use serde::{Deserialize, Deserializer};
 pub use units::Units;
 
+mod cost;
+pub use cost::{format_energy_price, format_xcb, TxCostEstimate};
+
+mod ican;
+pub use ican::{format_ican, parse_ican, IcanError, IcanFormat, PaymentUri, PAYMENT_URI_SCHEME};
+
 /// Re-export RLP
 pub use rlp;
 
@@ -373,6 +385,11 @@ pub fn to_ican(addr: &H160, network: &Network) -> Address {
     construct_ican_address(prefix, &checksum, addr)
 }
 
+/// Returns true if `addr` carries the ICAN prefix of `network` and a valid checksum.
+pub fn is_valid_ican(addr: &Address, network: &Network) -> bool {
+    to_ican(&H160::from_slice(&addr[2..]), network) == *addr
+}
+
 fn get_number_string(addr: &H160, network: &Network) -> String {
     let prefix = match network {
         Network::Mainnet => MAINNET,
@@ -434,6 +451,19 @@ pub fn secret_key_to_h160_address(secret_key: &SigningKey) -> H160 {
     H160::from(bytes)
 }
 
+/// Wraps key material so that it is cleared from memory on drop if the `zeroize` feature is
+/// enabled
+#[cfg(feature = "zeroize")]
+pub fn secret_bytes(bytes: Vec<u8>) -> zeroize::Zeroizing<Vec<u8>> {
+    zeroize::Zeroizing::new(bytes)
+}
+
+/// Returns the key material as is, enable the `zeroize` feature to clear it from memory on drop
+#[cfg(not(feature = "zeroize"))]
+pub fn secret_bytes(bytes: Vec<u8>) -> Vec<u8> {
+    bytes
+}
+
 /// Encodes an Ethereum address to its [EIP-55] checksum.
 ///
 /// You can optionally specify an [EIP-155 network ID] to encode the address using the [EIP-1191]
@@ -909,6 +939,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validates_ican() {
+        let addr = "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96".parse::<Address>().unwrap();
+        assert!(is_valid_ican(&addr, &Network::Mainnet));
+        assert!(!is_valid_ican(&addr, &Network::Devin));
+
+        let bad_checksum =
+            "cb3780e5927ec9af1efc619ee6d4198e97c91ab72a96".parse::<Address>().unwrap();
+        assert!(!is_valid_ican(&bad_checksum, &Network::Mainnet));
+    }
+
     #[test]
     fn bytes32_string_parsing() {
         let text_bytes_list = vec![
@@ -1,3 +1,4 @@
+use corebc_core::abi::ethereum_types::H176;
 use libgoldilocks::errors;
 use thiserror::Error;
 
@@ -8,6 +9,15 @@ pub enum KeystoreError {
     /// match the MAC declared in the keystore.
     #[error("Mac Mismatch")]
     MacMismatch,
+    /// An error thrown when the keystore's format version is not supported.
+    #[error("unsupported keystore version {0}")]
+    UnsupportedVersion(u8),
+    /// An error thrown when the decrypted private key does not have the length of its key type.
+    #[error("invalid private key length {0}")]
+    InvalidKeyLength(usize),
+    /// An error thrown when the address of the keystore does not belong to the decrypted key.
+    #[error("keystore address {0:?} does not match the key's address {1:?}")]
+    AddressMismatch(H176, H176),
     /// An error thrown by the Rust `std::io` module.
     #[error("IO: {0}")]
     StdIo(String),
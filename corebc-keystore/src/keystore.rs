@@ -2,17 +2,42 @@ use hex::{FromHex, ToHex};
 use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
 use uuid::Uuid;
 
-use corebc_core::abi::ethereum_types::H176 as Address;
+use corebc_core::{abi::ethereum_types::H176 as Address, types::Network};
 
 #[derive(Debug, Deserialize, Serialize)]
 /// This struct represents the deserialized form of an encrypted JSON keystore based on the
 /// [Web3 Secret Storage Definition](https://github.com/ethereum/wiki/wiki/Web3-Secret-Storage-Definition).
+///
+/// Version 4 keystores additionally tag the type of the encrypted key and the network of the ICAN
+/// `address`, version 3 keystores leave them empty.
 pub struct EthKeystore {
     pub address: Address,
 
     pub crypto: CryptoJson,
     pub id: Uuid,
     pub version: u8,
+
+    #[serde(rename = "keyType", default, skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<KeyType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// Types of private keys that can be stored in a keystore.
+pub enum KeyType {
+    /// A 57-byte Ed448 private key
+    Ed448,
+}
+
+impl KeyType {
+    /// Returns the length of the private keys of this type in bytes
+    pub const fn key_size(&self) -> usize {
+        match self {
+            KeyType::Ed448 => 57,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -114,6 +139,44 @@ mod tests {
             keystore.address.as_bytes().to_vec(),
             hex::decode("cb27de521e43741cf785cbad450d5649187b9612018f").unwrap()
         );
+        assert_eq!(keystore.key_type, None);
+        assert_eq!(keystore.network, None);
+    }
+
+    #[test]
+    fn deserialize_v4_keystore() {
+        let data = r#"
+        {
+            "address": "cb27de521e43741cf785cbad450d5649187b9612018f",
+            "crypto": {
+              "cipher": "aes-128-ctr",
+              "ciphertext": "13b3adc4203f236343e7c6a59c04ac56e6dee12258eba391697d2b5f1e9b87698f53ae12b5837bc906c42bce38f9b575ea82dfaef84b1d6795",
+              "cipherparams": {
+                "iv": "ce4b6edebbcb44c76ba42cb2c7a59dd3"
+              },
+              "kdf": "scrypt",
+              "kdfparams": {
+                "dklen": 32,
+                "n": 2,
+                "p": 1,
+                "r": 8,
+                "salt": "54eeca614269586df5dfbde87b39e4077b0353d23fe3fb0e72312973b8d4d3df"
+              },
+              "mac": "d7450eab867cc3b4f7619a586878428cf2a73056fcae32cc605df8ce07e799f6"
+            },
+            "id": "84c5fc55-b541-42ed-aaa7-28f508cd85c0",
+            "version": 4,
+            "keyType": "ed448",
+            "network": "mainnet"
+          }"#;
+        let keystore: EthKeystore = serde_json::from_str(data).unwrap();
+        assert_eq!(keystore.version, 4);
+        assert_eq!(keystore.key_type, Some(KeyType::Ed448));
+        assert_eq!(keystore.network, Some(Network::Mainnet));
+
+        let json = serde_json::to_value(&keystore).unwrap();
+        assert_eq!(json["keyType"], "ed448");
+        assert_eq!(json["network"], "mainnet");
     }
 
     // #[test]
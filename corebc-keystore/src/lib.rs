@@ -1,16 +1,22 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 //! A minimalist library to interact with encrypted JSON keystores as per the
 //! [Web3 Secret Storage Definition](https://github.com/ethereum/wiki/wiki/Web3-Secret-Storage-Definition).
+//!
+//! New keystores are written in version 4 of the format, which tags the type of the encrypted key
+//! and the network of the ICAN address. Version 3 keystores can still be decrypted and are
+//! upgraded with [`migrate_key`].
 
 use aes::{
     cipher::{self, InnerIvInit, KeyInit, StreamCipherCore},
     Aes128,
 };
-use corebc_core::types::Network;
+use corebc_core::{types::Network, utils::secret_bytes};
 use digest::{Digest, Update};
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
 use rand::{CryptoRng, Rng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use scrypt::{scrypt, Params as ScryptParams};
 use sha3::Sha3_256;
 use uuid::Uuid;
@@ -18,7 +24,7 @@ use uuid::Uuid;
 use std::{
     fs::File,
     io::{Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 mod error;
@@ -28,8 +34,12 @@ mod utils;
 use utils::gocore_compat::address_from_pk;
 
 pub use error::KeystoreError;
-pub use keystore::{CipherparamsJson, CryptoJson, EthKeystore, KdfType, KdfparamsType};
+pub use keystore::{CipherparamsJson, CryptoJson, EthKeystore, KdfType, KdfparamsType, KeyType};
 
+#[cfg(feature = "zeroize")]
+pub use zeroize::Zeroizing;
+
+const KEYSTORE_VERSION: u8 = 4u8;
 const DEFAULT_CIPHER: &str = "aes-128-ctr";
 const DEFAULT_KEY_SIZE: usize = 57usize;
 const DEFAULT_IV_SIZE: usize = 16usize;
@@ -102,26 +112,42 @@ where
     P: AsRef<Path>,
     S: AsRef<[u8]>,
 {
-    // Read the file contents as string and deserialize it.
+    let keystore = read_keystore(path)?;
+    decrypt_keystore(&keystore, password)
+}
+
+/// Reads and deserializes the encrypted JSON keystore at `path`.
+fn read_keystore(path: impl AsRef<Path>) -> Result<EthKeystore, KeystoreError> {
     let mut file = File::open(path)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
-    let keystore: EthKeystore = serde_json::from_str(&contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Decrypts the private key of `keystore`, checking that it matches the key type and address of
+/// version 4 keystores.
+fn decrypt_keystore<S: AsRef<[u8]>>(
+    keystore: &EthKeystore,
+    password: S,
+) -> Result<Vec<u8>, KeystoreError> {
+    if !(3..=KEYSTORE_VERSION).contains(&keystore.version) {
+        return Err(KeystoreError::UnsupportedVersion(keystore.version))
+    }
 
     // Derive the key.
     let key = match keystore.crypto.kdfparams {
-        KdfparamsType::Pbkdf2 { c, dklen, prf: _, salt } => {
-            let mut key = vec![0u8; dklen as usize];
-            pbkdf2::<Hmac<Sha3_256>>(password.as_ref(), &salt, c, key.as_mut_slice());
+        KdfparamsType::Pbkdf2 { c, dklen, prf: _, ref salt } => {
+            let mut key = secret_bytes(vec![0u8; dklen as usize]);
+            pbkdf2::<Hmac<Sha3_256>>(password.as_ref(), salt, c, key.as_mut_slice());
             key
         }
-        KdfparamsType::Scrypt { dklen, n, p, r, salt } => {
-            let mut key = vec![0u8; dklen as usize];
+        KdfparamsType::Scrypt { dklen, n, p, r, ref salt } => {
+            let mut key = secret_bytes(vec![0u8; dklen as usize]);
             // TODO: use int_log https://github.com/rust-lang/rust/issues/70887
             // TODO: when it is stable
             let log_n = (n as f32).log2().ceil() as u8;
             let scrypt_params = ScryptParams::new(log_n, r, p)?;
-            scrypt(password.as_ref(), &salt, &scrypt_params, key.as_mut_slice())?;
+            scrypt(password.as_ref(), salt, &scrypt_params, key.as_mut_slice())?;
             key
         }
     };
@@ -138,12 +164,96 @@ where
     let decryptor =
         Aes128Ctr::new(&key[..16], &keystore.crypto.cipherparams.iv[..16]).expect("invalid length");
 
-    let mut pk = keystore.crypto.ciphertext;
+    let mut pk = keystore.crypto.ciphertext.clone();
     decryptor.apply_keystream(&mut pk);
 
+    if let Some(key_type) = keystore.key_type {
+        if pk.len() != key_type.key_size() {
+            return Err(KeystoreError::InvalidKeyLength(pk.len()))
+        }
+    }
+    if let Some(network) = keystore.network {
+        let address = address_from_pk(&pk, &network)?;
+        if address != keystore.address {
+            return Err(KeystoreError::AddressMismatch(keystore.address, address))
+        }
+    }
+
     Ok(pk)
 }
 
+/// Upgrades the encrypted JSON keystore at `path` to version 4 of the format, tagging it with the
+/// Ed448 key type and the `network` of its address.
+///
+/// The keystore is decrypted with `password` to check the key before it is rewritten, the
+/// encrypted key itself is left unchanged. Fails if the address stored in the keystore does not
+/// belong to the key on `network`. Keystores that already are in version 4 are left untouched.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_core::types::Network;
+/// use corebc_keystore::migrate_key;
+///
+/// # async fn foobar() -> Result<(), Box<dyn std::error::Error>> {
+/// migrate_key("./keys/my-key", "password_to_keystore", &Network::Mainnet)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn migrate_key<P, S>(path: P, password: S, network: &Network) -> Result<(), KeystoreError>
+where
+    P: AsRef<Path>,
+    S: AsRef<[u8]>,
+{
+    let path = path.as_ref();
+    let mut keystore = read_keystore(path)?;
+    if keystore.version == KEYSTORE_VERSION {
+        return Ok(())
+    }
+
+    keystore.version = KEYSTORE_VERSION;
+    keystore.key_type = Some(KeyType::Ed448);
+    keystore.network = Some(*network);
+    // checks the password, key length and address before anything is written
+    decrypt_keystore(&keystore, password)?;
+
+    // replace the file atomically, so it is never left partially written
+    let contents = serde_json::to_string(&keystore)?;
+    let tmp = path.with_extension("migrating");
+    let mut file = File::create(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(tmp, path)?;
+
+    Ok(())
+}
+
+/// Decrypts an encrypted JSON keystore like [`decrypt_key`], but returns the private key in a
+/// wrapper that clears it from memory on drop.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_keystore::decrypt_key_zeroizing;
+///
+/// # async fn foobar() -> Result<(), Box<dyn std::error::Error>> {
+/// let private_key = decrypt_key_zeroizing("./keys/my-key", "password_to_keystore")?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub fn decrypt_key_zeroizing<P, S>(
+    path: P,
+    password: S,
+) -> Result<Zeroizing<Vec<u8>>, KeystoreError>
+where
+    P: AsRef<Path>,
+    S: AsRef<[u8]>,
+{
+    decrypt_key(path, password).map(Zeroizing::new)
+}
+
 /// Encrypts the given private key using the [Scrypt](https://tools.ietf.org/html/rfc7914.html)
 /// password-based key derivation function, and stores it in the provided directory. On success, it
 /// returns the `id` (Uuid) generated for this keystore.
@@ -160,8 +270,8 @@ where
 /// let dir = Path::new("./keys");
 /// let mut rng = rand::thread_rng();
 ///
-/// // Construct a 32-byte random private key.
-/// let mut private_key = vec![0u8; 32];
+/// // Construct a 57-byte random private key.
+/// let mut private_key = vec![0u8; 57];
 /// rng.fill_bytes(private_key.as_mut_slice());
 ///
 /// // Since we specify a custom filename for the keystore, it will be stored in `$dir/my-key`
@@ -183,20 +293,170 @@ where
     B: AsRef<[u8]>,
     S: AsRef<[u8]>,
 {
-    // Generate a random salt.
-    let mut salt = vec![0u8; DEFAULT_KEY_SIZE];
-    rng.fill_bytes(salt.as_mut_slice());
+    let params = KeystoreParams::random(rng);
+    // If a file name is not specified for the keystore, simply use the strigified uuid.
+    let name = if let Some(name) = name { name.to_string() } else { params.id.to_string() };
+
+    let keystore = encrypt_keystore(params, pk, password, network)?;
+    write_keystore(dir.as_ref().join(name), &keystore)?;
+
+    Ok(keystore.id.to_string())
+}
+
+/// Encrypts each of the given private keys like [`encrypt_key`], and stores them in the provided
+/// directory named by their generated `id`s, which are returned in the order of the keys.
+///
+/// With the `rayon` feature, the keys are encrypted in parallel, since the key derivation
+/// dominates the time it takes.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_core::types::Network;
+/// use corebc_keystore::encrypt_keys;
+/// use rand::RngCore;
+///
+/// # async fn foobar() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut rng = rand::thread_rng();
+/// let private_keys: Vec<Vec<u8>> = (0..10)
+///     .map(|_| {
+///         let mut private_key = vec![0u8; 57];
+///         rng.fill_bytes(private_key.as_mut_slice());
+///         private_key
+///     })
+///     .collect();
+///
+/// let ids = encrypt_keys("./keys", &mut rng, private_keys, "password_to_keystore", &Network::Mainnet)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encrypt_keys<P, R, I, B, S>(
+    dir: P,
+    rng: &mut R,
+    pks: I,
+    password: S,
+    network: &Network,
+) -> Result<Vec<String>, KeystoreError>
+where
+    P: AsRef<Path>,
+    R: Rng + CryptoRng,
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]> + Send,
+    S: AsRef<[u8]> + Sync,
+{
+    // the random parameters are generated upfront, the rng can't be shared between threads
+    let keys: Vec<_> = pks.into_iter().map(|pk| (KeystoreParams::random(rng), pk)).collect();
+    #[cfg(feature = "rayon")]
+    let keys = keys.into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let keys = keys.into_iter();
+    let keystores = keys
+        .map(|(params, pk)| encrypt_keystore(params, pk, password.as_ref(), network))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    keystores
+        .iter()
+        .map(|keystore| {
+            let id = keystore.id.to_string();
+            write_keystore(dir.as_ref().join(&id), keystore)?;
+            Ok(id)
+        })
+        .collect()
+}
+
+/// Decrypts all encrypted JSON keystores in the provided directory with the provided `password`,
+/// like [`decrypt_key`].
+///
+/// With the `rayon` feature, the keystores are decrypted in parallel. Returns the path of every
+/// file in the directory, sorted, with the decrypted private key or the reason the file could not
+/// be decrypted.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_keystore::decrypt_all;
+///
+/// # async fn foobar() -> Result<(), Box<dyn std::error::Error>> {
+/// for (path, private_key) in decrypt_all("./keys", "password_to_keystore")? {
+///     match private_key {
+///         Ok(private_key) => println!("decrypted {}", path.display()),
+///         Err(err) => println!("could not decrypt {}: {err}", path.display()),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn decrypt_all<P, S>(
+    dir: P,
+    password: S,
+) -> Result<Vec<(PathBuf, Result<Vec<u8>, KeystoreError>)>, KeystoreError>
+where
+    P: AsRef<Path>,
+    S: AsRef<[u8]> + Sync,
+{
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    #[cfg(feature = "rayon")]
+    let paths = paths.into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let paths = paths.into_iter();
+    Ok(paths
+        .map(|path| {
+            let pk = decrypt_key(&path, password.as_ref());
+            (path, pk)
+        })
+        .collect())
+}
+
+/// The random parameters of a new keystore
+struct KeystoreParams {
+    id: Uuid,
+    salt: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl KeystoreParams {
+    fn random<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        // Generate a random salt.
+        let mut salt = vec![0u8; DEFAULT_KEY_SIZE];
+        rng.fill_bytes(salt.as_mut_slice());
+
+        let mut iv = vec![0u8; DEFAULT_IV_SIZE];
+        rng.fill_bytes(iv.as_mut_slice());
+
+        Self { id: Uuid::new_v4(), salt, iv }
+    }
+}
+
+/// Encrypts the given private key into a keystore using the [Scrypt](https://tools.ietf.org/html/rfc7914.html)
+/// password-based key derivation function.
+fn encrypt_keystore<B, S>(
+    params: KeystoreParams,
+    pk: B,
+    password: S,
+    network: &Network,
+) -> Result<EthKeystore, KeystoreError>
+where
+    B: AsRef<[u8]>,
+    S: AsRef<[u8]>,
+{
+    let KeystoreParams { id, salt, iv } = params;
 
     // Derive the key.
-    let mut key = vec![0u8; DEFAULT_KDF_PARAMS_DKLEN as usize];
+    let mut key = secret_bytes(vec![0u8; DEFAULT_KDF_PARAMS_DKLEN as usize]);
     let scrypt_params =
         ScryptParams::new(DEFAULT_KDF_PARAMS_LOG_N, DEFAULT_KDF_PARAMS_R, DEFAULT_KDF_PARAMS_P)?;
     scrypt(password.as_ref(), &salt, &scrypt_params, key.as_mut_slice())?;
 
     // Encrypt the private key using AES-128-CTR.
-    let mut iv = vec![0u8; DEFAULT_IV_SIZE];
-    rng.fill_bytes(iv.as_mut_slice());
-
     let encryptor = Aes128Ctr::new(&key[..16], &iv[..16]).expect("invalid length");
 
     let mut ciphertext = pk.as_ref().to_vec();
@@ -205,14 +465,12 @@ where
     // Calculate the MAC.
     let mac = Sha3_256::new().chain(&key[16..32]).chain(&ciphertext).finalize();
 
-    // If a file name is not specified for the keystore, simply use the strigified uuid.
-    let id = Uuid::new_v4();
-    let name = if let Some(name) = name { name.to_string() } else { id.to_string() };
-
-    // Construct and serialize the encrypted JSON keystore.
-    let keystore = EthKeystore {
+    // Construct the encrypted JSON keystore.
+    Ok(EthKeystore {
         id,
-        version: 3,
+        version: KEYSTORE_VERSION,
+        key_type: Some(KeyType::Ed448),
+        network: Some(*network),
         crypto: CryptoJson {
             cipher: String::from(DEFAULT_CIPHER),
             cipherparams: CipherparamsJson { iv },
@@ -228,14 +486,17 @@ where
             mac: mac.to_vec(),
         },
         address: address_from_pk(&pk, network)?,
-    };
-    let contents = serde_json::to_string(&keystore)?;
+    })
+}
+
+/// Serializes the keystore and stores it at `path`.
+fn write_keystore(path: impl AsRef<Path>, keystore: &EthKeystore) -> Result<(), KeystoreError> {
+    let contents = serde_json::to_string(keystore)?;
 
     // Create a file in write-only mode, to store the encrypted JSON keystore.
-    let mut file = File::create(dir.as_ref().join(name))?;
+    let mut file = File::create(path)?;
     file.write_all(contents.as_bytes())?;
-
-    Ok(id.to_string())
+    Ok(())
 }
 
 struct Aes128Ctr {
@@ -1,4 +1,7 @@
-use corebc_keystore::{decrypt_key, encrypt_key, new};
+use corebc_core::types::Network;
+use corebc_keystore::{
+    decrypt_all, decrypt_key, encrypt_key, encrypt_keys, migrate_key, new, EthKeystore, KeyType,
+};
 use hex::FromHex;
 use std::path::Path;
 
@@ -45,6 +48,35 @@ mod tests {
         assert!(std::fs::remove_file(&keypath).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_decrypt_zeroizing() {
+        use corebc_keystore::{decrypt_key_zeroizing, Zeroizing};
+        use zeroize::Zeroize;
+
+        let dir = Path::new("./tests/test-keys");
+        let mut rng = rand::thread_rng();
+        let name = "my_zeroizing_keystore";
+        let (secret, _id) = new(
+            &dir,
+            &mut rng,
+            "thebestrandompassword",
+            Some(name),
+            &corebc_core::types::Network::Mainnet,
+        )
+        .unwrap();
+
+        let keypath = dir.join(name);
+        let mut decrypted: Zeroizing<Vec<u8>> =
+            decrypt_key_zeroizing(&keypath, "thebestrandompassword").unwrap();
+        assert_eq!(*decrypted, secret);
+        assert!(std::fs::remove_file(&keypath).is_ok());
+
+        // this is what the wrapper does on drop
+        decrypted.zeroize();
+        assert!(decrypted.is_empty());
+    }
+
     // #[test]
     // fn test_decrypt_pbkdf2() {
     //     let secret =
@@ -87,4 +119,88 @@ mod tests {
         assert!(decrypt_key(&keypath, "notanewpassword").is_err());
         assert!(std::fs::remove_file(&keypath).is_ok());
     }
+
+    #[test]
+    fn test_migrate_key() {
+        let dir = Path::new("./tests/test-keys");
+        let mut rng = rand::thread_rng();
+        let name = "my_migrated_keystore";
+        let (secret, _id) =
+            new(&dir, &mut rng, "thebestrandompassword", Some(name), &Network::Mainnet).unwrap();
+
+        // downgrade to a version 3 keystore
+        let keypath = dir.join(name);
+        let mut json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&keypath).unwrap()).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("keyType");
+        object.remove("network");
+        object.insert("version".to_string(), 3.into());
+        std::fs::write(&keypath, json.to_string()).unwrap();
+        assert_eq!(decrypt_key(&keypath, "thebestrandompassword").unwrap(), secret);
+
+        // the address of the keystore is not a devin address
+        assert!(migrate_key(&keypath, "thebestrandompassword", &Network::Devin).is_err());
+        assert!(migrate_key(&keypath, "notthebestrandompassword", &Network::Mainnet).is_err());
+        migrate_key(&keypath, "thebestrandompassword", &Network::Mainnet).unwrap();
+
+        let keystore: EthKeystore =
+            serde_json::from_str(&std::fs::read_to_string(&keypath).unwrap()).unwrap();
+        assert_eq!(keystore.version, 4);
+        assert_eq!(keystore.key_type, Some(KeyType::Ed448));
+        assert_eq!(keystore.network, Some(Network::Mainnet));
+        assert_eq!(decrypt_key(&keypath, "thebestrandompassword").unwrap(), secret);
+        assert!(std::fs::remove_file(&keypath).is_ok());
+    }
+
+    #[test]
+    fn test_new_keystore_is_v4() {
+        let dir = Path::new("./tests/test-keys");
+        let mut rng = rand::thread_rng();
+        let name = "my_v4_keystore";
+        new(&dir, &mut rng, "thebestrandompassword", Some(name), &Network::Devin).unwrap();
+
+        let keypath = dir.join(name);
+        let keystore: EthKeystore =
+            serde_json::from_str(&std::fs::read_to_string(&keypath).unwrap()).unwrap();
+        assert_eq!(keystore.version, 4);
+        assert_eq!(keystore.key_type, Some(KeyType::Ed448));
+        assert_eq!(keystore.network, Some(Network::Devin));
+        assert!(std::fs::remove_file(&keypath).is_ok());
+    }
+
+    #[test]
+    fn test_encrypt_keys_decrypt_all() {
+        let dir = Path::new("./tests/test-keys/batch");
+        std::fs::create_dir_all(dir).unwrap();
+        let mut rng = rand::thread_rng();
+        let secrets: Vec<Vec<u8>> = (0..4)
+            .map(|_| {
+                let mut secret = vec![0u8; 57];
+                rand::RngCore::fill_bytes(&mut rng, secret.as_mut_slice());
+                secret
+            })
+            .collect();
+
+        let ids =
+            encrypt_keys(dir, &mut rng, &secrets, "batchpassword", &Network::Mainnet).unwrap();
+        assert_eq!(ids.len(), secrets.len());
+        for (id, secret) in ids.iter().zip(&secrets) {
+            assert_eq!(&decrypt_key(dir.join(id), "batchpassword").unwrap(), secret);
+        }
+
+        std::fs::write(dir.join("not-a-keystore"), "{}").unwrap();
+        let decrypted = decrypt_all(dir, "batchpassword").unwrap();
+        assert_eq!(decrypted.len(), secrets.len() + 1);
+        for (path, secret) in decrypted {
+            if path.ends_with("not-a-keystore") {
+                assert!(secret.is_err());
+            } else {
+                let id = path.file_name().unwrap().to_str().unwrap();
+                let index = ids.iter().position(|i| i == id).unwrap();
+                assert_eq!(secret.unwrap(), secrets[index]);
+            }
+        }
+        assert!(std::fs::remove_dir_all(dir).is_ok());
+    }
 }
@@ -0,0 +1,400 @@
+//! Journaling of the transactions sent through a middleware stack
+
+use corebc_core::types::{transaction::eip2718::TypedTransaction, BlockId, Bytes, TxHash};
+use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Display},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// When an [`AuditEntry`] was recorded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditStage {
+    /// Before the transaction was sent, the entry has neither a hash nor an error
+    #[default]
+    Sending,
+    /// Once the node answered, or the transaction could not be filled
+    Done,
+}
+
+/// A transaction sent through an [`AuditMiddleware`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Seconds since the unix epoch at which the entry was recorded
+    pub timestamp: u64,
+    /// Whether the entry was recorded before or after sending the transaction
+    #[serde(default)]
+    pub stage: AuditStage,
+    /// The transaction as passed to `send_transaction`, `None` for raw transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<TypedTransaction>,
+    /// The transaction after its missing fields were filled, or decoded from the raw transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filled: Option<TypedTransaction>,
+    /// The signed transaction, if it was sent with `send_raw_transaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Bytes>,
+    /// The hash of the transaction, if the node accepted it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<TxHash>,
+    /// The error sending the transaction failed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    fn now() -> Self {
+        Self { timestamp: unix_timestamp(), ..Default::default() }
+    }
+
+    /// Returns the entry recording the `result` of sending the transaction
+    fn done<T: Display>(&self, result: Result<TxHash, T>) -> Self {
+        let (hash, error) = match result {
+            Ok(hash) => (Some(hash), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        Self { timestamp: unix_timestamp(), stage: AuditStage::Done, hash, error, ..self.clone() }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    instant::SystemTime::now()
+        .duration_since(instant::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Basic trait for the destinations transactions are journaled to, e.g. a file or a database.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AuditSink: Sync + Send + Debug {
+    type Error: Sync + Send + Debug;
+
+    /// Records the entry.
+    async fn record(&self, entry: &AuditEntry) -> Result<(), Self::Error>;
+}
+
+/// A sink keeping all entries in memory.
+#[derive(Clone, Debug, Default)]
+pub struct MemorySink {
+    entries: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl MemorySink {
+    /// Creates a new, empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the entries recorded so far
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AuditSink for MemorySink {
+    type Error = ();
+
+    async fn record(&self, entry: &AuditEntry) -> Result<(), Self::Error> {
+        self.entries.lock().unwrap().push(entry.clone());
+        Ok(())
+    }
+}
+
+/// A sink appending entries to a file, one JSON object per line.
+///
+/// Entries are written on tokio's blocking thread pool, so the sink must be used within a tokio
+/// runtime.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct JsonFileSink {
+    file: Arc<Mutex<std::fs::File>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsonFileSink {
+    /// Opens the file at `path` for appending, creating it if it does not exist
+    pub fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Arc::new(Mutex::new(file)) })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl AuditSink for JsonFileSink {
+    type Error = std::io::Error;
+
+    async fn record(&self, entry: &AuditEntry) -> Result<(), Self::Error> {
+        use std::io::Write;
+
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let file = self.file.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut file = file.lock().unwrap();
+            file.write_all(&line)?;
+            file.flush()
+        })
+        .await
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+    }
+}
+
+/// Middleware recording every transaction sent through it to an [`AuditSink`], e.g. to keep a
+/// compliance trail.
+///
+/// Each transaction is recorded twice: before it is sent, with the request and the transaction
+/// after its missing fields were filled, and once the node answered, with the result of sending
+/// it as well, see [`AuditStage`]. An [`AuditStage::Sending`] entry without a matching
+/// [`AuditStage::Done`] one marks a transaction whose outcome is unknown, e.g. because the
+/// process crashed while sending it. Placed below a
+/// [`SignerMiddleware`](crate::SignerMiddleware), the entries hold the signed transactions as
+/// well:
+///
+/// ```no_run
+/// # use corebc_core::{rand::thread_rng, types::Network};
+/// # use corebc_middleware::{audit::JsonFileSink, AuditMiddleware, SignerMiddleware};
+/// # use corebc_providers::{Http, Provider};
+/// # use corebc_signers::LocalWallet;
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let audited = AuditMiddleware::new(provider, JsonFileSink::open("transactions.jsonl")?);
+/// let wallet = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+/// let client = SignerMiddleware::new(audited, wallet);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// If the first entry can not be recorded, the transaction is not sent. If the second one can not
+/// be recorded, the error holds the hash of the transaction, which was sent already.
+#[derive(Clone, Debug)]
+pub struct AuditMiddleware<M, S> {
+    pub(crate) inner: M,
+    pub(crate) sink: S,
+}
+
+impl<M, S> AuditMiddleware<M, S>
+where
+    M: Middleware,
+    S: AuditSink,
+{
+    /// Creates a new client recording transactions to `sink`.
+    pub fn new(inner: M, sink: S) -> Self {
+        Self { inner, sink }
+    }
+
+    /// Returns the sink transactions are recorded to
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    async fn record(&self, entry: AuditEntry) -> Result<(), AuditMiddlewareError<M, S>> {
+        self.sink
+            .record(&entry)
+            .await
+            .map_err(|error| AuditMiddlewareError::SinkError { hash: entry.hash, error })
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when the client interacts with the audit middleware.
+pub enum AuditMiddlewareError<M: Middleware, S: AuditSink> {
+    /// Thrown when the transaction could not be recorded, `hash` is set if it was sent
+    #[error("failed to record transaction {hash:?}: {error:?}")]
+    SinkError {
+        /// The hash of the transaction, if it was sent
+        hash: Option<TxHash>,
+        /// The error of the sink
+        error: S::Error,
+    },
+    /// Thrown when an internal middleware errors
+    #[error(transparent)]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware, S: AuditSink> MiddlewareError for AuditMiddlewareError<M, S> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        AuditMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            AuditMiddlewareError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M, S> Middleware for AuditMiddleware<M, S>
+where
+    M: Middleware,
+    S: AuditSink,
+{
+    type Error = AuditMiddlewareError<M, S>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Fills the transaction, records it, sends it via the inner middleware and records the
+    /// result
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let request = tx.into();
+        let mut entry = AuditEntry { request: Some(request.clone()), ..AuditEntry::now() };
+
+        let mut tx = request;
+        if let Err(err) = self.inner.fill_transaction(&mut tx, block).await {
+            self.record(entry.done(Err(&err))).await?;
+            return Err(AuditMiddlewareError::MiddlewareError(err))
+        }
+        entry.filled = Some(tx.clone());
+        self.record(entry.clone()).await?;
+
+        let result = self.inner.send_transaction(tx, block).await;
+        self.record(entry.done(result.as_ref().map(|pending| **pending))).await?;
+        result.map_err(AuditMiddlewareError::MiddlewareError)
+    }
+
+    /// Records the signed transaction, sends it via the inner middleware and records the result
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        let filled = TypedTransaction::decode_signed_bytes(&tx).ok().map(|(tx, _)| tx);
+        let entry = AuditEntry { filled, raw: Some(tx.clone()), ..AuditEntry::now() };
+        self.record(entry.clone()).await?;
+
+        let result = self.inner.send_raw_transaction(tx).await;
+        self.record(entry.done(result.as_ref().map(|pending| **pending))).await?;
+        result.map_err(AuditMiddlewareError::MiddlewareError)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::types::{Address, TransactionRequest, H256, U256};
+    use corebc_providers::{JsonRpcError, Provider};
+
+    #[tokio::test]
+    async fn records_sent_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let sink = MemorySink::new();
+        let client = AuditMiddleware::new(provider, sink.clone());
+
+        let from = Address::repeat_byte(1);
+        let hash = H256::repeat_byte(2);
+        let tx = TransactionRequest::new()
+            .from(from)
+            .to(Address::repeat_byte(3))
+            .nonce(7)
+            .network_id(1)
+            .energy(21000)
+            .energy_price(1);
+        mock.expect("xcb_sendTransaction").returns(hash);
+
+        let pending = client.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, hash);
+
+        let entries = sink.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].stage, AuditStage::Sending);
+        assert_eq!(entries[0].request, Some(tx.clone().into()));
+        assert_eq!(entries[0].filled.as_ref().and_then(|tx| tx.nonce()), Some(&U256::from(7)));
+        assert!(entries[0].hash.is_none());
+        assert!(entries[0].timestamp > 0);
+        assert_eq!(entries[1].stage, AuditStage::Done);
+        assert_eq!(
+            (&entries[1].request, &entries[1].filled),
+            (&entries[0].request, &entries[0].filled)
+        );
+        assert_eq!(entries[1].hash, Some(hash));
+        assert!(entries[1].raw.is_none());
+        assert!(entries[1].error.is_none());
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn records_failed_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let sink = MemorySink::new();
+        let client = AuditMiddleware::new(provider, sink.clone());
+
+        let raw = Bytes::from(vec![0xf8, 0x01]);
+        mock.expect("xcb_sendRawTransaction").return_error(JsonRpcError {
+            code: -32000,
+            message: "nonce too low".to_string(),
+            data: None,
+        });
+
+        assert!(client.send_raw_transaction(raw.clone()).await.is_err());
+
+        let entries = sink.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].stage, entries[1].stage), (AuditStage::Sending, AuditStage::Done));
+        assert_eq!(entries[1].raw, Some(raw));
+        assert!(entries[1].filled.is_none());
+        assert!(entries[1].hash.is_none());
+        assert!(entries[1].error.as_deref().unwrap().contains("nonce too low"));
+    }
+
+    #[derive(Debug)]
+    struct FailingSink;
+
+    #[async_trait]
+    impl AuditSink for FailingSink {
+        type Error = ();
+
+        async fn record(&self, _: &AuditEntry) -> Result<(), Self::Error> {
+            Err(())
+        }
+    }
+
+    #[tokio::test]
+    async fn does_not_send_unrecorded_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let client = AuditMiddleware::new(provider, FailingSink);
+
+        let err = client.send_raw_transaction(Bytes::from(vec![0xf8, 0x01])).await.unwrap_err();
+        assert!(matches!(err, AuditMiddlewareError::SinkError { hash: None, .. }));
+        // the transaction never reached the node
+        assert!(mock.assert_request("xcb_sendRawTransaction", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn writes_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let sink = JsonFileSink::open(&path).unwrap();
+
+        let entry = AuditEntry { hash: Some(H256::repeat_byte(1)), ..AuditEntry::now() };
+        sink.record(&entry).await.unwrap();
+        sink.record(&entry).await.unwrap();
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        let entries = lines
+            .lines()
+            .map(|line| serde_json::from_str::<AuditEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![entry.clone(), entry]);
+    }
+}
@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use corebc_core::{
+    types::{Bytes, H256},
+    utils::sha3,
+};
+use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+use futures_util::{
+    future::{select, Either},
+    pin_mut,
+    stream::{FuturesUnordered, StreamExt},
+};
+use thiserror::Error;
+
+/// Middleware that submits signed transactions to several endpoints at once.
+///
+/// Raw transactions are sent to the inner middleware and all backups concurrently, and the
+/// pending transaction is returned as soon as one of them accepts it. Endpoints that report the
+/// transaction as already known count as accepting it. Submissions that are still in flight at
+/// that point are dropped, the nodes that accepted the transaction propagate it to the network.
+///
+/// Place it below the [`SignerMiddleware`](crate::SignerMiddleware), which sends the signed
+/// transactions as raw transactions.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Middleware, Provider, Http};
+/// use corebc_middleware::{BroadcastMiddleware, SignerMiddleware};
+/// use corebc_signers::LocalWallet;
+/// use std::convert::TryFrom;
+///
+/// # async fn foo(wallet: LocalWallet) -> Result<(), Box<dyn std::error::Error>> {
+/// let primary = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let backups = vec![
+///     Provider::<Http>::try_from("http://backup-1:8545")?,
+///     Provider::<Http>::try_from("http://backup-2:8545")?,
+/// ];
+/// let provider = BroadcastMiddleware::new(primary, backups);
+/// let client = SignerMiddleware::new(provider, wallet);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BroadcastMiddleware<M, B> {
+    inner: M,
+    backups: Vec<B>,
+}
+
+impl<M, B> BroadcastMiddleware<M, B>
+where
+    M: Middleware,
+    B: Middleware,
+{
+    /// Creates a new client that submits raw transactions to `inner` and all `backups`
+    pub fn new(inner: M, backups: impl IntoIterator<Item = B>) -> Self {
+        Self { inner, backups: backups.into_iter().collect() }
+    }
+
+    /// Returns the backup endpoints
+    pub fn backups(&self) -> &[B] {
+        &self.backups
+    }
+
+    /// Submits the transaction to all backups, returns the hash once one accepted it or the
+    /// errors of all of them
+    async fn broadcast_to_backups(&self, tx: &Bytes, hash: H256) -> Result<H256, Vec<String>> {
+        let mut submissions = self
+            .backups
+            .iter()
+            .map(|backup| async move {
+                match backup.send_raw_transaction(tx.clone()).await {
+                    Ok(pending) => Ok(pending.tx_hash()),
+                    Err(err) if is_already_known(&err) => Ok(hash),
+                    Err(err) => Err(err.to_string()),
+                }
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut errors = Vec::with_capacity(self.backups.len());
+        while let Some(res) = submissions.next().await {
+            match res {
+                Ok(hash) => return Ok(hash),
+                Err(err) => errors.push(err),
+            }
+        }
+        Err(errors)
+    }
+}
+
+/// Whether the node rejected the transaction because it knows it already
+fn is_already_known(err: &impl MiddlewareError) -> bool {
+    let msg = match err.as_error_response() {
+        Some(err) => err.message.to_lowercase(),
+        None => err.to_string().to_lowercase(),
+    };
+    msg.contains("already known") || msg.contains("known transaction")
+}
+
+#[derive(Error, Debug)]
+/// Thrown when the broadcast middleware could not submit a transaction
+pub enum BroadcastError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when the inner middleware and all backups rejected the transaction
+    #[error("all endpoints rejected the transaction: {primary}; backups: [{}]", .backups.join(", "))]
+    AllRejected {
+        /// The error of the inner middleware
+        primary: M::Error,
+        /// The errors of the backups
+        backups: Vec<String>,
+    },
+}
+
+impl<M: Middleware> MiddlewareError for BroadcastError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        BroadcastError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            BroadcastError::MiddlewareError(e) => Some(e),
+            BroadcastError::AllRejected { primary, .. } => Some(primary),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M, B> Middleware for BroadcastMiddleware<M, B>
+where
+    M: Middleware,
+    B: Middleware,
+{
+    type Error = BroadcastError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Submits the transaction to the inner middleware and all backups, and returns as soon as
+    /// one of them accepted it.
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        let hash = H256::from(sha3(&tx));
+
+        let primary = async {
+            match self.inner.send_raw_transaction(tx.clone()).await {
+                Ok(pending) => Ok(pending.tx_hash()),
+                Err(err) if is_already_known(&err) => Ok(hash),
+                Err(err) => Err(err),
+            }
+        };
+        let backups = self.broadcast_to_backups(&tx, hash);
+        pin_mut!(primary, backups);
+
+        let hash = match select(primary, backups).await {
+            Either::Left((Ok(hash), _)) | Either::Right((Ok(hash), _)) => hash,
+            Either::Left((Err(primary), backups)) => match backups.await {
+                Ok(hash) => hash,
+                Err(backups) => return Err(BroadcastError::AllRejected { primary, backups }),
+            },
+            Either::Right((Err(backups), primary)) => match primary.await {
+                Ok(hash) => hash,
+                Err(primary) => return Err(BroadcastError::AllRejected { primary, backups }),
+            },
+        };
+
+        Ok(PendingTransaction::new(hash, self.provider()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_providers::{JsonRpcError, Provider};
+
+    fn already_known() -> JsonRpcError {
+        JsonRpcError { code: -32000, message: "already known".to_string(), data: None }
+    }
+
+    #[tokio::test]
+    async fn returns_once_one_endpoint_accepts() {
+        let (primary, primary_mock) = Provider::mocked();
+        let (backup, backup_mock) = Provider::mocked();
+        let client = BroadcastMiddleware::new(primary, vec![backup]);
+
+        let tx = Bytes::from(vec![1u8, 2, 3]);
+        let hash = H256::from(sha3(&tx));
+
+        // the primary has no response and fails
+        backup_mock.push(hash).unwrap();
+        let pending = client.send_raw_transaction(tx.clone()).await.unwrap();
+        assert_eq!(pending.tx_hash(), hash);
+        primary_mock.assert_request("xcb_sendRawTransaction", [&tx]).unwrap();
+        backup_mock.assert_request("xcb_sendRawTransaction", [&tx]).unwrap();
+
+        // already known transactions are accepted, the backup is not awaited once the primary
+        // accepted the transaction
+        primary_mock.expect("xcb_sendRawTransaction").times(1).return_error(already_known());
+        let pending = client.send_raw_transaction(tx.clone()).await.unwrap();
+        assert_eq!(pending.tx_hash(), hash);
+        primary_mock.verify().unwrap();
+
+        // the backup is awaited if the primary rejects the transaction
+        let (primary, primary_mock) = Provider::mocked();
+        let (backup, backup_mock) = Provider::mocked();
+        let client = BroadcastMiddleware::new(primary, vec![backup]);
+        let underpriced =
+            JsonRpcError { code: -32000, message: "underpriced".to_string(), data: None };
+        primary_mock.expect("xcb_sendRawTransaction").return_error(underpriced);
+        backup_mock.expect("xcb_sendRawTransaction").return_error(already_known());
+        let pending = client.send_raw_transaction(tx).await.unwrap();
+        assert_eq!(pending.tx_hash(), hash);
+        primary_mock.verify().unwrap();
+        backup_mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn fails_if_all_endpoints_reject() {
+        let (primary, primary_mock) = Provider::mocked();
+        let (backup, backup_mock) = Provider::mocked();
+        let client = BroadcastMiddleware::new(primary, vec![backup]);
+
+        let underpriced =
+            JsonRpcError { code: -32000, message: "underpriced".to_string(), data: None };
+        primary_mock.expect("xcb_sendRawTransaction").return_error(underpriced.clone());
+        backup_mock.expect("xcb_sendRawTransaction").return_error(underpriced);
+
+        let err = client.send_raw_transaction(vec![1u8].into()).await.unwrap_err();
+        assert!(matches!(&err, BroadcastError::AllRejected { backups, .. } if backups.len() == 1));
+        assert_eq!(err.as_error_response().unwrap().message, "underpriced");
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    energy_oracle::{EnergyOracle, EnergyOracleMiddleware},
+    energy_oracle::{EnergyOracle, EnergyOracleMiddleware, ProviderOracle},
     NonceManagerMiddleware, SignerMiddleware,
 };
 use corebc_core::types::Address;
 use corebc_providers::Middleware;
 use corebc_signers::Signer;
+use std::marker::PhantomData;
 
 // A builder trait to compose different [`Middleware`](corebc_providers::Middleware) layers
 // and then build a composed [`Provider`](corebc_providers::Provider) architecture.
@@ -90,3 +91,146 @@ pub trait MiddlewareBuilder: Middleware + Sized + 'static {
 }
 
 impl<M> MiddlewareBuilder for M where M: Middleware + Sized + 'static {}
+
+/// The layers of [`MiddlewareStack::standard`], from the outermost to the innermost one
+pub type StandardMiddleware<M, S, G = ProviderOracle<M>> =
+    NonceManagerMiddleware<SignerMiddleware<EnergyOracleMiddleware<M, G>, S>>;
+
+/// Marks a [`MiddlewareStack`] that does not contain a [`SignerMiddleware`] yet
+#[derive(Clone, Copy, Debug)]
+pub struct Unsigned;
+
+/// Marks a [`MiddlewareStack`] that contains a [`SignerMiddleware`]
+#[derive(Clone, Copy, Debug)]
+pub struct Signed;
+
+/// A typed builder for middleware stacks.
+///
+/// The stack tracks whether it contains a [`SignerMiddleware`] already, so a signer can only be
+/// added once:
+///
+/// ```compile_fail
+/// # use corebc_core::{rand::thread_rng, types::Network};
+/// # use corebc_middleware::builder::MiddlewareStack;
+/// # use corebc_providers::Provider;
+/// # use corebc_signers::LocalWallet;
+/// let (provider, _mock) = Provider::mocked();
+/// let signer = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+/// let client = MiddlewareStack::new(provider)
+///     .signer(signer.clone())
+///     .signer(signer) // does not compile
+///     .build();
+/// ```
+///
+/// [`MiddlewareStack::standard`] wires the common layers in the right order.
+///
+/// ```no_run
+/// # use corebc_core::{rand::thread_rng, types::Network};
+/// # use corebc_middleware::builder::MiddlewareStack;
+/// # use corebc_providers::{Http, Provider};
+/// # use corebc_signers::LocalWallet;
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let signer = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+/// let client = MiddlewareStack::standard(provider, signer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct MiddlewareStack<M, State = Unsigned> {
+    inner: M,
+    _state: PhantomData<State>,
+}
+
+impl<M: Middleware> MiddlewareStack<M, Unsigned> {
+    /// Starts a stack on top of `inner`
+    pub fn new(inner: M) -> Self {
+        Self { inner, _state: PhantomData }
+    }
+
+    /// Wraps the stack inside a [`SignerMiddleware`]
+    pub fn signer<S>(self, signer: S) -> MiddlewareStack<SignerMiddleware<M, S>, Signed>
+    where
+        S: Signer,
+    {
+        self.wrap(|inner| SignerMiddleware::new(inner, signer))
+    }
+
+    /// Builds the standard stack for sending transactions from `signer`.
+    ///
+    /// The energy price is fetched from `provider`, then transactions are signed, and the
+    /// nonces are managed locally in the outermost layer, so that concurrent transactions do not
+    /// need to wait for each other.
+    pub fn standard<S>(provider: M, signer: S) -> StandardMiddleware<M, S>
+    where
+        M: Clone,
+        M::Error: 'static,
+        S: Signer,
+    {
+        let oracle = ProviderOracle::new(provider.clone());
+        Self::standard_with_oracle(provider, signer, oracle)
+    }
+
+    /// Builds the [standard](Self::standard) stack with a custom energy oracle
+    pub fn standard_with_oracle<S, G>(
+        provider: M,
+        signer: S,
+        energy_oracle: G,
+    ) -> StandardMiddleware<M, S, G>
+    where
+        S: Signer,
+        G: EnergyOracle,
+    {
+        let address = signer.address();
+        Self::new(provider)
+            .energy_oracle(energy_oracle)
+            .signer(signer)
+            .nonce_manager(address)
+            .build()
+    }
+}
+
+impl<M: Middleware, State> MiddlewareStack<M, State> {
+    /// Wraps the stack inside an [`EnergyOracleMiddleware`]
+    pub fn energy_oracle<G>(
+        self,
+        energy_oracle: G,
+    ) -> MiddlewareStack<EnergyOracleMiddleware<M, G>, State>
+    where
+        G: EnergyOracle,
+    {
+        self.wrap(|inner| EnergyOracleMiddleware::new(inner, energy_oracle))
+    }
+
+    /// Wraps the stack inside a [`NonceManagerMiddleware`] for `address`
+    pub fn nonce_manager(
+        self,
+        address: Address,
+    ) -> MiddlewareStack<NonceManagerMiddleware<M>, State> {
+        self.wrap(|inner| NonceManagerMiddleware::new(inner, address))
+    }
+
+    /// Wraps the stack inside a middleware that does not sign transactions.
+    ///
+    /// Use [`signer`](MiddlewareStack::signer) to add a [`SignerMiddleware`].
+    pub fn wrap_into<F, T>(self, f: F) -> MiddlewareStack<T, State>
+    where
+        F: FnOnce(M) -> T,
+        T: Middleware,
+    {
+        self.wrap(f)
+    }
+
+    /// Returns the outermost middleware
+    pub fn build(self) -> M {
+        self.inner
+    }
+
+    fn wrap<F, T, S>(self, f: F) -> MiddlewareStack<T, S>
+    where
+        F: FnOnce(M) -> T,
+    {
+        MiddlewareStack { inner: f(self.inner), _state: PhantomData }
+    }
+}
@@ -0,0 +1,346 @@
+//! [CCIP-read](https://eips.ethereum.org/EIPS/eip-3668) support for `xcb_call`.
+//!
+//! Contracts that store their data offchain revert with an `OffchainLookup` error that
+//! describes which gateways to query. The [`OffchainLookupMiddleware`] intercepts these reverts,
+//! fetches the data from one of the gateways and calls the contract's callback function with the
+//! response.
+use async_trait::async_trait;
+use corebc_core::{
+    abi::{self, ParamType, Token},
+    types::{transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, Selector},
+    utils::id,
+};
+use corebc_providers::{Middleware, MiddlewareError};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// The signature of the error a contract reverts with in order to request an offchain lookup.
+pub const OFFCHAIN_LOOKUP_SIGNATURE: &str = "OffchainLookup(address,string[],bytes,bytes4,bytes)";
+
+/// The maximum number of consecutive lookups a single call may trigger before giving up.
+pub const DEFAULT_MAX_REDIRECTS: u8 = 4;
+
+/// The decoded arguments of an `OffchainLookup` revert.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OffchainLookup {
+    /// The address of the contract that reverted
+    pub sender: Address,
+    /// The gateway URL templates, tried in order
+    pub urls: Vec<String>,
+    /// The data to send to the gateway
+    pub call_data: Bytes,
+    /// The selector of the function to call with the gateway response
+    pub callback_function: Selector,
+    /// Opaque data that is passed back to the callback function
+    pub extra_data: Bytes,
+}
+
+impl OffchainLookup {
+    /// Returns the selector of the `OffchainLookup` error.
+    pub fn selector() -> Selector {
+        id(OFFCHAIN_LOOKUP_SIGNATURE)
+    }
+
+    /// Decodes the revert data of a call, returns `None` if it is not an `OffchainLookup` error.
+    pub fn decode(revert_data: &[u8]) -> Option<Self> {
+        if revert_data.len() < 4 || revert_data[..4] != Self::selector() {
+            return None
+        }
+
+        let params = [
+            ParamType::Address,
+            ParamType::Array(Box::new(ParamType::String)),
+            ParamType::Bytes,
+            ParamType::FixedBytes(4),
+            ParamType::Bytes,
+        ];
+        let mut tokens = abi::decode(&params, &revert_data[4..]).ok()?.into_iter();
+
+        let sender = tokens.next()?.into_address()?;
+        let urls = tokens
+            .next()?
+            .into_array()?
+            .into_iter()
+            .map(Token::into_string)
+            .collect::<Option<Vec<_>>>()?;
+        let call_data = tokens.next()?.into_bytes()?.into();
+        let callback_function = tokens.next()?.into_fixed_bytes()?.try_into().ok()?;
+        let extra_data = tokens.next()?.into_bytes()?.into();
+
+        Some(Self { sender, urls, call_data, callback_function, extra_data })
+    }
+
+    /// Returns the calldata for the callback function, given the gateway's `response`.
+    pub fn callback_data(&self, response: &Bytes) -> Bytes {
+        let args =
+            abi::encode(&[Token::Bytes(response.to_vec()), Token::Bytes(self.extra_data.to_vec())]);
+        [&self.callback_function[..], &args].concat().into()
+    }
+}
+
+/// The JSON body POSTed to gateways whose URL does not contain the `{data}` placeholder.
+#[derive(Serialize)]
+struct GatewayRequest<'a> {
+    data: &'a Bytes,
+    sender: String,
+}
+
+/// The JSON body returned by a gateway.
+#[derive(Deserialize)]
+struct GatewayResponse {
+    data: Bytes,
+}
+
+/// Middleware that transparently resolves `OffchainLookup` reverts on `xcb_call`, enabling
+/// CCIP-read style contracts.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Middleware, Provider, Http};
+/// use corebc_middleware::ccip::OffchainLookupMiddleware;
+/// use corebc_core::types::{TransactionRequest, Address};
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let provider = OffchainLookupMiddleware::new(provider);
+///
+/// let tx = TransactionRequest::new().to(Address::zero()).data(vec![0u8; 4]);
+/// // if the contract reverts with `OffchainLookup`, the gateway is queried and
+/// // the result of the callback is returned instead
+/// let result = provider.call(&tx.into(), None).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OffchainLookupMiddleware<M> {
+    inner: M,
+    client: Client,
+    max_redirects: u8,
+}
+
+impl<M> OffchainLookupMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Creates a new middleware that resolves offchain lookups with a default HTTP client.
+    pub fn new(inner: M) -> Self {
+        Self::with_client(inner, Client::new())
+    }
+
+    /// Same as [`Self::new`] but with a custom [`Client`].
+    pub fn with_client(inner: M, client: Client) -> Self {
+        Self { inner, client, max_redirects: DEFAULT_MAX_REDIRECTS }
+    }
+
+    /// Sets the maximum number of consecutive lookups a single call may trigger.
+    #[must_use]
+    pub fn max_redirects(mut self, max_redirects: u8) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Queries the lookup's gateways in order and returns the first successful response.
+    ///
+    /// As per EIP-3668, a 4xx response aborts the lookup while a 5xx response moves on to the next
+    /// gateway.
+    pub async fn fetch(&self, lookup: &OffchainLookup) -> Result<Bytes, OffchainLookupError<M>> {
+        let sender = format!("{:?}", lookup.sender);
+        let data = lookup.call_data.to_string();
+        let mut errors = Vec::with_capacity(lookup.urls.len());
+
+        for url in &lookup.urls {
+            let href = url.replace("{sender}", &sender).replace("{data}", &data);
+            let request = if url.contains("{data}") {
+                self.client.get(&href)
+            } else {
+                self.client
+                    .post(&href)
+                    .json(&GatewayRequest { data: &lookup.call_data, sender: sender.clone() })
+            };
+
+            debug!(gateway = %href, "offchain lookup");
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(err) => {
+                    warn!(gateway = %href, "offchain lookup failed: {}", err);
+                    errors.push(format!("{href}: {err}"));
+                    continue
+                }
+            };
+
+            let status = response.status();
+            if status.is_client_error() {
+                return Err(OffchainLookupError::GatewayRejected { url: href, status })
+            }
+            if !status.is_success() {
+                errors.push(format!("{href}: {status}"));
+                continue
+            }
+
+            match response.json::<GatewayResponse>().await {
+                Ok(res) => return Ok(res.data),
+                Err(err) => errors.push(format!("{href}: {err}")),
+            }
+        }
+
+        Err(OffchainLookupError::GatewaysFailed(errors))
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when resolving an offchain lookup fails
+pub enum OffchainLookupError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when the contract requesting the lookup is not the one that was called
+    #[error("offchain lookup sender {sender:?} does not match the called contract {to:?}")]
+    SenderMismatch {
+        /// The contract that was called
+        to: Address,
+        /// The sender reported in the `OffchainLookup` error
+        sender: Address,
+    },
+
+    /// Thrown when a gateway answers with a 4xx status code
+    #[error("gateway {url} rejected the offchain lookup: {status}")]
+    GatewayRejected {
+        /// The URL of the gateway
+        url: String,
+        /// The returned status code
+        status: StatusCode,
+    },
+
+    /// Thrown when none of the gateways returned a usable response
+    #[error("all gateways failed: {}", .0.join(", "))]
+    GatewaysFailed(Vec<String>),
+
+    /// Thrown when a call keeps requesting offchain lookups
+    #[error("too many offchain lookup redirects (max {0})")]
+    TooManyRedirects(u8),
+}
+
+impl<M: Middleware> MiddlewareError for OffchainLookupError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        OffchainLookupError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            OffchainLookupError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for OffchainLookupMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = OffchainLookupError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Executes the call and, if the contract reverts with `OffchainLookup`, resolves the lookup
+    /// and returns the result of the callback function instead.
+    async fn call(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        let mut tx = tx.clone();
+
+        for _ in 0..=self.max_redirects {
+            let err = match self.inner.call(&tx, block).await {
+                Ok(res) => return Ok(res),
+                Err(err) => err,
+            };
+
+            let lookup = match err
+                .as_error_response()
+                .and_then(|e| e.as_revert_data())
+                .and_then(|data| OffchainLookup::decode(&data))
+            {
+                Some(lookup) => lookup,
+                None => return Err(OffchainLookupError::MiddlewareError(err)),
+            };
+
+            if let Some(to) = tx.to_addr() {
+                if *to != lookup.sender {
+                    return Err(OffchainLookupError::SenderMismatch {
+                        to: *to,
+                        sender: lookup.sender,
+                    })
+                }
+            }
+
+            let response = self.fetch(&lookup).await?;
+            tx.set_data(lookup.callback_data(&response));
+        }
+
+        Err(OffchainLookupError::TooManyRedirects(self.max_redirects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup() -> OffchainLookup {
+        OffchainLookup {
+            sender: "0x00000000000000000000000000000000000000000001".parse().unwrap(),
+            urls: vec!["https://gateway.example/{sender}/{data}.json".to_string()],
+            call_data: vec![1u8, 2, 3].into(),
+            callback_function: [0xde, 0xad, 0xbe, 0xef],
+            extra_data: vec![4u8, 5].into(),
+        }
+    }
+
+    #[test]
+    fn decodes_offchain_lookup() {
+        let lookup = lookup();
+        let args = abi::encode(&[
+            Token::Address(lookup.sender),
+            Token::Array(lookup.urls.iter().cloned().map(Token::String).collect()),
+            Token::Bytes(lookup.call_data.to_vec()),
+            Token::FixedBytes(lookup.callback_function.to_vec()),
+            Token::Bytes(lookup.extra_data.to_vec()),
+        ]);
+        let revert_data = [&OffchainLookup::selector()[..], &args].concat();
+
+        assert_eq!(OffchainLookup::decode(&revert_data), Some(lookup));
+    }
+
+    #[test]
+    fn ignores_other_reverts() {
+        // `Error(string)`
+        let revert_data =
+            [&id("Error(string)")[..], &abi::encode(&[Token::String("x".into())])].concat();
+        assert!(OffchainLookup::decode(&revert_data).is_none());
+        assert!(OffchainLookup::decode(&[]).is_none());
+    }
+
+    #[test]
+    fn encodes_callback_data() {
+        let lookup = lookup();
+        let response: Bytes = vec![9u8; 3].into();
+        let data = lookup.callback_data(&response);
+
+        assert_eq!(data[..4], lookup.callback_function);
+        let tokens = abi::decode(&[ParamType::Bytes, ParamType::Bytes], &data[4..]).unwrap();
+        assert_eq!(tokens, vec![Token::Bytes(response.to_vec()), Token::Bytes(vec![4, 5])]);
+    }
+}
@@ -181,11 +181,12 @@ where
             .await
             .map_err(MiddlewareError::from_err)?;
 
-        let TypedTransaction::Legacy(tx) = tx;
-
-        // insert the tx in the pending txs
-        let mut lock = self.txs.lock().await;
-        lock.push((*pending_tx, tx, Instant::now(), block));
+        // insert the tx in the pending txs, only legacy transactions are re-sent with a higher
+        // energy price
+        if let TypedTransaction::Legacy(tx) = tx {
+            let mut lock = self.txs.lock().await;
+            lock.push((*pending_tx, tx, Instant::now(), block));
+        }
 
         Ok(pending_tx)
     }
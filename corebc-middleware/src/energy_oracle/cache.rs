@@ -3,33 +3,156 @@ use async_trait::async_trait;
 use corebc_core::types::U256;
 use futures_locks::RwLock;
 use instant::{Duration, Instant};
-use std::{fmt::Debug, future::Future};
+use std::{
+    fmt::Debug,
+    future::Future,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
 
+/// Caches the price returned by the inner oracle.
+///
+/// A cached price is served as-is while it is younger than the `validity` (soft TTL). With
+/// [`Cache::stale_while_revalidate`], a price older than the soft TTL but younger than the hard TTL
+/// is refreshed by a single caller, while concurrent callers are served the stale price
+/// immediately. Once the hard TTL has passed, all callers wait for the inner oracle.
 #[derive(Debug)]
 pub struct Cache<T: EnergyOracle> {
     inner: T,
     validity: Duration,
+    max_staleness: Duration,
     fee: Cached<U256>,
+    metrics: Counters,
+}
+
+/// Counters describing how a [`Cache`] served its requests, useful to tune its TTLs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// Requests served from a fresh cached price
+    pub hits: u64,
+    /// Requests served from a stale cached price, while another request refreshed it or after the
+    /// refresh failed
+    pub stale_hits: u64,
+    /// Requests that had to wait for the inner oracle
+    pub misses: u64,
+    /// Refreshes of a stale price that failed
+    pub refresh_errors: u64,
 }
 
 #[derive(Default, Debug)]
-struct Cached<T: Clone>(RwLock<Option<(Instant, T)>>);
+struct Counters {
+    hits: AtomicU64,
+    stale_hits: AtomicU64,
+    misses: AtomicU64,
+    refresh_errors: AtomicU64,
+}
+
+#[derive(Default, Debug)]
+struct Cached<T: Clone> {
+    value: RwLock<Option<(Instant, T)>>,
+    refreshing: AtomicBool,
+}
+
+/// Clears the `refreshing` flag of a [`Cached`] value once the refresh is done or cancelled
+struct Refreshing<'a>(&'a AtomicBool);
+
+impl Drop for Refreshing<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl<T: EnergyOracle> EnergyOracle for Cache<T> {
     async fn fetch(&self) -> Result<U256> {
-        self.fee.get(self.validity, || self.inner.fetch()).await
+        if let Some((last_fetch, value)) = self.fee.peek().await {
+            let age = Instant::now().duration_since(last_fetch);
+            if age < self.validity {
+                self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(value)
+            }
+            if age < self.max_staleness {
+                return Ok(self.revalidate(value).await)
+            }
+        }
+
+        self.fee
+            .get(self.validity, || {
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                self.inner.fetch()
+            })
+            .await
     }
 }
 
 impl<T: EnergyOracle> Cache<T> {
     pub fn new(validity: Duration, inner: T) -> Self {
-        Self { inner, validity, fee: Cached::default() }
+        Self {
+            inner,
+            validity,
+            max_staleness: validity,
+            fee: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Keeps serving a cached price for up to `max_staleness` after it was fetched. Once the price
+    /// is older than the validity, the first caller refreshes it while the others are served the
+    /// stale price.
+    ///
+    /// Has no effect if `max_staleness` is not greater than the validity.
+    #[must_use]
+    pub fn stale_while_revalidate(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Returns the hit/miss counters accumulated so far.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.metrics.hits.load(Ordering::Relaxed),
+            stale_hits: self.metrics.stale_hits.load(Ordering::Relaxed),
+            misses: self.metrics.misses.load(Ordering::Relaxed),
+            refresh_errors: self.metrics.refresh_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Refreshes the `stale` price, unless another caller is already refreshing it.
+    ///
+    /// Returns the stale price if another caller is refreshing it or if the refresh fails.
+    async fn revalidate(&self, stale: U256) -> U256 {
+        if self.fee.refreshing.swap(true, Ordering::SeqCst) {
+            self.metrics.stale_hits.fetch_add(1, Ordering::Relaxed);
+            return stale
+        }
+        let _refreshing = Refreshing(&self.fee.refreshing);
+
+        self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+        match self.inner.fetch().await {
+            Ok(value) => {
+                self.fee.set(value).await;
+                value
+            }
+            Err(err) => {
+                self.metrics.refresh_errors.fetch_add(1, Ordering::Relaxed);
+                self.metrics.stale_hits.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!("Failed to refresh cached energy price: {}", err);
+                stale
+            }
+        }
     }
 }
 
 impl<T: Clone> Cached<T> {
+    /// Returns the cached value and the time it was fetched, regardless of its age.
+    async fn peek(&self) -> Option<(Instant, T)> {
+        self.value.read().await.as_ref().cloned()
+    }
+
+    async fn set(&self, value: T) {
+        *self.value.write().await = Some((Instant::now(), value));
+    }
+
     async fn get<F, E, Fut>(&self, validity: Duration, fetch: F) -> Result<T, E>
     where
         F: FnOnce() -> Fut,
@@ -37,7 +160,7 @@ impl<T: Clone> Cached<T> {
     {
         // Try with a read lock
         {
-            let lock = self.0.read().await;
+            let lock = self.value.read().await;
             if let Some((last_fetch, value)) = lock.as_ref() {
                 if Instant::now().duration_since(*last_fetch) < validity {
                     return Ok(value.clone())
@@ -46,7 +169,7 @@ impl<T: Clone> Cached<T> {
         }
         // Acquire a write lock
         {
-            let mut lock = self.0.write().await;
+            let mut lock = self.value.write().await;
             // Check again, a concurrent thread may have raced us to the write.
             if let Some((last_fetch, value)) = lock.as_ref() {
                 if Instant::now().duration_since(*last_fetch) < validity {
@@ -60,3 +183,91 @@ impl<T: Clone> Cached<T> {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::energy_oracle::EnergyOracleError;
+
+    /// Returns 0, 1, 2, ... and fails from the `fail_from`th call on
+    #[derive(Debug, Default)]
+    struct Counter {
+        calls: AtomicU64,
+        fail_from: Option<u64>,
+        gate: RwLock<()>,
+    }
+
+    #[async_trait]
+    impl EnergyOracle for Counter {
+        async fn fetch(&self) -> Result<U256> {
+            let _gate = self.gate.read().await;
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.fail_from {
+                Some(fail_from) if call >= fail_from => Err(EnergyOracleError::NoValues),
+                _ => Ok(call.into()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_fresh_values_from_cache() {
+        let cache = Cache::new(Duration::from_secs(60), Counter::default());
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+        assert_eq!(cache.metrics(), CacheMetrics { hits: 1, misses: 1, ..Default::default() });
+    }
+
+    #[tokio::test]
+    async fn serves_stale_values_while_revalidating() {
+        // every cached value is stale right away, but may be served for a minute
+        let cache = Cache::new(Duration::ZERO, Counter::default())
+            .stale_while_revalidate(Duration::from_secs(60));
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+
+        // the first caller refreshes the value, the second one is served the stale value
+        // while the refresh is blocked
+        let gate = cache.inner.gate.write().await;
+        let (refreshed, stale) = futures_util::join!(cache.fetch(), async {
+            let stale = cache.fetch().await;
+            drop(gate);
+            stale
+        });
+        assert_eq!(refreshed.unwrap(), U256::from(1));
+        assert_eq!(stale.unwrap(), U256::from(0));
+        assert_eq!(
+            cache.metrics(),
+            CacheMetrics { stale_hits: 1, misses: 2, ..Default::default() }
+        );
+
+        // the next caller refreshes the value again
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(2));
+    }
+
+    #[tokio::test]
+    async fn serves_stale_values_if_revalidation_fails() {
+        let inner = Counter { fail_from: Some(1), ..Default::default() };
+        let cache =
+            Cache::new(Duration::ZERO, inner).stale_while_revalidate(Duration::from_secs(60));
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+
+        let metrics = cache.metrics();
+        assert_eq!((metrics.stale_hits, metrics.refresh_errors), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn blocks_past_hard_ttl() {
+        let cache = Cache::new(Duration::ZERO, Counter::default());
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(0));
+        assert_eq!(cache.fetch().await.unwrap(), U256::from(1));
+        assert_eq!(cache.metrics().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn propagates_errors() {
+        let inner = Counter { fail_from: Some(0), ..Default::default() };
+        let cache = Cache::new(Duration::from_secs(1), inner);
+        assert!(cache.fetch().await.is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use super::{median::weighted_fractile_by_key, EnergyOracle, EnergyOracleError, Result};
+use async_trait::async_trait;
+use corebc_core::types::{U256, U64};
+use corebc_providers::Middleware;
+use futures_util::future::try_join_all;
+use std::{collections::VecDeque, fmt::Debug, sync::Mutex};
+
+/// Precision used when converting the `f32` alpha to an integer for `U256` arithmetic.
+const PRECISION: f32 = 1_000_000.0;
+
+/// Smooths the energy prices paid in recent blocks with an exponentially weighted moving average.
+///
+/// Every block contributes the median energy price of its transactions, blocks without
+/// transactions are skipped. Each fetch only requests the blocks mined since the previous fetch,
+/// at most the last `blocks` blocks. With a [percentile floor](Self::percentile_floor) the
+/// estimate never drops below that percentile of the prices paid in the last `blocks` blocks.
+///
+/// Falls back to the provider's energy price until a block with transactions was sampled.
+#[derive(Debug)]
+#[must_use]
+pub struct Ewma<M: Middleware> {
+    provider: M,
+    alpha: f32,
+    blocks: u64,
+    floor: Option<f32>,
+    state: Mutex<EwmaState>,
+}
+
+#[derive(Debug, Default)]
+struct EwmaState {
+    /// The last sampled block
+    last_block: Option<U64>,
+    /// The smoothed price
+    average: Option<U256>,
+    /// The prices paid in the sampled blocks that are still in the window
+    window: VecDeque<(U64, Vec<(f32, U256)>)>,
+}
+
+impl<M: Middleware> Ewma<M> {
+    /// Smooths the prices of the last 20 blocks with an alpha of `0.3`
+    pub fn new(provider: M) -> Self {
+        Self { provider, alpha: 0.3, blocks: 20, floor: None, state: Default::default() }
+    }
+
+    /// Sets the weight of the newest block, higher values follow price changes faster.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in the range `0.0..=1.0` or is zero.
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0);
+        self.alpha = alpha;
+        self
+    }
+
+    /// Sets the number of recent blocks that are sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is `0`.
+    pub fn blocks(mut self, blocks: u64) -> Self {
+        assert!(blocks > 0);
+        self.blocks = blocks;
+        self
+    }
+
+    /// Never returns less than the `percentile` of the prices paid in the sampled blocks, e.g.
+    /// `0.25` for the price at least a quarter of the recent transactions paid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not in the range `0.0..=1.0`.
+    pub fn percentile_floor(mut self, percentile: f32) -> Self {
+        assert!((0.0..=1.0).contains(&percentile));
+        self.floor = Some(percentile);
+        self
+    }
+
+    /// Returns the smoothed price of the sampled blocks, if any block had transactions
+    pub fn average(&self) -> Option<U256> {
+        self.state.lock().unwrap().average
+    }
+
+    /// Samples the `blocks`, ordered by number, and returns the estimate
+    fn sample(&self, latest: U64, blocks: Vec<(U64, Vec<U256>)>) -> Option<U256> {
+        let mut state = self.state.lock().unwrap();
+        for (number, prices) in blocks {
+            // skip blocks a concurrent fetch already sampled
+            if state.last_block.map_or(false, |last| number <= last) {
+                continue
+            }
+            state.last_block = Some(number);
+
+            let mut prices: Vec<_> = prices.into_iter().map(|price| (1.0, price)).collect();
+            let median = match weighted_fractile_by_key(0.5, &mut prices, |price| price) {
+                Some(median) => *median,
+                None => continue,
+            };
+            state.average = Some(match state.average {
+                Some(average) => ewma(self.alpha, average, median),
+                None => median,
+            });
+            state.window.push_back((number, prices));
+        }
+        while state.window.front().map_or(false, |(number, _)| *number + self.blocks <= latest) {
+            state.window.pop_front();
+        }
+
+        let average = state.average?;
+        let floor = self.floor.and_then(|percentile| {
+            let mut prices: Vec<_> =
+                state.window.iter().flat_map(|(_, prices)| prices.iter().copied()).collect();
+            weighted_fractile_by_key(percentile, &mut prices, |price| price).copied()
+        });
+        Some(floor.map_or(average, |floor| average.max(floor)))
+    }
+}
+
+/// Weighs the new `sample` with `alpha` and the previous `average` with `1 - alpha`
+fn ewma(alpha: f32, average: U256, sample: U256) -> U256 {
+    let precision = U256::from(PRECISION as u64);
+    let alpha = U256::from((alpha * PRECISION) as u64);
+    (sample * alpha + average * (precision - alpha)) / precision
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Middleware> EnergyOracle for Ewma<M>
+where
+    M::Error: 'static,
+{
+    async fn fetch(&self) -> Result<U256> {
+        let latest = self.provider.get_block_number().await.map_err(provider_error)?;
+        let oldest = latest.saturating_sub((self.blocks - 1).into());
+        let first = match self.state.lock().unwrap().last_block {
+            Some(last) => oldest.max(last + 1),
+            None => oldest,
+        };
+
+        let blocks = try_join_all(
+            (first.as_u64()..=latest.as_u64()).map(|n| self.provider.get_block_with_txs(n)),
+        )
+        .await
+        .map_err(provider_error)?;
+        let blocks = blocks
+            .into_iter()
+            .flatten()
+            .filter_map(|block| {
+                let prices = block.transactions.iter().map(|tx| tx.energy_price).collect();
+                Some((block.number?, prices))
+            })
+            .collect();
+
+        match self.sample(latest, blocks) {
+            Some(price) => Ok(price),
+            None => self.provider.get_energy_price().await.map_err(provider_error),
+        }
+    }
+}
+
+fn provider_error<E: std::error::Error + Send + Sync + 'static>(err: E) -> EnergyOracleError {
+    EnergyOracleError::ProviderError(Box::new(err))
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::types::{Block, Transaction};
+    use corebc_providers::{MockProvider, Provider};
+
+    fn block(number: u64, prices: &[u64]) -> Block<Transaction> {
+        let transactions = prices
+            .iter()
+            .map(|price| Transaction { energy_price: (*price).into(), ..Default::default() })
+            .collect();
+        Block { number: Some(number.into()), transactions, ..Default::default() }
+    }
+
+    fn expect_block(mock: &MockProvider, block: Block<Transaction>) {
+        let number = serde_json::to_value(block.number.unwrap()).unwrap();
+        mock.expect("xcb_getBlockByNumber")
+            .with_params(serde_json::json!([number, true]))
+            .times(1)
+            .returns(block);
+    }
+
+    #[test]
+    fn weighs_samples() {
+        assert_eq!(ewma(0.5, 100.into(), 200.into()), U256::from(150));
+        assert_eq!(ewma(0.25, 100.into(), 200.into()), U256::from(125));
+        assert_eq!(ewma(1.0, 100.into(), 200.into()), U256::from(200));
+    }
+
+    #[tokio::test]
+    async fn smooths_block_prices() {
+        let (provider, mock) = Provider::mocked();
+        let oracle = Ewma::new(provider).alpha(0.5).blocks(2);
+
+        // no transactions yet, falls back to the provider
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(1));
+        expect_block(&mock, block(0, &[]));
+        expect_block(&mock, block(1, &[]));
+        mock.expect("xcb_energyPrice").times(1).returns(U256::from(7));
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(7));
+        assert_eq!(oracle.average(), None);
+
+        // only the new blocks are requested
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(3));
+        expect_block(&mock, block(2, &[100, 10, 120]));
+        expect_block(&mock, block(3, &[300, 200]));
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(150));
+
+        // a spike moves the estimate halfway
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(4));
+        expect_block(&mock, block(4, &[1_000]));
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(575));
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn applies_percentile_floor() {
+        let (provider, mock) = Provider::mocked();
+        let oracle = Ewma::new(provider).alpha(0.1).blocks(2).percentile_floor(0.5);
+
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(1));
+        expect_block(&mock, block(0, &[100]));
+        expect_block(&mock, block(1, &[1_000]));
+        // the average is 190, the median of the window is 100
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(190));
+
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(2));
+        expect_block(&mock, block(2, &[1_000]));
+        // the first block left the window
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(1_000));
+        assert_eq!(oracle.average(), Some(U256::from(271)));
+        mock.verify().unwrap();
+    }
+}
@@ -5,9 +5,30 @@ use futures_util::future::join_all;
 use std::{fmt::Debug, future::Future};
 use tracing::warn;
 
+/// Precision used when converting `f32` weights and ratios to integers for `U256` arithmetic.
+const PRECISION: f32 = 1_000_000.0;
+
+/// How the values returned by the oracles are combined into a single price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Aggregation {
+    /// The weighted quantile of the values, `0.5` being the median.
+    Quantile(f32),
+    /// The weighted mean of the values, after discarding the given fraction of the total weight
+    /// at both the low and the high end.
+    TrimmedMean(f32),
+}
+
+impl Default for Aggregation {
+    fn default() -> Self {
+        Aggregation::Quantile(0.5)
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct Median {
     oracles: Vec<(f32, Box<dyn EnergyOracle>)>,
+    aggregation: Aggregation,
+    max_deviation: Option<f32>,
 }
 
 /// Computes the median gas price from a selection of oracles.
@@ -28,6 +49,44 @@ impl Median {
         self.oracles.push((weight, Box::new(oracle)));
     }
 
+    /// Returns the weighted `quantile` of the prices instead of the median.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quantile` is not in the range `0.0..=1.0`.
+    #[must_use]
+    pub fn quantile(mut self, quantile: f32) -> Self {
+        assert!((0.0..=1.0).contains(&quantile));
+        self.aggregation = Aggregation::Quantile(quantile);
+        self
+    }
+
+    /// Returns the weighted mean of the prices, ignoring the `trim` fraction of the total weight at
+    /// each end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trim` is not in the range `0.0..0.5`.
+    #[must_use]
+    pub fn trimmed_mean(mut self, trim: f32) -> Self {
+        assert!((0.0..0.5).contains(&trim));
+        self.aggregation = Aggregation::TrimmedMean(trim);
+        self
+    }
+
+    /// Discards prices that deviate from the weighted median by more than `max_deviation` (as a
+    /// fraction of the median, e.g. `0.2` for 20%) before aggregating them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_deviation` is negative.
+    #[must_use]
+    pub fn reject_outliers(mut self, max_deviation: f32) -> Self {
+        assert!(max_deviation >= 0.0);
+        self.max_deviation = Some(max_deviation);
+        self
+    }
+
     pub async fn query_all<'a, Fn, Fut, O>(&'a self, mut f: Fn) -> Result<Vec<(f32, O)>>
     where
         Fn: FnMut(&'a dyn EnergyOracle) -> Fut,
@@ -61,9 +120,79 @@ impl Median {
 impl EnergyOracle for Median {
     async fn fetch(&self) -> Result<U256> {
         let mut values = self.query_all(|oracle| oracle.fetch()).await?;
-        // `query_all` guarantees `values` is not empty
-        Ok(*weighted_fractile_by_key(0.5, &mut values, |fee| fee).unwrap())
+        if let Some(max_deviation) = self.max_deviation {
+            values = reject_outliers(max_deviation, values);
+        }
+        // `query_all` guarantees `values` is not empty, and `reject_outliers` always keeps the
+        // median
+        Ok(match self.aggregation {
+            Aggregation::Quantile(quantile) => {
+                *weighted_fractile_by_key(quantile, &mut values, |fee| fee).unwrap()
+            }
+            Aggregation::TrimmedMean(trim) => weighted_trimmed_mean(trim, &mut values).unwrap(),
+        })
+    }
+}
+
+/// Removes the values which deviate from the weighted median by more than `max_deviation` times
+/// the median.
+fn reject_outliers(max_deviation: f32, mut values: Vec<(f32, U256)>) -> Vec<(f32, U256)> {
+    let median = match weighted_fractile_by_key(0.5, &mut values, |fee| fee) {
+        Some(median) => *median,
+        None => return values,
+    };
+    let max_deviation = median * U256::from((max_deviation * PRECISION) as u64);
+
+    values
+        .into_iter()
+        .filter(|(_, value)| {
+            let deviation = if *value > median { *value - median } else { median - *value };
+            if deviation * U256::from(PRECISION as u64) > max_deviation {
+                warn!("Rejecting outlier gas price {} (median {})", value, median);
+                false
+            } else {
+                true
+            }
+        })
+        .collect()
+}
+
+/// Weighted trimmed mean.
+///
+/// Sorts the values in place and returns the weighted mean of the values that remain after
+/// discarding `trim` fraction of the total weight at both ends. Falls back to the weighted median
+/// if nothing remains.
+///
+/// Returns `None` if the values are empty.
+fn weighted_trimmed_mean(trim: f32, values: &mut [(f32, U256)]) -> Option<U256> {
+    if values.is_empty() {
+        return None
+    }
+    values.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+
+    let total_weight = values.iter().map(|(weight, _)| *weight).sum::<f32>();
+    let (lower, upper) = (trim * total_weight, (1.0 - trim) * total_weight);
+
+    let mut sum = U256::zero();
+    let mut kept_weight = U256::zero();
+    let mut cumulative_weight = 0.0_f32;
+    for (weight, value) in values.iter() {
+        let (start, end) = (cumulative_weight, cumulative_weight + *weight);
+        cumulative_weight = end;
+
+        // the part of this value's weight that falls inside the kept range
+        let kept = end.min(upper) - start.max(lower);
+        if kept > 0.0 {
+            let kept = U256::from((kept * PRECISION) as u64);
+            sum += *value * kept;
+            kept_weight += kept;
+        }
+    }
+
+    if kept_weight.is_zero() {
+        return weighted_fractile_by_key(0.5, values, |fee| fee).copied()
     }
+    Some(sum / kept_weight)
 }
 
 /// Weighted fractile by key.
@@ -80,7 +209,7 @@ impl EnergyOracle for Median {
 /// # Panics
 ///
 /// Panics if `fractile` is not in the range `0.0..=1.0`.
-fn weighted_fractile_by_key<'a, T, F, K>(
+pub(super) fn weighted_fractile_by_key<'a, T, F, K>(
     fractile: f32,
     values: &'a mut [(f32, T)],
     mut key: F,
@@ -110,3 +239,42 @@ where
     // `values` is not empty.
     Some(&values.last().unwrap().1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(prices: &[u64]) -> Vec<(f32, U256)> {
+        prices.iter().map(|price| (1.0, U256::from(*price))).collect()
+    }
+
+    #[test]
+    fn fractiles() {
+        let mut prices = values(&[40, 10, 30, 20, 50]);
+        assert_eq!(weighted_fractile_by_key(0.0, &mut prices, |fee| fee), Some(&10.into()));
+        assert_eq!(weighted_fractile_by_key(0.5, &mut prices, |fee| fee), Some(&30.into()));
+        assert_eq!(weighted_fractile_by_key(0.8, &mut prices, |fee| fee), Some(&40.into()));
+        assert_eq!(weighted_fractile_by_key(1.0, &mut prices, |fee| fee), Some(&50.into()));
+
+        let mut weighted = vec![(1.0, U256::from(10)), (3.0, U256::from(100))];
+        assert_eq!(weighted_fractile_by_key(0.5, &mut weighted, |fee| fee), Some(&100.into()));
+    }
+
+    #[test]
+    fn trimmed_mean() {
+        let mut prices = values(&[1000, 10, 20, 30, 0]);
+        // without trimming, this is the plain mean
+        assert_eq!(weighted_trimmed_mean(0.0, &mut prices), Some(212.into()));
+        // dropping the lowest and the highest value
+        assert_eq!(weighted_trimmed_mean(0.2, &mut prices), Some(20.into()));
+        assert_eq!(weighted_trimmed_mean(0.2, &mut []), None);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let prices = reject_outliers(0.2, values(&[100, 90, 115, 300, 10]));
+        let mut prices = prices.into_iter().map(|(_, price)| price.as_u64()).collect::<Vec<_>>();
+        prices.sort();
+        assert_eq!(prices, vec![90, 100, 115]);
+    }
+}
@@ -67,13 +67,9 @@ where
         tx: &mut TypedTransaction,
         block: Option<BlockId>,
     ) -> Result<(), Self::Error> {
-        match tx {
-            TypedTransaction::Legacy(ref mut tx) => {
-                if tx.energy_price.is_none() {
-                    tx.energy_price = Some(self.get_energy_price().await?);
-                }
-            }
-        };
+        if tx.energy_price().is_none() {
+            tx.set_energy_price(self.get_energy_price().await?);
+        }
 
         self.inner().fill_transaction(tx, block).await.map_err(METrait::from_err)
     }
@@ -5,14 +5,17 @@ pub mod middleware;
 pub use middleware::{EnergyOracleMiddleware, MiddlewareError};
 
 pub mod median;
-pub use median::Median;
+pub use median::{Aggregation, Median};
 
 pub mod cache;
-pub use cache::Cache;
+pub use cache::{Cache, CacheMetrics};
 
 pub mod provider_oracle;
 pub use provider_oracle::ProviderOracle;
 
+pub mod ewma;
+pub use ewma::Ewma;
+
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use corebc_core::types::U256;
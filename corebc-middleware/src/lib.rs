@@ -32,6 +32,11 @@ pub use signer::SignerMiddleware;
 pub mod policy;
 pub use policy::PolicyMiddleware;
 
+// The [Preconditions](crate::PreconditionsMiddleware) middleware checks balance, nonce, network
+// and recipient of transactions, reporting every failed check before anything is broadcast.
+pub mod preconditions;
+pub use preconditions::PreconditionsMiddleware;
+
 // The [TimeLag](crate::TimeLag) provides safety against reorgs by querying state N blocks
 // before the network tip
 pub mod timelag;
@@ -40,7 +45,23 @@ pub use timelag::TimeLag;
 // The [MiddlewareBuilder](crate::MiddlewareBuilder) provides a way to compose many
 // [`Middleware`](corebc_providers::Middleware) in a concise way
 pub mod builder;
-pub use builder::MiddlewareBuilder;
+pub use builder::{MiddlewareBuilder, MiddlewareStack};
+
+// The [OffchainLookup](crate::OffchainLookupMiddleware) middleware resolves CCIP-read
+// `OffchainLookup` reverts by querying the gateways requested by the contract
+pub mod ccip;
+pub use ccip::OffchainLookupMiddleware;
+
+pub mod broadcast;
+pub use broadcast::BroadcastMiddleware;
+
+pub mod audit;
+pub use audit::AuditMiddleware;
+
+// The [NetworkGuard](crate::NetworkGuardMiddleware) refuses to send transactions signed for another
+// network than the one of the provider
+pub mod network_guard;
+pub use network_guard::NetworkGuardMiddleware;
 
 // For macro expansions only, not public API.
 // See: [#2235](https://github.com/gakonst/ethers-rs/pull/2235)
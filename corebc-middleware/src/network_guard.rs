@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use corebc_core::{
+    types::{transaction::eip2718::TypedTransaction, BlockId, Bytes, U64},
+    utils::rlp,
+};
+use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+use thiserror::Error;
+
+/// Middleware that refuses to send transactions signed for another network than the one of the
+/// provider.
+///
+/// The network id of the provider is fetched once when the middleware is created. Transactions
+/// and raw transactions whose network id differs fail with [`NetworkGuardError::WrongNetwork`]
+/// before they reach the inner middleware, e.g. a mainnet transaction sent to a devin endpoint.
+/// Transactions without a network id are forwarded as they are.
+///
+/// Place it below the [`SignerMiddleware`](crate::SignerMiddleware), which sends the signed
+/// transactions as raw transactions.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Middleware, Provider, Http};
+/// use corebc_middleware::{NetworkGuardMiddleware, SignerMiddleware};
+/// use corebc_signers::LocalWallet;
+/// use std::convert::TryFrom;
+///
+/// # async fn foo(wallet: LocalWallet) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let provider = NetworkGuardMiddleware::new(provider).await?;
+/// let client = SignerMiddleware::new(provider, wallet);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NetworkGuardMiddleware<M> {
+    inner: M,
+    network_id: U64,
+}
+
+impl<M> NetworkGuardMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Creates a new client that only sends transactions for the network of `inner`
+    pub async fn new(inner: M) -> Result<Self, NetworkGuardError<M>> {
+        let network_id = inner.get_networkid().await.map_err(NetworkGuardError::MiddlewareError)?;
+        Ok(Self::with_network_id(inner, network_id.low_u64()))
+    }
+
+    /// Creates a new client that only sends transactions for `network_id`, without asking the
+    /// provider
+    pub fn with_network_id(inner: M, network_id: impl Into<U64>) -> Self {
+        Self { inner, network_id: network_id.into() }
+    }
+
+    /// Returns the network id transactions must be signed for
+    pub fn network_id(&self) -> U64 {
+        self.network_id
+    }
+
+    fn ensure_network(&self, network_id: Option<U64>) -> Result<(), NetworkGuardError<M>> {
+        match network_id {
+            Some(got) if got != self.network_id => {
+                Err(NetworkGuardError::WrongNetwork { expected: self.network_id, got })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when the network guard middleware refuses to send a transaction
+pub enum NetworkGuardError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when the transaction is signed for another network than the one of the provider
+    #[error("transaction is for network {got}, but the provider is on network {expected}")]
+    WrongNetwork {
+        /// The network id of the provider
+        expected: U64,
+        /// The network id of the transaction
+        got: U64,
+    },
+}
+
+impl<M: Middleware> MiddlewareError for NetworkGuardError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        NetworkGuardError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            NetworkGuardError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for NetworkGuardMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = NetworkGuardError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Checks the network id of the transaction before sending it via the inner middleware
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let tx = tx.into();
+        self.ensure_network(tx.network_id())?;
+        self.inner.send_transaction(tx, block).await.map_err(NetworkGuardError::MiddlewareError)
+    }
+
+    /// Checks the network id the transaction is signed for before sending it via the inner
+    /// middleware
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        // malformed transactions are left to the node to reject
+        if let Ok(decoded) = rlp::decode::<TypedTransaction>(&tx) {
+            self.ensure_network(decoded.network_id())?;
+        }
+        self.inner.send_raw_transaction(tx).await.map_err(NetworkGuardError::MiddlewareError)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use corebc_core::types::{Address, Network, TransactionRequest, H256, U256};
+    use corebc_providers::Provider;
+    use corebc_signers::{LocalWallet, Signer};
+
+    #[tokio::test]
+    async fn refuses_transactions_for_other_networks() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(3)).unwrap();
+        let client = NetworkGuardMiddleware::new(provider).await.unwrap();
+        assert_eq!(client.network_id(), U64::from(3));
+
+        let wallet = LocalWallet::new(&mut corebc_core::rand::thread_rng(), Network::Mainnet);
+        let tx = TransactionRequest::pay(Address::repeat_byte(1), 1)
+            .from(wallet.address())
+            .nonce(0)
+            .energy(21000)
+            .energy_price(1);
+
+        let err = client.send_transaction(tx.clone().network_id(1), None).await.unwrap_err();
+        match err {
+            NetworkGuardError::WrongNetwork { expected, got } => {
+                assert_eq!(expected, U64::from(3));
+                assert_eq!(got, U64::from(1));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+
+        let signed: TypedTransaction = tx.clone().network_id(1).into();
+        let signature = wallet.sign_transaction(&signed).await.unwrap();
+        let err = client.send_raw_transaction(signed.rlp_signed(&signature)).await.unwrap_err();
+        assert!(matches!(err, NetworkGuardError::WrongNetwork { .. }));
+
+        // transactions for the provider's network are forwarded
+        let hash = H256::repeat_byte(2);
+        mock.expect("xcb_sendTransaction").returns(hash);
+        let tx = tx.network_id(3);
+        assert_eq!(*client.send_transaction(tx, None).await.unwrap(), hash);
+        mock.verify().unwrap();
+    }
+}
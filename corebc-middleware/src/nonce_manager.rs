@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use corebc_core::types::{transaction::eip2718::TypedTransaction, *};
-use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+use corebc_providers::{JsonRpcError, Middleware, MiddlewareError, PendingTransaction};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use thiserror::Error;
 
@@ -84,12 +84,34 @@ where
     }
 }
 
+/// Messages of nodes rejecting a transaction because its nonce was used by another transaction
+const NONCE_CONFLICTS: [&str; 3] =
+    ["replacement transaction underpriced", "nonce too low", "nonce is too low"];
+
+/// Whether the node rejected a transaction because its nonce was used by another transaction
+fn is_nonce_conflict(err: &JsonRpcError) -> bool {
+    let message = err.message.to_lowercase();
+    NONCE_CONFLICTS.iter().any(|conflict| message.contains(conflict))
+}
+
 #[derive(Error, Debug)]
 /// Thrown when an error happens at the Nonce Manager
 pub enum NonceManagerError<M: Middleware> {
     /// Thrown when the internal middleware errors
     #[error("{0}")]
     MiddlewareError(M::Error),
+
+    /// Thrown when the nonce of a transaction was already used by another transaction, e.g. one
+    /// sent without the nonce manager. The manager continues with the `pending` nonce.
+    #[error("nonce {nonce} is used by another transaction, next nonce is {pending}: {error}")]
+    NonceConflict {
+        /// The nonce of the rejected transaction
+        nonce: U256,
+        /// The pending transaction count of the sender, which the manager continues with
+        pending: U256,
+        /// The error of the rejected transaction
+        error: M::Error,
+    },
 }
 
 impl<M: Middleware> MiddlewareError for NonceManagerError<M> {
@@ -102,6 +124,7 @@ impl<M: Middleware> MiddlewareError for NonceManagerError<M> {
     fn as_inner(&self) -> Option<&Self::Inner> {
         match self {
             NonceManagerError::MiddlewareError(e) => Some(e),
+            NonceManagerError::NonceConflict { error, .. } => Some(error),
         }
     }
 }
@@ -148,6 +171,18 @@ where
 
         match self.inner.send_transaction(tx.clone(), block).await {
             Ok(tx_hash) => Ok(tx_hash),
+            Err(err) if err.as_error_response().map_or(false, is_nonce_conflict) => {
+                // resubmitting would replace or collide with the other transaction, so the
+                // manager resyncs and leaves the decision to the caller
+                let pending = self
+                    .inner
+                    .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+                    .await
+                    .map_err(MiddlewareError::from_err)?;
+                self.nonce.store(pending.as_u64(), Ordering::SeqCst);
+                let nonce = tx.nonce().copied().unwrap_or_default();
+                Err(NonceManagerError::NonceConflict { nonce, pending, error: err })
+            }
             Err(err) => {
                 let nonce = self.get_transaction_count(self.address, block).await?;
                 if nonce != self.nonce.load(Ordering::SeqCst).into() {
@@ -155,7 +190,14 @@ where
                     // was a nonce mismatch
                     self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
                     tx.set_nonce(nonce);
-                    self.inner.send_transaction(tx, block).await.map_err(MiddlewareError::from_err)
+                    let pending = self
+                        .inner
+                        .send_transaction(tx, block)
+                        .await
+                        .map_err(MiddlewareError::from_err)?;
+                    // the resubmitted transaction used the nonce
+                    self.nonce.fetch_add(1, Ordering::SeqCst);
+                    Ok(pending)
                 } else {
                     // propagate the error otherwise
                     Err(MiddlewareError::from_err(err))
@@ -164,3 +206,58 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_providers::Provider;
+
+    #[tokio::test]
+    async fn surfaces_nonce_conflicts() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let client = NonceManagerMiddleware::new(provider, address);
+        let tx = TransactionRequest::new()
+            .from(address)
+            .to(Address::repeat_byte(2))
+            .energy(21000u64)
+            .energy_price(1u64)
+            .network_id(1u64);
+
+        mock.expect("xcb_getTransactionCount")
+            .with_params((address, "latest"))
+            .times(1)
+            .returns(U256::from(5));
+        mock.expect("xcb_getTransactionCount")
+            .with_params((address, "pending"))
+            .times(1)
+            .returns(U256::from(7));
+        mock.expect("xcb_sendTransaction").times(1).return_error(JsonRpcError {
+            code: -32000,
+            message: "replacement transaction underpriced".to_string(),
+            data: None,
+        });
+
+        let err = client.send_transaction(tx.clone(), None).await.unwrap_err();
+        match &err {
+            NonceManagerError::NonceConflict { nonce, pending, .. } => {
+                assert_eq!(*nonce, U256::from(5));
+                assert_eq!(*pending, U256::from(7));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        assert!(err.as_error_response().is_some());
+        mock.verify().unwrap();
+
+        // the next transaction continues with the pending nonce
+        let hash = H256::repeat_byte(3);
+        mock.expect("xcb_sendTransaction")
+            .with_params_matching(|params| params[0]["nonce"] == "0x7")
+            .times(1)
+            .returns(hash);
+        let pending = client.send_transaction(tx, None).await.unwrap();
+        assert_eq!(pending.tx_hash(), hash);
+        assert_eq!(client.next(), U256::from(8));
+        mock.verify().unwrap();
+    }
+}
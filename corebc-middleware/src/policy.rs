@@ -1,8 +1,12 @@
-use corebc_core::types::{transaction::eip2718::TypedTransaction, BlockId};
+use corebc_core::{
+    abi::Abi,
+    types::{transaction::eip2718::TypedTransaction, BlockId},
+};
 use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+use corebc_signers::TransactionSummary;
 
 use async_trait::async_trait;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use thiserror::Error;
 
 /// Basic trait to ensure that transactions about to be sent follow certain rules.
@@ -45,6 +49,61 @@ impl Policy for RejectEverything {
     }
 }
 
+/// A policy that asks a callback, e.g. a prompt to a human, to approve a [`TransactionSummary`]
+/// of every transaction.
+///
+/// Rejected transactions fail with their summary. The transaction is summarized as it is passed
+/// to the [`PolicyMiddleware`], so place the middleware filling in the energy and its price below
+/// it to include the costs in the summary.
+pub struct ApprovalPolicy<F> {
+    approve: F,
+    abi: Option<Abi>,
+}
+
+impl<F> ApprovalPolicy<F>
+where
+    F: Fn(&TransactionSummary) -> bool + Send + Sync,
+{
+    /// Creates a policy that sends the transactions `approve` returns `true` for
+    pub fn new(approve: F) -> Self {
+        Self { approve, abi: None }
+    }
+
+    /// Decodes the calls of the functions of `abi` in the summaries
+    #[must_use]
+    pub fn abi(mut self, abi: Abi) -> Self {
+        self.abi = Some(abi);
+        self
+    }
+}
+
+impl<F> Debug for ApprovalPolicy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApprovalPolicy").field("abi", &self.abi.is_some()).finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<F> Policy for ApprovalPolicy<F>
+where
+    F: Fn(&TransactionSummary) -> bool + Send + Sync,
+{
+    type Error = TransactionSummary;
+
+    async fn ensure_can_send(&self, tx: TypedTransaction) -> Result<TypedTransaction, Self::Error> {
+        let summary = match &self.abi {
+            Some(abi) => TransactionSummary::with_abi(&tx, abi),
+            None => TransactionSummary::new(&tx),
+        };
+        if (self.approve)(&summary) {
+            Ok(tx)
+        } else {
+            Err(summary)
+        }
+    }
+}
+
 /// Middleware used to enforce certain policies for transactions.
 #[derive(Clone, Debug)]
 pub struct PolicyMiddleware<M, P> {
@@ -0,0 +1,305 @@
+use async_trait::async_trait;
+use corebc_core::{
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, NameOrAddress,
+        Network, U256, U64,
+    },
+    utils::is_valid_ican,
+};
+use corebc_providers::{Middleware, MiddlewareError, PendingTransaction};
+use thiserror::Error;
+
+/// A precondition that an outgoing transaction does not meet.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum Violation {
+    /// The sender cannot pay for the value and the energy of the transaction
+    #[error("insufficient balance: {balance} available, {required} required")]
+    InsufficientBalance {
+        /// The balance of the sender
+        balance: U256,
+        /// `value + energy * energy_price`
+        required: U256,
+    },
+
+    /// The nonce has already been used by a mined or pending transaction
+    #[error("nonce too low: {nonce} is below the pending nonce {pending}")]
+    NonceTooLow {
+        /// The nonce of the transaction
+        nonce: U256,
+        /// The next nonce of the sender, including the pending pool
+        pending: U256,
+    },
+
+    /// The nonce leaves a gap, the transaction would be stuck until it is filled
+    #[error("nonce gap: {nonce} is above the pending nonce {pending}")]
+    NonceGap {
+        /// The nonce of the transaction
+        nonce: U256,
+        /// The next nonce of the sender, including the pending pool
+        pending: U256,
+    },
+
+    /// The transaction is signed for another network and could be replayed there
+    #[error("network id mismatch: transaction uses {tx}, the provider is on {provider}")]
+    NetworkIdMismatch {
+        /// The network id set on the transaction
+        tx: U64,
+        /// The network id reported by the provider
+        provider: U64,
+    },
+
+    /// The recipient is not a valid ICAN address for the provider's network
+    #[error("{address:?} is not a valid ICAN address on {network}")]
+    InvalidRecipient {
+        /// The recipient of the transaction
+        address: Address,
+        /// The network of the provider
+        network: Network,
+    },
+
+    /// The transaction carries data but the recipient is not a contract
+    #[error("{0:?} has no code but the transaction carries data")]
+    NoCode(Address),
+}
+
+/// Middleware that validates outgoing transactions before broadcasting them.
+///
+/// Transactions are filled by the inner middleware and then checked for:
+/// - a sender balance covering `value + energy * energy_price`
+/// - a nonce matching the sender's pending nonce
+/// - a network id matching the provider's network
+/// - a recipient that is a valid ICAN address on the provider's network
+/// - a recipient that has code when the transaction carries data
+///
+/// If any check fails, the transaction is not sent and all the [`Violation`]s are returned in a
+/// [`PreconditionsError::Violations`]. Use [`PreconditionsMiddleware::check`] to get the
+/// diagnostics without sending anything.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Middleware, Provider, Http};
+/// use corebc_middleware::preconditions::{PreconditionsMiddleware, PreconditionsError};
+/// use corebc_core::types::{TransactionRequest, Address};
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let provider = PreconditionsMiddleware::new(provider);
+///
+/// let tx = TransactionRequest::new().from(Address::random()).to(Address::zero()).value(100);
+/// match provider.send_transaction(tx, None).await {
+///     Err(PreconditionsError::Violations(violations)) => {
+///         for violation in violations {
+///             println!("{violation}");
+///         }
+///     }
+///     res => {
+///         res?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PreconditionsMiddleware<M> {
+    inner: M,
+}
+
+impl<M> PreconditionsMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Creates a new client that validates transactions before sending them.
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+
+    /// Returns all the preconditions the filled transaction `tx` does not meet.
+    ///
+    /// Checks that need a missing field (e.g. the sender or the nonce) are skipped.
+    pub async fn check(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Vec<Violation>, PreconditionsError<M>> {
+        let mut violations = Vec::new();
+
+        let network_id =
+            self.inner.get_networkid().await.map_err(PreconditionsError::MiddlewareError)?;
+        let network_id = U64::from(network_id.low_u64());
+        let network = Network::from(network_id.as_u64());
+        if let Some(tx_network_id) = tx.network_id() {
+            if tx_network_id != network_id {
+                violations
+                    .push(Violation::NetworkIdMismatch { tx: tx_network_id, provider: network_id });
+            }
+        }
+
+        if let Some(from) = tx.from().copied().or_else(|| self.inner.default_sender()) {
+            let balance = self
+                .inner
+                .get_balance(from, block)
+                .await
+                .map_err(PreconditionsError::MiddlewareError)?;
+            let value = tx.value().copied().unwrap_or_default();
+            let required = tx.max_cost().unwrap_or_default().saturating_add(value);
+            if balance < required {
+                violations.push(Violation::InsufficientBalance { balance, required });
+            }
+
+            if let Some(nonce) = tx.nonce().copied() {
+                let pending = self
+                    .inner
+                    .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+                    .await
+                    .map_err(PreconditionsError::MiddlewareError)?;
+                if nonce < pending {
+                    violations.push(Violation::NonceTooLow { nonce, pending });
+                } else if nonce > pending {
+                    violations.push(Violation::NonceGap { nonce, pending });
+                }
+            }
+        }
+
+        if let Some(NameOrAddress::Address(to)) = tx.to() {
+            if !is_valid_ican(to, &network) {
+                violations.push(Violation::InvalidRecipient { address: *to, network });
+            }
+
+            if tx.data().map_or(false, |data| !data.is_empty()) {
+                let code = self
+                    .inner
+                    .get_code(*to, block)
+                    .await
+                    .map_err(PreconditionsError::MiddlewareError)?;
+                if code.is_empty() {
+                    violations.push(Violation::NoCode(*to));
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when the preconditions middleware rejects a transaction
+pub enum PreconditionsError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when the transaction does not meet one or more preconditions
+    #[error("transaction rejected: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    Violations(Vec<Violation>),
+}
+
+impl<M: Middleware> MiddlewareError for PreconditionsError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        PreconditionsError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            PreconditionsError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for PreconditionsMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = PreconditionsError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Fills the transaction and only sends it if it meets all the preconditions.
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+        self.inner
+            .fill_transaction(&mut tx, block)
+            .await
+            .map_err(PreconditionsError::MiddlewareError)?;
+
+        let violations = self.check(&tx, block).await?;
+        if !violations.is_empty() {
+            return Err(PreconditionsError::Violations(violations))
+        }
+
+        self.inner.send_transaction(tx, block).await.map_err(MiddlewareError::from_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::types::{Bytes, TransactionRequest};
+    use corebc_providers::Provider;
+
+    #[tokio::test]
+    async fn reports_all_violations() {
+        let (provider, mock) = Provider::mocked();
+        let client = PreconditionsMiddleware::new(provider);
+
+        let to: Address = "cb3680e5927ec9af1efc619ee6d4198e97c91ab72a96".parse().unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(Address::zero())
+            .to(to)
+            .value(100)
+            .energy(21000)
+            .energy_price(1)
+            .nonce(3)
+            .network_id(3)
+            .data(vec![1u8])
+            .into();
+
+        // responses are popped from the back
+        mock.push(Bytes::default()).unwrap();
+        mock.push(U256::from(5)).unwrap();
+        mock.push(U256::from(10)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+
+        let violations = client.check(&tx, None).await.unwrap();
+        assert_eq!(
+            violations,
+            vec![
+                Violation::NetworkIdMismatch { tx: 3.into(), provider: 1.into() },
+                Violation::InsufficientBalance { balance: 10.into(), required: 21100.into() },
+                Violation::NonceTooLow { nonce: 3.into(), pending: 5.into() },
+                Violation::NoCode(to),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_recipient() {
+        let (provider, mock) = Provider::mocked();
+        let client = PreconditionsMiddleware::new(provider);
+
+        // a Devin address while the provider is on Mainnet
+        let to: Address = "ab3680e5927ec9af1efc619ee6d4198e97c91ab72a96".parse().unwrap();
+        let tx: TypedTransaction = TransactionRequest::new().to(to).into();
+        mock.push(U256::from(1)).unwrap();
+
+        let violations = client.check(&tx, None).await.unwrap();
+        assert_eq!(
+            violations,
+            vec![Violation::InvalidRecipient { address: to, network: Network::Mainnet }]
+        );
+    }
+}
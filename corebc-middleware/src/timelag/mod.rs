@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use corebc_core::types::{
-    transaction::eip2718::TypedTransaction, Block, BlockId, BlockNumber, Bytes, FilterBlockOption,
-    NameOrAddress, Transaction, TransactionReceipt, TxHash, U256,
+    transaction::eip2718::TypedTransaction, Block, BlockId, BlockNumber, Bytes,
+    EIP1186ProofResponse, FilterBlockOption, NameOrAddress, Transaction, TransactionReceipt,
+    TxHash, H256, U256, U64,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -20,7 +21,9 @@ where
     /// Thrown when an internal middleware errors
     MiddlewareError(M::Error),
 
-    #[error("Unsupported RPC. Timelag provider does not support filters or subscriptions.")]
+    #[error(
+        "Unsupported RPC. Timelag provider only supports log filters with a fixed end block, and no subscriptions."
+    )]
     Unsupported,
 }
 
@@ -40,6 +43,11 @@ impl<M: Middleware> MiddlewareError for TimeLagError<M> {
     }
 }
 /// TimeLag Provider
+///
+/// Serves every read as if the chain tip were `lag` blocks behind the actual tip, so that
+/// downstream logic only ever sees blocks that survived shallow reorgs. `Latest` (or an omitted
+/// block), `Pending` and any block number past the lagged tip are rewritten to the lagged tip,
+/// [`Middleware::get_block_number`] returns the lagged tip and log queries are clamped to it.
 #[derive(Debug)]
 pub struct TimeLag<M> {
     inner: Arc<M>,
@@ -54,6 +62,11 @@ where
     pub fn new(inner: M, lag: u8) -> Self {
         Self { inner: inner.into(), lag }
     }
+
+    /// Returns the number of blocks the reads lag behind the chain tip
+    pub fn lag(&self) -> u8 {
+        self.lag
+    }
 }
 
 impl<M> TimeLag<M>
@@ -74,31 +87,61 @@ where
         &self,
         number: Option<BlockNumber>,
     ) -> TimeLagResult<Option<BlockNumber>, M> {
-        let lag_tip = self.get_block_number().await?;
         match number {
-            Some(BlockNumber::Latest) => Ok(Some(BlockNumber::Number(lag_tip))),
-            Some(BlockNumber::Number(n)) => {
-                if n < lag_tip {
-                    Ok(Some(BlockNumber::Number(n)))
-                } else {
-                    Ok(Some(BlockNumber::Number(lag_tip)))
-                }
-            }
-            None => Ok(Some(BlockNumber::Number(lag_tip))),
-            _ => Ok(number),
+            // these are not affected by the tip of the chain
+            Some(BlockNumber::Earliest | BlockNumber::Safe | BlockNumber::Finalized) => Ok(number),
+            _ => Ok(Some(lag_block_number(number, self.get_block_number().await?))),
         }
     }
 
+    /// Clamps the filter range to the lagged tip, returns `None` if the range starts after it.
     async fn normalize_filter_range(
         &self,
         block_option: FilterBlockOption,
-    ) -> TimeLagResult<FilterBlockOption, M> {
+    ) -> TimeLagResult<Option<FilterBlockOption>, M> {
         match block_option {
-            FilterBlockOption::Range { from_block: _, to_block: None } => {
-                Ok(block_option.set_to_block(self.get_block_number().await?.into()))
+            FilterBlockOption::AtBlockHash(_) => Ok(Some(block_option)),
+            FilterBlockOption::Range { .. } => {
+                Ok(lag_filter_range(block_option, self.get_block_number().await?))
+            }
+        }
+    }
+}
+
+/// Rewrites `number` so that it does not go past the lagged tip. An omitted block means `Latest`.
+fn lag_block_number(number: Option<BlockNumber>, lag_tip: U64) -> BlockNumber {
+    match number {
+        Some(BlockNumber::Number(n)) if n < lag_tip => BlockNumber::Number(n),
+        Some(number @ (BlockNumber::Earliest | BlockNumber::Safe | BlockNumber::Finalized)) => {
+            number
+        }
+        _ => BlockNumber::Number(lag_tip),
+    }
+}
+
+/// Clamps both ends of a filter range to the lagged tip. Returns `None` if the range only covers
+/// blocks past the lagged tip.
+fn lag_filter_range(block_option: FilterBlockOption, lag_tip: U64) -> Option<FilterBlockOption> {
+    match block_option {
+        FilterBlockOption::Range { from_block, to_block } => {
+            if matches!(from_block, Some(BlockNumber::Number(n)) if n > lag_tip) {
+                return None
             }
-            _ => Ok(block_option),
+            Some(FilterBlockOption::Range {
+                from_block: Some(lag_block_number(from_block, lag_tip)),
+                to_block: Some(lag_block_number(to_block, lag_tip)),
+            })
         }
+        FilterBlockOption::AtBlockHash(_) => Some(block_option),
+    }
+}
+
+/// Returns true if the filter range ends at a fixed block, i.e. it does not follow the chain tip.
+fn is_bounded(block_option: &FilterBlockOption) -> bool {
+    match block_option.get_to_block() {
+        Some(BlockNumber::Number(_) | BlockNumber::Earliest) => true,
+        None => matches!(block_option, FilterBlockOption::AtBlockHash(_)),
+        _ => false,
     }
 }
 
@@ -122,7 +165,7 @@ where
         self.inner()
             .get_block_number()
             .await
-            .map(|num| num - self.lag)
+            .map(|num| num.saturating_sub(self.lag.into()))
             .map_err(corebc_providers::MiddlewareError::from_err)
     }
 
@@ -211,6 +254,19 @@ where
             .map_err(corebc_providers::MiddlewareError::from_err)
     }
 
+    async fn estimate_energy(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        let block = self.normalize_block_id(block).await?;
+
+        self.inner()
+            .estimate_energy(tx, block)
+            .await
+            .map_err(corebc_providers::MiddlewareError::from_err)
+    }
+
     async fn call(
         &self,
         tx: &TypedTransaction,
@@ -284,6 +340,19 @@ where
             .map_err(corebc_providers::MiddlewareError::from_err)
     }
 
+    async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        locations: Vec<H256>,
+        block: Option<BlockId>,
+    ) -> Result<EIP1186ProofResponse, Self::Error> {
+        let block = self.normalize_block_id(block).await?;
+        self.inner()
+            .get_proof(from, locations, block)
+            .await
+            .map_err(corebc_providers::MiddlewareError::from_err)
+    }
+
     async fn fill_transaction(
         &self,
         tx: &mut TypedTransaction,
@@ -316,24 +385,38 @@ where
         filter: &corebc_core::types::Filter,
     ) -> Result<Vec<corebc_core::types::Log>, Self::Error> {
         let mut filter = filter.clone();
-        filter.block_option = self.normalize_filter_range(filter.block_option).await?;
+        filter.block_option = match self.normalize_filter_range(filter.block_option).await? {
+            Some(block_option) => block_option,
+            // the range has not been confirmed yet
+            None => return Ok(vec![]),
+        };
 
         self.inner().get_logs(&filter).await.map_err(corebc_providers::MiddlewareError::from_err)
     }
 
+    /// Only log filters that end at a fixed block are supported, as the node would otherwise
+    /// report logs from blocks past the lagged tip. Their range is clamped to the lagged tip.
     async fn new_filter(
         &self,
-        _filter: corebc_providers::FilterKind<'_>,
+        filter: corebc_providers::FilterKind<'_>,
     ) -> Result<U256, Self::Error> {
-        Err(TimeLagError::Unsupported)
-    }
+        let filter = match filter {
+            corebc_providers::FilterKind::Logs(filter) if is_bounded(&filter.block_option) => {
+                filter
+            }
+            _ => return Err(TimeLagError::Unsupported),
+        };
 
-    async fn get_filter_changes<T, R>(&self, _id: T) -> Result<Vec<R>, Self::Error>
-    where
-        T: Into<U256> + Send + Sync,
-        R: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + std::fmt::Debug,
-    {
-        Err(TimeLagError::Unsupported)
+        let mut filter = filter.clone();
+        filter.block_option = self
+            .normalize_filter_range(filter.block_option)
+            .await?
+            .ok_or(TimeLagError::Unsupported)?;
+
+        self.inner()
+            .new_filter(corebc_providers::FilterKind::Logs(&filter))
+            .await
+            .map_err(corebc_providers::MiddlewareError::from_err)
     }
 
     async fn watch_blocks(
@@ -393,3 +476,53 @@ where
         Err(TimeLagError::Unsupported)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lags_block_numbers() {
+        let tip = U64::from(100);
+        assert_eq!(lag_block_number(None, tip), BlockNumber::Number(tip));
+        assert_eq!(lag_block_number(Some(BlockNumber::Latest), tip), BlockNumber::Number(tip));
+        assert_eq!(lag_block_number(Some(BlockNumber::Pending), tip), BlockNumber::Number(tip));
+        assert_eq!(lag_block_number(Some(150u64.into()), tip), BlockNumber::Number(tip));
+        assert_eq!(lag_block_number(Some(50u64.into()), tip), 50u64.into());
+        assert_eq!(lag_block_number(Some(BlockNumber::Earliest), tip), BlockNumber::Earliest);
+    }
+
+    #[test]
+    fn lags_filter_ranges() {
+        let tip = U64::from(100);
+        let range = |from: Option<BlockNumber>, to: Option<BlockNumber>| FilterBlockOption::Range {
+            from_block: from,
+            to_block: to,
+        };
+
+        assert_eq!(
+            lag_filter_range(range(Some(10u64.into()), None), tip),
+            Some(range(Some(10u64.into()), Some(100u64.into())))
+        );
+        assert_eq!(
+            lag_filter_range(range(Some(BlockNumber::Latest), Some(BlockNumber::Latest)), tip),
+            Some(range(Some(100u64.into()), Some(100u64.into())))
+        );
+        assert_eq!(
+            lag_filter_range(range(Some(BlockNumber::Earliest), Some(120u64.into())), tip),
+            Some(range(Some(BlockNumber::Earliest), Some(100u64.into())))
+        );
+        assert_eq!(lag_filter_range(range(Some(101u64.into()), None), tip), None);
+
+        let at_hash = FilterBlockOption::AtBlockHash(H256::zero());
+        assert_eq!(lag_filter_range(at_hash, tip), Some(at_hash));
+    }
+
+    #[test]
+    fn bounded_filters() {
+        assert!(is_bounded(&FilterBlockOption::from(10u64)));
+        assert!(is_bounded(&FilterBlockOption::AtBlockHash(H256::zero())));
+        assert!(!is_bounded(&FilterBlockOption::from(10u64..)));
+        assert!(!is_bounded(&FilterBlockOption::Range { from_block: None, to_block: None }));
+    }
+}
@@ -3,7 +3,7 @@ use corebc_core::{
     types::{Network, U64},
 };
 use corebc_middleware::{
-    builder::MiddlewareBuilder,
+    builder::{MiddlewareBuilder, MiddlewareStack, StandardMiddleware},
     energy_escalator::{Frequency, GasEscalatorMiddleware, GeometricGasPrice},
     energy_oracle::EnergyOracleMiddleware,
     nonce_manager::NonceManagerMiddleware,
@@ -63,3 +63,39 @@ async fn build_declarative_middleware_stack() {
     mock.assert_request("xcb_blockNumber", ()).unwrap();
     mock.assert_request("xcb_blockNumber", ()).unwrap_err();
 }
+
+#[tokio::test]
+async fn build_standard_middleware_stack() {
+    let (provider, mock) = Provider::mocked();
+
+    let signer = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+    let address = signer.address();
+
+    let provider: StandardMiddleware<_, _> = MiddlewareStack::standard(provider, signer);
+    assert_eq!(provider.inner().address(), address);
+
+    mock.push(U64::from(12u64)).unwrap();
+    let block: U64 = provider.get_block_number().await.unwrap();
+    assert_eq!(block.as_u64(), 12);
+    mock.assert_request("xcb_blockNumber", ()).unwrap();
+}
+
+#[tokio::test]
+async fn build_typed_middleware_stack() {
+    let (provider, mock) = Provider::mocked();
+
+    let signer = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
+    let address = signer.address();
+    let escalator = GeometricGasPrice::new(1.125, 60u64, None::<u64>);
+
+    let provider = MiddlewareStack::new(provider)
+        .wrap_into(|p| GasEscalatorMiddleware::new(p, escalator, Frequency::PerBlock))
+        .signer(signer)
+        .nonce_manager(address)
+        .build();
+    assert_eq!(provider.inner().address(), address);
+
+    mock.push(U64::from(12u64)).unwrap();
+    let block: U64 = provider.get_block_number().await.unwrap();
+    assert_eq!(block.as_u64(), 12);
+}
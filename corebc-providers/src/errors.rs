@@ -172,6 +172,24 @@ pub enum ProviderError {
     #[error("unsupported node client")]
     UnsupportedNodeClient,
 
+    /// The request did not complete within its timeout
+    #[error("request {method} timed out after {timeout:?}")]
+    RequestTimeout {
+        /// The JSON-RPC method
+        method: String,
+        /// The timeout that elapsed
+        timeout: std::time::Duration,
+    },
+
+    /// Waiting for a pending transaction timed out or was cancelled
+    #[error(transparent)]
+    PendingTransaction(#[from] crate::PendingTxError),
+
+    /// A quantity of a response is not in its most compact form, see
+    /// [`QuantityMode::Strict`](crate::QuantityMode::Strict)
+    #[error(transparent)]
+    InvalidQuantity(#[from] crate::QuantityError),
+
     /// Signer is not available to this provider.
     #[error("Attempted to sign a transaction with no available signer. Hint: did you mean to use a SignerMiddleware?")]
     SignerUnavailable,
@@ -217,3 +235,66 @@ impl MiddlewareError for ProviderError {
         None
     }
 }
+
+/// The error type of a middleware that never fails on its own, it only forwards the errors of
+/// the next lower middleware layer.
+///
+/// Middlewares that merely override a few [`Middleware`](crate::Middleware) methods can use it
+/// instead of declaring an error type of their own.
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct ForwardedError<E: MiddlewareError>(pub E);
+
+impl<E: MiddlewareError> MiddlewareError for ForwardedError<E> {
+    type Inner = E;
+
+    fn from_err(e: E) -> Self {
+        ForwardedError(e)
+    }
+
+    fn as_inner(&self) -> Option<&E> {
+        Some(&self.0)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{Middleware, Provider};
+    use async_trait::async_trait;
+    use corebc_core::types::U64;
+
+    #[derive(Debug)]
+    struct Passthrough<M>(M);
+
+    #[async_trait]
+    impl<M: Middleware> Middleware for Passthrough<M> {
+        type Error = ForwardedError<M::Error>;
+        type Provider = M::Provider;
+        type Inner = M;
+
+        fn inner(&self) -> &M {
+            &self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_to_inner_middleware() {
+        let (provider, mock) = Provider::mocked();
+        let middleware = Passthrough(provider);
+
+        mock.push(U64::from(7)).unwrap();
+        assert_eq!(middleware.get_block_number().await.unwrap().as_u64(), 7);
+
+        mock.expect("xcb_blockNumber").return_error(JsonRpcError {
+            code: -32000,
+            message: "unavailable".to_string(),
+            data: None,
+        });
+        let err = middleware.get_block_number().await.unwrap_err();
+        assert!(err.is_inner());
+        assert_eq!(err.as_error_response().unwrap().message, "unavailable");
+        assert_eq!(err.to_string(), err.0.to_string());
+    }
+}
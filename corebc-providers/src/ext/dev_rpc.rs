@@ -30,12 +30,16 @@
 //! client.revert_to_snapshot(snap_id).await?;
 //! let balance_after_revert = client.get_balance(to, None).await?;
 //! assert_eq!(balance_after_revert, balance_before);
+//!
+//! // fast-forward one day and mine a block
+//! client.increase_time(24 * 60 * 60).await?;
+//! client.mine(None).await?;
 //! # Ok(()) }
 //! ```
 
 use crate::{Middleware, MiddlewareError, ProviderError};
 use async_trait::async_trait;
-use corebc_core::types::U256;
+use corebc_core::types::{Address, U256};
 use thiserror::Error;
 
 use std::fmt::Debug;
@@ -123,6 +127,68 @@ impl<M: Middleware> DevRpcMiddleware<M> {
             Err(DevRpcMiddlewareError::NoSnapshot)
         }
     }
+
+    /// Moves the clock of the DevRpc node forward by `seconds`. Returns the total time adjustment
+    /// applied so far, in seconds
+    pub async fn increase_time(&self, seconds: u64) -> Result<i64, DevRpcMiddlewareError<M>> {
+        self.provider()
+            .request::<[u64; 1], i64>("evm_increaseTime", [seconds])
+            .await
+            .map_err(From::from)
+    }
+
+    /// Sets the timestamp of the next block mined by the DevRpc node
+    pub async fn set_next_block_timestamp(
+        &self,
+        timestamp: u64,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider()
+            .request::<[u64; 1], ()>("evm_setNextBlockTimestamp", [timestamp])
+            .await
+            .map_err(From::from)
+    }
+
+    /// Mines a single block, optionally with the given `timestamp`
+    pub async fn mine(&self, timestamp: Option<u64>) -> Result<(), DevRpcMiddlewareError<M>> {
+        match timestamp {
+            Some(timestamp) => {
+                self.provider().request::<[u64; 1], U256>("evm_mine", [timestamp]).await?
+            }
+            None => self.provider().request::<(), U256>("evm_mine", ()).await?,
+        };
+        Ok(())
+    }
+
+    /// Mines `num_blocks` blocks, one after the other
+    pub async fn mine_blocks(&self, num_blocks: usize) -> Result<(), DevRpcMiddlewareError<M>> {
+        for _ in 0..num_blocks {
+            self.mine(None).await?;
+        }
+        Ok(())
+    }
+
+    /// Lets the DevRpc node accept unsigned transactions sent from `address` via
+    /// `xcb_sendTransaction`
+    pub async fn impersonate_account(
+        &self,
+        address: Address,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider()
+            .request::<[Address; 1], ()>("anvil_impersonateAccount", [address])
+            .await
+            .map_err(From::from)
+    }
+
+    /// Stops impersonating `address`, see [`Self::impersonate_account`]
+    pub async fn stop_impersonating_account(
+        &self,
+        address: Address,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider()
+            .request::<[Address; 1], ()>("anvil_stopImpersonatingAccount", [address])
+            .await
+            .map_err(From::from)
+    }
 }
 
 #[cfg(test)]
@@ -186,4 +252,30 @@ mod tests {
         assert_eq!(block, block0);
         assert_eq!(time, time0);
     }
+
+    #[tokio::test]
+    async fn test_time_manipulation() {
+        let anvil = Anvil::new().spawn();
+        let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
+        let client = DevRpcMiddleware::new(provider);
+
+        // mine a block with an explicit timestamp
+        let block0 = client.get_block(client.get_block_number().await.unwrap()).await.unwrap();
+        let time0 = block0.unwrap().timestamp.as_u64();
+        client.mine(Some(time0 + 100)).await.unwrap();
+        let block = client.get_block(client.get_block_number().await.unwrap()).await.unwrap();
+        assert_eq!(block.unwrap().timestamp.as_u64(), time0 + 100);
+
+        // schedule the timestamp of the next block
+        client.set_next_block_timestamp(time0 + 1000).await.unwrap();
+        client.mine(None).await.unwrap();
+        let block = client.get_block(client.get_block_number().await.unwrap()).await.unwrap();
+        assert_eq!(block.unwrap().timestamp.as_u64(), time0 + 1000);
+
+        // move the clock forward
+        assert!(client.increase_time(3600).await.unwrap() >= 3600);
+        let start = client.get_block_number().await.unwrap();
+        client.mine_blocks(3).await.unwrap();
+        assert_eq!(client.get_block_number().await.unwrap(), start + 3);
+    }
 }
@@ -5,7 +5,8 @@ use corebc_core::{
     utils::sha3,
 };
 
-use std::convert::TryInto;
+use instant::{Duration, Instant};
+use std::{collections::HashMap, convert::TryInto, sync::Mutex};
 
 /// ENS registry address (`0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e`)
 /// CORETODO: Here should be the real cns address
@@ -32,6 +33,11 @@ pub const FIELD_SELECTOR: Selector = [89, 209, 212, 60];
 /// supportsInterface(bytes4 interfaceID)
 pub const INTERFACE_SELECTOR: Selector = [1, 255, 201, 167];
 
+/// The maximum number of names resolved concurrently by
+/// [`Provider::resolve_names`](crate::Provider::resolve_names) and
+/// [`Provider::lookup_addresses`](crate::Provider::lookup_addresses)
+pub const ENS_RESOLUTION_CONCURRENCY: usize = 16;
+
 /// Returns a transaction request for calling the `resolver` method on the ENS server
 pub fn get_resolver<T: Into<NameOrAddress>>(ens_address: T, name: &str) -> TransactionRequest {
     // keccak256('resolver(bytes32)')
@@ -105,6 +111,70 @@ pub fn parameterhash(name: &str) -> Vec<u8> {
     }
 }
 
+/// A cache of resolved names and reverse records, see
+/// [`Provider::ens_cache`](crate::Provider::ens_cache)
+///
+/// Entries expire after the configured time to live, after which the name is resolved again.
+#[derive(Debug)]
+pub struct EnsCache {
+    ttl: Duration,
+    names: Mutex<HashMap<String, (Address, Instant)>>,
+    addresses: Mutex<HashMap<Address, (String, Instant)>>,
+}
+
+impl EnsCache {
+    /// Creates an empty cache whose entries expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, names: Default::default(), addresses: Default::default() }
+    }
+
+    /// Returns the time to live of the entries
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached address `name` resolves to, if it did not expire yet
+    pub fn address(&self, name: &str) -> Option<Address> {
+        let names = self.names.lock().unwrap();
+        names.get(name).filter(|(_, at)| at.elapsed() < self.ttl).map(|(address, _)| *address)
+    }
+
+    /// Returns the cached name of the reverse record of `address`, if it did not expire yet
+    pub fn name(&self, address: Address) -> Option<String> {
+        let addresses = self.addresses.lock().unwrap();
+        addresses
+            .get(&address)
+            .filter(|(_, at)| at.elapsed() < self.ttl)
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Caches the address `name` resolves to
+    pub fn insert_address(&self, name: impl Into<String>, address: Address) {
+        self.names.lock().unwrap().insert(name.into(), (address, Instant::now()));
+    }
+
+    /// Caches the name of the reverse record of `address`
+    pub fn insert_name(&self, address: Address, name: impl Into<String>) {
+        self.addresses.lock().unwrap().insert(address, (name.into(), Instant::now()));
+    }
+
+    /// Removes the cached address of `name`
+    pub fn invalidate_name(&self, name: &str) {
+        self.names.lock().unwrap().remove(name);
+    }
+
+    /// Removes the cached reverse record of `address`
+    pub fn invalidate_address(&self, address: Address) {
+        self.addresses.lock().unwrap().remove(&address);
+    }
+
+    /// Removes all entries
+    pub fn clear(&self) {
+        self.names.lock().unwrap().clear();
+        self.addresses.lock().unwrap().clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +191,23 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn cache_expires_entries() {
+        let address = Address::repeat_byte(1);
+        let cache = EnsCache::new(Duration::from_secs(60));
+        cache.insert_address("vitalik.xcb", address);
+        cache.insert_name(address, "vitalik.xcb");
+        assert_eq!(cache.address("vitalik.xcb"), Some(address));
+        assert_eq!(cache.name(address).as_deref(), Some("vitalik.xcb"));
+
+        cache.invalidate_name("vitalik.xcb");
+        assert_eq!(cache.address("vitalik.xcb"), None);
+        cache.clear();
+        assert_eq!(cache.name(address), None);
+
+        let cache = EnsCache::new(Duration::ZERO);
+        cache.insert_address("vitalik.xcb", address);
+        assert_eq!(cache.address("vitalik.xcb"), None);
+    }
 }
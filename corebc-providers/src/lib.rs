@@ -15,16 +15,20 @@ pub use toolbox::*;
 
 /// Crate utilities and type aliases
 mod utils;
-pub use utils::{interval, maybe, EscalationPolicy};
+pub use utils::{
+    block_escalation, capped_escalation, interval, max_escalation, maybe, time_escalation,
+    EnergyMargin, EscalationPolicy, RequestTimeouts,
+};
 
 /// Errors
 mod errors;
-pub use errors::{MiddlewareError, ProviderError, RpcError};
+pub use errors::{ForwardedError, MiddlewareError, ProviderError, RpcError};
 
 mod stream;
 pub use futures_util::StreamExt;
 pub use stream::{
-    tx_stream::TransactionStream, FilterWatcher, DEFAULT_LOCAL_POLL_INTERVAL, DEFAULT_POLL_INTERVAL,
+    tx_stream::TransactionStream, FilterWatcher, Heartbeat, StalenessMonitor, SyncProgressStream,
+    DEFAULT_LOCAL_POLL_INTERVAL, DEFAULT_POLL_INTERVAL, DEFAULT_STALL_BLOCKS,
 };
 
 mod middleware;
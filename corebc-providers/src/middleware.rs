@@ -9,7 +9,7 @@ use url::Url;
 use crate::{
     erc, EscalatingPending, EscalationPolicy, FilterKind, FilterWatcher, JsonRpcClient, LogQuery,
     MiddlewareError, NodeInfo, PeerInfo, PendingTransaction, Provider, ProviderError, PubsubClient,
-    SubscriptionStream,
+    RevertReason, SubscriptionStream, TraceQuery,
 };
 
 /// A middleware allows customizing requests send and received from an ethereum node.
@@ -20,6 +20,11 @@ use crate::{
 /// [`MiddlewareError`](crate::MiddlewareError) trait on your middleware's
 /// error type 3. implementing any of the methods you want to override
 ///
+/// Every method except `inner` has a default implementation that forwards the call to the inner
+/// middleware, so middlewares only implement the methods they change, and methods added to this
+/// trait later do not break them. Middlewares that do not fail on their own can use
+/// [`ForwardedError`](crate::ForwardedError) as their error type.
+///
 /// ```
 /// use corebc_providers::{Middleware, MiddlewareError};
 /// use corebc_core::types::{U64, TransactionRequest, U256, transaction::eip2718::TypedTransaction, BlockId};
@@ -78,6 +83,27 @@ use crate::{
 ///     }
 /// }
 /// ```
+///
+/// A middleware that only forwards the errors of the inner middleware:
+///
+/// ```
+/// use corebc_providers::{ForwardedError, Middleware};
+/// use async_trait::async_trait;
+///
+/// #[derive(Debug)]
+/// struct Passthrough<M>(M);
+///
+/// #[async_trait]
+/// impl<M: Middleware> Middleware for Passthrough<M> {
+///     type Error = ForwardedError<M::Error>;
+///     type Provider = M::Provider;
+///     type Inner = M;
+///
+///     fn inner(&self) -> &M {
+///         &self.0
+///     }
+/// }
+/// ```
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[auto_impl(&, Box, Arc)]
@@ -163,6 +189,10 @@ pub trait Middleware: Sync + Send + Debug {
     ///
     /// e.g. `Box::new(|start, escalation_index| start * 1250.pow(escalations) /
     /// 1000.pow(escalations))`
+    ///
+    /// See [`time_escalation`](crate::time_escalation) and
+    /// [`block_escalation`](crate::block_escalation) for policies that escalate based on the
+    /// time or the blocks since the first broadcast.
     async fn send_escalating<'a>(
         &'a self,
         tx: &TypedTransaction,
@@ -330,6 +360,19 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().get_transaction_count(from, block).await.map_err(MiddlewareError::from_err)
     }
 
+    /// Returns the address of the contract that the next contract creation transaction of `from`
+    /// deploys, computed from the pending nonce of `from`.
+    ///
+    /// This is only accurate if `from` sends no other transaction in the meantime.
+    async fn next_contract_address(&self, from: Address) -> Result<Address, Self::Error> {
+        let (nonce, network_id) = futures_util::try_join!(
+            self.get_transaction_count(from, Some(BlockNumber::Pending.into())),
+            self.get_networkid()
+        )?;
+        let network = Network::from(network_id.low_u64());
+        Ok(corebc_core::utils::get_contract_address(from, nonce, &network))
+    }
+
     /// Sends a transaction to a single Ethereum node and return the estimated amount of energy
     /// required (as a U256) to send it This is free, but only an estimate. Providing too little
     /// energy will result in a transaction being rejected (while still consuming all provided
@@ -712,6 +755,19 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(MiddlewareError::from_err)
     }
 
+    /// Replays all transactions of the given block, returning one trace per transaction, in the
+    /// order they appear in the block, configured with passed options
+    async fn debug_trace_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block: T,
+        trace_options: GoCoreDebugTracingOptions,
+    ) -> Result<Vec<GoCoreTxTrace>, Self::Error> {
+        self.inner()
+            .debug_trace_block(block, trace_options)
+            .await
+            .map_err(MiddlewareError::from_err)
+    }
+
     /// Executes the given call and returns a number of possible traces for it
     async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
         &self,
@@ -725,6 +781,51 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(MiddlewareError::from_err)
     }
 
+    /// Returns why the mined transaction `tx_hash` failed, or `None` if it succeeded.
+    ///
+    /// The transaction is replayed with `xcb_call` on top of the state of its parent block and
+    /// the data it reverts with is decoded into a [`RevertReason`]. As the replay does not include
+    /// the transactions that preceded it in its block, the transaction is traced with the
+    /// `callTracer` instead if the replay does not fail.
+    async fn revert_reason(&self, tx_hash: TxHash) -> Result<Option<RevertReason>, Self::Error> {
+        let not_found = || {
+            Self::convert_err(ProviderError::CustomError(format!(
+                "transaction {tx_hash:?} is not mined"
+            )))
+        };
+        let receipt = self.get_transaction_receipt(tx_hash).await?.ok_or_else(not_found)?;
+        if receipt.status != Some(U64::zero()) {
+            return Ok(None)
+        }
+        let tx = self.get_transaction(tx_hash).await?.ok_or_else(not_found)?;
+
+        let parent =
+            receipt.block_number.map(|num| BlockNumber::Number(num.saturating_sub(1.into())));
+        let replay: TypedTransaction = (&tx).into();
+        if let Err(err) = self.call(&replay, parent.map(Into::into)).await {
+            if !err.is_error_response() {
+                return Err(err)
+            }
+            // failures without revert data, e.g. running out of energy, are read from the trace
+            if let Some(data) = err.as_error_response().and_then(|resp| resp.as_revert_data()) {
+                return Ok(Some(RevertReason::decode(&data)))
+            }
+        }
+
+        let options = GoCoreDebugTracingOptions {
+            tracer: Some(GoCoreDebugTracerType::BuiltInTracer(
+                GoCoreDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        };
+        match self.debug_trace_transaction(tx_hash, options).await? {
+            GoCoreTrace::Known(GoCoreTraceFrame::CallTracer(frame)) => {
+                Ok(Some(RevertReason::from_call_frame(&frame).unwrap_or(RevertReason::Empty)))
+            }
+            _ => Ok(Some(RevertReason::Empty)),
+        }
+    }
+
     // Parity `trace` support
 
     /// Executes the given call and returns a number of possible traces for it
@@ -793,6 +894,15 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().trace_filter(filter).await.map_err(MiddlewareError::from_err)
     }
 
+    /// Returns a stream of traces matching the given filter, loaded in pages of the given size
+    fn trace_filter_paginated<'a>(
+        &'a self,
+        filter: TraceFilter,
+        page_size: usize,
+    ) -> TraceQuery<'a, Self::Provider> {
+        self.inner().trace_filter_paginated(filter, page_size)
+    }
+
     /// Returns trace at the given position
     async fn trace_get<T: Into<U64> + Send + Sync>(
         &self,
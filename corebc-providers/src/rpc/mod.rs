@@ -1,6 +1,9 @@
 mod provider;
 pub use provider::*;
 
+mod quantity;
+pub use quantity::{QuantityError, QuantityMode};
+
 mod transports;
 pub use transports::*;
 
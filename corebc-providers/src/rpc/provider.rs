@@ -1,14 +1,21 @@
 use corebc_core::types::SyncingStatus;
 
 use crate::{
-    call_raw::CallBuilder,
+    call_raw::{spoof, CallBuilder},
     errors::ProviderError,
     ext::{ens, erc},
-    rpc::pubsub::{PubsubClient, SubscriptionStream},
-    stream::{FilterWatcher, DEFAULT_LOCAL_POLL_INTERVAL, DEFAULT_POLL_INTERVAL},
-    utils::maybe,
-    Http as HttpProvider, JsonRpcClient, JsonRpcClientWrapper, LogQuery, MiddlewareError,
-    MockProvider, NodeInfo, PeerInfo, PendingTransaction, QuorumProvider, RwClient,
+    rpc::{
+        pubsub::{PubsubClient, SubscriptionStream},
+        quantity::{check_quantities, normalize_quantities},
+    },
+    stream::{
+        FilterWatcher, StalenessMonitor, SyncProgressStream, DEFAULT_LOCAL_POLL_INTERVAL,
+        DEFAULT_POLL_INTERVAL,
+    },
+    utils::{maybe, EnergyMargin, RequestTimeouts},
+    AccountSnapshot, Http as HttpProvider, JsonRpcClient, JsonRpcClientWrapper, LogQuery,
+    MiddlewareError, MockProvider, NodeInfo, PeerInfo, PendingTransaction, QuantityMode,
+    QuorumProvider, RpcError, RwClient, SimulatedTransaction, TraceQuery,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -22,28 +29,45 @@ use corebc_core::{
     abi::{self, Detokenize, ParamType},
     types::{
         transaction::eip2718::TypedTransaction, Address, Block, BlockId, BlockNumber, BlockTrace,
-        Bytes, EIP1186ProofResponse, Filter, FilterBlockOption, GoCoreDebugTracingCallOptions,
-        GoCoreDebugTracingOptions, GoCoreTrace, Log, NameOrAddress, Network, Selector, Signature,
-        Trace, TraceFilter, TraceType, Transaction, TransactionReceipt, TransactionRequest, TxHash,
-        TxpoolContent, TxpoolInspect, TxpoolStatus, H256, U256, U64,
+        Bytes, CallFrame, EIP1186ProofResponse, Filter, FilterBlockOption,
+        GoCoreDebugTracingCallOptions, GoCoreDebugTracingOptions, GoCoreTrace, GoCoreTxTrace, Log,
+        NameOrAddress, Network, Selector, Signature, Trace, TraceFilter, TraceType, Transaction,
+        TransactionReceipt, TransactionRequest, TxHash, TxpoolContent, TxpoolInspect, TxpoolStatus,
+        H256, U256, U64,
     },
     utils,
 };
-use futures_util::{lock::Mutex, try_join};
+use futures_timer::Delay;
+use futures_util::{
+    future::{self, Either},
+    lock::Mutex,
+    stream, try_join, StreamExt, TryStreamExt,
+};
 use hex::FromHex;
+use semver::Version;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    collections::VecDeque, convert::TryFrom, fmt::Debug, str::FromStr, sync::Arc, time::Duration,
+    collections::{HashMap, HashSet, VecDeque},
+    convert::TryFrom,
+    fmt::Debug,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
 };
 use tracing::trace;
 use tracing_futures::Instrument;
 use url::{ParseError, Url};
 
+/// The maximum number of times [`Provider::get_logs_auto_split`] halves a block range
+pub const GET_LOGS_MAX_SPLIT_DEPTH: usize = 16;
+
 /// Node Clients
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum NodeClient {
     /// GoCore
     GoCore,
+    /// Anvil
+    Anvil,
 }
 
 impl FromStr for NodeClient {
@@ -51,12 +75,54 @@ impl FromStr for NodeClient {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.split('/').next().unwrap().to_lowercase().as_str() {
-            "gocore" => Ok(NodeClient::GoCore),
+            "gocore" | "go-core" | "go_core" => Ok(NodeClient::GoCore),
+            "anvil" => Ok(NodeClient::Anvil),
             _ => Err(ProviderError::UnsupportedNodeClient),
         }
     }
 }
 
+/// The name and version of a node client, parsed from its `web3_clientVersion`, e.g.
+/// `Gocore/v1.1.11-stable-7cd4e4b6/linux-amd64/go1.20.4`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeClientInfo {
+    /// The name of the client, e.g. `Gocore`
+    pub name: String,
+    /// The version of the client, without pre-release or build metadata, `None` if the client
+    /// version does not include one
+    pub version: Option<Version>,
+}
+
+impl NodeClientInfo {
+    /// Parses a `web3_clientVersion` response, any client name is accepted
+    pub fn parse(client_version: &str) -> Self {
+        let mut parts = client_version.split('/');
+        let name = parts.next().unwrap_or_default().trim().to_string();
+        // clients may include a node name before the version, e.g. `Gocore/my-node/v1.1.0`
+        let version = parts.find_map(|part| {
+            let part = part.strip_prefix('v').unwrap_or(part);
+            let mut numbers =
+                part.split(|c: char| !c.is_ascii_digit()).take(3).map(|n| n.parse::<u64>().ok());
+            let major = numbers.next()??;
+            Some(Version::new(major, numbers.next()??, numbers.next().flatten().unwrap_or(0)))
+        });
+        Self { name, version }
+    }
+
+    /// Returns the client, if it is a known one
+    pub fn client(&self) -> Option<NodeClient> {
+        self.name.parse().ok()
+    }
+}
+
+impl FromStr for NodeClientInfo {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::parse(s))
+    }
+}
+
 /// An abstract provider for interacting with the [Ethereum JSON RPC
 /// API](https://github.com/ethereum/wiki/wiki/JSON-RPC). Must be instantiated
 /// with a data transport which implements the [`JsonRpcClient`](trait@crate::JsonRpcClient) trait
@@ -82,12 +148,19 @@ impl FromStr for NodeClient {
 pub struct Provider<P> {
     inner: P,
     ens: Option<Address>,
+    ens_cache: Option<Arc<ens::EnsCache>>,
     interval: Option<Duration>,
     from: Option<Address>,
+    energy_margin: Option<EnergyMargin>,
+    estimate_pending: bool,
+    request_timeouts: RequestTimeouts,
+    quantity_mode: QuantityMode,
     /// Node client hasn't been checked yet = `None`
-    /// Unsupported node client = `Some(None)`
-    /// Supported node client = `Some(Some(NodeClient))`
-    _node_client: Arc<Mutex<Option<NodeClient>>>,
+    _node_client: Arc<Mutex<Option<NodeClientInfo>>>,
+    /// The optional methods the node answered as unknown, they are not requested again
+    _unsupported_methods: Arc<std::sync::RwLock<HashSet<&'static str>>>,
+    /// Network id hasn't been requested yet = `None`
+    _network_id: Arc<Mutex<Option<U256>>>,
 }
 
 impl<P> AsRef<P> for Provider<P> {
@@ -116,27 +189,90 @@ impl<P: JsonRpcClient> Provider<P> {
         Self {
             inner: provider,
             ens: None,
+            ens_cache: None,
             interval: None,
             from: None,
+            energy_margin: None,
+            estimate_pending: false,
+            request_timeouts: RequestTimeouts::default(),
+            quantity_mode: QuantityMode::default(),
             _node_client: Arc::new(Mutex::new(None)),
+            _unsupported_methods: Default::default(),
+            _network_id: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Returns the id of the network the node is on, while also caching the value for use in
+    /// [`Middleware::fill_transaction`].
+    ///
+    /// Use [`Self::refresh_network_id`] if the node may switch networks.
+    pub async fn network_id(&self) -> Result<U256, ProviderError> {
+        let mut network_id = self._network_id.lock().await;
+
+        if let Some(id) = *network_id {
+            Ok(id)
+        } else {
+            let id = self.get_networkid().await?;
+            *network_id = Some(id);
+            Ok(id)
+        }
+    }
+
+    /// Requests the id of the network the node is on, replacing the cached value
+    pub async fn refresh_network_id(&self) -> Result<U256, ProviderError> {
+        let mut network_id = self._network_id.lock().await;
+        let id = self.get_networkid().await?;
+        *network_id = Some(id);
+        Ok(id)
+    }
+
     /// Returns the type of node we're connected to, while also caching the value for use
     /// in other node-specific API calls, such as the get_block_receipts call.
+    ///
+    /// Fails with [`ProviderError::UnsupportedNodeClient`] if the client is unknown, see
+    /// [`Self::node_client_info`] for any client.
     pub async fn node_client(&self) -> Result<NodeClient, ProviderError> {
+        self.node_client_info().await?.client().ok_or(ProviderError::UnsupportedNodeClient)
+    }
+
+    /// Returns the name and version of the node we're connected to, while also caching the value
+    /// for use in other node-specific API calls.
+    pub async fn node_client_info(&self) -> Result<NodeClientInfo, ProviderError> {
         let mut node_client = self._node_client.lock().await;
 
-        if let Some(node_client) = *node_client {
-            Ok(node_client)
+        if let Some(info) = &*node_client {
+            Ok(info.clone())
         } else {
-            let client_version = self.client_version().await?;
-            let client_version = match client_version.parse::<NodeClient>() {
-                Ok(res) => res,
-                Err(_) => return Err(ProviderError::UnsupportedNodeClient),
-            };
-            *node_client = Some(client_version);
-            Ok(client_version)
+            let info = NodeClientInfo::parse(&self.client_version().await?);
+            *node_client = Some(info.clone());
+            Ok(info)
+        }
+    }
+
+    /// Requests a method not every node serves. Once the node answered that it does not know the
+    /// method, it fails with [`ProviderError::UnsupportedRPC`] without sending the request.
+    async fn request_optional<T, R>(
+        &self,
+        method: &'static str,
+        params: T,
+    ) -> Result<R, ProviderError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned + Debug + Send,
+    {
+        let known_unsupported =
+            self._unsupported_methods.read().map_or(false, |methods| methods.contains(method));
+        if known_unsupported {
+            return Err(ProviderError::UnsupportedRPC)
+        }
+        match self.request(method, params).await {
+            Err(err) if is_method_not_found(&err) => {
+                if let Ok(mut methods) = self._unsupported_methods.write() {
+                    methods.insert(method);
+                }
+                Err(ProviderError::UnsupportedRPC)
+            }
+            res => res,
         }
     }
 
@@ -158,13 +294,49 @@ impl<P: JsonRpcClient> Provider<P> {
         // https://docs.rs/tracing/0.1.22/tracing/span/struct.Span.html#in-asynchronous-code
         let res = async move {
             trace!("tx");
-            let res: R = self.inner.request(method, params).await.map_err(Into::into)?;
+            // zero sized params, e.g. `()`, are left out of the request by the transports and
+            // must reach them untouched
+            let res: R = if std::mem::size_of::<T>() == 0 {
+                self.request_quantities(method, params).await?
+            } else {
+                let mut params = serde_json::to_value(params)?;
+                normalize_quantities(&mut params);
+                self.request_quantities(method, params).await?
+            };
             trace!(rx = ?serde_json::to_string(&res)?);
             Ok::<_, ProviderError>(res)
         }
-        .instrument(span)
-        .await?;
-        Ok(res)
+        .instrument(span);
+
+        let timeout = match self.request_timeouts.get(method) {
+            Some(timeout) => timeout,
+            None => return res.await,
+        };
+        futures_util::pin_mut!(res);
+        match future::select(res, Delay::new(timeout)).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => {
+                Err(ProviderError::RequestTimeout { method: method.to_string(), timeout })
+            }
+        }
+    }
+
+    /// Sends the request and checks the quantities of the response according to the quantity
+    /// mode
+    async fn request_quantities<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned + Debug + Send,
+    {
+        match self.quantity_mode {
+            QuantityMode::Lenient => self.inner.request(method, params).await.map_err(Into::into),
+            QuantityMode::Strict => {
+                let res: serde_json::Value =
+                    self.inner.request(method, params).await.map_err(Into::into)?;
+                check_quantities(&res)?;
+                Ok(serde_json::from_value(res)?)
+            }
+        }
     }
 
     async fn get_block_gen<Tx: Default + Serialize + DeserializeOwned + Debug + Send>(
@@ -175,7 +347,7 @@ impl<P: JsonRpcClient> Provider<P> {
         let include_txs = utils::serialize(&include_txs);
 
         Ok(match id {
-            BlockId::Hash(hash) => {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
                 let hash = utils::serialize(&hash);
                 self.request("xcb_getBlockByHash", [hash, include_txs]).await?
             }
@@ -263,6 +435,12 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
             }
         }
 
+        // set the network id, otherwise signers fall back to their own default network
+        if tx.network_id().is_none() {
+            let network_id = self.network_id().await?;
+            tx.set_network_id(network_id.low_u64());
+        }
+
         // TODO: Join the name resolution and energy price future
 
         // set the ENS name
@@ -272,17 +450,28 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         }
 
         // fill energy price
-        match tx {
-            TypedTransaction::Legacy(_) => {
-                let energy_price = maybe(tx.energy_price(), self.get_energy_price()).await?;
-                tx.set_energy_price(energy_price);
-            }
-        }
+        let energy_price = maybe(tx.energy_price(), self.get_energy_price()).await?;
+        tx.set_energy_price(energy_price);
 
         // Set energy to estimated value only if it was not set by the caller,
         // even if the access list has been populated and saves energy
         if tx.energy().is_none() {
-            let energy_estimate = self.estimate_energy(tx, block).await?;
+            let block = match block {
+                None if self.estimate_pending => {
+                    // simulate the transaction after the sender's pending transactions
+                    if let (Some(from), None) = (tx.from().copied(), tx.nonce()) {
+                        let nonce =
+                            self.get_transaction_count(from, Some(BlockNumber::Pending.into()));
+                        tx.set_nonce(nonce.await?);
+                    }
+                    Some(BlockNumber::Pending.into())
+                }
+                block => block,
+            };
+            let mut energy_estimate = self.estimate_energy(tx, block).await?;
+            if let Some(margin) = self.energy_margin {
+                energy_estimate = margin.apply(energy_estimate);
+            }
             tx.set_energy(energy_estimate);
         }
 
@@ -313,7 +502,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     ) -> Result<U256, Self::Error> {
         let id = block_hash_or_number.into();
         Ok(match id {
-            BlockId::Hash(hash) => {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
                 let hash = utils::serialize(&hash);
                 self.request("xcb_getUncleCountByBlockHash", [hash]).await?
             }
@@ -332,7 +521,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         let blk_id = block_hash_or_number.into();
         let idx = utils::serialize(&idx);
         Ok(match blk_id {
-            BlockId::Hash(hash) => {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
                 let hash = utils::serialize(&hash);
                 self.request("xcb_getUncleByBlockHashAndIndex", [hash, idx]).await?
             }
@@ -363,7 +552,21 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         &self,
         block: T,
     ) -> Result<Vec<TransactionReceipt>, Self::Error> {
-        self.request("xcb_getBlockReceipts", [block.into()]).await
+        let block = block.into();
+        match self.request_optional("xcb_getBlockReceipts", [block]).await {
+            Err(ProviderError::UnsupportedRPC) => {}
+            res => return res,
+        }
+
+        // fall back to requesting the receipts one by one
+        let hashes = match self.get_block(block).await? {
+            Some(block) => block.transactions,
+            None => return Ok(Vec::new()),
+        };
+        let receipts =
+            future::try_join_all(hashes.into_iter().map(|hash| self.get_transaction_receipt(hash)))
+                .await?;
+        Ok(receipts.into_iter().flatten().collect())
     }
 
     async fn parity_block_receipts<T: Into<BlockNumber> + Send + Sync>(
@@ -693,10 +896,20 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     }
 
     async fn resolve_name(&self, ens_name: &str) -> Result<Address, ProviderError> {
-        self.query_resolver(ParamType::Address, ens_name, ens::ADDR_SELECTOR).await
+        if let Some(address) = self.ens_cache.as_ref().and_then(|cache| cache.address(ens_name)) {
+            return Ok(address)
+        }
+        let address = self.query_resolver(ParamType::Address, ens_name, ens::ADDR_SELECTOR).await?;
+        if let Some(cache) = &self.ens_cache {
+            cache.insert_address(ens_name, address);
+        }
+        Ok(address)
     }
 
     async fn lookup_address(&self, address: Address) -> Result<String, ProviderError> {
+        if let Some(domain) = self.ens_cache.as_ref().and_then(|cache| cache.name(address)) {
+            return Ok(domain)
+        }
         let ens_name = ens::reverse_address(address);
         let domain: String =
             self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR).await?;
@@ -704,6 +917,9 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         if address != reverse_address {
             Err(ProviderError::EnsNotOwned(domain))
         } else {
+            if let Some(cache) = &self.ens_cache {
+                cache.insert_name(address, domain.clone());
+            }
             Ok(domain)
         }
     }
@@ -801,15 +1017,15 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     }
 
     async fn txpool_content(&self) -> Result<TxpoolContent, ProviderError> {
-        self.request("txpool_content", ()).await
+        self.request_optional("txpool_content", ()).await
     }
 
     async fn txpool_inspect(&self) -> Result<TxpoolInspect, ProviderError> {
-        self.request("txpool_inspect", ()).await
+        self.request_optional("txpool_inspect", ()).await
     }
 
     async fn txpool_status(&self) -> Result<TxpoolStatus, ProviderError> {
-        self.request("txpool_status", ()).await
+        self.request_optional("txpool_status", ()).await
     }
 
     async fn debug_trace_transaction(
@@ -822,6 +1038,24 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("debug_traceTransaction", [tx_hash, trace_options]).await
     }
 
+    async fn debug_trace_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block: T,
+        trace_options: GoCoreDebugTracingOptions,
+    ) -> Result<Vec<GoCoreTxTrace>, ProviderError> {
+        let trace_options = utils::serialize(&trace_options);
+        match block.into() {
+            BlockId::Number(num) => {
+                let num = utils::serialize(&num);
+                self.request("debug_traceBlockByNumber", [num, trace_options]).await
+            }
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
+                let hash = utils::serialize(&hash);
+                self.request("debug_traceBlockByHash", [hash, trace_options]).await
+            }
+        }
+    }
+
     async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
         &self,
         req: T,
@@ -900,6 +1134,14 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("trace_filter", vec![filter]).await
     }
 
+    fn trace_filter_paginated<'a>(
+        &'a self,
+        filter: TraceFilter,
+        page_size: usize,
+    ) -> TraceQuery<'a, P> {
+        TraceQuery::new(self, filter).with_page_size(page_size)
+    }
+
     async fn trace_get<T: Into<U64> + Send + Sync>(
         &self,
         hash: H256,
@@ -1071,6 +1313,277 @@ impl<P: JsonRpcClient> Provider<P> {
         Ok(())
     }
 
+    /// Returns a stream of the node's sync progress that completes once the node is synced, see
+    /// [`SyncProgressStream`]
+    pub fn sync_progress(&self) -> SyncProgressStream<'_, P> {
+        SyncProgressStream::new(self)
+    }
+
+    /// Waits until the node reports that it is synced, polling `xcb_syncing` every `poll`.
+    ///
+    /// Failed requests are retried, so this can also be used to wait for a node that is still
+    /// starting up. If the node is not synced after `timeout`, the last request error is
+    /// returned, or a [`ProviderError::CustomError`] if there was none.
+    pub async fn wait_until_synced(
+        &self,
+        poll: Duration,
+        timeout: Duration,
+    ) -> Result<(), ProviderError> {
+        let mut last_err = None;
+        let synced = {
+            let wait = async {
+                let mut progress = self.sync_progress().interval(poll);
+                while let Some(res) = progress.next().await {
+                    match res {
+                        Ok(progress) => trace!(
+                            current = %progress.current_block,
+                            highest = %progress.highest_block,
+                            "node is syncing"
+                        ),
+                        Err(err) => last_err = Some(err),
+                    }
+                }
+            };
+            futures_util::pin_mut!(wait);
+            matches!(future::select(wait, Delay::new(timeout)).await, Either::Left(_))
+        };
+
+        if synced {
+            return Ok(())
+        }
+        Err(last_err.unwrap_or_else(|| {
+            ProviderError::CustomError(format!("node is still syncing after {timeout:?}"))
+        }))
+    }
+
+    /// Fetches the balance, nonce and code hash of all `addresses` at `block`, e.g. to sync the
+    /// state of a large set of accounts.
+    ///
+    /// Each account is read with a single `xcb_getProof` request, with up to
+    /// [`ACCOUNT_SNAPSHOT_CONCURRENCY`](crate::ACCOUNT_SNAPSHOT_CONCURRENCY) requests in flight.
+    /// If the node does not serve proofs, the accounts are read with `xcb_getBalance`,
+    /// `xcb_getTransactionCount` and `xcb_getCode` instead.
+    pub async fn get_account_snapshots(
+        &self,
+        addresses: &[Address],
+        block: Option<BlockId>,
+    ) -> Result<HashMap<Address, AccountSnapshot>, ProviderError> {
+        let (first, rest) = match addresses.split_first() {
+            Some(split) => split,
+            None => return Ok(HashMap::new()),
+        };
+        // the latest block is pinned so that all accounts are read from the same state
+        let block = match block {
+            None | Some(BlockId::Number(BlockNumber::Latest)) => {
+                Some(self.get_block_number().await?.into())
+            }
+            block => block,
+        };
+
+        let first_snapshot = match self.get_proof(*first, vec![], block).await {
+            Ok(proof) => AccountSnapshot::from(proof),
+            Err(err) if RpcError::is_error_response(&err) => {
+                trace!(%err, "xcb_getProof failed, reading accounts without proofs");
+                return stream::iter(addresses)
+                    .map(|address| async move {
+                        let snapshot =
+                            self.get_account_snapshot_without_proof(*address, block).await?;
+                        Ok::<_, ProviderError>((*address, snapshot))
+                    })
+                    .buffer_unordered(crate::ACCOUNT_SNAPSHOT_CONCURRENCY)
+                    .try_collect()
+                    .await
+            }
+            Err(err) => return Err(err),
+        };
+
+        let mut snapshots = stream::iter(rest)
+            .map(|address| async move {
+                let proof = self.get_proof(*address, vec![], block).await?;
+                Ok::<_, ProviderError>((*address, AccountSnapshot::from(proof)))
+            })
+            .buffer_unordered(crate::ACCOUNT_SNAPSHOT_CONCURRENCY)
+            .try_collect::<HashMap<_, _>>()
+            .await?;
+        snapshots.insert(*first, first_snapshot);
+        Ok(snapshots)
+    }
+
+    /// Resolves all `ens_names` to their addresses, e.g. to display a page of names.
+    ///
+    /// The names are resolved with up to
+    /// [`ENS_RESOLUTION_CONCURRENCY`](crate::ENS_RESOLUTION_CONCURRENCY) lookups in flight and
+    /// are served from the [ENS cache](Self::ens_cache_ttl) if it is enabled. Names that are not
+    /// registered are left out of the returned map.
+    pub async fn resolve_names(
+        &self,
+        ens_names: &[&str],
+    ) -> Result<HashMap<String, Address>, ProviderError> {
+        let ens_names: HashSet<&str> = ens_names.iter().copied().collect();
+        let mut lookups = stream::iter(ens_names)
+            .map(|ens_name| async move { (ens_name, self.resolve_name(ens_name).await) })
+            .buffer_unordered(ens::ENS_RESOLUTION_CONCURRENCY);
+
+        let mut addresses = HashMap::new();
+        while let Some((ens_name, res)) = lookups.next().await {
+            if let Some(address) = registered(res)? {
+                addresses.insert(ens_name.to_string(), address);
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Looks up the names of all `addresses`, e.g. to display a page of accounts.
+    ///
+    /// The reverse records are resolved with up to
+    /// [`ENS_RESOLUTION_CONCURRENCY`](crate::ENS_RESOLUTION_CONCURRENCY) lookups in flight and
+    /// are served from the [ENS cache](Self::ens_cache_ttl) if it is enabled. Addresses without
+    /// a reverse record, or whose name does not resolve back to them, are left out of the
+    /// returned map.
+    pub async fn lookup_addresses(
+        &self,
+        addresses: &[Address],
+    ) -> Result<HashMap<Address, String>, ProviderError> {
+        let addresses: HashSet<Address> = addresses.iter().copied().collect();
+        let mut lookups = stream::iter(addresses)
+            .map(|address| async move { (address, self.lookup_address(address).await) })
+            .buffer_unordered(ens::ENS_RESOLUTION_CONCURRENCY);
+
+        let mut names = HashMap::new();
+        while let Some((address, res)) = lookups.next().await {
+            if let Some(name) = registered(res)? {
+                names.insert(address, name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Returns the logs matching `filter` like [`get_logs`](Middleware::get_logs), but splits
+    /// the block range of the filter if the node rejects the query for returning too many logs.
+    ///
+    /// The range is halved and both halves are queried again, up to
+    /// [`GET_LOGS_MAX_SPLIT_DEPTH`](crate::GET_LOGS_MAX_SPLIT_DEPTH) times. The logs are returned
+    /// in block order. Filters for a single block hash can't be split and fail like
+    /// [`get_logs`](Middleware::get_logs).
+    pub async fn get_logs_auto_split(&self, filter: &Filter) -> Result<Vec<Log>, ProviderError> {
+        let err = match self.get_logs(filter).await {
+            Ok(logs) => return Ok(logs),
+            Err(err) if is_too_many_results(&err) && filter.get_block_hash().is_none() => err,
+            Err(err) => return Err(err),
+        };
+        let from = self.resolve_filter_block(filter.block_option.get_from_block()).await?;
+        let to = self.resolve_filter_block(filter.block_option.get_to_block()).await?;
+        if from >= to {
+            return Err(err)
+        }
+        trace!(%from, %to, "xcb_getLogs returned too many results, splitting the block range");
+
+        let mut logs = Vec::new();
+        // the lower half is pushed last so that the logs are collected in block order
+        let mut ranges = vec![(from, to, 0)];
+        while let Some((from, to, depth)) = ranges.pop() {
+            let range = filter.clone().from_block(from).to_block(to);
+            match self.get_logs(&range).await {
+                Ok(mut range_logs) => logs.append(&mut range_logs),
+                Err(err)
+                    if is_too_many_results(&err) &&
+                        from < to &&
+                        depth < GET_LOGS_MAX_SPLIT_DEPTH =>
+                {
+                    let mid = from + (to - from) / 2;
+                    ranges.push((mid + 1u64, to, depth + 1));
+                    ranges.push((from, mid, depth + 1));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Returns the number of a `fromBlock` or `toBlock` of a filter, which default to the latest
+    /// block
+    async fn resolve_filter_block(
+        &self,
+        block: Option<&BlockNumber>,
+    ) -> Result<U64, ProviderError> {
+        match block {
+            Some(BlockNumber::Number(number)) => Ok(*number),
+            Some(BlockNumber::Earliest) => Ok(U64::zero()),
+            None | Some(BlockNumber::Latest) | Some(BlockNumber::Pending) => {
+                self.get_block_number().await
+            }
+            Some(tag) => self
+                .get_block(*tag)
+                .await?
+                .and_then(|block| block.number)
+                .ok_or_else(|| ProviderError::CustomError(format!("block {tag} not found"))),
+        }
+    }
+
+    /// Executes the `txs` in order on top of the state of `block`, each transaction sees the
+    /// effects of the previous ones, e.g. to preview an approval followed by a swap. Nothing is
+    /// broadcast.
+    ///
+    /// The bundle is executed with a single `trace_callMany` request. If the node does not serve
+    /// the `trace` namespace, every transaction is executed with `debug_traceCall` instead, with
+    /// the state changes of the previous transactions, traced with the `prestateTracer`, as state
+    /// overrides.
+    pub async fn simulate_bundle(
+        &self,
+        txs: Vec<TypedTransaction>,
+        block: Option<BlockNumber>,
+    ) -> Result<Vec<SimulatedTransaction>, ProviderError> {
+        let calls = txs.iter().map(|tx| (tx.clone(), vec![TraceType::Trace])).collect();
+        match self.trace_call_many(calls, block).await {
+            Ok(traces) => return Ok(traces.into_iter().map(Into::into).collect()),
+            Err(err) if is_method_not_found(&err) => {
+                trace!("trace_callMany is not supported, simulating with debug_traceCall")
+            }
+            Err(err) => return Err(err),
+        }
+
+        // the latest block is pinned so that all transactions run on top of the same state
+        let block = match block {
+            None | Some(BlockNumber::Latest) => BlockNumber::from(self.get_block_number().await?),
+            Some(block) => block,
+        };
+        let block = utils::serialize(&block);
+        let mut state = spoof::State::default();
+        let mut results = Vec::with_capacity(txs.len());
+        for (i, tx) in txs.iter().enumerate() {
+            let tx = utils::serialize(tx);
+            let options = serde_json::json!({ "tracer": "callTracer", "stateOverrides": state });
+            let frame: CallFrame =
+                self.request("debug_traceCall", [tx.clone(), block.clone(), options]).await?;
+            results.push(frame.into());
+            if i + 1 == txs.len() {
+                break
+            }
+
+            let options = serde_json::json!({
+                "tracer": "prestateTracer",
+                "tracerConfig": { "diffMode": true },
+                "stateOverrides": state,
+            });
+            let diff = self.request("debug_traceCall", [tx, block.clone(), options]).await?;
+            crate::toolbox::apply_state_diff(&mut state, diff);
+        }
+        Ok(results)
+    }
+
+    async fn get_account_snapshot_without_proof(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<AccountSnapshot, ProviderError> {
+        let (balance, nonce, code) = try_join!(
+            self.get_balance(address, block),
+            self.get_transaction_count(address, block),
+            self.get_code(address, block)
+        )?;
+        Ok(AccountSnapshot::new(balance, nonce, &code))
+    }
+
     /// Sets the ENS Address (default: mainnet)
     #[must_use]
     pub fn ens<T: Into<Address>>(mut self, ens: T) -> Self {
@@ -1078,6 +1591,22 @@ impl<P: JsonRpcClient> Provider<P> {
         self
     }
 
+    /// Caches resolved names and reverse records for `ttl` (default: disabled)
+    ///
+    /// Clones of the provider share the cache.
+    #[must_use]
+    pub fn ens_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ens_cache = Some(Arc::new(ens::EnsCache::new(ttl)));
+        self
+    }
+
+    /// Returns the cache of resolved names, if enabled with [`Self::ens_cache_ttl`].
+    ///
+    /// Use it to invalidate names whose records changed before their entries expire.
+    pub fn ens_cache(&self) -> Option<&ens::EnsCache> {
+        self.ens_cache.as_deref()
+    }
+
     /// Sets the default polling interval for event filters and pending transactions
     /// (default: 7 seconds)
     pub fn set_interval<T: Into<Duration>>(&mut self, interval: T) -> &mut Self {
@@ -1098,6 +1627,167 @@ impl<P: JsonRpcClient> Provider<P> {
     pub fn get_interval(&self) -> Duration {
         self.interval.unwrap_or(DEFAULT_POLL_INTERVAL)
     }
+
+    /// Sets the safety margin that is added to energy estimates when filling transactions
+    /// (default: none)
+    pub fn set_energy_margin(&mut self, margin: EnergyMargin) -> &mut Self {
+        self.energy_margin = Some(margin);
+        self
+    }
+
+    /// Sets the safety margin that is added to energy estimates when filling transactions
+    /// (default: none)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_providers::{EnergyMargin, Provider};
+    ///
+    /// // estimates are raised by 20% before being set on transactions
+    /// let (provider, _mock) = Provider::mocked();
+    /// let provider = provider.energy_margin(EnergyMargin::Percent(20));
+    /// ```
+    #[must_use]
+    pub fn energy_margin(mut self, margin: EnergyMargin) -> Self {
+        self.set_energy_margin(margin);
+        self
+    }
+
+    /// Gets the safety margin that is added to energy estimates when filling transactions
+    pub fn get_energy_margin(&self) -> Option<EnergyMargin> {
+        self.energy_margin
+    }
+
+    /// Sets whether transactions filled without a block are estimated against the pending block
+    /// (default: false)
+    ///
+    /// Estimates against the latest block ignore the sender's pending transactions, which can
+    /// spend the balance or change the state the transaction depends on. When enabled, a missing
+    /// nonce is also filled with the sender's pending transaction count.
+    pub fn set_estimate_pending(&mut self, estimate_pending: bool) -> &mut Self {
+        self.estimate_pending = estimate_pending;
+        self
+    }
+
+    /// Sets whether transactions filled without a block are estimated against the pending block
+    /// (default: false)
+    ///
+    /// See [`Self::set_estimate_pending`].
+    #[must_use]
+    pub fn estimate_pending(mut self, estimate_pending: bool) -> Self {
+        self.set_estimate_pending(estimate_pending);
+        self
+    }
+
+    /// Returns whether transactions are estimated against the pending block
+    pub fn get_estimate_pending(&self) -> bool {
+        self.estimate_pending
+    }
+
+    /// Sets the timeout of requests without a [method timeout](Self::method_timeout)
+    /// (default: none)
+    pub fn set_request_timeout<T: Into<Duration>>(&mut self, timeout: T) -> &mut Self {
+        self.request_timeouts.default = Some(timeout.into());
+        self
+    }
+
+    /// Sets the timeout of requests without a [method timeout](Self::method_timeout), requests
+    /// that take longer fail with [`ProviderError::RequestTimeout`] (default: none)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_providers::Provider;
+    /// use std::time::Duration;
+    ///
+    /// // interactive calls fail fast, traces of archive nodes may take a while
+    /// let (provider, _mock) = Provider::mocked();
+    /// let provider = provider
+    ///     .request_timeout(Duration::from_secs(10))
+    ///     .method_timeout("xcb_blockNumber", Duration::from_secs(2))
+    ///     .method_timeout("debug_trace*", Duration::from_secs(120));
+    /// ```
+    #[must_use]
+    pub fn request_timeout<T: Into<Duration>>(mut self, timeout: T) -> Self {
+        self.set_request_timeout(timeout);
+        self
+    }
+
+    /// Sets the timeout of requests to `method`, which may be a prefix ending with `*`, e.g.
+    /// `debug_trace*`
+    pub fn set_method_timeout<T: Into<Duration>>(
+        &mut self,
+        method: impl Into<String>,
+        timeout: T,
+    ) -> &mut Self {
+        self.request_timeouts.set(method, timeout.into());
+        self
+    }
+
+    /// Sets the timeout of requests to `method`, which may be a prefix ending with `*`, e.g.
+    /// `debug_trace*`
+    #[must_use]
+    pub fn method_timeout<T: Into<Duration>>(
+        mut self,
+        method: impl Into<String>,
+        timeout: T,
+    ) -> Self {
+        self.set_method_timeout(method, timeout);
+        self
+    }
+
+    /// Gets the timeouts of requests
+    pub fn get_request_timeouts(&self) -> &RequestTimeouts {
+        &self.request_timeouts
+    }
+
+    /// Sets how the hex quantities of responses are parsed (default: [`QuantityMode::Lenient`])
+    pub fn set_quantity_mode(&mut self, mode: QuantityMode) -> &mut Self {
+        self.quantity_mode = mode;
+        self
+    }
+
+    /// Sets how the hex quantities of responses are parsed (default: [`QuantityMode::Lenient`])
+    ///
+    /// Quantities of requests are always sent in their most compact form. In
+    /// [`QuantityMode::Strict`], responses with quantities that have leading zeros fail with
+    /// [`ProviderError::InvalidQuantity`], which names the offending field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_providers::{Provider, QuantityMode};
+    ///
+    /// // surface endpoints that deviate from the spec instead of silently accepting them
+    /// let (provider, _mock) = Provider::mocked();
+    /// let provider = provider.quantity_mode(QuantityMode::Strict);
+    /// ```
+    #[must_use]
+    pub fn quantity_mode(mut self, mode: QuantityMode) -> Self {
+        self.set_quantity_mode(mode);
+        self
+    }
+
+    /// Gets how the hex quantities of responses are parsed
+    pub fn get_quantity_mode(&self) -> QuantityMode {
+        self.quantity_mode
+    }
+}
+
+impl<P: PubsubClient> Provider<P> {
+    /// Subscribes to new blocks like [`Middleware::subscribe_blocks`], but also yields
+    /// [`Heartbeat::Stalled`](crate::Heartbeat::Stalled) if no block arrives for
+    /// [`DEFAULT_STALL_BLOCKS`](crate::DEFAULT_STALL_BLOCKS) average block times of the network
+    /// the node is on.
+    ///
+    /// See [`StalenessMonitor`] to choose the timeout.
+    pub async fn subscribe_blocks_monitored(
+        &self,
+    ) -> Result<StalenessMonitor<SubscriptionStream<'_, P, Block<TxHash>>>, ProviderError> {
+        let network = Network::from(self.network_id().await?.low_u64());
+        let blocks = self.subscribe_blocks().await?;
+        Ok(StalenessMonitor::for_network(blocks, network))
+    }
 }
 
 #[cfg(all(feature = "ipc", any(unix, windows)))]
@@ -1175,6 +1865,37 @@ impl Provider<MockProvider> {
     }
 }
 
+/// Whether the node responded that it does not know the requested method
+fn is_method_not_found(err: &ProviderError) -> bool {
+    err.as_error_response().map_or(false, |err| err.code == -32601)
+}
+
+/// Turns the error of a name that is not registered, or not owned by the looked up address, into
+/// `None`
+fn registered<T>(res: Result<T, ProviderError>) -> Result<Option<T>, ProviderError> {
+    match res {
+        Ok(value) => Ok(Some(value)),
+        Err(ProviderError::EnsError(_)) | Err(ProviderError::EnsNotOwned(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Whether the node rejected a `xcb_getLogs` query because its result is too large
+fn is_too_many_results(err: &ProviderError) -> bool {
+    const MESSAGES: [&str; 6] = [
+        "more than",
+        "too many",
+        "size exceeded",
+        "range is too",
+        "range too large",
+        "limit exceeded",
+    ];
+    err.as_error_response().map_or(false, |err| {
+        let message = err.message.to_lowercase();
+        MESSAGES.iter().any(|needle| message.contains(needle))
+    })
+}
+
 /// infallible conversion of Bytes to Address/String
 ///
 /// # Panics
@@ -1340,10 +2061,13 @@ pub fn is_local_endpoint(url: &str) -> bool {
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
     use super::*;
-    use crate::Http;
+    use crate::{Http, JsonRpcError, RevertReason};
     use corebc_core::{
-        types::{TransactionRequest, H256},
-        utils::{Genesis, GoCore, GoCoreInstance},
+        types::{
+            Action, ActionType, Call, CallResult, GoCoreDebugBuiltInTracerType,
+            GoCoreDebugTracerType, Res, TransactionRequest, TransactionTrace, H256,
+        },
+        utils::{id, Genesis, GoCore, GoCoreInstance},
     };
     use std::path::PathBuf;
 
@@ -1540,6 +2264,7 @@ mod tests {
             .to(to)
             .energy(energy)
             .energy_price(energy_price)
+            .network_id(3)
             .into();
         provider.fill_transaction(&mut tx, None).await.unwrap();
 
@@ -1547,17 +2272,562 @@ mod tests {
         assert_eq!(tx.to(), Some(&to.into()));
         assert_eq!(tx.energy(), Some(&energy));
         assert_eq!(tx.energy_price(), Some(energy_price));
+        assert_eq!(tx.network_id(), Some(U64::from(3)));
 
         // --- fills an empty legacy transaction
         let mut tx = TransactionRequest::new().into();
         mock.push(energy).unwrap();
         mock.push(energy_price).unwrap();
+        mock.push(U256::one()).unwrap();
         provider.fill_transaction(&mut tx, None).await.unwrap();
 
         assert_eq!(tx.from(), provider.from.as_ref());
         assert!(tx.to().is_none());
         assert_eq!(tx.energy(), Some(&energy));
         assert_eq!(tx.energy_price(), Some(energy_price));
+        assert_eq!(tx.network_id(), Some(U64::one()));
+    }
+
+    #[tokio::test]
+    async fn test_fill_transaction_energy_margin() {
+        let (provider, mock) = Provider::mocked();
+        let mut provider = provider.energy_margin(EnergyMargin::Percent(20));
+
+        let mut tx = TransactionRequest::new().energy_price(1).into();
+        mock.push(U256::from(21000)).unwrap();
+        mock.push(U256::one()).unwrap();
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.energy(), Some(&U256::from(25200)));
+
+        // explicitly set energy limits are left untouched
+        provider.set_energy_margin(EnergyMargin::Fixed(U256::from(5000)));
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.energy(), Some(&U256::from(25200)));
+
+        let mut tx = TransactionRequest::new().energy_price(1).into();
+        mock.push(U256::from(21000)).unwrap();
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.energy(), Some(&U256::from(26000)));
+    }
+
+    #[tokio::test]
+    async fn test_fill_transaction_estimate_pending() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.estimate_pending(true);
+        let from = Address::random();
+
+        let mut tx = TransactionRequest::new().from(from).energy_price(1).into();
+        mock.expect("xcb_networkId").times(1).returns(U256::one());
+        mock.expect("xcb_getTransactionCount")
+            .with_params((from, "pending"))
+            .returns(U256::from(4));
+        mock.expect("xcb_estimateEnergy")
+            .with_params_matching(|params| params[1] == "pending")
+            .returns(U256::from(21000));
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.nonce(), Some(&U256::from(4)));
+        assert_eq!(tx.energy(), Some(&U256::from(21000)));
+
+        // an explicit block is respected
+        let mut tx = TransactionRequest::new().from(from).energy_price(1).into();
+        mock.expect("xcb_estimateEnergy")
+            .with_params_matching(|params| params[1] == "latest")
+            .returns(U256::from(22000));
+        provider.fill_transaction(&mut tx, Some(BlockNumber::Latest.into())).await.unwrap();
+        assert_eq!(tx.nonce(), None);
+        assert_eq!(tx.energy(), Some(&U256::from(22000)));
+    }
+
+    #[tokio::test]
+    async fn test_network_id_cache() {
+        let (provider, mock) = Provider::mocked();
+        mock.expect("xcb_networkId").times(1).returns(U256::from(3));
+
+        // requested once, then served from the cache
+        assert_eq!(provider.network_id().await.unwrap(), U256::from(3));
+        let mut tx = TransactionRequest::new().energy(21000).energy_price(1).into();
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.network_id(), Some(U64::from(3)));
+        mock.verify().unwrap();
+
+        // a refresh replaces the cached id
+        mock.expect("xcb_networkId").times(1).returns(U256::from(4));
+        assert_eq!(provider.refresh_network_id().await.unwrap(), U256::from(4));
+        assert_eq!(provider.network_id().await.unwrap(), U256::from(4));
+        mock.verify().unwrap();
+    }
+
+    #[test]
+    fn parses_node_client_info() {
+        let info = NodeClientInfo::parse("Gocore/v1.1.11-stable-7cd4e4b6/linux-amd64/go1.20.4");
+        assert_eq!(info.name, "Gocore");
+        assert_eq!(info.version, Some(Version::new(1, 1, 11)));
+        assert_eq!(info.client(), Some(NodeClient::GoCore));
+
+        let info = NodeClientInfo::parse("go-core/validator-1/v2.0/linux-amd64");
+        assert_eq!(info.client(), Some(NodeClient::GoCore));
+        assert_eq!(info.version, Some(Version::new(2, 0, 0)));
+
+        let info = NodeClientInfo::parse("anvil/v0.1.0");
+        assert_eq!(info.client(), Some(NodeClient::Anvil));
+
+        let info = NodeClientInfo::parse("Shuttle");
+        assert_eq!((info.client(), info.version), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_fallback() {
+        let (provider, mock) = Provider::mocked();
+        let hashes = [H256::random(), H256::random()];
+        mock.expect("xcb_getBlockReceipts").times(1).return_error(JsonRpcError {
+            code: -32601,
+            message: "the method xcb_getBlockReceipts does not exist/is not available".to_string(),
+            data: None,
+        });
+        // the node is asked for the block receipts only once
+        for _ in 0..2 {
+            mock.push_for(
+                "xcb_getBlockByNumber",
+                Block { transactions: hashes.to_vec(), ..Default::default() },
+            )
+            .unwrap();
+            for hash in hashes {
+                let receipt = TransactionReceipt { transaction_hash: hash, ..Default::default() };
+                mock.push_for("xcb_getTransactionReceipt", receipt).unwrap();
+            }
+
+            let receipts = provider.get_block_receipts(7).await.unwrap();
+            let receipt_hashes: Vec<_> = receipts.iter().map(|r| r.transaction_hash).collect();
+            assert_eq!(receipt_hashes, hashes);
+        }
+        mock.verify().unwrap();
+    }
+
+    /// A client that answers every request with the same value after a delay
+    #[derive(Debug)]
+    struct SlowClient(Duration);
+
+    #[async_trait]
+    impl JsonRpcClient for SlowClient {
+        type Error = ProviderError;
+
+        async fn request<T, R>(&self, _method: &str, _params: T) -> Result<R, ProviderError>
+        where
+            T: Debug + Serialize + Send + Sync,
+            R: DeserializeOwned + Send,
+        {
+            tokio::time::sleep(self.0).await;
+            Ok(serde_json::from_value(serde_json::json!("0x7"))?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_timeouts() {
+        let provider = Provider::new(SlowClient(Duration::from_millis(100)))
+            .request_timeout(Duration::from_millis(10))
+            .method_timeout("xcb_blockNumber", Duration::from_secs(5));
+
+        // the override is long enough
+        assert_eq!(provider.get_block_number().await.unwrap().as_u64(), 7);
+
+        let err = provider.get_networkid().await.unwrap_err();
+        match err {
+            ProviderError::RequestTimeout { method, timeout } => {
+                assert_eq!(method, "xcb_networkId");
+                assert_eq!(timeout, Duration::from_millis(10));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quantity_mode() {
+        let (provider, mock) = Provider::mocked();
+        let receipt = serde_json::json!({ "blockNumber": "0x7", "logs": [{ "logIndex": "0x01" }] });
+        mock.expect("xcb_call")
+            .with_params(serde_json::json!([{ "value": "0x10" }, "latest"]))
+            .returns("0x");
+        mock.expect("xcb_getTransactionReceipt").returns(receipt);
+
+        // requests are sent with compact quantities
+        let params = serde_json::json!([{ "value": "0x0010" }, "latest"]);
+        let res: Bytes = provider.request("xcb_call", params).await.unwrap();
+        assert!(res.is_empty());
+
+        // zero sized params are passed through
+        mock.push(U64::from(7)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(7));
+        mock.assert_request("xcb_call", serde_json::json!([{ "value": "0x10" }, "latest"]))
+            .unwrap();
+        mock.assert_request("xcb_blockNumber", ()).unwrap();
+
+        let hash = H256::repeat_byte(1);
+        let receipt: serde_json::Value =
+            provider.request("xcb_getTransactionReceipt", [hash]).await.unwrap();
+        assert_eq!(receipt["logs"][0]["logIndex"], "0x01");
+
+        let provider = provider.quantity_mode(QuantityMode::Strict);
+        let err = provider
+            .request::<_, serde_json::Value>("xcb_getTransactionReceipt", [hash])
+            .await
+            .unwrap_err();
+        match err {
+            ProviderError::InvalidQuantity(err) => {
+                assert_eq!(err.field, "logs[0].logIndex");
+                assert_eq!(err.value, "0x01");
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ens_cache() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.ens_cache_ttl(Duration::from_secs(60));
+        let resolver = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+
+        mock.push_for("xcb_call", encode(abi::Token::Address(resolver))).unwrap();
+        mock.push_for("xcb_call", encode(abi::Token::Bool(true))).unwrap();
+        mock.push_for("xcb_call", encode(abi::Token::Address(owner))).unwrap();
+        assert_eq!(provider.resolve_name("owner.xcb").await.unwrap(), owner);
+        // resolved from the cache without any requests
+        assert_eq!(provider.resolve_name("owner.xcb").await.unwrap(), owner);
+        assert_eq!(provider.clone().resolve_name("owner.xcb").await.unwrap(), owner);
+
+        provider.ens_cache().unwrap().invalidate_name("owner.xcb");
+        let err = provider.resolve_name("owner.xcb").await.unwrap_err();
+        assert!(err.to_string().contains("empty responses"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_names_and_lookup_addresses() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+        let stranger = Address::repeat_byte(3);
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+        let expect_call = |tx: TransactionRequest, returns: Bytes| {
+            let data = serde_json::to_value(tx.data).unwrap();
+            mock.expect("xcb_call")
+                .with_params_matching(move |params| params[0]["data"] == data)
+                .returns(returns);
+        };
+
+        // `owner.xcb` resolves to `owner` and the reverse record of `owner` points back to it
+        for name in ["owner.xcb", ens::reverse_address(owner).as_str()] {
+            expect_call(
+                ens::get_resolver(ens::CNS_ADDRESS, name),
+                encode(abi::Token::Address(resolver)),
+            );
+        }
+        expect_call(
+            ens::supports_interface(resolver, ens::ADDR_SELECTOR),
+            encode(abi::Token::Bool(true)),
+        );
+        expect_call(
+            ens::resolve(resolver, ens::ADDR_SELECTOR, "owner.xcb", None),
+            encode(abi::Token::Address(owner)),
+        );
+        expect_call(
+            ens::resolve(resolver, ens::NAME_SELECTOR, &ens::reverse_address(owner), None),
+            encode(abi::Token::String("owner.xcb".to_string())),
+        );
+        // neither `unknown.xcb` nor the reverse record of `stranger` are registered
+        for name in ["unknown.xcb", ens::reverse_address(stranger).as_str()] {
+            expect_call(
+                ens::get_resolver(ens::CNS_ADDRESS, name),
+                encode(abi::Token::Address(Address::zero())),
+            );
+        }
+
+        let addresses =
+            provider.resolve_names(&["owner.xcb", "unknown.xcb", "owner.xcb"]).await.unwrap();
+        assert_eq!(addresses, HashMap::from([("owner.xcb".to_string(), owner)]));
+
+        let names = provider.lookup_addresses(&[owner, stranger]).await.unwrap();
+        assert_eq!(names, HashMap::from([(owner, "owner.xcb".to_string())]));
+
+        assert!(provider.resolve_names(&[]).await.unwrap().is_empty());
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_auto_split() {
+        let (provider, mock) = Provider::mocked();
+        let too_many = || JsonRpcError {
+            code: -32005,
+            message: "query returned more than 10000 results".to_string(),
+            data: None,
+        };
+        let log = |block: u64| Log { block_number: Some(block.into()), ..Default::default() };
+        let range = |from: u64, to: u64| {
+            let (from, to) = (format!("{from:#x}"), format!("{to:#x}"));
+            move |params: &serde_json::Value| {
+                params[0]["fromBlock"] == from.as_str() && params[0]["toBlock"] == to.as_str()
+            }
+        };
+
+        // `0..=7` is split into `0..=3` and `4..=7`, `0..=3` once more
+        mock.expect("xcb_getLogs").with_params_matching(range(0, 7)).return_error(too_many());
+        mock.expect("xcb_getLogs").with_params_matching(range(0, 3)).return_error(too_many());
+        mock.expect("xcb_getLogs").with_params_matching(range(0, 1)).returns(vec![log(1)]);
+        mock.expect("xcb_getLogs").with_params_matching(range(2, 3)).returns(vec![log(2)]);
+        mock.expect("xcb_getLogs").with_params_matching(range(4, 7)).returns(vec![log(4), log(7)]);
+
+        let filter = Filter::new().from_block(0u64).to_block(7u64);
+        let logs = provider.get_logs_auto_split(&filter).await.unwrap();
+        let blocks: Vec<_> = logs.iter().map(|log| log.block_number.unwrap().as_u64()).collect();
+        assert_eq!(blocks, vec![1, 2, 4, 7]);
+        mock.verify().unwrap();
+
+        // a single block can't be split
+        mock.expect("xcb_getLogs").with_params_matching(range(5, 5)).return_error(too_many());
+        let filter = Filter::new().from_block(5u64).to_block(5u64);
+        let err = provider.get_logs_auto_split(&filter).await.unwrap_err();
+        assert!(is_too_many_results(&err), "{err}");
+
+        // other errors are not retried
+        mock.expect("xcb_getLogs").times(1).return_error(JsonRpcError {
+            code: -32000,
+            message: "filter not found".to_string(),
+            data: None,
+        });
+        let filter = Filter::new().from_block(0u64).to_block(9u64);
+        assert!(provider.get_logs_auto_split(&filter).await.is_err());
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_account_snapshots() {
+        let (provider, mock) = Provider::mocked();
+        let a1 = Address::from_low_u64_be(1);
+        let a2 = Address::from_low_u64_be(2);
+
+        // --- reads proofs at the pinned latest block
+        let proof = |address, balance: u64| EIP1186ProofResponse {
+            address,
+            balance: balance.into(),
+            nonce: 3.into(),
+            code_hash: H256::from(utils::sha3([])),
+            ..Default::default()
+        };
+        mock.push_for("xcb_blockNumber", U64::from(10)).unwrap();
+        for (address, balance) in [(a1, 100u64), (a2, 200)] {
+            mock.expect("xcb_getProof")
+                .with_params((address, Vec::<H256>::new(), "0xa"))
+                .times(1)
+                .returns(proof(address, balance));
+        }
+        let snapshots = provider.get_account_snapshots(&[a1, a2], None).await.unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[&a1].balance, U256::from(100));
+        assert_eq!(snapshots[&a2].balance, U256::from(200));
+        assert_eq!(snapshots[&a2].nonce, U256::from(3));
+        assert!(!snapshots[&a2].is_contract());
+        mock.verify().unwrap();
+
+        // --- falls back to plain reads if the node does not serve proofs
+        mock.expect("xcb_getProof").times(1).return_error(JsonRpcError {
+            code: -32601,
+            message: "the method xcb_getProof does not exist".to_string(),
+            data: None,
+        });
+        mock.push_for("xcb_getBalance", U256::from(5)).unwrap();
+        mock.push_for("xcb_getTransactionCount", U256::from(1)).unwrap();
+        let code = Bytes::from(vec![0x60u8, 0x00]);
+        mock.expect("xcb_getCode").with_params((a1, "0x7")).times(1).returns(&code);
+        let block = BlockNumber::Number(7.into()).into();
+        let snapshots = provider.get_account_snapshots(&[a1], Some(block)).await.unwrap();
+        assert_eq!(snapshots[&a1], AccountSnapshot::new(5.into(), 1.into(), &code));
+        assert!(snapshots[&a1].is_contract());
+    }
+
+    #[tokio::test]
+    async fn test_next_contract_address() {
+        let (provider, mock) = Provider::mocked();
+        let from: Address = "0x00006ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse().unwrap();
+
+        mock.push(U256::from(1)).unwrap();
+        mock.push(U256::from(2)).unwrap();
+        let address = provider.next_contract_address(from).await.unwrap();
+        assert_eq!(address, "cb38a7d7c76ecab54e75a0d6a06faf3b441a304a9c64".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_revert_reason() {
+        let (provider, mock) = Provider::mocked();
+        let hash = H256::random();
+
+        // --- a successful transaction has no revert reason
+        let receipt = TransactionReceipt { status: Some(1.into()), ..Default::default() };
+        mock.push(receipt).unwrap();
+        assert_eq!(provider.revert_reason(hash).await.unwrap(), None);
+
+        // --- falls back to tracing when the replay does not fail
+        let receipt = TransactionReceipt {
+            status: Some(0.into()),
+            block_number: Some(10.into()),
+            ..Default::default()
+        };
+        let revert_data =
+            [&id("Error(string)")[..], &abi::encode(&[abi::Token::String("not owner".into())])]
+                .concat();
+        let frame = CallFrame {
+            error: Some("execution reverted".to_string()),
+            output: Some(revert_data.into()),
+            ..Default::default()
+        };
+        // responses are popped from the back
+        mock.push(frame).unwrap();
+        mock.push(Bytes::default()).unwrap();
+        mock.push(Transaction::default()).unwrap();
+        mock.push(receipt).unwrap();
+
+        assert_eq!(
+            provider.revert_reason(hash).await.unwrap(),
+            Some(RevertReason::Message("not owner".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_debug_trace_block() {
+        let (provider, mock) = Provider::mocked();
+        let tx_hash = H256::random();
+        let frame = CallFrame { typ: "CALL".to_string(), ..Default::default() };
+        let traces = serde_json::json!([
+            { "txHash": tx_hash, "result": frame },
+            { "error": "execution timeout" },
+        ]);
+        let options = GoCoreDebugTracingOptions {
+            tracer: Some(GoCoreDebugTracerType::BuiltInTracer(
+                GoCoreDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        };
+
+        mock.expect("debug_traceBlockByNumber")
+            .with_params(serde_json::json!(["0xa", { "tracer": "callTracer" }]))
+            .returns(traces.clone());
+        let block_hash = H256::random();
+        mock.expect("debug_traceBlockByHash")
+            .with_params(serde_json::json!([block_hash, { "tracer": "callTracer" }]))
+            .times(2)
+            .returns(traces);
+
+        let by_number = provider.debug_trace_block(10u64, options.clone()).await.unwrap();
+        assert_eq!(by_number.len(), 2);
+        assert_eq!(by_number[0].tx_hash, Some(tx_hash));
+        assert_eq!(by_number[0].call_frame(), Some(&frame));
+        assert_eq!(by_number[1].call_frame(), None);
+        assert_eq!(by_number[1].error.as_deref(), Some("execution timeout"));
+
+        let by_hash = provider.debug_trace_block(block_hash, options.clone()).await.unwrap();
+        assert_eq!(by_hash, by_number);
+        let canonical = BlockId::CanonicalHash(block_hash);
+        let by_canonical_hash = provider.debug_trace_block(canonical, options).await.unwrap();
+        assert_eq!(by_canonical_hash, by_number);
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_simulate_bundle() {
+        let (provider, mock) = Provider::mocked();
+        let txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::new().to(Address::repeat_byte(1)).into(),
+            TransactionRequest::new().to(Address::repeat_byte(2)).into(),
+        ];
+        let trace = |error: Option<&str>| BlockTrace {
+            output: vec![1].into(),
+            trace: Some(vec![TransactionTrace {
+                trace_address: vec![],
+                subtraces: 0,
+                action: Action::Call(Call::default()),
+                action_type: ActionType::Call,
+                result: Some(Res::Call(CallResult {
+                    energy_used: 21_000.into(),
+                    output: vec![1].into(),
+                })),
+                error: error.map(str::to_string),
+            }]),
+            vm_trace: None,
+            state_diff: None,
+            transaction_hash: None,
+        };
+        mock.expect("trace_callMany")
+            .with_params(serde_json::json!([
+                [[utils::serialize(&txs[0]), ["trace"]], [utils::serialize(&txs[1]), ["trace"]]],
+                "latest"
+            ]))
+            .returns(vec![trace(None), trace(Some("Reverted"))]);
+
+        let results = provider.simulate_bundle(txs, None).await.unwrap();
+        assert!(results[0].is_success());
+        assert_eq!(results[0].energy_used, Some(21_000.into()));
+        assert_eq!(results[0].output, Bytes::from(vec![1]));
+        assert_eq!(results[1].error.as_deref(), Some("Reverted"));
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_simulate_bundle_with_debug_trace_call() {
+        let (provider, mock) = Provider::mocked();
+        let token = Address::repeat_byte(1);
+        let txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::new().to(token).data(vec![1]).into(),
+            TransactionRequest::new().to(token).data(vec![2]).into(),
+        ];
+        let slot = H256::from_low_u64_be(1);
+        let value = H256::from_low_u64_be(100);
+        // the calls run on top of the pinned block, with the state changes so far as overrides
+        let overridden_slot = move |params: &serde_json::Value| {
+            let overrides = &params[2]["stateOverrides"];
+            overrides[format!("{token:?}")]["stateDiff"][format!("{slot:?}")] ==
+                format!("{value:?}")
+        };
+        let traced_with = move |tracer: &'static str, overridden: bool| {
+            move |params: &serde_json::Value| {
+                params[1] == "0x5" &&
+                    params[2]["tracer"] == tracer &&
+                    overridden_slot(params) == overridden
+            }
+        };
+
+        mock.expect("trace_callMany").return_error(JsonRpcError {
+            code: -32601,
+            message: "the method trace_callMany does not exist/is not available".to_string(),
+            data: None,
+        });
+        mock.expect("xcb_blockNumber").returns(U64::from(5));
+        mock.expect("debug_traceCall")
+            .with_params_matching(traced_with("callTracer", false))
+            .times(1)
+            .returns(CallFrame { output: Some(vec![1].into()), ..Default::default() });
+        // the first transaction writes a storage slot of the token
+        mock.expect("debug_traceCall")
+            .with_params_matching(traced_with("prestateTracer", false))
+            .times(1)
+            .returns(serde_json::json!({
+                "pre": { format!("{token:?}"): { "balance": "0x0" } },
+                "post": { format!("{token:?}"): { "storage": { format!("{slot:?}"): value } } },
+            }));
+        mock.expect("debug_traceCall")
+            .with_params_matching(traced_with("callTracer", true))
+            .times(1)
+            .returns(CallFrame {
+                energy_used: 30_000.into(),
+                error: Some("execution reverted".to_string()),
+                ..Default::default()
+            });
+
+        let results = provider.simulate_bundle(txs, None).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_success());
+        assert_eq!(results[0].output, Bytes::from(vec![1]));
+        assert!(!results[1].is_success());
+        assert_eq!(results[1].energy_used, Some(30_000.into()));
+        mock.verify().unwrap();
     }
 
     // CORETODO: Uncomment once signatures are done
@@ -0,0 +1,198 @@
+//! Encoding of hex quantities in JSON-RPC requests and responses
+
+use serde_json::Value;
+use std::fmt::Write;
+use thiserror::Error;
+
+/// The fields whose values are hex encoded quantities, e.g. `"0x1a"`.
+///
+/// The `nonce` of blocks is 8 bytes of data rather than a quantity, see [`is_quantity_field`].
+const QUANTITY_FIELDS: &[&str] = &[
+    "balance",
+    "blockNumber",
+    "cumulativeEnergyUsed",
+    "currentBlock",
+    "difficulty",
+    "energy",
+    "energyLimit",
+    "energyPrice",
+    "energyUsed",
+    "highestBlock",
+    "logIndex",
+    "networkId",
+    "nonce",
+    "number",
+    "size",
+    "startingBlock",
+    "status",
+    "timestamp",
+    "totalDifficulty",
+    "transactionIndex",
+    "transactionLogIndex",
+    "value",
+];
+
+/// How a [`Provider`](crate::Provider) treats the hex quantities of responses, see
+/// [`Provider::quantity_mode`](crate::Provider::quantity_mode).
+///
+/// Quantities of requests are always normalized to their most compact form, e.g. `"0x0010"` is
+/// sent as `"0x10"`, since some nodes reject quantities with leading zeros.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuantityMode {
+    /// Accepts any quantity that fits its type, including ones with leading zeros
+    #[default]
+    Lenient,
+    /// Rejects responses with quantities that are not in their most compact form, naming the
+    /// offending field
+    Strict,
+}
+
+/// A quantity of a response that is not in its most compact form, returned in
+/// [`QuantityMode::Strict`]
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[error("invalid quantity {value:?} at `{field}`: {reason}")]
+pub struct QuantityError {
+    /// The path of the field in the response, e.g. `transactions[3].nonce`
+    pub field: String,
+    /// The value of the field
+    pub value: String,
+    /// Why the value is not a compact quantity
+    pub reason: &'static str,
+}
+
+/// Whether the value of `key` is a quantity, `is_block` is set for objects that are block headers
+fn is_quantity_field(key: &str, is_block: bool) -> bool {
+    !(is_block && key == "nonce") && QUANTITY_FIELDS.contains(&key)
+}
+
+/// Checks that `value` is a hex quantity without leading zeros
+fn check_quantity(value: &str) -> Result<(), &'static str> {
+    let digits = value.strip_prefix("0x").ok_or("missing 0x prefix")?;
+    if digits.is_empty() {
+        return Err("missing digits")
+    }
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("not a hex number")
+    }
+    if digits.len() > 1 && digits.starts_with('0') {
+        return Err("leading zeros")
+    }
+    Ok(())
+}
+
+/// Returns the compact form of the hex quantity `value`, `None` if it is already compact or not a
+/// hex quantity
+fn compact_quantity(value: &str) -> Option<String> {
+    let digits = value.strip_prefix("0x")?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None
+    }
+    let trimmed = digits.trim_start_matches('0');
+    let trimmed = if trimmed.is_empty() { "0" } else { trimmed };
+    (trimmed != digits).then(|| format!("0x{trimmed}"))
+}
+
+/// Strips the leading zeros of all quantities in `value`
+pub(crate) fn normalize_quantities(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(normalize_quantities),
+        Value::Object(object) => {
+            let is_block = object.contains_key("parentHash");
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(s) if is_quantity_field(key, is_block) => {
+                        if let Some(compact) = compact_quantity(s) {
+                            *s = compact;
+                        }
+                    }
+                    value => normalize_quantities(value),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks that all quantities in `value` are in their most compact form
+pub(crate) fn check_quantities(value: &Value) -> Result<(), QuantityError> {
+    check_quantities_at(value, &mut String::new())
+}
+
+fn check_quantities_at(value: &Value, path: &mut String) -> Result<(), QuantityError> {
+    let len = path.len();
+    match value {
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                let _ = write!(path, "[{i}]");
+                check_quantities_at(value, path)?;
+                path.truncate(len);
+            }
+        }
+        Value::Object(object) => {
+            let is_block = object.contains_key("parentHash");
+            for (key, value) in object {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+                match value {
+                    Value::String(s) if is_quantity_field(key, is_block) => {
+                        check_quantity(s).map_err(|reason| QuantityError {
+                            field: path.clone(),
+                            value: s.clone(),
+                            reason,
+                        })?;
+                    }
+                    value => check_quantities_at(value, path)?,
+                }
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn normalizes_quantities() {
+        let mut params = json!([
+            { "value": "0x0010", "energy": "0x00", "nonce": "0x", "data": "0x0001" },
+            "0x0001"
+        ]);
+        normalize_quantities(&mut params);
+        assert_eq!(
+            params,
+            json!([{ "value": "0x10", "energy": "0x0", "nonce": "0x0", "data": "0x0001" }, "0x0001"])
+        );
+    }
+
+    #[test]
+    fn checks_quantities() {
+        let block = json!({
+            "parentHash": "0x00",
+            "nonce": "0x0000000000000042",
+            "number": "0x7",
+            "transactions": [{ "nonce": "0x1" }, { "nonce": "0x01" }]
+        });
+        assert_eq!(
+            check_quantities(&block),
+            Err(QuantityError {
+                field: "transactions[1].nonce".to_string(),
+                value: "0x01".to_string(),
+                reason: "leading zeros",
+            })
+        );
+
+        assert!(check_quantities(&json!({ "value": "0x0", "input": "0x00" })).is_ok());
+        assert_eq!(
+            check_quantities(&json!({ "value": "0x" })).unwrap_err().reason,
+            "missing digits"
+        );
+        assert_eq!(check_quantities(&json!([{ "energy": "10" }])).unwrap_err().field, "[0].energy");
+    }
+}
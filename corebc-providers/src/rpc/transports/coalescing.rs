@@ -0,0 +1,307 @@
+//! A [JsonRpcClient] implementation that deduplicates concurrent identical requests
+
+use super::{common::JsonRpcError, Http};
+use crate::{errors::ProviderError, JsonRpcClient, RpcError};
+use async_trait::async_trait;
+use futures_util::future::{FutureExt, Shared};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// Methods that are never coalesced by default, because every request is expected to have its
+/// own effect or response
+const NOT_COALESCED: [&str; 7] = [
+    "xcb_newFilter",
+    "xcb_newBlockFilter",
+    "xcb_newPendingTransactionFilter",
+    "xcb_getFilterChanges",
+    "xcb_subscribe",
+    "xcb_sendTransaction",
+    "personal_sendTransaction",
+];
+
+#[cfg(target_arch = "wasm32")]
+type ResponseFut<E> = Pin<Box<dyn Future<Output = Result<Value, Arc<E>>>>>;
+#[cfg(not(target_arch = "wasm32"))]
+type ResponseFut<E> = Pin<Box<dyn Future<Output = Result<Value, Arc<E>>> + Send>>;
+
+/// The requests that are in flight, by method and serialized params
+type InFlight<E> = Arc<Mutex<HashMap<(String, String), Shared<ResponseFut<E>>>>>;
+
+/// A client that sends concurrent identical requests to the inner client only once.
+///
+/// A request with the same method and params as a request that is still in flight does not
+/// reach the inner client, it waits for the response of the request in flight instead. This
+/// prevents thundering herds, e.g. when many tasks ask for the latest block at the same time.
+/// Responses are not cached, a request sent after the response arrived is sent again.
+///
+/// Requests that create filters or subscriptions, poll filter changes or send transactions are
+/// never coalesced, further methods can be excluded with [`CoalescingClient::exclude`].
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{CoalescingClient, Http, Middleware, Provider};
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = CoalescingClient::new(Http::new(url::Url::parse("http://localhost:8545")?));
+/// let provider = Provider::new(client);
+/// // sent to the node once
+/// let (a, b) = futures_util::try_join!(provider.get_block_number(), provider.get_block_number())?;
+/// assert_eq!(a, b);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CoalescingClient<C: JsonRpcClient = Http> {
+    inner: Arc<C>,
+    excluded: HashSet<String>,
+    in_flight: InFlight<C::Error>,
+}
+
+impl<C: JsonRpcClient> Clone for CoalescingClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            excluded: self.excluded.clone(),
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+impl<C: JsonRpcClient> fmt::Debug for CoalescingClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalescingClient")
+            .field("inner", &self.inner)
+            .field("excluded", &self.excluded)
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+impl<C: JsonRpcClient> CoalescingClient<C> {
+    /// Creates a client that coalesces the requests sent to `inner`
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            excluded: NOT_COALESCED.iter().map(ToString::to_string).collect(),
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Never coalesces requests of `method`
+    #[must_use]
+    pub fn exclude(mut self, method: impl Into<String>) -> Self {
+        self.excluded.insert(method.into());
+        self
+    }
+
+    /// Returns the inner client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns the number of distinct requests that are in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by the [`CoalescingClient`]
+pub enum CoalescingClientError<C>
+where
+    C: JsonRpcClient,
+    <C as JsonRpcClient>::Error: RpcError + Sync + Send + 'static,
+{
+    /// Thrown if the inner client failed, shared by all coalesced requests
+    #[error(transparent)]
+    Client(Arc<C::Error>),
+    /// (De)Serialization error
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<C> RpcError for CoalescingClientError<C>
+where
+    C: JsonRpcClient,
+    <C as JsonRpcClient>::Error: RpcError + Sync + Send + 'static,
+{
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            CoalescingClientError::Client(e) => e.as_error_response(),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            CoalescingClientError::Client(e) => e.as_serde_error(),
+            CoalescingClientError::SerdeJson(e) => Some(e),
+        }
+    }
+}
+
+impl<C> From<CoalescingClientError<C>> for ProviderError
+where
+    C: JsonRpcClient + 'static,
+    <C as JsonRpcClient>::Error: Sync + Send + 'static,
+{
+    fn from(src: CoalescingClientError<C>) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for CoalescingClient<C>
+where
+    C: JsonRpcClient + 'static,
+    <C as JsonRpcClient>::Error: Sync + Send + 'static,
+{
+    type Error = CoalescingClientError<C>;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        if self.excluded.contains(method) {
+            return self
+                .inner
+                .request(method, params)
+                .await
+                .map_err(|err| CoalescingClientError::Client(Arc::new(err)))
+        }
+
+        // zero sized params, e.g. `()`, are left out of the request by the transports, so they
+        // must not reach the inner client as `null`
+        let params =
+            if std::mem::size_of::<T>() == 0 { None } else { Some(serde_json::to_value(&params)?) };
+        let key = (method.to_owned(), params.as_ref().map(Value::to_string).unwrap_or_default());
+        let response = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(response) => response.clone(),
+                None => {
+                    let inner = self.inner.clone();
+                    let requests = self.in_flight.clone();
+                    let method = method.to_owned();
+                    let done = key.clone();
+                    let response: ResponseFut<C::Error> = Box::pin(async move {
+                        let response = match params {
+                            Some(params) => inner.request::<_, Value>(&method, params).await,
+                            None => inner.request::<_, Value>(&method, ()).await,
+                        };
+                        // later requests are sent again
+                        requests.lock().unwrap().remove(&done);
+                        response.map_err(Arc::new)
+                    });
+                    let response = response.shared();
+                    in_flight.insert(key, response.clone());
+                    response
+                }
+            }
+        };
+
+        let value = response.await.map_err(CoalescingClientError::Client)?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockError, MockProvider, Provider};
+    use corebc_core::types::{U256, U64};
+    use futures_util::future::join_all;
+    use std::time::Duration;
+
+    /// Answers requests after a delay, so that concurrent requests are in flight together
+    #[derive(Debug)]
+    struct SlowClient(MockProvider);
+
+    #[async_trait]
+    impl JsonRpcClient for SlowClient {
+        type Error = MockError;
+
+        async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+        where
+            T: std::fmt::Debug + Serialize + Send + Sync,
+            R: DeserializeOwned + Send,
+        {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.0.request(method, params).await
+        }
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_requests() {
+        let mock = MockProvider::new();
+        let client = CoalescingClient::new(SlowClient(mock.clone()));
+        let provider = Provider::new(client.clone());
+
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(12));
+        let blocks = join_all((0..10).map(|_| provider.get_block_number())).await;
+        assert!(blocks.into_iter().all(|block| block.unwrap() == U64::from(12)));
+        assert_eq!(client.in_flight(), 0);
+        mock.verify().unwrap();
+        // zero sized params reach the inner client untouched
+        mock.assert_request("xcb_blockNumber", ()).unwrap();
+
+        // requests with other params are not coalesced
+        mock.expect("xcb_getBlockTransactionCountByNumber")
+            .with_params(["0x1"])
+            .times(1)
+            .returns(U256::from(1));
+        mock.expect("xcb_getBlockTransactionCountByNumber")
+            .with_params(["0x2"])
+            .times(1)
+            .returns(U256::from(2));
+        let (a, b) = futures_util::join!(
+            provider.request::<_, U256>("xcb_getBlockTransactionCountByNumber", ["0x1"]),
+            provider.request::<_, U256>("xcb_getBlockTransactionCountByNumber", ["0x2"]),
+        );
+        assert_eq!((a.unwrap(), b.unwrap()), (U256::from(1), U256::from(2)));
+        mock.verify().unwrap();
+
+        // the response is not cached
+        mock.expect("xcb_blockNumber").times(1).returns(U64::from(13));
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(13));
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn shares_errors_and_skips_excluded_methods() {
+        let mock = MockProvider::new();
+        let client = CoalescingClient::new(SlowClient(mock.clone())).exclude("xcb_blockNumber");
+        let provider = Provider::new(client);
+
+        mock.expect("xcb_networkId").times(1).return_error(JsonRpcError {
+            code: -32000,
+            message: "unavailable".to_string(),
+            data: None,
+        });
+        let (a, b) = futures_util::join!(provider.get_networkid(), provider.get_networkid());
+        assert_eq!(a.unwrap_err().as_error_response().unwrap().code, -32000);
+        assert_eq!(b.unwrap_err().as_error_response().unwrap().code, -32000);
+
+        mock.expect("xcb_blockNumber").times(2).returns(U64::from(12));
+        mock.expect("xcb_newBlockFilter").times(2).returns(U256::from(1));
+        let (a, b, c, d) = futures_util::join!(
+            provider.get_block_number(),
+            provider.get_block_number(),
+            provider.new_filter(crate::FilterKind::NewBlocks),
+            provider.new_filter(crate::FilterKind::NewBlocks),
+        );
+        assert_eq!(a.unwrap(), b.unwrap());
+        assert_eq!(c.unwrap(), d.unwrap());
+        mock.verify().unwrap();
+    }
+}
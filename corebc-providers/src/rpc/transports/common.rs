@@ -14,7 +14,7 @@ use std::fmt;
 use thiserror::Error;
 
 /// A JSON-RPC 2.0 error
-#[derive(Deserialize, Debug, Clone, Error)]
+#[derive(Serialize, Deserialize, Debug, Clone, Error)]
 pub struct JsonRpcError {
     /// The error code
     pub code: i64,
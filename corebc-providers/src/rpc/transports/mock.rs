@@ -1,10 +1,12 @@
+use super::JsonRpcError;
 use crate::{JsonRpcClient, ProviderError};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use std::{
     borrow::Borrow,
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
+    fmt,
     sync::{Arc, Mutex},
 };
 use thiserror::Error;
@@ -18,11 +20,85 @@ enum MockParams {
     Zst,
 }
 
+/// A scripted response to a request
+#[derive(Clone, Debug)]
+enum MockResponse {
+    Value(Value),
+    Error(JsonRpcError),
+}
+
+type ParamsMatcher = Box<dyn Fn(&Value) -> bool + Send + Sync>;
+
+/// An expected request registered with [`MockProvider::expect`]
+struct Expectation {
+    method: String,
+    params: Option<ParamsMatcher>,
+    times: Option<usize>,
+    calls: usize,
+    response: MockResponse,
+}
+
+impl Expectation {
+    fn matches(&self, method: &str, params: &Value) -> bool {
+        self.method == method &&
+            self.times.map_or(true, |times| self.calls < times) &&
+            self.params.as_ref().map_or(true, |matches| matches(params))
+    }
+
+    fn is_satisfied(&self) -> bool {
+        match self.times {
+            Some(times) => self.calls == times,
+            None => self.calls > 0,
+        }
+    }
+}
+
+impl fmt::Debug for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Expectation")
+            .field("method", &self.method)
+            .field("times", &self.times)
+            .field("calls", &self.calls)
+            .field("response", &self.response)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Mock transport used in test environments.
+///
+/// Responses are looked up in this order:
+/// 1. the first matching expectation registered with [`expect`](Self::expect)
+/// 2. the responses pushed for the method with [`push_for`](Self::push_for), first in first out
+/// 3. the responses pushed with [`push`](Self::push), last in first out
+///
+/// Once the last clone of the mock is dropped, it panics if any expectation is unmet, see
+/// [`verify`](Self::verify).
+///
+/// # Example
+///
+/// ```
+/// use corebc_core::types::{Address, U64};
+/// use corebc_providers::{Middleware, Provider};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let (provider, mock) = Provider::mocked();
+/// let address: Address = "0x00006fc21092da55b392b045ed78f4732bff3c580e2c".parse()?;
+/// mock.expect("xcb_blockNumber").times(2).returns(U64::from(12));
+/// mock.expect("xcb_getBalance").with_params((address, "latest")).returns(U64::from(100));
+///
+/// assert_eq!(provider.get_block_number().await?, U64::from(12));
+/// assert_eq!(provider.get_block_number().await?, U64::from(12));
+/// assert_eq!(provider.get_balance(address, None).await?, 100.into());
+/// mock.verify()?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct MockProvider {
     requests: Arc<Mutex<VecDeque<(String, MockParams)>>>,
     responses: Arc<Mutex<VecDeque<Value>>>,
+    method_responses: Arc<Mutex<HashMap<String, VecDeque<Value>>>>,
+    expectations: Arc<Mutex<Vec<Expectation>>>,
 }
 
 impl Default for MockProvider {
@@ -48,9 +124,17 @@ impl JsonRpcClient for MockProvider {
         } else {
             MockParams::Value(serde_json::to_value(params)?)
         };
+        let response = match &params {
+            MockParams::Value(value) => self.scripted_response(method, value),
+            MockParams::Zst => self.scripted_response(method, &Value::Null),
+        };
         self.requests.lock().unwrap().push_back((method.to_owned(), params));
-        let mut data = self.responses.lock().unwrap();
-        let element = data.pop_back().ok_or(MockError::EmptyResponses)?;
+
+        let element = match response {
+            Some(MockResponse::Value(value)) => value,
+            Some(MockResponse::Error(err)) => return Err(MockError::JsonRpcError(err)),
+            None => self.responses.lock().unwrap().pop_back().ok_or(MockError::EmptyResponses)?,
+        };
         let res: R = serde_json::from_value(element)?;
 
         Ok(res)
@@ -83,6 +167,8 @@ impl MockProvider {
         Self {
             requests: Arc::new(Mutex::new(VecDeque::new())),
             responses: Arc::new(Mutex::new(VecDeque::new())),
+            method_responses: Arc::new(Mutex::new(HashMap::new())),
+            expectations: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -92,6 +178,147 @@ impl MockProvider {
         self.responses.lock().unwrap().push_back(value);
         Ok(())
     }
+
+    /// Pushes the data to the responses of `method`.
+    ///
+    /// Unlike [`push`](Self::push), the responses of a method are returned in the order they
+    /// were pushed in.
+    pub fn push_for<T: Serialize + Send + Sync, K: Borrow<T>>(
+        &self,
+        method: &str,
+        data: K,
+    ) -> Result<(), MockError> {
+        let value = serde_json::to_value(data.borrow())?;
+        self.method_responses
+            .lock()
+            .unwrap()
+            .entry(method.to_owned())
+            .or_default()
+            .push_back(value);
+        Ok(())
+    }
+
+    /// Expects a request to `method`, the expectation is registered once its response is set.
+    ///
+    /// Without [`times`](ExpectationBuilder::times), the expectation answers any number of
+    /// requests but must be met at least once.
+    pub fn expect(&self, method: impl Into<String>) -> ExpectationBuilder<'_> {
+        ExpectationBuilder { mock: self, method: method.into(), params: None, times: None }
+    }
+
+    /// Checks that all expectations were met
+    pub fn verify(&self) -> Result<(), MockError> {
+        let unmet = self
+            .expectations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|expectation| !expectation.is_satisfied())
+            .map(|expectation| match expectation.times {
+                Some(times) => format!(
+                    "{} expected {times} call(s), got {}",
+                    expectation.method, expectation.calls
+                ),
+                None => format!("{} was never called", expectation.method),
+            })
+            .collect::<Vec<_>>();
+        if unmet.is_empty() {
+            Ok(())
+        } else {
+            Err(MockError::UnmetExpectations(unmet.join(", ")))
+        }
+    }
+
+    fn scripted_response(&self, method: &str, params: &Value) -> Option<MockResponse> {
+        let mut expectations = self.expectations.lock().unwrap();
+        if let Some(expectation) = expectations.iter_mut().find(|e| e.matches(method, params)) {
+            expectation.calls += 1;
+            return Some(expectation.response.clone())
+        }
+        let value = self.method_responses.lock().unwrap().get_mut(method)?.pop_front()?;
+        Some(MockResponse::Value(value))
+    }
+}
+
+impl Drop for MockProvider {
+    fn drop(&mut self) {
+        // only the last clone reports, and never while already unwinding
+        if Arc::strong_count(&self.expectations) > 1 || std::thread::panicking() {
+            return
+        }
+        if let Err(err) = self.verify() {
+            panic!("{err}")
+        }
+    }
+}
+
+/// Builds an expected request of a [`MockProvider`], see [`MockProvider::expect`]
+#[must_use = "expectations are only registered once their response is set"]
+pub struct ExpectationBuilder<'a> {
+    mock: &'a MockProvider,
+    method: String,
+    params: Option<ParamsMatcher>,
+    times: Option<usize>,
+}
+
+impl<'a> ExpectationBuilder<'a> {
+    /// Only matches requests whose params serialize to the same JSON as `params`
+    pub fn with_params<T: Serialize>(self, params: T) -> Self {
+        let expected = serde_json::to_value(params).expect("could not serialize params");
+        self.with_params_matching(move |params| *params == expected)
+    }
+
+    /// Only matches requests whose JSON params satisfy `matches`
+    pub fn with_params_matching<F>(mut self, matches: F) -> Self
+    where
+        F: Fn(&Value) -> bool + Send + Sync + 'static,
+    {
+        self.params = Some(Box::new(matches));
+        self
+    }
+
+    /// Expects exactly `times` matching requests, further requests are not answered by this
+    /// expectation
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// Answers the matching requests with `value`
+    pub fn return_json(self, value: Value) {
+        self.register(MockResponse::Value(value))
+    }
+
+    /// Answers the matching requests with the JSON serialization of `data`
+    pub fn returns<T: Serialize>(self, data: T) {
+        let value = serde_json::to_value(data).expect("could not serialize response");
+        self.return_json(value)
+    }
+
+    /// Answers the matching requests with a JSON-RPC error response
+    pub fn return_error(self, err: JsonRpcError) {
+        self.register(MockResponse::Error(err))
+    }
+
+    fn register(self, response: MockResponse) {
+        let Self { mock, method, params, times } = self;
+        mock.expectations.lock().unwrap().push(Expectation {
+            method,
+            params,
+            times,
+            calls: 0,
+            response,
+        });
+    }
+}
+
+impl fmt::Debug for ExpectationBuilder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpectationBuilder")
+            .field("method", &self.method)
+            .field("times", &self.times)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -108,11 +335,22 @@ pub enum MockError {
     /// Empty responses array
     #[error("empty responses array, please push some responses")]
     EmptyResponses,
+
+    /// A JSON-RPC error response scripted with [`ExpectationBuilder::return_error`]
+    #[error(transparent)]
+    JsonRpcError(JsonRpcError),
+
+    /// Expectations that were not met, see [`MockProvider::verify`]
+    #[error("unmet expectations: {0}")]
+    UnmetExpectations(String),
 }
 
 impl crate::RpcError for MockError {
     fn as_error_response(&self) -> Option<&super::JsonRpcError> {
-        None
+        match self {
+            MockError::JsonRpcError(e) => Some(e),
+            _ => None,
+        }
     }
 
     fn as_serde_error(&self) -> Option<&serde_json::Error> {
@@ -167,6 +405,61 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn answers_expectations() {
+        let mock = MockProvider::new();
+        mock.expect("xcb_call").with_params([1]).times(2).returns(U64::from(1));
+        mock.expect("xcb_call").return_json(serde_json::json!("0x2"));
+        mock.push_for("xcb_blockNumber", U64::from(3)).unwrap();
+        mock.push_for("xcb_blockNumber", U64::from(4)).unwrap();
+
+        for expected in [1, 2, 1, 2] {
+            let params = if expected == 1 { [1] } else { [2] };
+            let res: U64 = mock.request("xcb_call", params).await.unwrap();
+            assert_eq!(res.as_u64(), expected);
+        }
+        // the first expectation is exhausted
+        let res: U64 = mock.request("xcb_call", [1]).await.unwrap();
+        assert_eq!(res.as_u64(), 2);
+
+        let res: U64 = mock.request("xcb_blockNumber", ()).await.unwrap();
+        assert_eq!(res.as_u64(), 3);
+        let res: U64 = mock.request("xcb_blockNumber", ()).await.unwrap();
+        assert_eq!(res.as_u64(), 4);
+
+        mock.assert_request("xcb_call", [1]).unwrap();
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn returns_scripted_errors() {
+        let mock = MockProvider::new();
+        mock.expect("xcb_call").return_error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some("0x".into()),
+        });
+        let err = mock.request::<_, U64>("xcb_call", [1]).await.unwrap_err();
+        assert_eq!(crate::RpcError::as_error_response(&err).unwrap().code, 3);
+    }
+
+    #[tokio::test]
+    async fn reports_unmet_expectations() {
+        let mock = MockProvider::new();
+        mock.expect("xcb_call").times(2).returns(U64::from(1));
+        mock.expect("xcb_chainId").returns(U64::from(1));
+        let _: U64 = mock.request("xcb_call", [1]).await.unwrap();
+
+        let err = mock.verify().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unmet expectations: xcb_call expected 2 call(s), got 1, xcb_chainId was never called"
+        );
+
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || drop(mock)));
+        assert!(res.is_err());
+    }
+
     #[tokio::test]
     async fn composes_with_provider() {
         let (provider, mock) = crate::Provider::mocked();
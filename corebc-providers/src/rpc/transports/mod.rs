@@ -9,11 +9,14 @@ mod ipc;
 #[cfg(all(feature = "ipc", any(unix, windows)))]
 pub use ipc::{Ipc, IpcError};
 
+mod coalescing;
+pub use coalescing::{CoalescingClient, CoalescingClientError};
+
 mod quorum;
 pub use quorum::{JsonRpcClientWrapper, Quorum, QuorumError, QuorumProvider, WeightedProvider};
 
 mod rw;
-pub use rw::{RwClient, RwClientError};
+pub use rw::{Route, RoutingRule, RwClient, RwClientError};
 
 mod retry;
 pub use retry::*;
@@ -21,7 +24,7 @@ pub use retry::*;
 #[cfg(all(feature = "ws", not(feature = "legacy-ws")))]
 mod ws;
 #[cfg(all(feature = "ws", not(feature = "legacy-ws")))]
-pub use ws::{ConnectionDetails, WsClient as Ws, WsClientError};
+pub use ws::{CloseReason, ConnectionDetails, WsClient as Ws, WsClientError, WsError};
 
 /// archival websocket
 #[cfg(feature = "legacy-ws")]
@@ -29,5 +32,8 @@ pub mod legacy_ws;
 #[cfg(feature = "legacy-ws")]
 pub use legacy_ws::{ClientError as WsClientError, Ws};
 
+mod replay;
+pub use replay::{RecordedRequest, RecordedResponse, ReplayClient, ReplayClientError};
+
 mod mock;
-pub use mock::{MockError, MockProvider};
+pub use mock::{ExpectationBuilder, MockError, MockProvider};
@@ -6,9 +6,11 @@ use futures_util::{future::join_all, FutureExt, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{value::RawValue, Value};
 use std::{
+    collections::{HashMap, VecDeque},
     fmt::Debug,
     future::Future,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 use thiserror::Error;
@@ -70,6 +72,14 @@ use thiserror::Error;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Subscriptions
+///
+/// A `QuorumProvider` of [`PubsubClient`]s subscribes on all of its providers and hands out a
+/// subscription id of its own. Notifications are deduplicated by block hash and log position, and
+/// only yielded once the providers that sent them reach the quorum, so streamed data has the same
+/// trust level as quorum reads. The subscription is successful as long as the providers that
+/// accepted it can still reach the quorum.
 #[derive(Debug, Clone)]
 pub struct QuorumProvider<T = Box<dyn JsonRpcClientWrapper>> {
     /// What kind of quorum is required
@@ -78,6 +88,24 @@ pub struct QuorumProvider<T = Box<dyn JsonRpcClientWrapper>> {
     quorum_weight: u64,
     /// All the internal providers this providers runs
     providers: Vec<WeightedProvider<T>>,
+    /// The subscriptions installed on the providers
+    subscriptions: Arc<Mutex<QuorumSubscriptions>>,
+}
+
+/// The subscription ids of the providers, by the id handed out by the `QuorumProvider`
+#[derive(Debug, Default)]
+struct QuorumSubscriptions {
+    next_id: u64,
+    ids: HashMap<U256, Vec<(usize, U256)>>,
+}
+
+impl QuorumSubscriptions {
+    fn insert(&mut self, ids: Vec<(usize, U256)>) -> U256 {
+        self.next_id += 1;
+        let id = U256::from(self.next_id);
+        self.ids.insert(id, ids);
+        id
+    }
 }
 
 impl QuorumProvider<Box<dyn JsonRpcClientWrapper>> {
@@ -158,7 +186,12 @@ impl<T> QuorumProviderBuilder<T> {
 
     pub fn build(self) -> QuorumProvider<T> {
         let quorum_weight = self.quorum.weight(&self.providers);
-        QuorumProvider { quorum: self.quorum, quorum_weight, providers: self.providers }
+        QuorumProvider {
+            quorum: self.quorum,
+            quorum_weight,
+            providers: self.providers,
+            subscriptions: Default::default(),
+        }
     }
 }
 
@@ -182,6 +215,60 @@ impl<T: JsonRpcClientWrapper> QuorumProvider<T> {
             .ok_or_else(|| ProviderError::CustomError("No Providers".to_string()))
     }
 
+    /// Installs the subscription on all providers, returning the id of the quorum subscription
+    async fn subscribe_all(&self, params: QuorumParams) -> Result<Value, ProviderError> {
+        let responses = join_all(
+            self.providers
+                .iter()
+                .map(|provider| provider.inner.request("xcb_subscribe", params.clone())),
+        )
+        .await;
+
+        let mut ids = Vec::new();
+        let mut weight = 0;
+        let mut errors = Vec::new();
+        for (idx, res) in responses.into_iter().enumerate() {
+            match res.and_then(|id| Ok(serde_json::from_value::<U256>(id)?)) {
+                Ok(id) => {
+                    weight += self.providers[idx].weight;
+                    ids.push((idx, id));
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if weight < self.quorum_weight {
+            // the subscription is of no use, cancel it on the providers that accepted it
+            join_all(ids.into_iter().map(|(idx, id)| {
+                self.providers[idx].inner.request("xcb_unsubscribe", unsubscribe_params(id))
+            }))
+            .await;
+            return Err(QuorumError::NoQuorumReached { values: Vec::new(), errors }.into())
+        }
+
+        let id = self.subscriptions.lock().unwrap().insert(ids);
+        Ok(serde_json::to_value(id)?)
+    }
+
+    /// Cancels the subscription on all providers it was installed on, returns `None` if `params`
+    /// is not a quorum subscription
+    async fn unsubscribe_all(&self, params: &QuorumParams) -> Option<Value> {
+        let id = match params {
+            QuorumParams::Value(Value::Array(params)) => {
+                serde_json::from_value::<U256>(params.first()?.clone()).ok()?
+            }
+            _ => return None,
+        };
+        let ids = self.subscriptions.lock().unwrap().ids.get(&id).cloned()?;
+
+        let responses = join_all(ids.into_iter().map(|(idx, id)| {
+            self.providers[idx].inner.request("xcb_unsubscribe", unsubscribe_params(id))
+        }))
+        .await;
+        let cancelled = responses.iter().any(|res| matches!(res, Ok(Value::Bool(true))));
+        Some(Value::Bool(cancelled))
+    }
+
     /// Normalizes the request payload depending on the call
     async fn normalize_request(&self, method: &str, q_params: &mut QuorumParams) {
         let params = if let QuorumParams::Value(v) = q_params {
@@ -472,6 +559,17 @@ where
         } else {
             QuorumParams::Value(serde_json::to_value(params)?)
         };
+        match method {
+            "xcb_subscribe" => {
+                return Ok(serde_json::from_value(self.subscribe_all(params).await?)?)
+            }
+            "xcb_unsubscribe" => {
+                if let Some(cancelled) = self.unsubscribe_all(&params).await {
+                    return Ok(serde_json::from_value(cancelled)?)
+                }
+            }
+            _ => {}
+        }
         self.normalize_request(method, &mut params).await;
 
         let requests = self
@@ -490,17 +588,38 @@ where
     }
 }
 
-// A stream that returns a value and the weight of its provider
+// A stream that returns a value, and the index and weight of its provider
 type WeightedNotificationStream =
-    Pin<Box<dyn futures_core::Stream<Item = (Box<RawValue>, u64)> + Send + Unpin + 'static>>;
+    Pin<Box<dyn futures_core::Stream<Item = (Box<RawValue>, usize, u64)> + Send + Unpin + 'static>>;
+
+/// The maximum amount of notifications a [`QuorumStream`] keeps track of, both while waiting for
+/// them to reach quorum and after yielding them
+const MAX_TRACKED_NOTIFICATIONS: usize = 1024;
+
+/// A notification that did not reach quorum yet
+struct PendingNotification {
+    key: String,
+    /// The parsed notification, providers only agree on equal notifications
+    body: Value,
+    value: Box<RawValue>,
+    /// The providers that sent the notification
+    providers: Vec<usize>,
+    /// The cumulative weight of the providers
+    weight: u64,
+}
 
 /// A Subscription stream that only yields the next value if the underlying
 /// providers reached quorum.
+///
+/// Providers agree on a notification if they sent equal values. Notifications are yielded at most
+/// once per block hash for new heads, and per block hash and log index for logs.
 pub struct QuorumStream {
     // Weight required to reach quorum
     quorum_weight: u64,
-    /// The different notifications with their cumulative weight
-    responses: Vec<(Box<RawValue>, u64)>,
+    /// The notifications waiting for quorum
+    responses: Vec<PendingNotification>,
+    /// The keys of the notifications that were yielded already
+    yielded: VecDeque<String>,
     /// All provider notification streams
     active: Vec<WeightedNotificationStream>,
     /// Provider streams that already yielded a new value and are waiting for
@@ -510,8 +629,77 @@ pub struct QuorumStream {
 
 impl QuorumStream {
     fn new(quorum_weight: u64, notifications: Vec<WeightedNotificationStream>) -> Self {
-        Self { quorum_weight, responses: Vec::new(), active: notifications, benched: Vec::new() }
+        Self {
+            quorum_weight,
+            responses: Vec::new(),
+            yielded: VecDeque::new(),
+            active: notifications,
+            benched: Vec::new(),
+        }
+    }
+
+    /// Adds the notification of provider `idx`, returns it if it reached quorum
+    fn tally(&mut self, value: Box<RawValue>, idx: usize, weight: u64) -> Option<Box<RawValue>> {
+        let body = serde_json::from_str::<Value>(value.get())
+            .unwrap_or_else(|_| Value::String(value.get().to_string()));
+        let key = notification_key(&body);
+        if self.yielded.contains(&key) {
+            return None
+        }
+
+        let pos = match self.responses.iter().position(|n| n.body == body) {
+            Some(pos) => pos,
+            None => {
+                if self.responses.len() >= MAX_TRACKED_NOTIFICATIONS {
+                    // forget the oldest notification, it is unlikely to reach quorum anymore
+                    self.responses.remove(0);
+                }
+                self.responses.push(PendingNotification {
+                    key,
+                    body,
+                    value,
+                    providers: Vec::new(),
+                    weight: 0,
+                });
+                self.responses.len() - 1
+            }
+        };
+
+        let notification = &mut self.responses[pos];
+        if notification.providers.contains(&idx) {
+            return None
+        }
+        notification.providers.push(idx);
+        notification.weight += weight;
+        if notification.weight < self.quorum_weight {
+            return None
+        }
+
+        let notification = self.responses.remove(pos);
+        // conflicting versions of the notification can't be yielded anymore
+        self.responses.retain(|n| n.key != notification.key);
+        if self.yielded.len() >= MAX_TRACKED_NOTIFICATIONS {
+            self.yielded.pop_front();
+        }
+        self.yielded.push_back(notification.key);
+        Some(notification.value)
+    }
+}
+
+/// Returns the identity of a notification: the hash of a block header, the position of a log, or
+/// the notification itself otherwise, e.g. for transaction hashes
+fn notification_key(value: &Value) -> String {
+    if let Value::Object(obj) = value {
+        let field = |name: &str| obj.get(name).map(Value::to_string).unwrap_or_default();
+        if obj.contains_key("logIndex") {
+            // removed logs are notified again on reorgs
+            return format!("log:{}:{}:{}", field("blockHash"), field("logIndex"), field("removed"))
+        }
+        if obj.contains_key("hash") {
+            return format!("block:{}", field("hash"))
+        }
     }
+    value.to_string()
 }
 
 impl Stream for QuorumStream {
@@ -528,27 +716,11 @@ impl Stream for QuorumStream {
             let mut stream = this.active.swap_remove(n);
 
             match stream.poll_next_unpin(cx) {
-                Poll::Ready(Some((val, response_weight))) => {
-                    if let Some((_, weight)) =
-                        this.responses.iter_mut().find(|(v, _)| val.get() == v.get())
-                    {
-                        *weight += response_weight;
-                        if *weight >= this.quorum_weight {
-                            // reached quorum with multiple notification
-                            this.benched.push(stream);
-                            return Poll::Ready(Some(val))
-                        } else {
-                            this.responses.push((val, response_weight));
-                        }
-                    } else if response_weight >= this.quorum_weight {
-                        // reached quorum with single notification
-                        this.benched.push(stream);
+                Poll::Ready(Some((val, idx, response_weight))) => {
+                    this.benched.push(stream);
+                    if let Some(val) = this.tally(val, idx, response_weight) {
                         return Poll::Ready(Some(val))
-                    } else {
-                        this.responses.push((val, response_weight));
                     }
-
-                    this.benched.push(stream);
                 }
                 Poll::Ready(None) => {}
                 _ => {
@@ -560,6 +732,10 @@ impl Stream for QuorumStream {
         if this.active.is_empty() && this.benched.is_empty() {
             return Poll::Ready(None)
         }
+        if this.active.is_empty() {
+            // all streams yielded, poll them again
+            cx.waker().wake_by_ref();
+        }
         Poll::Pending
     }
 }
@@ -572,24 +748,58 @@ where
 
     fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, Self::Error> {
         let id = id.into();
-        let mut notifications = Vec::with_capacity(self.providers.len());
-        for provider in &self.providers {
-            let weight = provider.weight;
-            let fut = provider.inner.subscribe(id)?.map(move |val| (val, weight));
-            notifications.push(Box::pin(fut) as WeightedNotificationStream);
+        // ids that were not handed out by `xcb_subscribe` are used as is on all providers
+        let ids = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .ids
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| (0..self.providers.len()).map(|idx| (idx, id)).collect());
+
+        let mut notifications = Vec::with_capacity(ids.len());
+        let mut weight = 0;
+        let mut errors = Vec::new();
+        for (idx, id) in ids {
+            let provider = &self.providers[idx];
+            let provider_weight = provider.weight;
+            match provider.inner.subscribe(id) {
+                Ok(stream) => {
+                    weight += provider_weight;
+                    let stream = stream.map(move |val| (val, idx, provider_weight));
+                    notifications.push(Box::pin(stream) as WeightedNotificationStream);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if weight < self.quorum_weight {
+            return Err(QuorumError::NoQuorumReached { values: Vec::new(), errors }.into())
         }
         Ok(QuorumStream::new(self.quorum_weight, notifications))
     }
 
     fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), Self::Error> {
         let id = id.into();
-        for provider in &self.providers {
-            provider.inner.unsubscribe(id)?;
+        let ids = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .ids
+            .remove(&id)
+            .unwrap_or_else(|| (0..self.providers.len()).map(|idx| (idx, id)).collect());
+        for (idx, id) in ids {
+            self.providers[idx].inner.unsubscribe(id)?;
         }
         Ok(())
     }
 }
 
+fn unsubscribe_params(id: U256) -> QuorumParams {
+    QuorumParams::Value(Value::Array(vec![serde_json::to_value(id).unwrap_or_default()]))
+}
+
 /// Helper type that can be used to pass through the `params` value.
 /// This is necessary because the wrapper provider is supposed to skip the `params` if it's of
 /// size 0, see `crate::transports::common::Request`
@@ -602,9 +812,128 @@ pub enum QuorumParams {
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
-    use super::{Quorum, QuorumProvider, WeightedProvider};
-    use crate::{Middleware, MockProvider, Provider};
-    use corebc_core::types::U64;
+    use super::*;
+    use crate::{Middleware, MockError, MockProvider, Provider};
+
+    /// A mocked provider streaming the given notifications to every subscription
+    #[derive(Debug, Clone)]
+    struct PubsubMock {
+        mock: MockProvider,
+        notifications: Vec<&'static str>,
+        subscribed: Arc<Mutex<Vec<U256>>>,
+    }
+
+    impl PubsubMock {
+        fn new(notifications: Vec<&'static str>) -> Self {
+            Self { mock: MockProvider::new(), notifications, subscribed: Default::default() }
+        }
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for PubsubMock {
+        type Error = MockError;
+
+        async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+            &self,
+            method: &str,
+            params: T,
+        ) -> Result<R, Self::Error> {
+            JsonRpcClient::request(&self.mock, method, params).await
+        }
+    }
+
+    impl PubsubClient for PubsubMock {
+        type NotificationStream = futures_util::stream::Iter<std::vec::IntoIter<Box<RawValue>>>;
+
+        fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, MockError> {
+            self.subscribed.lock().unwrap().push(id.into());
+            let notifications = self
+                .notifications
+                .iter()
+                .map(|n| RawValue::from_string(n.to_string()).unwrap())
+                .collect::<Vec<_>>();
+            Ok(futures_util::stream::iter(notifications))
+        }
+
+        fn unsubscribe<T: Into<U256>>(&self, _id: T) -> Result<(), MockError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribes_with_quorum() {
+        let head_a = r#"{"hash":"0x0a","number":"0x1","miner":"0x01"}"#;
+        let head_a_other = r#"{"hash":"0x0a","number":"0x1","miner":"0x02"}"#;
+        let head_b = r#"{"hash":"0x0b","number":"0x2"}"#;
+        let head_c = r#"{"hash":"0x0c","number":"0x2"}"#;
+        let members = [
+            PubsubMock::new(vec![head_a, head_b]),
+            PubsubMock::new(vec![head_a_other, head_a, head_c]),
+            PubsubMock::new(vec![head_a]),
+        ];
+        members[0].mock.expect("xcb_subscribe").returns(U256::from(10));
+        members[1].mock.expect("xcb_subscribe").returns(U256::from(11));
+        members[2].mock.expect("xcb_subscribe").return_error(crate::JsonRpcError {
+            code: -32601,
+            message: "notifications not supported".to_string(),
+            data: None,
+        });
+        members[0].mock.expect("xcb_unsubscribe").with_params([U256::from(10)]).returns(true);
+        members[1].mock.expect("xcb_unsubscribe").with_params([U256::from(11)]).returns(true);
+
+        let quorum = QuorumProvider::new(
+            Quorum::Majority,
+            members.iter().cloned().map(WeightedProvider::new),
+        );
+        let provider = Provider::new(quorum);
+
+        let stream = provider.subscribe::<_, Value>(["newHeads"]).await.unwrap();
+        // the third member rejected the subscription, its notifications are not streamed
+        assert_eq!(*members[0].subscribed.lock().unwrap(), vec![U256::from(10)]);
+        assert_eq!(*members[1].subscribed.lock().unwrap(), vec![U256::from(11)]);
+        assert!(members[2].subscribed.lock().unwrap().is_empty());
+
+        assert!(stream.unsubscribe().await.unwrap());
+        let heads = stream.collect::<Vec<_>>().await;
+        // the head with another miner does not count towards the head with the same hash
+        assert_eq!(heads, vec![serde_json::from_str::<Value>(head_a).unwrap()]);
+
+        for member in &members {
+            member.mock.verify().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn quorum_stream_yields_notifications_once() {
+        let log = |block: &str, index: &str, removed: bool| {
+            RawValue::from_string(format!(
+                r#"{{"blockHash":"{block}","logIndex":"{index}","removed":{removed}}}"#
+            ))
+            .unwrap()
+        };
+        let member = |idx: usize, items: Vec<Box<RawValue>>| {
+            Box::pin(futures_util::stream::iter(items).map(move |val| (val, idx, 1)))
+                as WeightedNotificationStream
+        };
+        let stream = QuorumStream::new(
+            2,
+            vec![
+                member(0, vec![log("0x01", "0x0", false), log("0x01", "0x0", false)]),
+                member(1, vec![log("0x01", "0x0", false), log("0x01", "0x1", false)]),
+                member(2, vec![log("0x01", "0x0", false), log("0x01", "0x0", true)]),
+                member(3, vec![log("0x01", "0x0", true)]),
+            ],
+        );
+
+        let logs = stream.map(|log| log.get().to_string()).collect::<Vec<_>>().await;
+        assert_eq!(
+            logs,
+            vec![
+                log("0x01", "0x0", false).get().to_string(),
+                log("0x01", "0x0", true).get().to_string()
+            ]
+        );
+    }
 
     async fn test_quorum(q: Quorum) {
         let num = 5u64;
@@ -0,0 +1,286 @@
+//! A [JsonRpcClient] implementation that records the requests of another [JsonRpcClient] and
+//! replays them later without a node
+
+use super::{common::JsonRpcError, Http};
+use crate::{errors::ProviderError, JsonRpcClient, RpcError};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// A request and the response it received
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// The JSON-RPC method
+    pub method: String,
+    /// The params of the request, `null` if the request had none
+    pub params: Value,
+    /// The response to the request
+    #[serde(flatten)]
+    pub response: RecordedResponse,
+}
+
+/// The response of a [`RecordedRequest`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedResponse {
+    /// A successful response
+    Result(Value),
+    /// A JSON-RPC error response
+    Error(JsonRpcError),
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    requests: Vec<RecordedRequest>,
+    /// Whether the request at the same index was replayed already
+    replayed: Vec<bool>,
+}
+
+/// A client that records the requests of another client to a JSON fixture, and replays
+/// them deterministically without a node.
+///
+/// When replaying, every request is answered with the response of the first recorded request
+/// with the same method and params that was not replayed yet. Requests that were not recorded
+/// fail with [`ReplayClientError::NotRecorded`].
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Http, Middleware, Provider, ReplayClient};
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// // record the requests against a live node
+/// let client = ReplayClient::record(Http::new(url::Url::parse("http://localhost:8545")?));
+/// let provider = Provider::new(client.clone());
+/// let block = provider.get_block_number().await?;
+/// client.save("fixtures/block_number.json")?;
+///
+/// // and replay them in tests
+/// let provider = Provider::new(ReplayClient::load("fixtures/block_number.json")?);
+/// assert_eq!(provider.get_block_number().await?, block);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplayClient<C = Http> {
+    /// The client requests are sent to while recording, `None` when replaying
+    inner: Option<C>,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl<C> ReplayClient<C> {
+    /// Creates a client that sends all requests to `inner` and records them
+    pub fn record(inner: C) -> Self {
+        Self { inner: Some(inner), recording: Default::default() }
+    }
+
+    /// Whether requests are sent to the inner client and recorded
+    pub fn is_recording(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the recorded requests
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.recording.lock().unwrap().requests.clone()
+    }
+
+    /// Whether every recorded request was replayed
+    pub fn replayed_all(&self) -> bool {
+        self.recording.lock().unwrap().replayed.iter().all(|replayed| *replayed)
+    }
+
+    /// Writes the recorded requests to the JSON fixture at `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.recording.lock().unwrap().requests)?;
+        fs::write(path, json)
+    }
+}
+
+impl ReplayClient {
+    /// Creates a client that replays the `requests`.
+    ///
+    /// Replaying clients never send requests, so the type of their inner client is irrelevant.
+    pub fn replay(requests: Vec<RecordedRequest>) -> Self {
+        let replayed = vec![false; requests.len()];
+        Self { inner: None, recording: Arc::new(Mutex::new(Recording { requests, replayed })) }
+    }
+
+    /// Creates a client that replays the requests of the JSON fixture at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let requests = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(Self::replay(requests))
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by the [`ReplayClient`]
+pub enum ReplayClientError<C>
+where
+    C: JsonRpcClient,
+    <C as JsonRpcClient>::Error: RpcError + Sync + Send + 'static,
+{
+    /// Thrown if the inner client failed while recording
+    #[error(transparent)]
+    Client(C::Error),
+    /// A recorded JSON-RPC error response
+    #[error(transparent)]
+    JsonRpcError(JsonRpcError),
+    /// Thrown if no request with the method and params was recorded, or all of them were
+    /// replayed already
+    #[error("no recorded response for {method} with params {params}")]
+    NotRecorded {
+        /// The JSON-RPC method
+        method: String,
+        /// The params of the request
+        params: Value,
+    },
+    /// (De)Serialization error
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<C> RpcError for ReplayClientError<C>
+where
+    C: JsonRpcClient,
+    <C as JsonRpcClient>::Error: RpcError + Sync + Send + 'static,
+{
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            ReplayClientError::Client(e) => e.as_error_response(),
+            ReplayClientError::JsonRpcError(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            ReplayClientError::Client(e) => e.as_serde_error(),
+            ReplayClientError::SerdeJson(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<C> From<ReplayClientError<C>> for ProviderError
+where
+    C: JsonRpcClient + 'static,
+    <C as JsonRpcClient>::Error: Sync + Send + 'static,
+{
+    fn from(src: ReplayClientError<C>) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for ReplayClient<C>
+where
+    C: JsonRpcClient + 'static,
+    <C as JsonRpcClient>::Error: Sync + Send + 'static,
+{
+    type Error = ReplayClientError<C>;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params_json = serde_json::to_value(&params)?;
+
+        let response = match &self.inner {
+            Some(inner) => {
+                let response = match inner.request::<_, Value>(method, params).await {
+                    Ok(value) => RecordedResponse::Result(value),
+                    Err(err) => match err.as_error_response() {
+                        Some(err) => RecordedResponse::Error(err.clone()),
+                        // transport failures are not worth replaying
+                        None => return Err(ReplayClientError::Client(err)),
+                    },
+                };
+                let mut recording = self.recording.lock().unwrap();
+                recording.requests.push(RecordedRequest {
+                    method: method.to_owned(),
+                    params: params_json,
+                    response: response.clone(),
+                });
+                recording.replayed.push(true);
+                response
+            }
+            None => {
+                let mut recording = self.recording.lock().unwrap();
+                let Recording { requests, replayed } = &mut *recording;
+                let idx = requests
+                    .iter()
+                    .zip(replayed.iter())
+                    .position(|(req, done)| {
+                        !done && req.method == method && req.params == params_json
+                    })
+                    .ok_or_else(|| ReplayClientError::NotRecorded {
+                        method: method.to_owned(),
+                        params: params_json,
+                    })?;
+                replayed[idx] = true;
+                requests[idx].response.clone()
+            }
+        };
+
+        match response {
+            RecordedResponse::Result(value) => Ok(serde_json::from_value(value)?),
+            RecordedResponse::Error(err) => Err(ReplayClientError::JsonRpcError(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockProvider, Provider};
+    use corebc_core::types::U64;
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let mock = MockProvider::new();
+        mock.push(U64::from(7)).unwrap();
+        mock.push(U64::from(12)).unwrap();
+        mock.expect("xcb_call").return_error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: None,
+        });
+
+        let client = ReplayClient::record(mock);
+        let provider = Provider::new(client.clone());
+        assert_eq!(provider.get_block_number().await.unwrap().as_u64(), 12);
+        assert_eq!(provider.get_networkid().await.unwrap().as_u64(), 7);
+        let err = provider.call(&Default::default(), None).await.unwrap_err();
+        assert!(err.as_error_response().is_some());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixtures").join("recording.json");
+        client.save(&path).unwrap();
+
+        let client = ReplayClient::load(&path).unwrap();
+        assert!(!client.is_recording());
+        let provider = Provider::new(client.clone());
+        // requests are matched by method and params rather than by order
+        assert_eq!(provider.get_networkid().await.unwrap().as_u64(), 7);
+        assert_eq!(provider.get_block_number().await.unwrap().as_u64(), 12);
+        assert!(!client.replayed_all());
+        let err = provider.call(&Default::default(), None).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, 3);
+        assert!(client.replayed_all());
+
+        let err = provider.get_block_number().await.unwrap_err();
+        assert!(err.to_string().contains("no recorded response for xcb_blockNumber"), "{err}");
+    }
+}
@@ -1,25 +1,122 @@
 //! A [JsonRpcClient] implementation that serves as a wrapper around two different [JsonRpcClient]
 //! and uses a dedicated client for read and the other for write operations
 
-use crate::{errors::ProviderError, JsonRpcClient};
+use crate::{errors::ProviderError, JsonRpcClient, RpcError};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt, sync::Arc};
 use thiserror::Error;
 
+/// The client of a [`RwClient`] a request is sent to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Route {
+    /// The _read_ client
+    Read,
+    /// The _write_ client
+    Write,
+}
+
+impl Route {
+    /// Returns the other client
+    pub fn other(self) -> Self {
+        match self {
+            Route::Read => Route::Write,
+            Route::Write => Route::Read,
+        }
+    }
+}
+
+/// Sends the requests whose method matches to one of the clients of a [`RwClient`]
+#[derive(Clone)]
+pub struct RoutingRule {
+    matches: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+    route: Route,
+    fallback: bool,
+}
+
+impl RoutingRule {
+    /// Routes the methods matching `pattern`, which is either the exact method name or a prefix
+    /// followed by `*`, e.g. `"debug_*"`
+    pub fn new(pattern: impl Into<String>, route: Route) -> Self {
+        let pattern = pattern.into();
+        let matches = move |method: &str| match pattern.strip_suffix('*') {
+            Some(prefix) => method.starts_with(prefix),
+            None => method == pattern,
+        };
+        Self::matching(matches, route)
+    }
+
+    /// Routes the methods for which `matches` returns `true`
+    pub fn matching<F>(matches: F, route: Route) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Self { matches: Arc::new(matches), route, fallback: false }
+    }
+
+    /// Retries requests on the other client if the routed client fails without a JSON-RPC error
+    /// response, e.g. because it is unreachable
+    #[must_use]
+    pub fn with_fallback(mut self) -> Self {
+        self.fallback = true;
+        self
+    }
+
+    /// Whether the rule applies to `method`
+    pub fn matches(&self, method: &str) -> bool {
+        (self.matches)(method)
+    }
+
+    /// The client the matching requests are sent to
+    pub fn route(&self) -> Route {
+        self.route
+    }
+}
+
+impl fmt::Debug for RoutingRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutingRule")
+            .field("route", &self.route)
+            .field("fallback", &self.fallback)
+            .finish_non_exhaustive()
+    }
+}
+
 /// A client containing two clients.
 ///
 /// One is used for _read_ operations
 /// One is used for _write_ operations that consume gas `["xcb_sendTransaction",
 /// "xcb_sendRawTransaction"]`
 ///
+/// Custom [`RoutingRule`]s are checked in the order they were added before the default routing,
+/// the first matching rule decides the client.
+///
 /// **Note**: if the method is unknown this client falls back to the _read_ client
-// # Example
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn t() {
+/// use corebc_providers::{Http, Route, RoutingRule, RwClient};
+/// # use url::Url;
+/// let node = Http::new(Url::parse("http://localhost:8545").unwrap());
+/// let archive = Http::new(Url::parse("http://localhost:8546").unwrap());
+/// // send the tracing requests to the archive node, and fall back to the node if it is down
+/// let rw = RwClient::new(node, archive)
+///     .rule(RoutingRule::new("trace_*", Route::Write).with_fallback())
+///     .rule(RoutingRule::new("debug_*", Route::Write).with_fallback())
+///     // but keep sending transactions to the node
+///     .rule(RoutingRule::new("xcb_send*", Route::Read));
+/// # }
+/// ```
 #[derive(Debug, Clone)]
 pub struct RwClient<Read, Write> {
     /// client used to read
     r: Read,
     /// client used to write
     w: Write,
+    /// custom routing rules, checked before the default routing
+    rules: Vec<RoutingRule>,
 }
 
 impl<Read, Write> RwClient<Read, Write> {
@@ -37,7 +134,34 @@ impl<Read, Write> RwClient<Read, Write> {
     /// # }
     /// ```
     pub fn new(r: Read, w: Write) -> RwClient<Read, Write> {
-        Self { r, w }
+        Self { r, w, rules: Vec::new() }
+    }
+
+    /// Creates a new client that routes the requests with the given rules before the default
+    /// routing
+    pub fn with_rules(r: Read, w: Write, rules: Vec<RoutingRule>) -> RwClient<Read, Write> {
+        Self { r, w, rules }
+    }
+
+    /// Adds a routing rule, which is checked after the rules added before
+    #[must_use]
+    pub fn rule(mut self, rule: RoutingRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Returns the custom routing rules
+    pub fn rules(&self) -> &[RoutingRule] {
+        &self.rules
+    }
+
+    /// Returns the client `method` is sent to
+    pub fn route(&self, method: &str) -> Route {
+        self.routing_rule(method).map(RoutingRule::route).unwrap_or_else(|| default_route(method))
+    }
+
+    fn routing_rule(&self, method: &str) -> Option<&RoutingRule> {
+        self.rules.iter().find(|rule| rule.matches(method))
     }
 
     /// Returns the client used for read operations
@@ -50,19 +174,27 @@ impl<Read, Write> RwClient<Read, Write> {
         &self.w
     }
 
-    /// Returns a new `RwClient` with transposed clients
+    /// Returns a new `RwClient` with transposed clients, the routing rules are kept
     pub fn transpose(self) -> RwClient<Write, Read> {
-        let RwClient { r, w } = self;
-        RwClient::new(w, r)
+        let RwClient { r, w, rules } = self;
+        RwClient::with_rules(w, r, rules)
     }
 
     /// Consumes the client and returns the underlying clients
     pub fn split(self) -> (Read, Write) {
-        let RwClient { r, w } = self;
+        let RwClient { r, w, .. } = self;
         (r, w)
     }
 }
 
+/// The client `method` is sent to if no routing rule matches
+fn default_route(method: &str) -> Route {
+    match method {
+        "xcb_sendTransaction" | "xcb_sendRawTransaction" => Route::Write,
+        _ => Route::Read,
+    }
+}
+
 #[derive(Error, Debug)]
 /// Error thrown when using either read or write client
 pub enum RwClientError<Read, Write>
@@ -132,11 +264,116 @@ where
         T: std::fmt::Debug + Serialize + Send + Sync,
         R: DeserializeOwned + Send,
     {
-        match method {
-            "xcb_sendTransaction" | "xcb_sendRawTransaction" => {
-                self.w.request(method, params).await.map_err(RwClientError::Write)
+        let rule = self.routing_rule(method);
+        let route = rule.map(RoutingRule::route).unwrap_or_else(|| default_route(method));
+        if !rule.map_or(false, |rule| rule.fallback) {
+            return self.request_on(route, method, params).await
+        }
+
+        // the params are sent twice, zero sized params are skipped by the transports anyway
+        if std::mem::size_of::<T>() == 0 {
+            self.request_with_fallback(route, method, ()).await
+        } else {
+            self.request_with_fallback(route, method, &params).await
+        }
+    }
+}
+
+impl<Read, Write> RwClient<Read, Write>
+where
+    Read: JsonRpcClient + 'static,
+    <Read as JsonRpcClient>::Error: Sync + Send + 'static,
+    Write: JsonRpcClient + 'static,
+    <Write as JsonRpcClient>::Error: Sync + Send + 'static,
+{
+    async fn request_on<T, R>(
+        &self,
+        route: Route,
+        method: &str,
+        params: T,
+    ) -> Result<R, RwClientError<Read, Write>>
+    where
+        T: std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        match route {
+            Route::Read => self.r.request(method, params).await.map_err(RwClientError::Read),
+            Route::Write => self.w.request(method, params).await.map_err(RwClientError::Write),
+        }
+    }
+
+    async fn request_with_fallback<T, R>(
+        &self,
+        route: Route,
+        method: &str,
+        params: T,
+    ) -> Result<R, RwClientError<Read, Write>>
+    where
+        T: Copy + std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        match self.request_on(route, method, params).await {
+            Err(err) if !err.is_error_response() => {
+                tracing::debug!(?err, method, "falling back to the {:?} client", route.other());
+                self.request_on(route.other(), method, params).await
             }
-            _ => self.r.request(method, params).await.map_err(RwClientError::Read),
+            res => res,
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{MockError, MockProvider};
+    use corebc_core::types::U64;
+
+    /// A client that can't be reached
+    #[derive(Debug)]
+    struct Offline;
+
+    #[async_trait]
+    impl JsonRpcClient for Offline {
+        type Error = MockError;
+
+        async fn request<T, R>(&self, _: &str, _: T) -> Result<R, MockError>
+        where
+            T: std::fmt::Debug + Serialize + Send + Sync,
+            R: DeserializeOwned + Send,
+        {
+            Err(MockError::EmptyResponses)
+        }
+    }
+
+    #[test]
+    fn routes_by_rules() {
+        let rw = RwClient::new(MockProvider::new(), MockProvider::new())
+            .rule(RoutingRule::new("trace_*", Route::Write))
+            .rule(RoutingRule::matching(|method| method.ends_with("Raw"), Route::Write))
+            .rule(RoutingRule::new("xcb_sendRawTransaction", Route::Read));
+
+        assert_eq!(rw.route("trace_block"), Route::Write);
+        assert_eq!(rw.route("debug_getRaw"), Route::Write);
+        assert_eq!(rw.route("xcb_sendRawTransaction"), Route::Read);
+        assert_eq!(rw.route("xcb_sendTransaction"), Route::Write);
+        assert_eq!(rw.route("xcb_blockNumber"), Route::Read);
+        assert_eq!(rw.transpose().route("trace_block"), Route::Write);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_other_client() {
+        let read = MockProvider::new();
+        let rw = RwClient::new(read.clone(), Offline)
+            .rule(RoutingRule::new("trace_*", Route::Write).with_fallback())
+            .rule(RoutingRule::new("debug_*", Route::Write));
+
+        read.push(U64::from(1)).unwrap();
+        let res: U64 = rw.request("trace_block", [1]).await.unwrap();
+        assert_eq!(res.as_u64(), 1);
+        read.assert_request("trace_block", [1]).unwrap();
+
+        let err = rw.request::<_, U64>("debug_traceBlock", [1]).await.unwrap_err();
+        assert!(matches!(err, RwClientError::Write(_)));
+    }
+}
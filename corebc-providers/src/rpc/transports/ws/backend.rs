@@ -3,7 +3,7 @@ use futures_util::{select, sink::SinkExt, stream::StreamExt, FutureExt};
 
 use serde_json::value::RawValue;
 
-use super::{types::*, WsClientError};
+use super::{types::*, CloseReason, WsClientError, WsError};
 use tracing::{error, trace};
 
 /// `BackendDriver` drives a specific `WsBackend`. It can be used to issue
@@ -12,7 +12,7 @@ pub struct BackendDriver {
     // Pubsub items from the backend, received via WS
     pub to_handle: mpsc::UnboundedReceiver<PubSubItem>,
     // Notification from the backend of a terminal error
-    pub error: oneshot::Receiver<()>,
+    pub error: oneshot::Receiver<WsError>,
 
     // Requests that the backend should dispatch
     pub dispatcher: mpsc::UnboundedSender<Box<RawValue>>,
@@ -39,7 +39,7 @@ pub struct WsBackend {
     // channel to the manager, through which to send items received via WS
     handler: mpsc::UnboundedSender<PubSubItem>,
     // notify manager of an error causing this task to halt
-    error: oneshot::Sender<()>,
+    error: oneshot::Sender<WsError>,
 
     // channel of inbound requests to dispatch
     to_dispatch: mpsc::UnboundedReceiver<Box<RawValue>>,
@@ -104,8 +104,8 @@ impl WsBackend {
             Ok(item) => match item {
                 Message::Text(t) => self.handle_text(t).await,
                 Message::Ping(data) => {
-                    if self.server.send(Message::Pong(data)).await.is_err() {
-                        return Err(WsClientError::UnexpectedClose)
+                    if let Err(e) = self.server.send(Message::Pong(data)).await {
+                        return Err(e.into())
                     }
                     Ok(())
                 }
@@ -115,10 +115,14 @@ impl WsBackend {
 
                 Message::Binary(buf) => Err(WsClientError::UnexpectedBinary(buf)),
                 Message::Close(frame) => {
-                    if frame.is_some() {
-                        error!("Close frame: {}", frame.unwrap());
-                    }
-                    Err(WsClientError::UnexpectedClose)
+                    let reason = match frame {
+                        Some(frame) => {
+                            error!("Close frame: {}", frame);
+                            CloseReason::new(frame.code.into(), frame.reason)
+                        }
+                        None => CloseReason::abnormal(),
+                    };
+                    Err(WsError::Closed(reason).into())
                 }
             },
             Err(e) => {
@@ -138,7 +142,7 @@ impl WsBackend {
 
     pub fn spawn(mut self) {
         let fut = async move {
-            let mut err = false;
+            let mut err = None;
             loop {
                 #[cfg(not(target_arch = "wasm32"))]
                 let keepalive = tokio::time::sleep(std::time::Duration::from_secs(10)).fuse();
@@ -154,7 +158,7 @@ impl WsBackend {
                         #[cfg(not(target_arch = "wasm32"))]
                         if let Err(e) = self.server.send(Message::Ping(vec![])).await {
                             error!(err = %e, "WS connection error");
-                            err = true;
+                            err = Some(WsError::from(&e));
                             break
                         }
                         #[cfg(target_arch = "wasm32")]
@@ -163,12 +167,15 @@ impl WsBackend {
                     resp = self.server.next() => {
                         match resp {
                             Some(item) => {
-                                err = self.handle(item).await.is_err();
-                                if err { break }
+                                if let Err(e) = self.handle(item).await {
+                                    let abnormal = || WsError::Closed(CloseReason::abnormal());
+                                    err = Some(e.ws_error().unwrap_or_else(abnormal));
+                                    break
+                                }
                             },
                             None => {
                                 error!("WS server has gone away");
-                                err = true;
+                                err = Some(WsError::Closed(CloseReason::abnormal()));
                                 break
                             },
                         }
@@ -180,7 +187,7 @@ impl WsBackend {
                             Some(msg) => {
                                 if let Err(e) = self.server.send(Message::Text(msg.to_string())).await {
                                     error!(err = %e, "WS connection error");
-                                    err = true;
+                                    err = Some(WsError::from(&e));
                                     break
                                 }
                             },
@@ -196,8 +203,8 @@ impl WsBackend {
                     },
                 }
             }
-            if err {
-                let _ = self.error.send(());
+            if let Some(err) = err {
+                let _ = self.error.send(err);
             }
         };
 
@@ -1,8 +1,139 @@
 use corebc_core::types::U256;
+use std::fmt;
 
 use crate::{JsonRpcError, ProviderError};
 
-use super::WsError;
+use super::WsTransportError;
+
+/// The close code of a normal closure
+const CLOSE_NORMAL: u16 = 1000;
+/// The close code of a server that is going away, e.g. shutting down
+const CLOSE_GOING_AWAY: u16 = 1001;
+/// The close code reported when the connection dropped without a close frame
+const CLOSE_ABNORMAL: u16 = 1006;
+/// The close code of a server that rejects a message because of its policy
+const CLOSE_POLICY_VIOLATION: u16 = 1008;
+/// The close code of a server that restarts
+const CLOSE_SERVICE_RESTART: u16 = 1012;
+/// The close code of a server that is temporarily overloaded
+const CLOSE_TRY_AGAIN_LATER: u16 = 1013;
+
+/// Why the server closed the websocket connection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloseReason {
+    /// The close code, see [RFC 6455](https://www.rfc-editor.org/rfc/rfc6455#section-7.4.1)
+    pub code: u16,
+    /// The reason sent by the server, may be empty
+    pub reason: String,
+}
+
+impl CloseReason {
+    pub fn new(code: u16, reason: impl Into<String>) -> Self {
+        Self { code, reason: reason.into() }
+    }
+
+    /// The connection dropped without a close frame
+    pub fn abnormal() -> Self {
+        Self::new(CLOSE_ABNORMAL, "")
+    }
+
+    /// Whether the server closed the connection intentionally
+    pub fn is_normal(&self) -> bool {
+        self.code == CLOSE_NORMAL
+    }
+
+    /// Whether the server shuts down or restarts, and will likely accept new connections soon
+    pub fn is_restart(&self) -> bool {
+        matches!(self.code, CLOSE_GOING_AWAY | CLOSE_SERVICE_RESTART | CLOSE_TRY_AGAIN_LATER)
+    }
+
+    /// Whether the server closed the connection because of its policy, e.g. because the
+    /// credentials are invalid or a rate limit was exceeded
+    pub fn is_policy_violation(&self) -> bool {
+        self.code == CLOSE_POLICY_VIOLATION
+    }
+}
+
+impl fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.reason.is_empty() {
+            write!(f, "code {}", self.code)
+        } else {
+            write!(f, "code {}: {}", self.code, self.reason)
+        }
+    }
+}
+
+/// The cause of a websocket failure, see [`WsClientError::ws_error`].
+///
+/// Reconnect logic can use [`WsError::is_reconnectable`] to decide whether connecting again is
+/// worth it.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum WsError {
+    /// The server rejected the credentials during the handshake, contains the HTTP status
+    #[error("authentication failed with HTTP status {0}")]
+    AuthenticationFailed(u16),
+    /// The server closed the connection, e.g. because it restarts
+    #[error("server closed the connection with {0}")]
+    Closed(CloseReason),
+    /// The connection could not be established or broke, e.g. because of an I/O or TLS error
+    #[error("connection failed: {0}")]
+    Connection(String),
+    /// The server violated the websocket protocol or sent unexpected data
+    #[error("websocket protocol error: {0}")]
+    Protocol(String),
+    /// The server rejected re-establishing the subscription after a reconnect, so it no longer
+    /// receives notifications
+    #[error("subscription {id:?} was dropped: {reason}")]
+    SubscriptionDropped {
+        /// The id of the subscription
+        id: U256,
+        /// The error the server responded with
+        reason: String,
+    },
+}
+
+impl WsError {
+    /// Whether connecting again may succeed.
+    ///
+    /// Failed authentication, policy violations and protocol errors will likely fail again.
+    pub fn is_reconnectable(&self) -> bool {
+        match self {
+            WsError::Closed(reason) => !reason.is_policy_violation(),
+            WsError::Connection(_) => true,
+            WsError::AuthenticationFailed(_) |
+            WsError::Protocol(_) |
+            WsError::SubscriptionDropped { .. } => false,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<&WsTransportError> for WsError {
+    fn from(err: &WsTransportError) -> Self {
+        match err {
+            WsTransportError::ConnectionClosed | WsTransportError::AlreadyClosed => {
+                WsError::Closed(CloseReason::abnormal())
+            }
+            WsTransportError::Io(err) => WsError::Connection(err.to_string()),
+            WsTransportError::Http(response) if matches!(response.status().as_u16(), 401 | 403) => {
+                WsError::AuthenticationFailed(response.status().as_u16())
+            }
+            WsTransportError::Http(response) => {
+                WsError::Connection(format!("HTTP status {}", response.status()))
+            }
+            WsTransportError::Url(err) => WsError::Connection(err.to_string()),
+            err => WsError::Protocol(err.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<&WsTransportError> for WsError {
+    fn from(err: &WsTransportError) -> Self {
+        WsError::Connection(err.to_string())
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum WsClientError {
@@ -16,7 +147,11 @@ pub enum WsClientError {
 
     /// Internal lib error
     #[error(transparent)]
-    InternalError(#[from] WsError),
+    InternalError(#[from] WsTransportError),
+
+    /// The connection failed or was closed for good
+    #[error(transparent)]
+    Ws(#[from] WsError),
 
     /// Remote server sent a Close message
     #[error("Websocket closed unexpectedly")]
@@ -39,6 +174,21 @@ pub enum WsClientError {
     TooManyReconnects,
 }
 
+impl WsClientError {
+    /// Returns the cause of the error if it is related to the websocket connection
+    pub fn ws_error(&self) -> Option<WsError> {
+        match self {
+            WsClientError::InternalError(err) => Some(err.into()),
+            WsClientError::Ws(err) => Some(err.clone()),
+            WsClientError::UnexpectedClose => Some(WsError::Closed(CloseReason::abnormal())),
+            WsClientError::UnexpectedBinary(_) => {
+                Some(WsError::Protocol("unexpected binary data".to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl crate::RpcError for WsClientError {
     fn as_error_response(&self) -> Option<&JsonRpcError> {
         if let WsClientError::JsonRpcError(err) = self {
@@ -61,3 +211,39 @@ impl From<WsClientError> for ProviderError {
         ProviderError::JsonRpcClientError(Box::new(src))
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_transport_errors() {
+        let response = |status| http::Response::builder().status(status).body(None).unwrap();
+        let err = WsClientError::InternalError(WsTransportError::Http(response(401)));
+        assert_eq!(err.ws_error(), Some(WsError::AuthenticationFailed(401)));
+        assert!(!err.ws_error().unwrap().is_reconnectable());
+
+        let err = WsTransportError::Http(response(502));
+        assert!(WsError::from(&err).is_reconnectable());
+
+        let err = WsError::from(&WsTransportError::ConnectionClosed);
+        assert_eq!(err, WsError::Closed(CloseReason::abnormal()));
+        assert!(err.is_reconnectable());
+    }
+
+    #[test]
+    fn classifies_close_codes() {
+        let restart = CloseReason::new(1012, "restarting");
+        assert!(restart.is_restart());
+        assert!(WsError::Closed(restart).is_reconnectable());
+
+        let policy = CloseReason::new(1008, "invalid api key");
+        assert!(policy.is_policy_violation());
+        assert!(!WsError::Closed(policy.clone()).is_reconnectable());
+        assert_eq!(
+            WsError::Closed(policy).to_string(),
+            "server closed the connection with code 1008: invalid api key"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::{
     backend::{BackendDriver, WsBackend},
     ActiveSub, ConnectionDetails, InFlight, Instruction, Notification, PubSubItem, Response, SubId,
-    WsClient, WsClientError,
+    WsClient, WsClientError, WsError,
 };
 use crate::JsonRpcError;
 use corebc_core::types::U256;
@@ -18,6 +18,17 @@ use std::{
 
 pub type SharedChannelMap = Arc<Mutex<HashMap<U256, mpsc::UnboundedReceiver<Box<RawValue>>>>>;
 
+/// The failures the `RequestManager` reports to the `WsClient`s
+#[derive(Debug, Default)]
+pub struct ConnectionErrors {
+    /// Why the connection was closed for good
+    pub closed: Option<WsError>,
+    /// Why subscriptions were dropped, by subscription id
+    pub dropped_subs: HashMap<U256, WsError>,
+}
+
+pub type SharedErrors = Arc<Mutex<ConnectionErrors>>;
+
 pub const DEFAULT_RECONNECTS: usize = 5;
 
 /// This struct manages the relationship between the u64 request ID, and U256
@@ -85,6 +96,16 @@ impl SubscriptionManager {
         None
     }
 
+    /// Drops a subscription the server no longer serves, which ends its notification stream
+    fn drop_subscription(&mut self, id: u64) {
+        if let Some(sub) = self.subs.remove(&id) {
+            if let Some(server_id) = sub.current_server_id {
+                self.aliases.remove(&server_id);
+            }
+        }
+        self.channel_map.lock().unwrap().remove(&U256::from(id));
+    }
+
     #[tracing::instrument(skip_all, fields(server_id = ?notification.subscription))]
     fn handle_notification(&mut self, notification: Notification) {
         let server_id = notification.subscription;
@@ -198,6 +219,10 @@ pub struct RequestManager {
     conn: ConnectionDetails,
     // Instructions from the user-facing providers
     instructions: mpsc::UnboundedReceiver<Instruction>,
+    // Failures shared with the user-facing providers
+    errors: SharedErrors,
+    // The error that caused the last reconnect
+    last_error: Option<WsError>,
 }
 
 impl RequestManager {
@@ -217,6 +242,7 @@ impl RequestManager {
 
         let (instructions_tx, instructions_rx) = mpsc::unbounded();
         let channel_map: SharedChannelMap = Default::default();
+        let errors: SharedErrors = Default::default();
 
         ws.spawn();
 
@@ -229,11 +255,24 @@ impl RequestManager {
                 backend,
                 conn,
                 instructions: instructions_rx,
+                errors: errors.clone(),
+                last_error: None,
             },
-            WsClient { instructions: instructions_tx, channel_map },
+            WsClient { instructions: instructions_tx, channel_map, errors },
         ))
     }
 
+    /// Reconnects after the backend failed with `error`, unless the error is unlikely to go away
+    async fn reconnect_after(&mut self, error: Option<WsError>) -> Result<(), WsClientError> {
+        if let Some(error) = error {
+            if !error.is_reconnectable() {
+                return Err(error.into())
+            }
+            self.last_error = Some(error);
+        }
+        self.reconnect().await
+    }
+
     async fn reconnect(&mut self) -> Result<(), WsClientError> {
         if self.reconnects == 0 {
             return Err(WsClientError::TooManyReconnects)
@@ -306,6 +345,12 @@ impl RequestManager {
         if let Some(req) = self.reqs.remove(&id) {
             // pending fut has been dropped, this is fine
             let _ = req.channel.send(Err(error));
+        } else if self.subs.has(id) {
+            // the server rejected a subscription that was re-issued after a reconnect
+            tracing::warn!(id, %error, "Subscription was dropped");
+            self.subs.drop_subscription(id);
+            let dropped = WsError::SubscriptionDropped { id: id.into(), reason: error.to_string() };
+            self.errors.lock().unwrap().dropped_subs.insert(id.into(), dropped);
         }
     }
 
@@ -371,13 +416,13 @@ impl RequestManager {
                         match item_opt {
                             Some(item) => self.handle(item),
                             // Backend is gone, so reconnect
-                            None => if let Err(e) = self.reconnect().await {
+                            None => if let Err(e) = self.reconnect_after(None).await {
                                 break Err(e);
                             }
                         }
                     },
-                    _ = &mut self.backend.error => {
-                        if let Err(e) = self.reconnect().await {
+                    error = &mut self.backend.error => {
+                        if let Err(e) = self.reconnect_after(error.ok()).await {
                             break Err(e);
                         }
                     },
@@ -392,6 +437,12 @@ impl RequestManager {
             };
             if let Err(err) = result {
                 tracing::error!(%err, "Error during reconnection");
+                // report the failure that made us give up, rather than the reconnect limit
+                let closed = match err {
+                    WsClientError::TooManyReconnects => self.last_error.take(),
+                    err => err.ws_error(),
+                };
+                self.errors.lock().unwrap().closed = closed;
             }
             // Issue the shutdown command. we don't care if it is received
             self.backend.shutdown();
@@ -4,7 +4,7 @@ mod backend;
 
 mod manager;
 
-use manager::{RequestManager, SharedChannelMap};
+use manager::{RequestManager, SharedChannelMap, SharedErrors};
 use std::fmt;
 
 mod types;
@@ -30,6 +30,8 @@ pub struct WsClient {
     instructions: mpsc::UnboundedSender<Instruction>,
     // Used to receive sub notifications channels with the backend
     channel_map: SharedChannelMap,
+    // Used to receive the causes of failures from the request manager
+    errors: SharedErrors,
 }
 
 impl WsClient {
@@ -50,6 +52,25 @@ impl WsClient {
         Ok(this)
     }
 
+    /// Returns why the connection was closed for good, `None` while it is open.
+    ///
+    /// Once closed, requests fail with [`WsClientError::Ws`] containing the same error.
+    pub fn close_reason(&self) -> Option<WsError> {
+        self.errors.lock().unwrap().closed.clone()
+    }
+
+    /// Returns why the subscription with the given id was dropped, if it was.
+    ///
+    /// The notification stream of a dropped subscription ends.
+    pub fn subscription_error(&self, id: impl Into<U256>) -> Option<WsError> {
+        self.errors.lock().unwrap().dropped_subs.get(&id.into()).cloned()
+    }
+
+    /// The error of a request whose channel to the `RequestManager` closed
+    fn closed_error(&self) -> WsClientError {
+        self.close_reason().map(WsClientError::Ws).unwrap_or(WsClientError::UnexpectedClose)
+    }
+
     #[tracing::instrument(skip(self, params), err)]
     async fn make_request<R>(&self, method: &str, params: Box<RawValue>) -> Result<R, WsClientError>
     where
@@ -57,11 +78,9 @@ impl WsClient {
     {
         let (tx, rx) = oneshot::channel();
         let instruction = Instruction::Request { method: method.to_owned(), params, sender: tx };
-        self.instructions
-            .unbounded_send(instruction)
-            .map_err(|_| WsClientError::UnexpectedClose)?;
+        self.instructions.unbounded_send(instruction).map_err(|_| self.closed_error())?;
 
-        let res = rx.await.map_err(|_| WsClientError::UnexpectedClose)??;
+        let res = rx.await.map_err(|_| self.closed_error())??;
         tracing::trace!(res = %res, "Received response from request manager");
         let resp = serde_json::from_str(res.get())?;
         tracing::trace!("Deserialization success");
@@ -107,7 +126,7 @@ impl PubsubClient for WsClient {
     fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), WsClientError> {
         self.instructions
             .unbounded_send(Instruction::Unsubscribe { id: id.into() })
-            .map_err(|_| WsClientError::UnexpectedClose)
+            .map_err(|_| self.closed_error())
     }
 }
 
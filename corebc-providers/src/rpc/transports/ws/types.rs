@@ -251,7 +251,7 @@ mod aliases {
     pub use ws_stream_wasm::*;
 
     pub type Message = WsMessage;
-    pub type WsError = ws_stream_wasm::WsErr;
+    pub type WsTransportError = ws_stream_wasm::WsErr;
     pub type WsStreamItem = Message;
 
     pub type InternalStream = futures_util::stream::Fuse<WsStream>;
@@ -262,8 +262,8 @@ mod aliases {
     pub use tokio_tungstenite::{connect_async, tungstenite};
     use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
     pub type Message = tungstenite::protocol::Message;
-    pub type WsError = tungstenite::Error;
-    pub type WsStreamItem = Result<Message, WsError>;
+    pub type WsTransportError = tungstenite::Error;
+    pub type WsStreamItem = Result<Message, WsTransportError>;
 
     pub use http::Request as HttpRequest;
     pub use tungstenite::client::IntoClientRequest;
@@ -1,3 +1,9 @@
+pub mod staleness;
+pub use staleness::{Heartbeat, StalenessMonitor, DEFAULT_STALL_BLOCKS};
+
+pub mod sync;
+pub use sync::SyncProgressStream;
+
 pub mod tx_stream;
 
 pub mod watcher;
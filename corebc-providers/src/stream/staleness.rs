@@ -0,0 +1,141 @@
+use corebc_core::types::Network;
+use futures_core::stream::Stream;
+use futures_timer::Delay;
+use instant::Instant;
+use pin_project::pin_project;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The number of average block times without a new block after which a block subscription is
+/// considered stalled, see [`StalenessMonitor::for_network`]
+pub const DEFAULT_STALL_BLOCKS: u32 = 3;
+
+/// An item of a [`StalenessMonitor`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Heartbeat<T> {
+    /// An item of the monitored stream
+    Item(T),
+    /// The monitored stream did not yield an item for `elapsed`
+    Stalled {
+        /// The time since the last item, or since monitoring started
+        elapsed: Duration,
+    },
+}
+
+/// Monitors a stream, e.g. a `newHeads` subscription, for silence.
+///
+/// Items of the stream are yielded as [`Heartbeat::Item`]. If no item arrives within the timeout,
+/// [`Heartbeat::Stalled`] is yielded and repeated every timeout for as long as the stream stays
+/// silent. A WS or IPC subscription whose connection silently died never ends, so consumers that
+/// see it stall should unsubscribe and subscribe again, possibly on another connection.
+///
+/// # Example
+///
+/// ```no_run
+/// use corebc_providers::{Heartbeat, Provider, ProviderError, PubsubClient, StreamExt};
+///
+/// # async fn foo<P: PubsubClient>(provider: Provider<P>) -> Result<(), ProviderError> {
+/// let mut blocks = provider.subscribe_blocks_monitored().await?;
+/// while let Some(heartbeat) = blocks.next().await {
+///     match heartbeat {
+///         Heartbeat::Item(block) => println!("new block {:?}", block.number),
+///         Heartbeat::Stalled { elapsed } => {
+///             eprintln!("no block for {elapsed:?}, resubscribing");
+///             break
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+#[pin_project]
+pub struct StalenessMonitor<S> {
+    #[pin]
+    stream: S,
+    timeout: Duration,
+    last_item: Instant,
+    delay: Delay,
+}
+
+impl<S: Stream> StalenessMonitor<S> {
+    /// Monitors `stream`, which is considered stalled if it yields no item for `timeout`
+    pub fn new(stream: S, timeout: Duration) -> Self {
+        Self { stream, timeout, last_item: Instant::now(), delay: Delay::new(timeout) }
+    }
+
+    /// Monitors a block subscription on `network`, which is considered stalled if it yields no
+    /// block for [`DEFAULT_STALL_BLOCKS`] average block times
+    pub fn for_network(stream: S, network: Network) -> Self {
+        let blocktime = network.average_blocktime_hint().unwrap_or(Duration::from_secs(7));
+        Self::new(stream, blocktime * DEFAULT_STALL_BLOCKS)
+    }
+
+    /// Returns the time without an item after which the stream is considered stalled
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the time since the last item, or since monitoring started
+    pub fn since_last_item(&self) -> Duration {
+        self.last_item.elapsed()
+    }
+
+    /// Returns the monitored stream
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream> Stream for StalenessMonitor<S> {
+    type Item = Heartbeat<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                *this.last_item = Instant::now();
+                this.delay.reset(*this.timeout);
+                return Poll::Ready(Some(Heartbeat::Item(item)))
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => {}
+        }
+
+        if Pin::new(&mut *this.delay).poll(cx).is_ready() {
+            this.delay.reset(*this.timeout);
+            return Poll::Ready(Some(Heartbeat::Stalled { elapsed: this.last_item.elapsed() }))
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use futures_util::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn signals_stalled_streams() {
+        let timeout = Duration::from_millis(20);
+        let items = stream::iter([1, 2]).chain(stream::pending());
+        let mut monitor = StalenessMonitor::new(items, timeout);
+
+        assert_eq!(monitor.next().await, Some(Heartbeat::Item(1)));
+        assert_eq!(monitor.next().await, Some(Heartbeat::Item(2)));
+        for _ in 0..2 {
+            match monitor.next().await {
+                Some(Heartbeat::Stalled { elapsed }) => assert!(elapsed >= timeout),
+                heartbeat => panic!("unexpected heartbeat: {heartbeat:?}"),
+            }
+        }
+
+        let monitor = StalenessMonitor::new(stream::iter([1]), timeout);
+        assert_eq!(monitor.collect::<Vec<_>>().await, [Heartbeat::Item(1)]);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    utils::{interval, PinBoxFut},
+    JsonRpcClient, Middleware, Provider, ProviderError,
+};
+use corebc_core::types::{SyncProgress, SyncingStatus};
+use futures_core::stream::Stream;
+use futures_util::StreamExt;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+enum SyncProgressState<'a> {
+    WaitForInterval,
+    GetSyncing(PinBoxFut<'a, SyncingStatus>),
+    Synced,
+}
+
+/// Streams the sync progress of the node by polling `xcb_syncing`.
+///
+/// The node is queried right away and then once per polling interval. Every response of a
+/// syncing node is yielded as a [`SyncProgress`], failed requests are yielded as errors without
+/// ending the stream. The stream completes once the node reports that it is synced.
+#[must_use = "streams do nothing unless polled"]
+pub struct SyncProgressStream<'a, P> {
+    provider: &'a Provider<P>,
+    interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
+    state: SyncProgressState<'a>,
+}
+
+impl<'a, P> SyncProgressStream<'a, P>
+where
+    P: JsonRpcClient,
+{
+    /// Creates a new stream polling with the provider's interval
+    pub fn new(provider: &'a Provider<P>) -> Self {
+        Self {
+            provider,
+            interval: Box::new(interval(provider.get_interval())),
+            state: SyncProgressState::GetSyncing(Box::pin(provider.syncing())),
+        }
+    }
+
+    /// Sets the stream's polling interval
+    pub fn interval(mut self, duration: Duration) -> Self {
+        self.interval = Box::new(interval(duration));
+        self
+    }
+}
+
+impl<'a, P> Stream for SyncProgressStream<'a, P>
+where
+    P: JsonRpcClient,
+{
+    type Item = Result<SyncProgress, ProviderError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            this.state = match &mut this.state {
+                SyncProgressState::WaitForInterval => {
+                    let _ready = futures_util::ready!(this.interval.poll_next_unpin(cx));
+                    SyncProgressState::GetSyncing(Box::pin(this.provider.syncing()))
+                }
+                SyncProgressState::GetSyncing(fut) => {
+                    let res = futures_util::ready!(fut.as_mut().poll(cx));
+                    let item = match res {
+                        Ok(SyncingStatus::IsFalse) => {
+                            this.state = SyncProgressState::Synced;
+                            return Poll::Ready(None)
+                        }
+                        Ok(SyncingStatus::IsSyncing(progress)) => Ok(*progress),
+                        Err(err) => Err(err),
+                    };
+                    this.state = SyncProgressState::WaitForInterval;
+                    return Poll::Ready(Some(item))
+                }
+                SyncProgressState::Synced => return Poll::Ready(None),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::types::U64;
+
+    fn progress(current: u64) -> SyncingStatus {
+        SyncingStatus::IsSyncing(Box::new(SyncProgress {
+            current_block: U64::from(current),
+            highest_block: U64::from(10),
+            starting_block: U64::zero(),
+            pulled_states: None,
+            known_states: None,
+            healed_bytecode_bytes: None,
+            healed_bytecodes: None,
+            healed_trienode_bytes: None,
+            healed_trienodes: None,
+            healing_bytecode: None,
+            healing_trienodes: None,
+            synced_account_bytes: None,
+            synced_accounts: None,
+            synced_bytecode_bytes: None,
+            synced_bytecodes: None,
+            synced_storage: None,
+            synced_storage_bytes: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn streams_progress_until_synced() {
+        let (provider, mock) = Provider::mocked();
+        // responses are popped from the back
+        mock.push(SyncingStatus::IsFalse).unwrap();
+        mock.push(progress(8)).unwrap();
+        mock.push(progress(5)).unwrap();
+
+        let blocks = SyncProgressStream::new(&provider)
+            .interval(Duration::from_millis(1))
+            .map(|progress| progress.unwrap().current_block.as_u64())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(blocks, [5, 8]);
+    }
+
+    #[tokio::test]
+    async fn waits_until_synced() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(SyncingStatus::IsFalse).unwrap();
+        mock.push(progress(5)).unwrap();
+        provider.wait_until_synced(Duration::from_millis(1), Duration::from_secs(5)).await.unwrap();
+
+        // the mock errors once it runs out of responses
+        let err = provider
+            .wait_until_synced(Duration::from_millis(1), Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("empty responses"), "{err}");
+    }
+}
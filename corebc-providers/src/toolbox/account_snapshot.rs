@@ -0,0 +1,45 @@
+//! Snapshots of the state of many accounts, see
+//! [`Provider::get_account_snapshots`](crate::Provider::get_account_snapshots)
+
+use corebc_core::{
+    types::{Bytes, EIP1186ProofResponse, H256, U256},
+    utils::sha3,
+};
+
+/// The maximum number of requests
+/// [`Provider::get_account_snapshots`](crate::Provider::get_account_snapshots) keeps in flight
+pub const ACCOUNT_SNAPSHOT_CONCURRENCY: usize = 16;
+
+/// The balance, nonce and code hash of an account at a block
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountSnapshot {
+    /// The balance in ore
+    pub balance: U256,
+    /// The number of transactions sent from the account
+    pub nonce: U256,
+    /// The hash of the account's code, the hash of no code for externally owned accounts
+    pub code_hash: H256,
+}
+
+impl AccountSnapshot {
+    /// Creates a snapshot from the account's code
+    pub fn new(balance: U256, nonce: U256, code: &Bytes) -> Self {
+        Self { balance, nonce, code_hash: H256::from(sha3(code)) }
+    }
+
+    /// Whether the account has code. Nodes report the code hash of accounts that do not exist as
+    /// zero, which is not considered code either.
+    pub fn is_contract(&self) -> bool {
+        !self.code_hash.is_zero() && self.code_hash != H256::from(sha3([]))
+    }
+}
+
+impl From<EIP1186ProofResponse> for AccountSnapshot {
+    fn from(proof: EIP1186ProofResponse) -> Self {
+        Self {
+            balance: proof.balance,
+            nonce: proof.nonce.as_u64().into(),
+            code_hash: proof.code_hash,
+        }
+    }
+}
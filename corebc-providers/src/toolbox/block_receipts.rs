@@ -0,0 +1,101 @@
+//! Pairing the transactions of a block with their receipts, see
+//! [`BlockReceiptsExt::transactions_with_receipts`]
+
+use crate::Middleware;
+use async_trait::async_trait;
+use corebc_core::types::{Block, Transaction, TransactionReceipt, TxHash};
+use std::collections::HashMap;
+
+/// Fetches the receipts of a block's transactions
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait BlockReceiptsExt {
+    /// Fetches the receipts of the block's transactions and pairs every transaction with its
+    /// receipt, in the order of the block's transactions.
+    ///
+    /// The receipts are fetched with [`Middleware::get_block_receipts`], which requests all of
+    /// them at once if the node supports it. Transactions of pending blocks have no receipts yet
+    /// and are paired with `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_core::types::BlockNumber;
+    /// use corebc_providers::{BlockReceiptsExt, Middleware, Provider, Http};
+    /// use std::convert::TryFrom;
+    ///
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let block = provider.get_block_with_txs(BlockNumber::Latest).await?.unwrap();
+    /// for (tx, receipt) in block.transactions_with_receipts(&provider).await? {
+    ///     let energy_used = receipt.and_then(|receipt| receipt.energy_used);
+    ///     println!("{:?} used {:?}", tx.hash, energy_used);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn transactions_with_receipts<M: Middleware>(
+        &self,
+        provider: &M,
+    ) -> Result<Vec<(Transaction, Option<TransactionReceipt>)>, M::Error>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl BlockReceiptsExt for Block<Transaction> {
+    async fn transactions_with_receipts<M: Middleware>(
+        &self,
+        provider: &M,
+    ) -> Result<Vec<(Transaction, Option<TransactionReceipt>)>, M::Error> {
+        let mut receipts: HashMap<TxHash, TransactionReceipt> = match self.number {
+            Some(number) if !self.transactions.is_empty() => provider
+                .get_block_receipts(number)
+                .await?
+                .into_iter()
+                .map(|receipt| (receipt.transaction_hash, receipt))
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        Ok(self.transactions.iter().map(|tx| (tx.clone(), receipts.remove(&tx.hash))).collect())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::Provider;
+    use corebc_core::types::{H256, U64};
+
+    #[tokio::test]
+    async fn pairs_transactions_with_receipts() {
+        let (provider, mock) = Provider::mocked();
+        let txs: Vec<_> =
+            (0..3).map(|_| Transaction { hash: H256::random(), ..Default::default() }).collect();
+        let block = Block { number: Some(U64::from(7)), transactions: txs, ..Default::default() };
+
+        // the receipts are matched by hash, the last transaction has no receipt
+        let receipts: Vec<_> = block.transactions[..2]
+            .iter()
+            .rev()
+            .map(|tx| TransactionReceipt { transaction_hash: tx.hash, ..Default::default() })
+            .collect();
+        mock.push_for("web3_clientVersion", "Gocore/v1.1.11-stable-7cd4e4b6/linux-amd64/go1.20.4")
+            .unwrap();
+        mock.push_for("xcb_getBlockReceipts", receipts).unwrap();
+
+        let pairs = block.transactions_with_receipts(&provider).await.unwrap();
+        assert_eq!(pairs.len(), 3);
+        for (tx, receipt) in &pairs[..2] {
+            assert_eq!(receipt.as_ref().unwrap().transaction_hash, tx.hash);
+        }
+        assert!(pairs[2].1.is_none());
+
+        // pending blocks have no receipts yet
+        let pending = Block { number: None, ..block };
+        let pairs = pending.transactions_with_receipts(&provider).await.unwrap();
+        assert!(pairs.iter().all(|(_, receipt)| receipt.is_none()));
+        mock.verify().unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+//! Results of transactions executed in sequence, see
+//! [`Provider::simulate_bundle`](crate::Provider::simulate_bundle)
+
+use crate::call_raw::spoof;
+use corebc_core::types::{BlockTrace, Bytes, CallFrame, PreStateFrame, Res, U256, U64};
+
+/// The result of a transaction of a simulated bundle
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulatedTransaction {
+    /// The returned data, the revert data if the transaction failed
+    pub output: Bytes,
+    /// The energy used by the transaction, if the node reports it
+    pub energy_used: Option<U256>,
+    /// Why the transaction failed, e.g. `Reverted` or `execution reverted`
+    pub error: Option<String>,
+}
+
+impl SimulatedTransaction {
+    /// Whether the transaction succeeded
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl From<BlockTrace> for SimulatedTransaction {
+    fn from(trace: BlockTrace) -> Self {
+        // the first trace is the one of the top level call
+        let top = trace.trace.and_then(|traces| traces.into_iter().next());
+        let energy_used = top.as_ref().and_then(|top| match &top.result {
+            Some(Res::Call(res)) => Some(res.energy_used),
+            Some(Res::Create(res)) => Some(res.energy_used),
+            _ => None,
+        });
+        Self { output: trace.output, energy_used, error: top.and_then(|top| top.error) }
+    }
+}
+
+impl From<CallFrame> for SimulatedTransaction {
+    fn from(frame: CallFrame) -> Self {
+        Self {
+            output: frame.output.unwrap_or_default(),
+            energy_used: Some(frame.energy_used),
+            error: frame.error,
+        }
+    }
+}
+
+/// Overrides `state` with the state the transaction traced in `diff` left behind, so that the next
+/// transaction sees its effects
+pub(crate) fn apply_state_diff(state: &mut spoof::State, diff: PreStateFrame) {
+    let diff = match diff {
+        PreStateFrame::Diff(diff) => diff,
+        PreStateFrame::Default(_) => return,
+    };
+    // accounts that are only in the pre state were deleted
+    for address in diff.pre.keys().filter(|address| !diff.post.contains_key(address)) {
+        state
+            .account(*address)
+            .balance(U256::zero())
+            .nonce(U64::zero())
+            .code(Bytes::default())
+            .state(Default::default());
+    }
+    for (address, post) in diff.post {
+        let account = state.account(address);
+        if let Some(balance) = post.balance {
+            account.balance(balance);
+        }
+        if let Some(nonce) = post.nonce {
+            account.nonce(nonce.low_u64().into());
+        }
+        if let Some(code) = post.code.and_then(|code| code.parse().ok()) {
+            account.code(code);
+        }
+        for (key, value) in post.storage.unwrap_or_default() {
+            account.store(key, value);
+        }
+    }
+}
@@ -17,7 +17,7 @@ use std::{
     task::{Context, Poll},
 };
 
-pub use spoof::{balance, code, nonce, state, storage};
+pub use spoof::{balance, block, code, nonce, state, storage};
 
 /// Provides methods for overriding parameters to the `xcb_call` rpc method
 pub trait RawCall<'a> {
@@ -26,6 +26,9 @@ pub trait RawCall<'a> {
     /// Sets the [state override set](https://geth.ethereum.org/docs/rpc/ns-eth#3-object---state-override-set).
     /// Note that not all client implementations will support this as a parameter.
     fn state(self, state: &'a spoof::State) -> Self;
+    /// Sets the block overrides, see [`spoof::BlockOverrides`].
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a spoof::BlockOverrides) -> Self;
 
     /// Maps a closure `f` over the result of `.await`ing this call
     fn map<F>(self, f: F) -> Map<Self, F>
@@ -98,6 +101,11 @@ impl<'a, P> RawCall<'a> for CallBuilder<'a, P> {
     fn state(self, state: &'a spoof::State) -> Self {
         self.map_input(|call| call.input.state = Some(state))
     }
+    /// Sets the block overrides, see [`spoof::BlockOverrides`].
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a spoof::BlockOverrides) -> Self {
+        self.map_input(|call| call.input.block_overrides = Some(overrides))
+    }
 }
 
 impl<'a, P: JsonRpcClient> Future for CallBuilder<'a, P> {
@@ -145,11 +153,12 @@ struct CallInput<'a> {
     tx: &'a TypedTransaction,
     block: Option<BlockId>,
     state: Option<&'a spoof::State>,
+    block_overrides: Option<&'a spoof::BlockOverrides>,
 }
 
 impl<'a> CallInput<'a> {
     fn new(tx: &'a TypedTransaction) -> Self {
-        Self { tx, block: None, state: None }
+        Self { tx, block: None, state: None, block_overrides: None }
     }
 }
 
@@ -158,7 +167,11 @@ impl<'a> Serialize for CallInput<'a> {
     where
         S: serde::ser::Serializer,
     {
-        let len = 2 + self.state.is_some() as usize;
+        let len = match (self.state, self.block_overrides) {
+            (_, Some(_)) => 4,
+            (Some(_), None) => 3,
+            (None, None) => 2,
+        };
 
         let mut tup = serializer.serialize_tuple(len)?;
         tup.serialize_element(self.tx)?;
@@ -169,6 +182,13 @@ impl<'a> Serialize for CallInput<'a> {
         if let Some(state) = self.state {
             tup.serialize_element(state)?;
         }
+        if let Some(overrides) = self.block_overrides {
+            // the block overrides are positional, so they need an (empty) state override set
+            if self.state.is_none() {
+                tup.serialize_element(&spoof::State::default())?;
+            }
+            tup.serialize_element(overrides)?;
+        }
         tup.end()
     }
 }
@@ -214,6 +234,12 @@ where
     fn state(self, state: &'a spoof::State) -> Self {
         Self { inner: self.inner.state(state), f: self.f }
     }
+
+    /// Sets the block overrides, see [`spoof::BlockOverrides`].
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a spoof::BlockOverrides) -> Self {
+        Self { inner: self.inner.block_overrides(overrides), f: self.f }
+    }
 }
 
 impl<T, F, Y> Future for Map<T, F>
@@ -274,6 +300,26 @@ pub mod spoof {
             self.storage.get_or_insert_with(Default::default).insert(key, val);
             self
         }
+        /// Replace the whole storage of the account, all slots that are not in `storage` are
+        /// treated as empty
+        pub fn state(&mut self, storage: HashMap<H256, H256>) -> &mut Self {
+            self.storage = Some(Storage::Replace(storage));
+            self
+        }
+        /// Override the given storage slots of the account, all other slots keep their value
+        pub fn state_diff(&mut self, storage: HashMap<H256, H256>) -> &mut Self {
+            self.storage = Some(Storage::Diff(storage));
+            self
+        }
+        /// Treat the overridden storage slots as the whole storage of the account, see
+        /// [`Storage::Replace`]
+        pub fn replace_storage(&mut self) -> &mut Self {
+            let storage = match self.storage.take() {
+                Some(Storage::Diff(map)) | Some(Storage::Replace(map)) => map,
+                None => Default::default(),
+            };
+            self.state(storage)
+        }
     }
 
     /// Wraps a map from storage slot to the overriden value.
@@ -324,6 +370,68 @@ pub mod spoof {
         pub fn account(&mut self, adr: Address) -> &mut Account {
             self.0.entry(adr).or_default()
         }
+
+        /// Sets the overrides of the account at `adr`, returns the previous overrides if any.
+        pub fn insert(&mut self, adr: Address, account: Account) -> Option<Account> {
+            self.0.insert(adr, account)
+        }
+
+        /// Returns the overrides of the account at `adr`
+        pub fn get(&self, adr: &Address) -> Option<&Account> {
+            self.0.get(adr)
+        }
+    }
+
+    /// The fields of the block to override for the call.
+    ///
+    /// Block overrides are passed to `xcb_call` after the state override set. Note that not all
+    /// client implementations support them.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BlockOverrides {
+        /// Block number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub number: Option<U64>,
+        /// Block difficulty
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub difficulty: Option<U256>,
+        /// Block timestamp
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub time: Option<U64>,
+        /// Block energy limit
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub energy_limit: Option<U64>,
+        /// Block beneficiary
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub coinbase: Option<Address>,
+    }
+
+    impl BlockOverrides {
+        /// Override the block number
+        pub fn number(&mut self, number: U64) -> &mut Self {
+            self.number = Some(number);
+            self
+        }
+        /// Override the block difficulty
+        pub fn difficulty(&mut self, difficulty: U256) -> &mut Self {
+            self.difficulty = Some(difficulty);
+            self
+        }
+        /// Override the block timestamp
+        pub fn time(&mut self, time: U64) -> &mut Self {
+            self.time = Some(time);
+            self
+        }
+        /// Override the block energy limit
+        pub fn energy_limit(&mut self, energy_limit: U64) -> &mut Self {
+            self.energy_limit = Some(energy_limit);
+            self
+        }
+        /// Override the block beneficiary
+        pub fn coinbase(&mut self, coinbase: Address) -> &mut Self {
+            self.coinbase = Some(coinbase);
+            self
+        }
     }
 
     /// Returns an empty state override set.
@@ -486,12 +594,37 @@ pub mod spoof {
         state.account(adr).store(key, val);
         state
     }
+
+    /// Returns empty block overrides.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use corebc_core::types::{Address, TransactionRequest};
+    /// # use corebc_providers::{Provider, Http, Middleware, call_raw::{RawCall, spoof}};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    ///
+    /// let adr: Address = "0x00006fC21092DA55B392b045eD78F4732bff3C580e2c".parse()?;
+    /// let tx = TransactionRequest::default().to(adr).into();
+    ///
+    /// // execute the call as if it was included in a later block
+    /// let mut overrides = spoof::block();
+    /// overrides.number(1_000_000.into()).time(1_700_000_000.into());
+    /// provider.call_raw(&tx).block_overrides(&overrides).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block() -> BlockOverrides {
+        Default::default()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Provider;
+    use std::collections::HashMap;
 
     // Deserializes xcb_call parameters as owned data for testing serialization
     #[derive(Debug, Deserialize)]
@@ -499,10 +632,16 @@ mod tests {
         TypedTransaction,
         Option<BlockId>,
         #[serde(default)] Option<spoof::State>,
+        #[serde(default)] Option<spoof::BlockOverrides>,
     );
     impl<'a> From<&'a CallInputOwned> for CallInput<'a> {
         fn from(src: &'a CallInputOwned) -> Self {
-            Self { tx: &src.0, block: src.1, state: src.2.as_ref() }
+            Self {
+                tx: &src.0,
+                block: src.1,
+                state: src.2.as_ref(),
+                block_overrides: src.3.as_ref(),
+            }
         }
     }
 
@@ -514,7 +653,12 @@ mod tests {
         let de = CallInput::from(&de);
 
         assert_eq!(input.tx, de.tx);
-        assert_eq!(input.state, de.state);
+        assert_eq!(input.block_overrides, de.block_overrides);
+        if input.block_overrides.is_none() {
+            assert_eq!(input.state, de.state);
+        } else {
+            assert_eq!(input.state.cloned().unwrap_or_default(), de.state.cloned().unwrap());
+        }
 
         let block = input.block.or_else(|| Some(BlockNumber::Latest.into()));
         assert_eq!(block, de.block);
@@ -551,6 +695,54 @@ mod tests {
         state.account(adr1);
         let call = provider.call_raw(&tx).state(&state);
         test_encode(call);
+
+        let mut overrides = spoof::block();
+        overrides.number(100.into()).energy_limit(30_000_000.into());
+        let call = provider.call_raw(&tx).block_overrides(&overrides);
+        test_encode(call);
+
+        let mut state = spoof::state();
+        state.account(adr1).state(HashMap::from([(k1, v1)]));
+        let call = provider.call_raw(&tx).state(&state).block_overrides(&overrides);
+        test_encode(call);
+    }
+
+    #[test]
+    fn test_serialize_overrides() {
+        let adr: Address = "0x00006fC21092DA55B392b045eD78F4732bff3C580e2c".parse().unwrap();
+        let key = H256::from_low_u64_be(1);
+        let val = H256::from_low_u64_be(17);
+
+        let mut account = spoof::Account::default();
+        account.nonce(1.into()).code("0x00".parse().unwrap()).store(key, val);
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json["nonce"], "0x1");
+        assert_eq!(json["code"], "0x00");
+        assert_eq!(json["stateDiff"][format!("{key:?}")], format!("{val:?}"));
+        assert!(json.get("state").is_none());
+
+        account.replace_storage();
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json["state"][format!("{key:?}")], format!("{val:?}"));
+        assert!(json.get("stateDiff").is_none());
+
+        let mut state = spoof::state();
+        assert_eq!(state.insert(adr, account.clone()), None);
+        assert_eq!(state.get(&adr), Some(&account));
+
+        let mut overrides = spoof::block();
+        overrides.number(100.into()).time(1_000.into()).energy_limit(8_000_000.into());
+        assert_eq!(
+            serde_json::to_value(&overrides).unwrap(),
+            serde_json::json!({ "number": "0x64", "time": "0x3e8", "energyLimit": "0x7a1200" })
+        );
+
+        let tx = TypedTransaction::default();
+        let (provider, _) = Provider::mocked();
+        let input = provider.call_raw(&tx).block_overrides(&overrides).unwrap().input;
+        let json = utils::serialize(&input);
+        assert_eq!(json[2], serde_json::json!({}));
+        assert_eq!(json[3]["energyLimit"], "0x7a1200");
     }
 
     // #[tokio::test]
@@ -1,5 +1,5 @@
 mod pending_transaction;
-pub use pending_transaction::PendingTransaction;
+pub use pending_transaction::{PendingTransaction, PendingTxError, PendingTxStatus};
 
 mod pending_escalator;
 pub use pending_escalator::EscalatingPending;
@@ -7,5 +7,21 @@ pub use pending_escalator::EscalatingPending;
 mod log_query;
 pub use log_query::{LogQuery, LogQueryError};
 
+mod trace_query;
+pub use trace_query::{TraceQuery, TraceQueryError};
+
 pub mod call_raw;
 pub use call_raw::*;
+
+mod revert_reason;
+pub use revert_reason::{RevertReason, PANIC_SIGNATURE, REVERT_STRING_SIGNATURE};
+
+mod account_snapshot;
+pub use account_snapshot::{AccountSnapshot, ACCOUNT_SNAPSHOT_CONCURRENCY};
+
+mod block_receipts;
+pub use block_receipts::BlockReceiptsExt;
+
+mod bundle_simulation;
+pub(crate) use bundle_simulation::apply_state_diff;
+pub use bundle_simulation::SimulatedTransaction;
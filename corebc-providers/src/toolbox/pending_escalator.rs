@@ -1,6 +1,6 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use corebc_core::types::{Bytes, TransactionReceipt, H256};
+use corebc_core::types::{Bytes, TransactionReceipt, H256, U64};
 use futures_timer::Delay;
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use instant::{Duration, Instant};
@@ -15,6 +15,7 @@ use crate::{
 enum EscalatorStates<'a, P> {
     Initial(PinBoxFut<'a, PendingTransaction<'a, P>>),
     Sleeping(Pin<Box<Delay>>),
+    CheckingBlock(PinBoxFut<'a, U64>),
     BroadcastingNew(PinBoxFut<'a, PendingTransaction<'a, P>>),
     CheckingReceipts(FuturesUnordered<PinBoxFut<'a, Option<TransactionReceipt>>>),
     Completed,
@@ -31,9 +32,11 @@ where
 {
     provider: &'a Provider<P>,
     broadcast_interval: Duration,
+    broadcast_blocks: Option<u64>,
     polling_interval: Duration,
     txns: Vec<Bytes>,
     last: Instant,
+    last_block: Option<U64>,
     sent: Vec<H256>,
     state: EscalatorStates<'a, P>,
 }
@@ -59,11 +62,13 @@ where
         Self {
             provider,
             broadcast_interval: Duration::from_millis(150),
+            broadcast_blocks: None,
             polling_interval: Duration::from_millis(10),
             txns,
             // placeholder value. We set this again after the initial broadcast
             // future resolves
             last: Instant::now(),
+            last_block: None,
             sent: vec![],
             state: EscalatorStates::Initial(Box::pin(provider.send_raw_transaction(first))),
         }
@@ -76,6 +81,17 @@ where
         self
     }
 
+    /// Broadcast a new transaction once `blocks` blocks were mined since the
+    /// last broadcast, instead of after the broadcast interval. The block
+    /// number is checked every polling interval.
+    ///
+    /// This keeps the escalation speed independent of the block time of the
+    /// network, see [`block_escalation`](crate::block_escalation).
+    pub fn with_broadcast_blocks(mut self, blocks: u64) -> Self {
+        self.broadcast_blocks = Some(blocks);
+        self
+    }
+
     /// Set the polling interval. This controls how often the escalator checks
     /// transaction receipts for confirmation.
     pub fn with_polling_interval(mut self, duration: impl Into<Duration>) -> Self {
@@ -92,6 +108,12 @@ where
     pub fn get_broadcast_interval(&self) -> Duration {
         self.broadcast_interval
     }
+
+    /// Get the number of blocks after which a new transaction is broadcast,
+    /// if broadcasts are scheduled by blocks.
+    pub fn get_broadcast_blocks(&self) -> Option<u64> {
+        self.broadcast_blocks
+    }
 }
 
 macro_rules! check_all_receipts {
@@ -136,6 +158,8 @@ macro_rules! poll_broadcast_fut {
         match $fut.as_mut().poll($cx) {
             Poll::Ready(Ok(pending)) => {
                 *$this.last = Instant::now();
+                // the block of the broadcast is read on the next block check
+                *$this.last_block = None;
                 $this.sent.push(*pending);
                 tracing::info!(
                     tx_hash = ?*pending,
@@ -186,9 +210,16 @@ where
             }
             Sleeping(delay) => {
                 futures_util::ready!(delay.as_mut().poll(cx));
-                // if broadcast timer has elapsed and if we have a TX to
-                // broadcast, broadcast it
-                if this.last.elapsed() > *this.broadcast_interval {
+                if this.broadcast_blocks.is_some() {
+                    // check if enough blocks were mined for the next broadcast
+                    if !this.txns.is_empty() {
+                        *this.state = CheckingBlock(this.provider.get_block_number());
+                        cx.waker().wake_by_ref();
+                        return Poll::Pending
+                    }
+                } else if this.last.elapsed() > *this.broadcast_interval {
+                    // if broadcast timer has elapsed and if we have a TX to
+                    // broadcast, broadcast it
                     if let Some(next_to_broadcast) = this.txns.pop() {
                         let fut = this.provider.send_raw_transaction(next_to_broadcast);
                         *this.state = BroadcastingNew(fut);
@@ -198,6 +229,30 @@ where
                 }
                 check_all_receipts!(cx, this);
             }
+            CheckingBlock(fut) => {
+                let block = match futures_util::ready!(fut.as_mut().poll(cx)) {
+                    Ok(block) => block,
+                    // the block number is requested again on the next poll
+                    Err(e) => {
+                        tracing::warn!(error = ?e, "Error while checking the block number");
+                        check_all_receipts!(cx, this);
+                    }
+                };
+                let blocks = this.broadcast_blocks.unwrap_or_default();
+                match *this.last_block {
+                    Some(last) if block >= last + blocks => {
+                        if let Some(next_to_broadcast) = this.txns.pop() {
+                            let fut = this.provider.send_raw_transaction(next_to_broadcast);
+                            *this.state = BroadcastingNew(fut);
+                            cx.waker().wake_by_ref();
+                            return Poll::Pending
+                        }
+                    }
+                    Some(_) => {}
+                    None => *this.last_block = Some(block),
+                }
+                check_all_receipts!(cx, this);
+            }
             // This state is functionally equivalent to Initial, but we
             // differentiate it for clarity
             BroadcastingNew(fut) => {
@@ -244,6 +299,7 @@ impl<'a, P> std::fmt::Debug for EscalatorStates<'a, P> {
         let state = match self {
             Self::Initial(_) => "Initial",
             Self::Sleeping(_) => "Sleeping",
+            Self::CheckingBlock(_) => "CheckingBlock",
             Self::BroadcastingNew(_) => "BroadcastingNew",
             Self::CheckingReceipts(_) => "CheckingReceipts",
             Self::Completed => "Completed",
@@ -2,19 +2,77 @@ use crate::{
     utils::{interval, PinBoxFut},
     JsonRpcClient, Middleware, Provider, ProviderError,
 };
-use corebc_core::types::{Transaction, TransactionReceipt, TxHash, U64};
+use corebc_core::types::{Address, Transaction, TransactionReceipt, TxHash, TxpoolContent, U64};
 use futures_core::stream::Stream;
 use futures_timer::Delay;
 use futures_util::stream::StreamExt;
 use instant::Duration;
 use pin_project::pin_project;
 use std::{
+    collections::BTreeMap,
     fmt,
     future::Future,
     ops::Deref,
     pin::Pin,
     task::{Context, Poll},
 };
+use thiserror::Error;
+
+/// Where a transaction was last seen when waiting for it timed out
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PendingTxStatus {
+    /// The transaction is in the txpool and ready to be included
+    Pending,
+    /// The transaction is in the txpool but cannot be included yet, e.g. because of a nonce gap
+    Queued,
+    /// The transaction is not in the txpool, or the txpool could not be inspected
+    Unknown,
+}
+
+impl PendingTxStatus {
+    fn from_txpool(content: &TxpoolContent, tx_hash: TxHash) -> Self {
+        let contains = |txs: &BTreeMap<Address, BTreeMap<String, Transaction>>| {
+            txs.values().flat_map(BTreeMap::values).any(|tx| tx.hash == tx_hash)
+        };
+        if contains(&content.pending) {
+            PendingTxStatus::Pending
+        } else if contains(&content.queued) {
+            PendingTxStatus::Queued
+        } else {
+            PendingTxStatus::Unknown
+        }
+    }
+}
+
+impl fmt::Display for PendingTxStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PendingTxStatus::Pending => f.write_str("pending"),
+            PendingTxStatus::Queued => f.write_str("queued"),
+            PendingTxStatus::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Thrown when waiting for a [`PendingTransaction`] stopped before it resolved
+#[derive(Debug, Error)]
+pub enum PendingTxError {
+    /// The transaction was not confirmed before the [timeout](PendingTransaction::timeout)
+    #[error("transaction {tx_hash:?} was not confirmed within {timeout:?}, last status: {status}")]
+    Timeout {
+        /// The hash of the transaction
+        tx_hash: TxHash,
+        /// The timeout that elapsed
+        timeout: Duration,
+        /// Where the transaction was last seen
+        status: PendingTxStatus,
+    },
+    /// Waiting was [cancelled](PendingTransaction::cancel_on)
+    #[error("stopped waiting for transaction {0:?}")]
+    Cancelled(TxHash),
+}
+
+type CancelSignal<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
 
 /// A pending transaction is a transaction which has been submitted but is not yet mined.
 /// `await`'ing on a pending transaction will resolve to a transaction receipt
@@ -34,6 +92,23 @@ use std::{
 ///     .log_msg("Pending transfer hash") // print pending tx hash with message
 ///     .await?;                          // Result<Option<TransactionReceipt>, _>
 /// ```
+///
+/// By default, the future waits forever. Use [`timeout`](Self::timeout) to give up after a
+/// deadline, [`cancel_on`](Self::cancel_on) to stop waiting on a signal, and
+/// [`exponential_backoff`](Self::exponential_backoff) to poll less often the longer it takes.
+/// The first two fail with a [`PendingTxError`].
+///
+/// ```ignore
+/// let receipt = client
+///     .send_transaction(tx, None)
+///     .await?
+///     .timeout(Duration::from_secs(120))
+///     .exponential_backoff(Duration::from_secs(30))
+///     .await;
+/// if let Err(ProviderError::PendingTransaction(PendingTxError::Timeout { status, .. })) = receipt {
+///     println!("transaction is still {status}");
+/// }
+/// ```
 #[pin_project]
 pub struct PendingTransaction<'a, P> {
     tx_hash: TxHash,
@@ -42,6 +117,13 @@ pub struct PendingTransaction<'a, P> {
     state: PendingTxState<'a>,
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
     retries_remaining: usize,
+    /// The current polling interval
+    poll_interval: Duration,
+    /// The upper bound of the polling interval, if it backs off exponentially
+    max_poll_interval: Option<Duration>,
+    /// The timeout and the delay that elapses at the deadline
+    timeout: Option<(Duration, Pin<Box<Delay>>)>,
+    cancel: Option<CancelSignal<'a>>,
 }
 
 const DEFAULT_RETRIES: usize = 3;
@@ -58,6 +140,10 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
             state: PendingTxState::InitialDelay(delay),
             interval: Box::new(interval(provider.get_interval())),
             retries_remaining: DEFAULT_RETRIES,
+            poll_interval: provider.get_interval(),
+            max_poll_interval: None,
+            timeout: None,
+            cancel: None,
         }
     }
 
@@ -88,6 +174,7 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         let duration = duration.into();
 
         self.interval = Box::new(interval(duration));
+        self.poll_interval = duration;
 
         if matches!(self.state, PendingTxState::InitialDelay(_)) {
             self.state = PendingTxState::InitialDelay(Box::pin(Delay::new(duration)))
@@ -102,6 +189,33 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         self.retries_remaining = retries;
         self
     }
+
+    /// Fails with [`PendingTxError::Timeout`] if the transaction does not resolve within
+    /// `duration`. The error contains where the transaction was last seen in the txpool.
+    #[must_use]
+    pub fn timeout<T: Into<Duration>>(mut self, duration: T) -> Self {
+        let duration = duration.into();
+        self.timeout = Some((duration, Box::pin(Delay::new(duration))));
+        self
+    }
+
+    /// Doubles the polling interval after every poll, up to `max`
+    #[must_use]
+    pub fn exponential_backoff<T: Into<Duration>>(mut self, max: T) -> Self {
+        self.max_poll_interval = Some(max.into());
+        self
+    }
+
+    /// Stops waiting with [`PendingTxError::Cancelled`] once `signal` completes, e.g. a oneshot
+    /// receiver or a shutdown notification
+    #[must_use]
+    pub fn cancel_on<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'a,
+    {
+        self.cancel = Some(Box::pin(signal));
+        self
+    }
 }
 
 impl<'a, P> PendingTransaction<'a, P> {
@@ -128,6 +242,18 @@ impl<'a, P> PendingTransaction<'a, P> {
     }
 }
 
+/// Doubles the polling interval up to `max`, if the pending transaction backs off
+fn back_off(
+    interval_stream: &mut Box<dyn Stream<Item = ()> + Send + Unpin>,
+    poll_interval: &mut Duration,
+    max: Option<Duration>,
+) {
+    if let Some(max) = max {
+        *poll_interval = poll_interval.saturating_mul(2).min(max);
+        *interval_stream = Box::new(interval(*poll_interval));
+    }
+}
+
 macro_rules! rewake_with_new_state {
     ($ctx:ident, $this:ident, $new_state:expr) => {
         *$this.state = $new_state;
@@ -151,6 +277,25 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
     fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
         let this = self.project();
 
+        if let Some(cancel) = this.cancel {
+            if cancel.as_mut().poll(ctx).is_ready() {
+                *this.state = PendingTxState::Completed;
+                return Poll::Ready(Err(PendingTxError::Cancelled(*this.tx_hash).into()))
+            }
+        }
+
+        if let Some((_, deadline)) = this.timeout {
+            let timed_out = !matches!(
+                this.state,
+                PendingTxState::InspectingTxpool(_) | PendingTxState::Completed
+            ) && deadline.as_mut().poll(ctx).is_ready();
+            if timed_out {
+                tracing::debug!("Timed out waiting for pending tx {:?}", *this.tx_hash);
+                let fut = Box::pin(this.provider.txpool_content());
+                *this.state = PendingTxState::InspectingTxpool(fut);
+            }
+        }
+
         match this.state {
             PendingTxState::InitialDelay(fut) => {
                 futures_util::ready!(fut.as_mut().poll(ctx));
@@ -162,6 +307,7 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 // Wait the polling period so that we do not spam the network when no
                 // new block has been mined
                 let _ready = futures_util::ready!(this.interval.poll_next_unpin(ctx));
+                back_off(this.interval, this.poll_interval, *this.max_poll_interval);
                 let fut = Box::pin(this.provider.get_transaction(*this.tx_hash));
                 *this.state = PendingTxState::GettingTx(fut);
                 ctx.waker().wake_by_ref();
@@ -208,6 +354,7 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 // Wait the polling period so that we do not spam the network when no
                 // new block has been mined
                 let _ready = futures_util::ready!(this.interval.poll_next_unpin(ctx));
+                back_off(this.interval, this.poll_interval, *this.max_poll_interval);
                 let fut = Box::pin(this.provider.get_transaction_receipt(*this.tx_hash));
                 *this.state = PendingTxState::GettingReceipt(fut);
                 ctx.waker().wake_by_ref();
@@ -249,6 +396,7 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 // Wait the polling period so that we do not spam the network when no
                 // new block has been mined
                 let _ready = futures_util::ready!(this.interval.poll_next_unpin(ctx));
+                back_off(this.interval, this.poll_interval, *this.max_poll_interval);
 
                 // we need to re-instantiate the get_block_number future so that
                 // we poll again
@@ -280,6 +428,18 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                     ctx.waker().wake_by_ref();
                 }
             }
+            PendingTxState::InspectingTxpool(fut) => {
+                // nodes without the txpool namespace leave the status unknown
+                let status = match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                    Ok(content) => PendingTxStatus::from_txpool(&content, *this.tx_hash),
+                    Err(_) => PendingTxStatus::Unknown,
+                };
+                let timeout =
+                    this.timeout.as_ref().map(|(timeout, _)| *timeout).unwrap_or_default();
+                *this.state = PendingTxState::Completed;
+                let err = PendingTxError::Timeout { tx_hash: *this.tx_hash, timeout, status };
+                return Poll::Ready(Err(err.into()))
+            }
             PendingTxState::Completed => {
                 panic!("polled pending transaction future after completion")
             }
@@ -349,6 +509,9 @@ enum PendingTxState<'a> {
     /// Polling the blockchain for the current block number
     GettingBlockNumber(PinBoxFut<'a, U64>, Option<TransactionReceipt>),
 
+    /// Inspecting the txpool after the timeout elapsed
+    InspectingTxpool(PinBoxFut<'a, TxpoolContent>),
+
     /// Future has completed and should panic if polled again
     Completed,
 }
@@ -364,9 +527,72 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
             PendingTxState::GettingBlockNumber(_, _) => "GettingBlockNumber",
             PendingTxState::PausedGettingBlockNumber(_) => "PausedGettingBlockNumber",
             PendingTxState::CheckingReceipt(_) => "CheckingReceipt",
+            PendingTxState::InspectingTxpool(_) => "InspectingTxpool",
             PendingTxState::Completed => "Completed",
         };
 
         f.debug_struct("PendingTxState").field("state", &state).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::types::H256;
+
+    #[tokio::test]
+    async fn times_out_with_txpool_status() {
+        let (provider, mock) = Provider::mocked();
+        let tx_hash = H256::repeat_byte(1);
+        let tx = Transaction { hash: tx_hash, ..Default::default() };
+        let mut content = TxpoolContent::default();
+        content.queued.entry(Address::zero()).or_default().insert("1".to_string(), tx.clone());
+
+        // the transaction is never mined
+        mock.expect("xcb_getTransactionByHash").returns(&tx);
+        mock.expect("txpool_content").returns(&content);
+
+        let err = PendingTransaction::new(tx_hash, &provider)
+            .interval(Duration::from_millis(1))
+            .exponential_backoff(Duration::from_millis(4))
+            .timeout(Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        match err {
+            ProviderError::PendingTransaction(PendingTxError::Timeout {
+                status, timeout, ..
+            }) => {
+                assert_eq!(status, PendingTxStatus::Queued);
+                assert_eq!(timeout, Duration::from_millis(50));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_when_cancelled() {
+        let (provider, _mock) = Provider::mocked();
+        let tx_hash = H256::repeat_byte(1);
+
+        let err =
+            PendingTransaction::new(tx_hash, &provider).cancel_on(async {}).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::PendingTransaction(PendingTxError::Cancelled(hash)) if hash == tx_hash
+        ));
+    }
+
+    #[test]
+    fn backs_off_up_to_max() {
+        let mut stream: Box<dyn Stream<Item = ()> + Send + Unpin> =
+            Box::new(interval(Duration::from_millis(1)));
+        let mut poll_interval = Duration::from_millis(3);
+
+        back_off(&mut stream, &mut poll_interval, None);
+        assert_eq!(poll_interval, Duration::from_millis(3));
+        back_off(&mut stream, &mut poll_interval, Some(Duration::from_millis(10)));
+        assert_eq!(poll_interval, Duration::from_millis(6));
+        back_off(&mut stream, &mut poll_interval, Some(Duration::from_millis(10)));
+        assert_eq!(poll_interval, Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,135 @@
+//! Decoding of the reasons why a transaction reverted, see
+//! [`Middleware::revert_reason`](crate::Middleware::revert_reason)
+
+use corebc_core::{
+    abi::{self, ParamType},
+    types::{Bytes, CallFrame, Selector, U256},
+    utils::id,
+};
+use std::fmt;
+
+/// The signature of the error ylem reverts with on `require(cond, "reason")` and `revert("reason")`
+pub const REVERT_STRING_SIGNATURE: &str = "Error(string)";
+
+/// The signature of the error ylem reverts with on failed assertions, arithmetic overflows, ...
+pub const PANIC_SIGNATURE: &str = "Panic(uint256)";
+
+/// Why a transaction failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevertReason {
+    /// The transaction reverted with a string, `Error(string)`
+    Message(String),
+    /// The transaction reverted with a `Panic(uint256)` code
+    Panic(U256),
+    /// The transaction reverted with a custom error, the data includes its selector
+    Custom(Bytes),
+    /// The transaction reverted without any data
+    Empty,
+    /// The execution was halted without reverting, e.g. because it ran out of energy. Contains
+    /// the error reported by the node.
+    Halted(String),
+}
+
+impl RevertReason {
+    /// Decodes the data a transaction reverted with
+    pub fn decode(data: &[u8]) -> Self {
+        if data.is_empty() {
+            return RevertReason::Empty
+        }
+        if data.len() >= 4 {
+            let (selector, args) = data.split_at(4);
+            if selector == id(REVERT_STRING_SIGNATURE) {
+                if let Some(msg) = abi::decode(&[ParamType::String], args)
+                    .ok()
+                    .and_then(|mut tokens| tokens.pop()?.into_string())
+                {
+                    return RevertReason::Message(msg)
+                }
+            } else if selector == id(PANIC_SIGNATURE) {
+                if let Some(code) = abi::decode(&[ParamType::Uint(256)], args)
+                    .ok()
+                    .and_then(|mut tokens| tokens.pop()?.into_uint())
+                {
+                    return RevertReason::Panic(code)
+                }
+            }
+        }
+        RevertReason::Custom(data.to_vec().into())
+    }
+
+    /// Reads the reason from the top-level frame of a `callTracer` trace, returns `None` if the
+    /// call did not fail
+    pub fn from_call_frame(frame: &CallFrame) -> Option<Self> {
+        let error = frame.error.as_ref()?;
+        match frame.output.as_ref().filter(|output| !output.is_empty()) {
+            Some(output) => Some(Self::decode(output)),
+            None if error.contains("revert") => Some(RevertReason::Empty),
+            None => Some(RevertReason::Halted(error.clone())),
+        }
+    }
+
+    /// Returns the selector of the custom error, if any
+    pub fn selector(&self) -> Option<Selector> {
+        match self {
+            RevertReason::Custom(data) => data.get(..4)?.try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Message(msg) => write!(f, "reverted: {msg}"),
+            RevertReason::Panic(code) => write!(f, "panicked with code {code:#x}"),
+            RevertReason::Custom(data) => write!(f, "reverted with custom error {data}"),
+            RevertReason::Empty => f.write_str("reverted without a reason"),
+            RevertReason::Halted(err) => write!(f, "halted: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::abi::Token;
+
+    fn encode(signature: &str, token: Token) -> Vec<u8> {
+        [&id(signature)[..], &abi::encode(&[token])].concat()
+    }
+
+    #[test]
+    fn decodes_revert_data() {
+        let data = encode(REVERT_STRING_SIGNATURE, Token::String("not owner".to_string()));
+        assert_eq!(RevertReason::decode(&data), RevertReason::Message("not owner".to_string()));
+
+        let data = encode(PANIC_SIGNATURE, Token::Uint(0x11.into()));
+        assert_eq!(RevertReason::decode(&data), RevertReason::Panic(0x11.into()));
+        assert_eq!(RevertReason::decode(&data).to_string(), "panicked with code 0x11");
+
+        let data = encode("Unauthorized(uint256)", Token::Uint(1.into()));
+        let reason = RevertReason::decode(&data);
+        assert_eq!(reason, RevertReason::Custom(data.clone().into()));
+        assert_eq!(reason.selector(), Some(id("Unauthorized(uint256)")));
+
+        assert_eq!(RevertReason::decode(&[]), RevertReason::Empty);
+    }
+
+    #[test]
+    fn reads_call_frames() {
+        let mut frame = CallFrame { output: Some(Bytes::default()), ..Default::default() };
+        assert_eq!(RevertReason::from_call_frame(&frame), None);
+
+        frame.error = Some("out of energy".to_string());
+        assert_eq!(
+            RevertReason::from_call_frame(&frame),
+            Some(RevertReason::Halted("out of energy".to_string()))
+        );
+
+        frame.error = Some("execution reverted".to_string());
+        assert_eq!(RevertReason::from_call_frame(&frame), Some(RevertReason::Empty));
+
+        frame.output = Some(encode(PANIC_SIGNATURE, Token::Uint(1.into())).into());
+        assert_eq!(RevertReason::from_call_frame(&frame), Some(RevertReason::Panic(1.into())));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{utils::PinBoxFut, JsonRpcClient, Middleware, Provider, ProviderError};
+use corebc_core::types::{Trace, TraceFilter};
+use futures_core::stream::Stream;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+
+/// A trace query provides streaming access to the results of a `trace_filter` request, loading
+/// them in pages via the filter's `after`/`count` offsets. Archive nodes cap the number of traces
+/// returned per request, so large block ranges have to be walked this way.
+///
+/// If the filter sets `after`, paging starts from that offset. If it sets `count`, the stream
+/// yields at most that many traces in total.
+pub struct TraceQuery<'a, P> {
+    provider: &'a Provider<P>,
+    filter: TraceFilter,
+    offset: usize,
+    remaining: Option<usize>,
+    page_size: usize,
+    requested: usize,
+    exhausted: bool,
+    current_traces: VecDeque<Trace>,
+    state: TraceQueryState<'a>,
+}
+
+enum TraceQueryState<'a> {
+    Initial,
+    LoadTraces(PinBoxFut<'a, Vec<Trace>>),
+    Consume,
+}
+
+impl<'a, P> TraceQuery<'a, P>
+where
+    P: JsonRpcClient,
+{
+    /// Instantiate a new `TraceQuery`
+    pub fn new(provider: &'a Provider<P>, filter: TraceFilter) -> Self {
+        Self {
+            provider,
+            offset: filter.get_after().unwrap_or_default(),
+            remaining: filter.get_count(),
+            filter,
+            page_size: 1000,
+            requested: 0,
+            exhausted: false,
+            current_traces: VecDeque::new(),
+            state: TraceQueryState::Initial,
+        }
+    }
+
+    /// set page size for pagination
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Returns the future loading the next page, or `None` if there are no more pages to load
+    fn next_page(&mut self) -> Option<PinBoxFut<'a, Vec<Trace>>> {
+        if self.exhausted {
+            return None
+        }
+        let count = self.remaining.map_or(self.page_size, |r| r.min(self.page_size));
+        if count == 0 {
+            return None
+        }
+
+        let filter = self.filter.clone().after(self.offset).count(count);
+        self.offset += count;
+        self.remaining = self.remaining.map(|r| r - count);
+        self.requested = count;
+
+        let provider = self.provider;
+        Some(Box::pin(async move { provider.trace_filter(filter).await }))
+    }
+}
+
+macro_rules! rewake_with_new_state {
+    ($ctx:ident, $this:ident, $new_state:expr) => {
+        $this.state = $new_state;
+        $ctx.waker().wake_by_ref();
+        return Poll::Pending
+    };
+}
+
+/// Errors while querying for traces
+#[derive(Error, Debug)]
+pub enum TraceQueryError<E> {
+    /// Error loading a page of traces
+    #[error(transparent)]
+    LoadTracesError(E),
+}
+
+impl<'a, P> Stream for TraceQuery<'a, P>
+where
+    P: JsonRpcClient,
+{
+    type Item = Result<Trace, TraceQueryError<ProviderError>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match &mut self.state {
+            TraceQueryState::Initial => match self.next_page() {
+                Some(fut) => {
+                    rewake_with_new_state!(ctx, self, TraceQueryState::LoadTraces(fut));
+                }
+                None => Poll::Ready(None),
+            },
+            TraceQueryState::LoadTraces(fut) => {
+                match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                    Ok(traces) => {
+                        // a short page means the node has no more traces for this filter
+                        if traces.len() < self.requested {
+                            self.exhausted = true;
+                        }
+                        self.current_traces = VecDeque::from(traces);
+                        rewake_with_new_state!(ctx, self, TraceQueryState::Consume);
+                    }
+                    Err(err) => {
+                        // don't retry the failed page forever
+                        self.exhausted = true;
+                        self.state = TraceQueryState::Consume;
+                        Poll::Ready(Some(Err(TraceQueryError::LoadTracesError(err))))
+                    }
+                }
+            }
+            TraceQueryState::Consume => match self.current_traces.pop_front() {
+                Some(trace) => Poll::Ready(Some(Ok(trace))),
+                // consumed the current page, load the next one if there is any
+                None => match self.next_page() {
+                    Some(fut) => {
+                        rewake_with_new_state!(ctx, self, TraceQueryState::LoadTraces(fut));
+                    }
+                    None => Poll::Ready(None),
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    fn trace(position: usize) -> serde_json::Value {
+        serde_json::json!({
+            "action": {
+                "author": "0x0000d1220a0cf47c7b9be7a2e6ba89f429762e7b9adb",
+                "value": "0x0",
+                "rewardType": "block"
+            },
+            "blockHash": "0x6474a53a9ebf72d306a1406ec12ded12e210b6c3141b4373bfb3a3cea987dfb8",
+            "blockNumber": 988775,
+            "result": null,
+            "subtraces": 0,
+            "traceAddress": [],
+            "transactionHash": null,
+            "transactionPosition": position,
+            "type": "reward"
+        })
+    }
+
+    #[tokio::test]
+    async fn paginates_trace_filter() {
+        let (provider, mock) = Provider::mocked();
+        mock.expect("trace_filter")
+            .with_params(serde_json::json!([{ "fromBlock": "0x1", "after": 0, "count": 2 }]))
+            .returns(vec![trace(0), trace(1)]);
+        mock.expect("trace_filter")
+            .with_params(serde_json::json!([{ "fromBlock": "0x1", "after": 2, "count": 2 }]))
+            .returns(vec![trace(2)]);
+
+        let filter = TraceFilter::new().from_block(1u64);
+        let traces = provider
+            .trace_filter_paginated(filter, 2)
+            .map(|trace| trace.unwrap().transaction_position)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(traces, vec![Some(0), Some(1), Some(2)]);
+        mock.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn respects_after_and_count() {
+        let (provider, mock) = Provider::mocked();
+        mock.expect("trace_filter")
+            .with_params(serde_json::json!([{ "after": 5, "count": 2 }]))
+            .returns(vec![trace(5), trace(6)]);
+        mock.expect("trace_filter")
+            .with_params(serde_json::json!([{ "after": 7, "count": 1 }]))
+            .returns(vec![trace(7)]);
+
+        let filter = TraceFilter::new().after(5).count(3);
+        let traces = provider.trace_filter_paginated(filter, 2).collect::<Vec<_>>().await;
+        assert_eq!(traces.len(), 3);
+        mock.verify().unwrap();
+    }
+}
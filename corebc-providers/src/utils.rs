@@ -7,6 +7,123 @@ use std::{future::Future, pin::Pin};
 /// A simple gas escalation policy
 pub type EscalationPolicy = Box<dyn Fn(U256, usize) -> U256 + Send + Sync>;
 
+/// Returns an [`EscalationPolicy`] that raises the energy price by `percent` for every full
+/// `period` that elapsed since the first broadcast, for escalations that are broadcast every
+/// `broadcast_interval` (see
+/// [`EscalatingPending::with_broadcast_interval`](crate::EscalatingPending::with_broadcast_interval)).
+pub fn time_escalation(
+    broadcast_interval: instant::Duration,
+    period: instant::Duration,
+    percent: u64,
+) -> EscalationPolicy {
+    Box::new(move |price, escalation| {
+        let elapsed = broadcast_interval.saturating_mul(escalation as u32);
+        let periods = elapsed.as_millis().checked_div(period.as_millis()).unwrap_or_default();
+        compound(price, percent, periods as u64)
+    })
+}
+
+/// Returns an [`EscalationPolicy`] that raises the energy price by `percent` for every full
+/// `blocks` that were mined since the first broadcast, for escalations that are broadcast every
+/// `broadcast_blocks` blocks (see
+/// [`EscalatingPending::with_broadcast_blocks`](crate::EscalatingPending::with_broadcast_blocks)).
+///
+/// Unlike [`time_escalation`], this escalates equally fast on networks with different block
+/// times.
+pub fn block_escalation(broadcast_blocks: u64, blocks: u64, percent: u64) -> EscalationPolicy {
+    Box::new(move |price, escalation| {
+        let mined = broadcast_blocks.saturating_mul(escalation as u64);
+        compound(price, percent, mined.checked_div(blocks).unwrap_or_default())
+    })
+}
+
+/// Returns an [`EscalationPolicy`] that uses the highest energy price of all `policies`
+pub fn max_escalation(policies: Vec<EscalationPolicy>) -> EscalationPolicy {
+    Box::new(move |price, escalation| {
+        policies.iter().map(|policy| policy(price, escalation)).max().unwrap_or(price)
+    })
+}
+
+/// Returns an [`EscalationPolicy`] that never exceeds `max_price`
+pub fn capped_escalation(policy: EscalationPolicy, max_price: U256) -> EscalationPolicy {
+    Box::new(move |price, escalation| policy(price, escalation).min(max_price))
+}
+
+/// Raises `price` by `percent`, `times` times
+fn compound(price: U256, percent: u64, times: u64) -> U256 {
+    let mut price = price;
+    for _ in 0..times {
+        let raised = price.saturating_add(price.saturating_mul(percent.into()) / 100);
+        if raised == price {
+            break
+        }
+        price = raised;
+    }
+    price
+}
+
+/// A safety margin added to energy estimates, as estimates of calls whose execution depends on
+/// the state they run against (e.g. storage heavy ones) can be too low by the time the
+/// transaction is mined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnergyMargin {
+    /// Raises the estimate by the given percentage
+    Percent(u64),
+    /// Adds a fixed amount of energy to the estimate
+    Fixed(U256),
+}
+
+impl EnergyMargin {
+    /// Returns the estimate with the margin added
+    pub fn apply(&self, estimate: U256) -> U256 {
+        match self {
+            EnergyMargin::Percent(percent) => {
+                estimate.saturating_add(estimate.saturating_mul(U256::from(*percent)) / 100)
+            }
+            EnergyMargin::Fixed(energy) => estimate.saturating_add(*energy),
+        }
+    }
+}
+
+/// The timeouts of the requests of a [`Provider`](crate::Provider), see
+/// [`Provider::request_timeout`](crate::Provider::request_timeout)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestTimeouts {
+    /// The timeout of methods without an override, `None` to wait forever
+    pub default: Option<instant::Duration>,
+    /// Method names, or prefixes ending with `*`, and their timeouts
+    overrides: Vec<(String, instant::Duration)>,
+}
+
+impl RequestTimeouts {
+    /// Sets the timeout of `method`, which may be a prefix ending with `*`, e.g. `debug_trace*`
+    pub fn set(&mut self, method: impl Into<String>, timeout: instant::Duration) -> &mut Self {
+        let method = method.into();
+        self.overrides.retain(|(m, _)| *m != method);
+        self.overrides.push((method, timeout));
+        self
+    }
+
+    /// Returns the timeout of `method`.
+    ///
+    /// An override of the exact method wins over prefixes, and longer prefixes win over shorter
+    /// ones.
+    pub fn get(&self, method: &str) -> Option<instant::Duration> {
+        let exact = self.overrides.iter().find(|(m, _)| m == method);
+        let prefix = || {
+            self.overrides
+                .iter()
+                .filter_map(|(m, timeout)| {
+                    let prefix = m.strip_suffix('*')?;
+                    method.starts_with(prefix).then_some((prefix.len(), *timeout))
+                })
+                .max_by_key(|(len, _)| *len)
+                .map(|(_, timeout)| timeout)
+        };
+        exact.map(|(_, timeout)| *timeout).or_else(prefix).or(self.default)
+    }
+}
+
 // Helper type alias
 #[cfg(target_arch = "wasm32")]
 pub(crate) type PinBoxFut<'a, T> = Pin<Box<dyn Future<Output = Result<T, ProviderError>> + 'a>>;
@@ -33,3 +150,57 @@ pub fn interval(
 ) -> impl futures_core::stream::Stream<Item = ()> + Send + Unpin {
     stream::unfold((), move |_| Delay::new(duration).map(|_| Some(((), ())))).map(drop)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use instant::Duration;
+
+    #[test]
+    fn escalates_by_time_and_blocks() {
+        let price = U256::from(100);
+
+        // broadcast every 5s, +10% every 10s
+        let policy = time_escalation(Duration::from_secs(5), Duration::from_secs(10), 10);
+        let prices = (0..5).map(|i| policy(price, i).as_u64()).collect::<Vec<_>>();
+        assert_eq!(prices, [100, 100, 110, 110, 121]);
+
+        // broadcast every block, +10% every 2 blocks
+        let policy = block_escalation(1, 2, 10);
+        let prices = (0..5).map(|i| policy(price, i).as_u64()).collect::<Vec<_>>();
+        assert_eq!(prices, [100, 100, 110, 110, 121]);
+
+        let policy = capped_escalation(
+            max_escalation(vec![
+                block_escalation(1, 1, 10),
+                time_escalation(Duration::from_secs(1), Duration::from_secs(1), 50),
+            ]),
+            200.into(),
+        );
+        let prices = (0..4).map(|i| policy(price, i).as_u64()).collect::<Vec<_>>();
+        assert_eq!(prices, [100, 150, 200, 200]);
+    }
+
+    #[test]
+    fn resolves_request_timeouts() {
+        let mut timeouts = RequestTimeouts::default();
+        assert_eq!(timeouts.get("xcb_blockNumber"), None);
+
+        timeouts.default = Some(Duration::from_secs(10));
+        timeouts
+            .set("debug_*", Duration::from_secs(60))
+            .set("debug_trace*", Duration::from_secs(120))
+            .set("debug_traceCall", Duration::from_secs(30))
+            .set("xcb_blockNumber", Duration::from_secs(2));
+
+        assert_eq!(timeouts.get("xcb_blockNumber"), Some(Duration::from_secs(2)));
+        assert_eq!(timeouts.get("xcb_call"), Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.get("debug_getRawBlock"), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.get("debug_traceTransaction"), Some(Duration::from_secs(120)));
+        assert_eq!(timeouts.get("debug_traceCall"), Some(Duration::from_secs(30)));
+
+        // overrides replace earlier ones
+        timeouts.set("xcb_blockNumber", Duration::from_secs(1));
+        assert_eq!(timeouts.get("xcb_blockNumber"), Some(Duration::from_secs(1)));
+    }
+}
@@ -3,11 +3,20 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod wallet;
-pub use wallet::{MnemonicBuilder, Wallet, WalletError};
+pub use wallet::{MnemonicBuilder, PublicKeySigner, Wallet, WalletError};
+
+mod summary;
+pub use summary::TransactionSummary;
 
 /// Re-export the BIP-32 crate so that wordlists can be accessed conveniently.
 pub use coins_bip39;
 
+/// Re-export the BIP-32 crate so that extended keys can be accessed conveniently.
+pub use coins_bip32;
+
+#[cfg(feature = "zeroize")]
+pub use zeroize::Zeroizing;
+
 /// A wallet instantiated with a locally stored private key
 pub type LocalWallet = Wallet<corebc_core::libgoldilocks::SigningKey>;
 
@@ -42,7 +51,7 @@ pub use yubihsm;
 use async_trait::async_trait;
 use corebc_core::types::{
     transaction::{cip712::Cip712, eip2718::TypedTransaction},
-    Address, Signature,
+    Address, Signature, H256, H456,
 };
 use std::error::Error;
 
@@ -51,13 +60,18 @@ pub fn to_eip155_v<T: Into<u8>>(recovery_id: T, network_id: u64) -> u64 {
     (recovery_id.into() as u64) + 35 + network_id * 2
 }
 
+/// Returned by the default implementations of the optional [`Signer`] methods
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("the signer does not support `{0}`")]
+pub struct UnsupportedSignerMethod(pub &'static str);
+
 /// Trait for signing transactions and messages
 ///
 /// Implement this trait to support different signing modes, e.g. Ledger, hosted etc.
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait Signer: std::fmt::Debug + Send + Sync {
-    type Error: Error + Send + Sync;
+    type Error: Error + Send + Sync + From<UnsupportedSignerMethod>;
     /// Signs the hash of the provided message after prefixing it
     async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
         &self,
@@ -74,6 +88,22 @@ pub trait Signer: std::fmt::Debug + Send + Sync {
         payload: &T,
     ) -> Result<Signature, Self::Error>;
 
+    /// Signs the raw 32 byte digest without hashing or prefixing it.
+    ///
+    /// Signers that refuse to blind-sign digests, e.g. hardware wallets, return an error, which
+    /// is what the default implementation does.
+    async fn sign_prehash(&self, _hash: H256) -> Result<Signature, Self::Error> {
+        Err(UnsupportedSignerMethod("sign_prehash").into())
+    }
+
+    /// Returns the signer's Ed448 public key.
+    ///
+    /// Signers that cannot export their public key, e.g. disconnected hardware wallets, return
+    /// an error, which is what the default implementation does.
+    fn public_key(&self) -> Result<H456, Self::Error> {
+        Err(UnsupportedSignerMethod("public_key").into())
+    }
+
     /// Returns the signer's Ethereum Address
     fn address(&self) -> Address;
 
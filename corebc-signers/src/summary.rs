@@ -0,0 +1,248 @@
+//! Human readable previews of transactions
+
+use corebc_core::{
+    abi::{Abi, FunctionExt, Token},
+    types::{transaction::eip2718::TypedTransaction, Address, NameOrAddress, Selector, U256},
+    utils::{format_xcb, TxCostEstimate},
+};
+use std::fmt;
+
+/// The energy every transaction uses
+const TX_ENERGY: u64 = 21_000;
+/// The additional energy of contract deployments
+const TX_CREATE_ENERGY: u64 = 32_000;
+/// The energy per zero byte of input data
+const TX_DATA_ZERO_ENERGY: u64 = 4;
+/// The energy per non-zero byte of input data
+const TX_DATA_NON_ZERO_ENERGY: u64 = 16;
+
+/// A preview of a transaction, e.g. to let a human approve it before a hardware wallet or a
+/// policy-gated remote signer signs it.
+///
+/// The [`Display`](fmt::Display) implementation lists the fields line by line:
+///
+/// ```
+/// use corebc_core::{
+///     abi::parse_abi,
+///     types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest},
+///     utils::parse_units,
+/// };
+/// use corebc_signers::TransactionSummary;
+///
+/// let erc20 = parse_abi(&["function approve(address spender, uint256 amount)"]).unwrap();
+/// let data = erc20.function("approve")?.encode_input(&[
+///     corebc_core::abi::Token::Address(Address::zero()),
+///     corebc_core::abi::Token::Uint(100.into()),
+/// ])?;
+/// let tx: TypedTransaction = TransactionRequest::new()
+///     .to(Address::zero())
+///     .data(data)
+///     .energy(60_000u64)
+///     .energy_price(parse_units(1, "nucle").unwrap())
+///     .into();
+///
+/// let summary = TransactionSummary::with_abi(&tx, &erc20);
+/// assert_eq!(summary.function.as_deref(), Some("approve(address,uint256)"));
+/// println!("{summary}");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionSummary {
+    /// The sender, if set
+    pub from: Option<Address>,
+    /// The recipient, `None` for contract deployments
+    pub to: Option<NameOrAddress>,
+    /// The value transferred, in ore
+    pub value: U256,
+    /// The network id, if set
+    pub network_id: Option<u64>,
+    /// The nonce, if set
+    pub nonce: Option<U256>,
+    /// The length of the input data
+    pub data_len: usize,
+    /// The selector of the called function, if the input data has one
+    pub selector: Option<Selector>,
+    /// The signature of the called function, e.g. `transfer(address,uint256)`, if its ABI was
+    /// supplied
+    pub function: Option<String>,
+    /// The names and values of the decoded arguments of the called function
+    pub args: Vec<(String, Token)>,
+    /// The cost if the transaction only uses its intrinsic energy, if the energy price is set
+    pub min_cost: Option<TxCostEstimate>,
+    /// The cost if the transaction uses its entire energy limit, if the energy and its price are
+    /// set
+    pub max_cost: Option<TxCostEstimate>,
+}
+
+impl TransactionSummary {
+    /// Summarizes `tx` without decoding its input data
+    pub fn new(tx: &TypedTransaction) -> Self {
+        let data = tx.data().map(|data| data.as_ref()).unwrap_or_default();
+        let value = tx.value().copied().unwrap_or_default();
+        let selector = data.get(..4).map(|selector| selector.try_into().expect("4 bytes"));
+        let min_cost = tx.energy_price().map(|price| {
+            TxCostEstimate::new(value, intrinsic_energy(data, tx.to().is_none()), price)
+        });
+
+        Self {
+            from: tx.from().copied(),
+            to: tx.to().cloned(),
+            value,
+            network_id: tx.network_id().map(|id| id.as_u64()),
+            nonce: tx.nonce().copied(),
+            data_len: data.len(),
+            selector,
+            function: None,
+            args: Vec::new(),
+            min_cost,
+            max_cost: TxCostEstimate::from_tx(tx),
+        }
+    }
+
+    /// Summarizes `tx` and decodes its input data if it calls a function of the `abi`
+    pub fn with_abi(tx: &TypedTransaction, abi: &Abi) -> Self {
+        let mut summary = Self::new(tx);
+        let data = tx.data().map(|data| data.as_ref()).unwrap_or_default();
+        let selector = match summary.selector {
+            Some(selector) if summary.to.is_some() => selector,
+            _ => return summary,
+        };
+        for function in abi.functions().filter(|function| function.selector() == selector) {
+            if let Ok(args) = function.decode_input(&data[4..]) {
+                summary.function = Some(function.abi_signature());
+                summary.args = function
+                    .inputs
+                    .iter()
+                    .zip(args)
+                    .map(|(param, arg)| (param.name.clone(), arg))
+                    .collect();
+                break
+            }
+        }
+        summary
+    }
+
+    /// Whether the transaction deploys a contract
+    pub fn is_deployment(&self) -> bool {
+        self.to.is_none()
+    }
+}
+
+/// The energy a transaction uses before executing any code
+fn intrinsic_energy(data: &[u8], deployment: bool) -> U256 {
+    let zeros = data.iter().filter(|byte| **byte == 0).count() as u64;
+    let non_zeros = data.len() as u64 - zeros;
+    let create = if deployment { TX_CREATE_ENERGY } else { 0 };
+    U256::from(
+        TX_ENERGY + create + zeros * TX_DATA_ZERO_ENERGY + non_zeros * TX_DATA_NON_ZERO_ENERGY,
+    )
+}
+
+impl fmt::Display for TransactionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.to {
+            Some(NameOrAddress::Address(to)) => writeln!(f, "to: {to:?}")?,
+            Some(NameOrAddress::Name(to)) => writeln!(f, "to: {to}")?,
+            None => writeln!(f, "to: new contract")?,
+        }
+        if let Some(from) = self.from {
+            writeln!(f, "from: {from:?}")?;
+        }
+        writeln!(f, "value: {}", format_xcb(self.value))?;
+        match (&self.function, self.selector) {
+            (Some(function), _) => {
+                writeln!(f, "call: {function}")?;
+                for (name, arg) in &self.args {
+                    writeln!(f, "  {name}: {arg}")?;
+                }
+            }
+            (None, Some(selector)) if !self.is_deployment() => {
+                writeln!(f, "call: unknown function 0x{}", hex::encode(selector))?;
+            }
+            _ => {}
+        }
+        if self.data_len > 0 {
+            writeln!(f, "data: {} bytes", self.data_len)?;
+        }
+        if let Some(network_id) = self.network_id {
+            writeln!(f, "network: {network_id}")?;
+        }
+        if let Some(nonce) = self.nonce {
+            writeln!(f, "nonce: {nonce}")?;
+        }
+        match (&self.min_cost, &self.max_cost) {
+            (Some(min), Some(max)) => {
+                write!(f, "fee: {} to {}", min.fee_string(), max.fee_string())
+            }
+            (Some(min), None) => write!(f, "fee: at least {}", min.fee_string()),
+            _ => write!(f, "fee: unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::{
+        abi::parse_abi,
+        types::{Bytes, TransactionRequest},
+    };
+
+    #[test]
+    fn summarizes_transactions() {
+        let erc20 =
+            parse_abi(&["function transfer(address to, uint256 amount) returns (bool)"]).unwrap();
+        let recipient = Address::repeat_byte(2);
+        let transfer = erc20.function("transfer").unwrap();
+        let data =
+            transfer.encode_input(&[Token::Address(recipient), Token::Uint(100.into())]).unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .to(Address::repeat_byte(3))
+            .data(data.clone())
+            .energy(100_000u64)
+            .energy_price(2u64)
+            .network_id(1u64)
+            .into();
+
+        let summary = TransactionSummary::new(&tx);
+        assert_eq!(summary.selector, Some(transfer.selector()));
+        assert_eq!(summary.function, None);
+        assert_eq!(summary.data_len, 68);
+        assert_eq!(summary.min_cost.unwrap().energy, intrinsic_energy(&data, false));
+        assert_eq!(summary.max_cost.unwrap().fee(), U256::from(200_000));
+        let unknown = format!("call: unknown function 0x{}", hex::encode(transfer.selector()));
+        assert!(summary.to_string().contains(&unknown));
+
+        let summary = TransactionSummary::with_abi(&tx, &erc20);
+        assert_eq!(summary.function.as_deref(), Some("transfer(address,uint256)"));
+        assert_eq!(
+            summary.args,
+            vec![
+                ("to".to_string(), Token::Address(recipient)),
+                ("amount".to_string(), Token::Uint(100.into()))
+            ]
+        );
+        let display = summary.to_string();
+        assert!(display.contains("call: transfer(address,uint256)\n  to: "), "{display}");
+        assert!(display.contains("network: 1\n"), "{display}");
+        assert!(display.contains("\nfee: ") && display.contains(" to "), "{display}");
+    }
+
+    #[test]
+    fn summarizes_deployments() {
+        let tx: TypedTransaction =
+            TransactionRequest::new().data(Bytes::from(vec![0, 1])).value(5u64).into();
+        let summary = TransactionSummary::new(&tx);
+        assert!(summary.is_deployment());
+        assert_eq!(summary.selector, None);
+        assert_eq!(summary.min_cost, None);
+        assert_eq!(summary.max_cost, None);
+        assert_eq!(intrinsic_energy(&[0, 1], true), U256::from(21_000 + 32_000 + 4 + 16));
+
+        let display = summary.to_string();
+        assert!(display.starts_with("to: new contract\nvalue: "), "{display}");
+        assert!(display.contains("data: 2 bytes\n"), "{display}");
+        assert!(display.ends_with("fee: unknown"), "{display}");
+    }
+}
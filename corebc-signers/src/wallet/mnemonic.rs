@@ -2,21 +2,24 @@
 //! specifications
 use crate::{Wallet, WalletError};
 
-use coins_bip32::path::DerivationPath;
+use coins_bip32::{
+    path::DerivationPath,
+    xkeys::{Parent, XPriv, XPub},
+};
 use coins_bip39::{Mnemonic, Wordlist};
 use corebc_core::{
     libgoldilocks::SigningKey,
-    types::PathOrString,
-    utils::{secret_key_to_address, to_checksum},
+    types::{Network, PathOrString},
+    utils::{secret_bytes, secret_key_to_address, to_checksum},
 };
 use rand::Rng;
-use std::{fs::File, io::Write, marker::PhantomData, path::PathBuf, str::FromStr};
+use std::{fmt, fs::File, io::Write, marker::PhantomData, path::PathBuf, str::FromStr};
 use thiserror::Error;
 
 const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
 
 /// Represents a structure that can resolve into a `Wallet<SigningKey>`.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct MnemonicBuilder<W: Wordlist> {
     /// The mnemonic phrase can be supplied to the builder as a string or a path to the file whose
     /// contents are the phrase. A builder that has a valid phrase should `build` the wallet.
@@ -32,10 +35,27 @@ pub struct MnemonicBuilder<W: Wordlist> {
     /// Optional field that if enabled, writes the mnemonic phrase to disk storage at the provided
     /// path.
     write_to: Option<PathBuf>,
+    /// The network of the wallet, which determines the ICAN prefix of its address. By default
+    /// this is mainnet.
+    network: Network,
     /// PhantomData
     _wordlist: PhantomData<W>,
 }
 
+// do not log the phrase and the password
+impl<W: Wordlist> fmt::Debug for MnemonicBuilder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MnemonicBuilder")
+            .field("phrase", &self.phrase.as_ref().map(|_| "<redacted>"))
+            .field("word_count", &self.word_count)
+            .field("derivation_path", &self.derivation_path)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("write_to", &self.write_to)
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
 /// Error produced by the mnemonic wallet module
 #[derive(Error, Debug)]
 pub enum MnemonicBuilderError {
@@ -59,6 +79,7 @@ impl<W: Wordlist> Default for MnemonicBuilder<W> {
             .expect("should parse the default derivation path"),
             password: None,
             write_to: None,
+            network: Network::Mainnet,
             _wordlist: PhantomData,
         }
     }
@@ -143,19 +164,96 @@ impl<W: Wordlist> MnemonicBuilder<W> {
         self
     }
 
+    /// Sets the network of the wallet, which determines the ICAN prefix of its address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_core::types::Network;
+    /// use corebc_signers::{MnemonicBuilder, Signer, coins_bip39::English};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let wallet = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .network(Network::Devin)
+    ///     .build()?;
+    /// assert_eq!(wallet.network_id(), 3);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
     /// the phrase field to be set.
     pub fn build(&self) -> Result<Wallet<SigningKey>, WalletError> {
-        let mnemonic = match &self.phrase {
-            Some(path_or_string) => {
-                let phrase = path_or_string.read()?;
-                Mnemonic::<W>::new_from_phrase(&phrase)?
-            }
-            None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
-        };
+        let mnemonic = self.read_mnemonic()?;
         self.mnemonic_to_wallet(&mnemonic)
     }
 
+    /// Returns the extended private key (xprv) at the derivation path, using the parameters set
+    /// in the mnemonic builder. This method expects the phrase field to be set.
+    ///
+    /// The wallets of all paths below the derivation path can be derived from the xprv with
+    /// [`Wallet::derive_from_xpriv`], without access to the phrase.
+    ///
+    /// See [`MnemonicBuilder::build_xpub`] for the watch-only counterpart.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_core::types::Network;
+    /// use corebc_signers::{MnemonicBuilder, LocalWallet, coins_bip39::English};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let xpriv = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .derivation_path("m/44'/60'/0'/0")?
+    ///     .build_xpriv()?;
+    /// let wallet = LocalWallet::derive_from_xpriv(&xpriv, "m/1", Network::Mainnet)?;
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_xpriv(&self) -> Result<XPriv, WalletError> {
+        let mnemonic = self.read_mnemonic()?;
+        Ok(mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?)
+    }
+
+    /// Returns the extended public key (xpub) at the derivation path, using the parameters set in
+    /// the mnemonic builder. This method expects the phrase field to be set.
+    ///
+    /// The xpub holds no secrets, the public keys of all non-hardened paths below the derivation
+    /// path can be derived from it with [`Parent::derive_path`], e.g. on an untrusted server.
+    ///
+    /// Note that ICAN addresses are derived from the Ed448 public key of the child's private key,
+    /// which cannot be computed from the secp256k1 keys of the xpub. Only the xpub tree itself is
+    /// watch-only.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_signers::{MnemonicBuilder, coins_bip39::English};
+    /// use corebc_signers::coins_bip32::xkeys::Parent;
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let xpub = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .derivation_path("m/44'/60'/0'/0")?
+    ///     .build_xpub()?;
+    /// let child = xpub.derive_path("m/1")?;
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_xpub(&self) -> Result<XPub, WalletError> {
+        Ok(self.build_xpriv()?.verify_key())
+    }
+
     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
     /// the phrase using the provided random number generator.
     pub fn build_random<R: Rng>(&self, rng: &mut R) -> Result<Wallet<SigningKey>, WalletError> {
@@ -174,17 +272,45 @@ impl<W: Wordlist> MnemonicBuilder<W> {
         Ok(wallet)
     }
 
+    fn read_mnemonic(&self) -> Result<Mnemonic<W>, WalletError> {
+        match &self.phrase {
+            Some(path_or_string) => {
+                let phrase = path_or_string.read()?;
+                Ok(Mnemonic::<W>::new_from_phrase(&phrase)?)
+            }
+            None => Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
+        }
+    }
+
     fn mnemonic_to_wallet(
         &self,
         mnemonic: &Mnemonic<W>,
     ) -> Result<Wallet<SigningKey>, WalletError> {
         let derived_priv_key =
             mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
-        let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-        let signer = SigningKey::from_bytes(&key.to_bytes())?;
-        let address = secret_key_to_address(&signer, &corebc_core::types::Network::Mainnet);
+        Wallet::from_xpriv(&derived_priv_key, self.network)
+    }
+}
 
-        Ok(Wallet::<SigningKey> { signer, address, network_id: 1 })
+impl Wallet<SigningKey> {
+    /// Creates the wallet of the extended private key, see [`MnemonicBuilder::build_xpriv`]
+    pub fn from_xpriv(xpriv: &XPriv, network: Network) -> Result<Self, WalletError> {
+        let key: &coins_bip32::prelude::SigningKey = xpriv.as_ref();
+        let secret = secret_bytes(key.to_bytes().to_vec());
+        let signer = SigningKey::from_bytes(secret.as_slice())?;
+        let address = secret_key_to_address(&signer, &network);
+
+        Ok(Self { signer, address, network_id: network.into() })
+    }
+
+    /// Creates the wallet at the `path` relative to the extended private key, e.g. `m/0/1`
+    pub fn derive_from_xpriv(
+        xpriv: &XPriv,
+        path: &str,
+        network: Network,
+    ) -> Result<Self, WalletError> {
+        let path = DerivationPath::from_str(path)?;
+        Self::from_xpriv(&xpriv.derive_path(&path)?, network)
     }
 }
 
@@ -281,4 +407,35 @@ mod tests {
 
         dir.close().unwrap();
     }
+
+    #[test]
+    fn derives_extended_keys() {
+        let phrase =
+            "work man father plunge mystery proud hollow address reunion sauce theory bonus";
+        let builder = MnemonicBuilder::<English>::default().phrase(phrase);
+        let parent = builder.clone().derivation_path("m/44'/60'/0'/2").unwrap();
+        let child = builder.derivation_path(TEST_DERIVATION_PATH).unwrap();
+
+        let xpriv = parent.build_xpriv().unwrap();
+        let xpub = parent.build_xpub().unwrap();
+        assert_eq!(xpub, xpriv.verify_key());
+
+        let expected = child.build_xpub().unwrap();
+        assert_eq!(xpriv.derive_path("m/1").unwrap().verify_key(), expected);
+        // watch-only derivation from the xpub yields the same public key
+        assert_eq!(xpub.derive_path("m/1").unwrap(), expected);
+        // hardened children need the private key
+        assert!(xpub.derive_path("m/1'").is_err());
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let phrase =
+            "work man father plunge mystery proud hollow address reunion sauce theory bonus";
+        let builder = MnemonicBuilder::<English>::default().phrase(phrase).password("TREZOR123");
+        let debug = format!("{builder:?}");
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("plunge"));
+        assert!(!debug.contains("TREZOR123"));
+    }
 }
@@ -9,10 +9,10 @@ mod yubi;
 
 use crate::Signer;
 use corebc_core::{
-    libgoldilocks::{PrehashSigner, Signature as RecoverableSignature},
+    libgoldilocks::{PrehashSigner, Signature as RecoverableSignature, SigningKey},
     types::{
         transaction::{cip712::Cip712, eip2718::TypedTransaction},
-        Address, Signature, H1368, H160, H256,
+        Address, Network, Signature, H1368, H160, H256, H456,
     },
     utils::{hash_message, to_ican},
 };
@@ -38,15 +38,14 @@ use std::fmt;
 /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
 /// let wallet = LocalWallet::new(&mut thread_rng(), Network::Mainnet);
 ///
-/// // Optionally, the wallet's network id can be set, in order to use EIP-155
-/// // replay protection with different networks
-/// let wallet = wallet.with_network_id(1337u64);
+/// // Optionally, the wallet can be moved to another network, which also changes the ICAN prefix
+/// // of its address and provides EIP-155 replay protection
+/// let wallet = wallet.try_with_network(Network::Private(1337))?;
 ///
 /// // The wallet can be used to sign messages
 /// let message = b"hello";
 /// let signature = wallet.sign_message(message).await?;
-/// let network = Network::try_from(wallet.network_id()).unwrap();
-/// assert_eq!(signature.recover(&message[..], &network).unwrap(), wallet.address());
+/// assert_eq!(signature.recover(&message[..], &wallet.network()).unwrap(), wallet.address());
 ///
 /// // LocalWallet is clonable:
 /// let wallet_clone = wallet.clone();
@@ -73,11 +72,48 @@ impl<D: PrehashSigner<RecoverableSignature>> Wallet<D> {
     pub fn new_with_signer(signer: D, address: Address, network_id: u64) -> Self {
         Wallet { signer, address, network_id }
     }
+
+    /// Returns the network of the wallet
+    pub fn network(&self) -> Network {
+        Network::from(self.network_id)
+    }
+
+    /// Moves the wallet to the given network, changing the ICAN prefix of its address.
+    ///
+    /// Prefer creating the wallet with the right network, e.g. with [`Wallet::new`] or
+    /// [`MnemonicBuilder::network`](crate::MnemonicBuilder::network).
+    ///
+    /// # Errors
+    ///
+    /// Fails if `network` is not a valid network id
+    pub fn try_with_network<N>(mut self, network: N) -> Result<Self, WalletError>
+    where
+        N: TryInto<Network>,
+        N::Error: fmt::Display,
+    {
+        let network =
+            network.try_into().map_err(|err| WalletError::InvalidNetwork(err.to_string()))?;
+        self.network_id = network.into();
+        self.address = to_ican(&H160::from_slice(&self.address[2..]), &network);
+        Ok(self)
+    }
+}
+
+/// The inner signer of a [`Wallet`], which knows its public key without signing
+pub trait PublicKeySigner: PrehashSigner<RecoverableSignature> {
+    /// Returns the Ed448 public key of the signer
+    fn public_key(&self) -> H456;
+}
+
+impl PublicKeySigner for SigningKey {
+    fn public_key(&self) -> H456 {
+        H456::from_slice(self.verifying_key().as_bytes())
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-impl<D: Sync + Send + PrehashSigner<RecoverableSignature>> Signer for Wallet<D> {
+impl<D: Sync + Send + PublicKeySigner> Signer for Wallet<D> {
     type Error = WalletError;
 
     async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
@@ -108,6 +144,14 @@ impl<D: Sync + Send + PrehashSigner<RecoverableSignature>> Signer for Wallet<D>
         self.sign_hash(H256::from(encoded))
     }
 
+    async fn sign_prehash(&self, hash: H256) -> Result<Signature, Self::Error> {
+        self.sign_hash(hash)
+    }
+
+    fn public_key(&self) -> Result<H456, Self::Error> {
+        Ok(PublicKeySigner::public_key(&self.signer))
+    }
+
     fn address(&self) -> Address {
         self.address
     }
@@ -117,18 +161,14 @@ impl<D: Sync + Send + PrehashSigner<RecoverableSignature>> Signer for Wallet<D>
         self.network_id
     }
 
-    /// Sets the wallet's network_id, used in conjunction with EIP-155 signing
-    // CORETODO: Move setting of network_id to the point of wallet creation
-    fn with_network_id<T: Into<u64>>(mut self, network_id: T) -> Self {
-        self.network_id = network_id.into();
-        let network = From::from(self.network_id);
-
-        let mut bytes = [0u8; 20];
-        bytes.copy_from_slice(&self.address[2..]);
-        let addr = H160::from(bytes);
-        self.address = to_ican(&addr, &network);
-
-        self
+    /// Sets the wallet's network_id, used in conjunction with EIP-155 signing, and changes the
+    /// ICAN prefix of its address accordingly.
+    ///
+    /// Prefer creating the wallet with the right network, or [`Wallet::try_with_network`] which
+    /// does not panic on invalid ids.
+    fn with_network_id<T: Into<u64>>(self, network_id: T) -> Self {
+        let network_id = network_id.into();
+        self.try_with_network(network_id).expect("network ids that fit in u64 are valid")
     }
 }
 
@@ -8,7 +8,7 @@ use corebc_core::{
     libgoldilocks::{errors::LibgoldilockErrors, SigningKey},
     rand::{CryptoRng, Rng},
     types::Network,
-    utils::secret_key_to_address,
+    utils::{secret_bytes, secret_key_to_address},
 };
 #[cfg(not(target_arch = "wasm32"))]
 use corebc_keystore::KeystoreError;
@@ -47,6 +47,12 @@ pub enum WalletError {
     /// Error type from Cip712Error message
     #[error("error encoding cip712 struct: {0:?}")]
     Cip712Error(String),
+    /// Thrown if the network of the wallet is invalid
+    #[error("invalid network: {0}")]
+    InvalidNetwork(String),
+    /// Thrown by the optional signer methods the wallet does not support
+    #[error(transparent)]
+    Unsupported(#[from] crate::UnsupportedSignerMethod),
 }
 
 impl Wallet<SigningKey> {
@@ -68,6 +74,7 @@ impl Wallet<SigningKey> {
         S: AsRef<[u8]>,
     {
         let (secret, uuid) = corebc_keystore::new(dir, rng, password, name, &network)?;
+        let secret = secret_bytes(secret);
         let signer = SigningKey::from_bytes(secret.as_slice())?;
         let address = secret_key_to_address(&signer, &network);
         Ok((Self { signer, address, network_id: u64::from(network) }, uuid))
@@ -84,7 +91,7 @@ impl Wallet<SigningKey> {
         P: AsRef<Path>,
         S: AsRef<[u8]>,
     {
-        let secret = corebc_keystore::decrypt_key(keypath, password)?;
+        let secret = secret_bytes(corebc_keystore::decrypt_key(keypath, password)?);
         let signer = SigningKey::from_bytes(secret.as_slice())?;
         let address = secret_key_to_address(&signer, &network);
         Ok(Self { signer, address, network_id: u64::from(network) })
@@ -139,7 +146,7 @@ impl FromStr for Wallet<SigningKey> {
 
     fn from_str(src: &str) -> Result<Self, Self::Err> {
         let src = src.strip_prefix("0x").or_else(|| src.strip_prefix("0X")).unwrap_or(src);
-        let src = hex::decode(src)?;
+        let src = secret_bytes(hex::decode(src)?);
         let sk = SigningKey::from_bytes(src.as_slice())?;
         Ok(sk.into())
     }
@@ -166,7 +173,7 @@ impl TryFrom<String> for Wallet<SigningKey> {
 mod tests {
     use super::*;
     use crate::Signer;
-    use corebc_core::types::Address;
+    use corebc_core::types::{Address, H256, U64};
     use tempfile::tempdir;
 
     #[test]
@@ -362,6 +369,56 @@ mod tests {
         assert_eq!(wallet.signer, wallet_from_bytes.signer);
     }
 
+    #[test]
+    fn wallet_with_network() {
+        let key = [1u8; 57];
+        let wallet = Wallet::from_bytes(&key, Network::Mainnet).unwrap();
+        let devin = Wallet::from_bytes(&key, Network::Devin).unwrap();
+
+        let moved = wallet.clone().try_with_network(Network::Devin).unwrap();
+        assert_eq!(moved, devin);
+        assert_eq!(moved.network(), Network::Devin);
+        assert_eq!(wallet.try_with_network(U64::from(3)).unwrap().address, devin.address);
+    }
+
+    #[tokio::test]
+    async fn signs_prehash_and_exposes_public_key() {
+        let wallet = Wallet::from_bytes(&[1u8; 57], Network::Devin).unwrap();
+        let public_key = wallet.public_key().unwrap();
+        assert_eq!(public_key.as_bytes(), wallet.signer.verifying_key().as_bytes());
+
+        let hash = H256::repeat_byte(0x42);
+        let signature = wallet.sign_prehash(hash).await.unwrap();
+        assert_eq!(signature.public_key(), public_key);
+        assert_eq!(signature.recover(hash, &Network::Devin).unwrap(), wallet.address);
+        assert_ne!(signature, wallet.sign_message(hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn conforms_to_test_vectors() {
+        use corebc_core::{test_vectors::TestVectors, types::Bytes};
+
+        let vectors = TestVectors::canonical();
+        for vector in &vectors.keys {
+            let wallet = Wallet::from_bytes(&vector.private_key, Network::Mainnet).unwrap();
+            assert_eq!(wallet.address, vector.address);
+            assert_eq!(wallet.public_key().unwrap().as_bytes(), &vector.public_key[..]);
+        }
+        for vector in &vectors.messages {
+            let wallet = Wallet::from_bytes(&vector.private_key, Network::Mainnet).unwrap();
+            let signature = wallet.sign_message(&vector.message).await.unwrap();
+            assert_eq!(Bytes::from(signature.to_vec()), vector.signature);
+        }
+        for vector in &vectors.transactions {
+            let network = Network::from(vector.network_id.as_u64());
+            let wallet = Wallet::from_bytes(&vector.private_key, network).unwrap();
+            let tx: crate::TypedTransaction = vector.request().into();
+            let signature = wallet.sign_transaction(&tx).await.unwrap();
+            assert_eq!(Bytes::from(signature.to_vec()), vector.signature);
+            assert_eq!(tx.rlp_signed(&signature), vector.signed);
+        }
+    }
+
     #[test]
     fn key_from_str() {
         let wallet: Wallet<SigningKey> =
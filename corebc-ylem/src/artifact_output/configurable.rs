@@ -21,7 +21,7 @@ use crate::{
         UserDoc,
     },
     sources::VersionedSourceFile,
-    Artifact, ArtifactOutput, SourceFile, YlemConfig, YlemError,
+    Artifact, ArtifactOutput, ArtifactProfile, SourceFile, YlemConfig, YlemError,
 };
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, collections::BTreeMap, fs, path::Path};
@@ -99,6 +99,29 @@ impl ConfigurableContractArtifact {
     pub fn source_file(&self) -> Option<SourceFile> {
         self.id.map(|id| SourceFile { id, ast: self.ast.clone() })
     }
+
+    /// Removes everything from the artifact that is not part of the given `profile`
+    pub fn with_profile(self, profile: ArtifactProfile) -> Self {
+        fn strip_source_map(mut bytecode: CompactBytecode) -> CompactBytecode {
+            bytecode.source_map = None;
+            bytecode
+        }
+
+        match profile {
+            ArtifactProfile::Full => self,
+            ArtifactProfile::AbiOnly => Self { abi: self.abi, ..Default::default() },
+            ArtifactProfile::Minimal => Self {
+                abi: self.abi,
+                bytecode: self.bytecode.map(strip_source_map),
+                deployed_bytecode: self.deployed_bytecode.map(|mut deployed| {
+                    deployed.bytecode = deployed.bytecode.map(strip_source_map);
+                    deployed
+                }),
+                method_identifiers: self.method_identifiers,
+                ..Default::default()
+            },
+        }
+    }
 }
 
 impl From<ConfigurableContractArtifact> for CompactContractBytecode {
@@ -148,6 +171,9 @@ pub struct ConfigurableArtifacts {
     /// A set of values that should be written to a separate file
     pub additional_files: ExtraOutputFiles,
 
+    /// How much of the configured output is written, see [`ArtifactProfile`]
+    pub profile: ArtifactProfile,
+
     /// PRIVATE: This structure may grow, As such, constructing this structure should
     /// _always_ be done using a public constructor or update syntax:
     ///
@@ -175,6 +201,13 @@ impl ConfigurableArtifacts {
         }
     }
 
+    /// Sets the [`ArtifactProfile`] of the emitted artifacts
+    #[must_use]
+    pub fn with_profile(mut self, profile: ArtifactProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
     /// Returns the `Settings` this configuration corresponds to
     pub fn settings(&self) -> Settings {
         YlemConfig::builder().additional_outputs(self.output_selection()).build().into()
@@ -232,7 +265,14 @@ impl ConfigurableArtifacts {
 impl ArtifactOutput for ConfigurableArtifacts {
     type Artifact = ConfigurableContractArtifact;
 
+    fn profile(&self) -> ArtifactProfile {
+        self.profile
+    }
+
     fn write_contract_extras(&self, contract: &Contract, file: &Path) -> Result<(), YlemError> {
+        if !self.profile.is_full() {
+            return Ok(())
+        }
         self.additional_files.write_extras(contract, file)
     }
 
@@ -353,6 +393,7 @@ impl ArtifactOutput for ConfigurableArtifacts {
             ast: source_file.and_then(|s| s.ast.clone()),
             generated_sources: generated_sources.unwrap_or_default(),
         }
+        .with_profile(self.profile)
     }
 
     fn standalone_source_file_to_artifact(
@@ -360,6 +401,10 @@ impl ArtifactOutput for ConfigurableArtifacts {
         _path: &str,
         file: &VersionedSourceFile,
     ) -> Option<Self::Artifact> {
+        // these artifacts only exist for the AST
+        if !self.profile.is_full() {
+            return None
+        }
         file.source_file.ast.clone().map(|ast| ConfigurableContractArtifact {
             abi: Some(LosslessAbi::default()),
             id: Some(file.source_file.id),
@@ -24,6 +24,7 @@ use std::{
     io,
     ops::Deref,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 use tracing::{error, trace};
 
@@ -590,6 +591,13 @@ pub trait ArtifactOutput {
         Ok(artifacts)
     }
 
+    /// The [`ArtifactProfile`] of the artifacts emitted by this handler.
+    ///
+    /// The profile is recorded in the cache file, artifacts are rewritten when it changes.
+    fn profile(&self) -> ArtifactProfile {
+        ArtifactProfile::Full
+    }
+
     /// Write additional files for the contract
     fn write_contract_extras(&self, contract: &Contract, file: &Path) -> Result<()> {
         ExtraOutputFiles::all().write_extras(contract, file)
@@ -952,6 +960,52 @@ pub trait ArtifactOutput {
     ) -> Option<Self::Artifact>;
 }
 
+/// Determines how much of the compiler output is written to the artifact files.
+///
+/// Large projects can use a reduced profile to avoid writing the AST and the source maps of every
+/// contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactProfile {
+    /// The ABI, the bytecodes without source maps and the method identifiers
+    Minimal,
+    /// The ABI only
+    AbiOnly,
+    /// Everything the artifacts handler is configured to emit
+    #[default]
+    Full,
+}
+
+impl ArtifactProfile {
+    /// Whether this is the [`ArtifactProfile::Full`] profile
+    pub fn is_full(&self) -> bool {
+        matches!(self, ArtifactProfile::Full)
+    }
+}
+
+impl fmt::Display for ArtifactProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtifactProfile::Minimal => f.write_str("minimal"),
+            ArtifactProfile::AbiOnly => f.write_str("abi-only"),
+            ArtifactProfile::Full => f.write_str("full"),
+        }
+    }
+}
+
+impl FromStr for ArtifactProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(ArtifactProfile::Minimal),
+            "abi-only" | "abi_only" | "abionly" => Ok(ArtifactProfile::AbiOnly),
+            "full" => Ok(ArtifactProfile::Full),
+            s => Err(format!("Unknown artifact profile: {s}")),
+        }
+    }
+}
+
 /// Additional context to use during [`ArtifactOutput::on_output()`]
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
@@ -1095,6 +1149,16 @@ mod tests {
         assert_artifact::<serde_json::Value>();
     }
 
+    #[test]
+    fn can_parse_artifact_profile() {
+        for profile in [ArtifactProfile::Minimal, ArtifactProfile::AbiOnly, ArtifactProfile::Full] {
+            assert_eq!(profile.to_string().parse::<ArtifactProfile>().unwrap(), profile);
+            let json = serde_json::to_string(&profile).unwrap();
+            assert_eq!(json, format!("\"{profile}\""));
+        }
+        assert!("ast-only".parse::<ArtifactProfile>().is_err());
+    }
+
     #[test]
     fn can_find_alternate_paths() {
         let mut already_taken = HashSet::new();
@@ -2,10 +2,12 @@
 
 use crate::{
     artifacts::{serde_helpers, FunctionDebugData, GeneratedSource, Offsets},
+    error::YlemError,
     sourcemap::{self, SourceMap, SyntaxError},
     utils,
 };
 use corebc_core::{abi::Address, types::Bytes};
+use semver::Version;
 use serde::{Deserialize, Serialize, Serializer};
 use std::collections::BTreeMap;
 
@@ -70,24 +72,34 @@ impl CompactBytecode {
         library: impl AsRef<str>,
         address: Address,
     ) -> bool {
-        if !self.object.is_unlinked() {
-            return true
-        }
+        link(&mut self.object, &mut self.link_references, file.as_ref(), library.as_ref(), address)
+    }
 
-        let file = file.as_ref();
-        let library = library.as_ref();
-        if let Some((key, mut contracts)) = self.link_references.remove_entry(file) {
-            if contracts.remove(library).is_some() {
-                self.object.link(file, library, address);
-            }
-            if !contracts.is_empty() {
-                self.link_references.insert(key, contracts.clone());
-            }
-            if self.link_references.is_empty() {
-                return self.object.resolve().is_some()
-            }
-        }
-        false
+    /// Links all references to the library `name` with the given address.
+    ///
+    /// `name` is either the fully qualified name of the library (`file.sol:Math`) or just its
+    /// name, in which case the library is linked in all files that define it.
+    ///
+    /// Returns true if the bytecode object is fully linked, false otherwise
+    pub fn link_library(&mut self, name: impl AsRef<str>, address: Address) -> bool {
+        link_library(&mut self.object, &mut self.link_references, name.as_ref(), address)
+    }
+
+    /// Returns the fully qualified names of all libraries that are not linked yet
+    pub fn unresolved_libraries(&self) -> Vec<String> {
+        unresolved_libraries(&self.object, &self.link_references)
+    }
+
+    /// Links all `libraries`, see [`Self::link_library()`], and returns the linked bytecode.
+    ///
+    /// Fails with [`YlemError::UnresolvedLibraries`] if the bytecode references any library that
+    /// is not in `libraries`.
+    pub fn link_libraries<I, S>(&mut self, libraries: I) -> Result<Bytes, YlemError>
+    where
+        I: IntoIterator<Item = (S, Address)>,
+        S: AsRef<str>,
+    {
+        link_libraries(&mut self.object, &mut self.link_references, libraries)
     }
 }
 
@@ -155,24 +167,7 @@ impl Bytecode {
         library: impl AsRef<str>,
         address: Address,
     ) -> bool {
-        if !self.object.is_unlinked() {
-            return true
-        }
-
-        let file = file.as_ref();
-        let library = library.as_ref();
-        if let Some((key, mut contracts)) = self.link_references.remove_entry(file) {
-            if contracts.remove(library).is_some() {
-                self.object.link(file, library, address);
-            }
-            if !contracts.is_empty() {
-                self.link_references.insert(key, contracts);
-            }
-            if self.link_references.is_empty() {
-                return self.object.resolve().is_some()
-            }
-        }
-        false
+        link(&mut self.object, &mut self.link_references, file.as_ref(), library.as_ref(), address)
     }
 
     /// Links the bytecode object with all provided `(file, lib, addr)`
@@ -203,6 +198,115 @@ impl Bytecode {
         }
         false
     }
+
+    /// Links all references to the library `name` with the given address.
+    ///
+    /// `name` is either the fully qualified name of the library (`file.sol:Math`) or just its
+    /// name, in which case the library is linked in all files that define it.
+    ///
+    /// Returns true if the bytecode object is fully linked, false otherwise
+    pub fn link_library(&mut self, name: impl AsRef<str>, address: Address) -> bool {
+        link_library(&mut self.object, &mut self.link_references, name.as_ref(), address)
+    }
+
+    /// Returns the fully qualified names of all libraries that are not linked yet
+    pub fn unresolved_libraries(&self) -> Vec<String> {
+        unresolved_libraries(&self.object, &self.link_references)
+    }
+
+    /// Links all `libraries`, see [`Self::link_library()`], and returns the linked bytecode.
+    ///
+    /// Fails with [`YlemError::UnresolvedLibraries`] if the bytecode references any library that
+    /// is not in `libraries`.
+    pub fn link_libraries<I, S>(&mut self, libraries: I) -> Result<Bytes, YlemError>
+    where
+        I: IntoIterator<Item = (S, Address)>,
+        S: AsRef<str>,
+    {
+        link_libraries(&mut self.object, &mut self.link_references, libraries)
+    }
+}
+
+/// The link references of a bytecode, by file and library name
+type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<Offsets>>>;
+
+/// Links the `library` of `file` in `object` and removes it from the `link_references`, returns
+/// true if the object is fully linked
+fn link(
+    object: &mut BytecodeObject,
+    link_references: &mut LinkReferences,
+    file: &str,
+    library: &str,
+    address: Address,
+) -> bool {
+    if !object.is_unlinked() {
+        return true
+    }
+
+    if let Some((key, mut contracts)) = link_references.remove_entry(file) {
+        if contracts.remove(library).is_some() {
+            object.link(file, library, address);
+        }
+        if !contracts.is_empty() {
+            link_references.insert(key, contracts);
+        }
+        if link_references.is_empty() {
+            return object.resolve().is_some()
+        }
+    }
+    false
+}
+
+/// Links the library `name`, fully qualified or not, in `object`, returns true if the object is
+/// fully linked
+fn link_library(
+    object: &mut BytecodeObject,
+    link_references: &mut LinkReferences,
+    name: &str,
+    address: Address,
+) -> bool {
+    if let Some((file, library)) = name.split_once(':') {
+        return link(object, link_references, file, library, address)
+    }
+    let files = link_references
+        .iter()
+        .filter(|(_, libraries)| libraries.contains_key(name))
+        .map(|(file, _)| file.clone())
+        .collect::<Vec<_>>();
+    for file in files {
+        link(object, link_references, &file, name, address);
+    }
+    !object.is_unlinked()
+}
+
+/// Links all `libraries` in `object` and returns the linked bytecode
+fn link_libraries<I, S>(
+    object: &mut BytecodeObject,
+    link_references: &mut LinkReferences,
+    libraries: I,
+) -> Result<Bytes, YlemError>
+where
+    I: IntoIterator<Item = (S, Address)>,
+    S: AsRef<str>,
+{
+    for (name, address) in libraries {
+        link_library(object, link_references, name.as_ref(), address);
+    }
+    object.resolve().cloned().ok_or_else(|| {
+        YlemError::UnresolvedLibraries(unresolved_libraries(object, link_references))
+    })
+}
+
+/// Returns the fully qualified names of the libraries in `link_references` if `object` is not
+/// fully linked
+fn unresolved_libraries(object: &BytecodeObject, link_references: &LinkReferences) -> Vec<String> {
+    if !object.is_unlinked() {
+        return Vec::new()
+    }
+    link_references
+        .iter()
+        .flat_map(|(file, libraries)| libraries.keys().map(move |lib| format!("{file}:{lib}")))
+        .collect()
 }
 
 /// Represents the bytecode of a contracts that might be not fully linked yet.
@@ -274,6 +378,12 @@ impl BytecodeObject {
         self.as_bytes().map(|c| !c.0.is_empty()).unwrap_or_default()
     }
 
+    /// Returns the metadata appended to the bytecode, if the object is a valid bytecode and ends
+    /// with a metadata trailer
+    pub fn metadata(&self) -> Option<BytecodeMetadata> {
+        self.as_bytes().and_then(|code| BytecodeMetadata::from_bytecode(code))
+    }
+
     /// Tries to resolve the unlinked string object a valid bytecode object in place
     ///
     /// Returns the string if it is a valid
@@ -426,6 +536,15 @@ pub struct CompactDeployedBytecode {
 }
 
 impl CompactDeployedBytecode {
+    /// Links the runtime bytecode with all `libraries`, see [`CompactBytecode::link_libraries()`]
+    pub fn link_libraries<I, S>(&mut self, libraries: I) -> Result<Option<Bytes>, YlemError>
+    where
+        I: IntoIterator<Item = (S, Address)>,
+        S: AsRef<str>,
+    {
+        self.bytecode.as_mut().map(|bytecode| bytecode.link_libraries(libraries)).transpose()
+    }
+
     /// Returns a new `CompactDeployedBytecode` object that contains nothing, as it's the case for
     /// interfaces and standalone solidity files that don't contain any contract definitions
     pub fn empty() -> Self {
@@ -458,8 +577,184 @@ impl From<CompactDeployedBytecode> for DeployedBytecode {
     }
 }
 
+/// The hash of the contract metadata, that ylem embeds in the bytecode
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataHash {
+    /// The multihash of the metadata file on IPFS
+    Ipfs(Bytes),
+    /// The legacy Swarm hash of the metadata file
+    Bzzr0(Bytes),
+    /// The Swarm hash of the metadata file
+    Bzzr1(Bytes),
+}
+
+impl MetadataHash {
+    /// Returns the digest, regardless of the hash method
+    pub fn digest(&self) -> &Bytes {
+        match self {
+            MetadataHash::Ipfs(digest) |
+            MetadataHash::Bzzr0(digest) |
+            MetadataHash::Bzzr1(digest) => digest,
+        }
+    }
+}
+
+/// The CBOR encoded metadata that ylem appends to the deployed bytecode.
+///
+/// The trailer is a CBOR map followed by its length as a big-endian `u16`, see also
+/// <https://docs.soliditylang.org/en/latest/metadata.html#encoding-of-the-metadata-hash-in-the-bytecode>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BytecodeMetadata {
+    /// The hash of the metadata file, if it was not disabled via `bytecodeHash: "none"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<MetadataHash>,
+    /// The version of the compiler, `None` if it was not included in the metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_version: Option<Version>,
+    /// Whether experimental features were used
+    #[serde(default)]
+    pub experimental: bool,
+    /// The length of the whole trailer in bytes, including the length suffix
+    pub length: usize,
+}
+
+impl BytecodeMetadata {
+    /// Parses the metadata trailer at the end of `code`
+    ///
+    /// Returns `None` if the bytecode does not end with a well-formed metadata trailer.
+    pub fn from_bytecode(code: &[u8]) -> Option<Self> {
+        let (rest, len) = code.split_at(code.len().checked_sub(2)?);
+        let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+        let cbor = &rest[rest.len().checked_sub(len)?..];
+
+        let mut decoder = cbor::Decoder::new(cbor);
+        let entries = decoder.map_len()?;
+        let mut metadata = BytecodeMetadata { length: len + 2, ..Default::default() };
+        for _ in 0..entries {
+            let key = decoder.text()?;
+            let value = decoder.value()?;
+            match (key, value) {
+                ("ipfs", cbor::Value::Bytes(hash)) => {
+                    metadata.hash = Some(MetadataHash::Ipfs(hash.to_vec().into()))
+                }
+                ("bzzr0", cbor::Value::Bytes(hash)) => {
+                    metadata.hash = Some(MetadataHash::Bzzr0(hash.to_vec().into()))
+                }
+                ("bzzr1", cbor::Value::Bytes(hash)) => {
+                    metadata.hash = Some(MetadataHash::Bzzr1(hash.to_vec().into()))
+                }
+                // release builds are encoded as `[major, minor, patch]`, others as full string
+                ("ylem" | "solc", cbor::Value::Bytes(&[major, minor, patch])) => {
+                    metadata.compiler_version =
+                        Some(Version::new(major.into(), minor.into(), patch.into()))
+                }
+                ("ylem" | "solc", cbor::Value::Text(version)) => {
+                    metadata.compiler_version = Version::parse(version).ok()
+                }
+                ("experimental", cbor::Value::Bool(experimental)) => {
+                    metadata.experimental = experimental
+                }
+                _ => {}
+            }
+        }
+
+        // the trailer must consist of exactly one map
+        decoder.is_empty().then_some(metadata)
+    }
+
+    /// Returns `code` without its metadata trailer, or `code` itself if it has none.
+    ///
+    /// Two builds of the same sources with different metadata (e.g. different paths or comments)
+    /// are identical once stripped.
+    pub fn strip(code: &[u8]) -> &[u8] {
+        match Self::from_bytecode(code) {
+            Some(metadata) => &code[..code.len() - metadata.length],
+            None => code,
+        }
+    }
+}
+
+/// A minimal CBOR decoder covering the subset of the format used by the bytecode metadata
+mod cbor {
+    pub(super) enum Value<'a> {
+        Uint,
+        Bytes(&'a [u8]),
+        Text(&'a str),
+        Bool(bool),
+    }
+
+    pub(super) struct Decoder<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Decoder<'a> {
+        pub(super) fn new(data: &'a [u8]) -> Self {
+            Self { data }
+        }
+
+        pub(super) fn is_empty(&self) -> bool {
+            self.data.is_empty()
+        }
+
+        fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+            if len > self.data.len() {
+                return None
+            }
+            let (head, tail) = self.data.split_at(len);
+            self.data = tail;
+            Some(head)
+        }
+
+        /// Reads an item header, returns the major type and the argument
+        fn header(&mut self) -> Option<(u8, u64)> {
+            let initial = self.take(1)?[0];
+            let (major, info) = (initial >> 5, initial & 0x1f);
+            let arg = match info {
+                0..=23 => info as u64,
+                24 => self.take(1)?[0] as u64,
+                25 => u16::from_be_bytes(self.take(2)?.try_into().ok()?) as u64,
+                26 => u32::from_be_bytes(self.take(4)?.try_into().ok()?) as u64,
+                27 => u64::from_be_bytes(self.take(8)?.try_into().ok()?),
+                // indefinite lengths and reserved values are never used in the metadata
+                _ => return None,
+            };
+            Some((major, arg))
+        }
+
+        pub(super) fn map_len(&mut self) -> Option<u64> {
+            match self.header()? {
+                (5, len) => Some(len),
+                _ => None,
+            }
+        }
+
+        pub(super) fn text(&mut self) -> Option<&'a str> {
+            match self.value()? {
+                Value::Text(text) => Some(text),
+                _ => None,
+            }
+        }
+
+        pub(super) fn value(&mut self) -> Option<Value<'a>> {
+            match self.header()? {
+                (0, _) => Some(Value::Uint),
+                (2, len) => self.take(len.try_into().ok()?).map(Value::Bytes),
+                (3, len) => {
+                    std::str::from_utf8(self.take(len.try_into().ok()?)?).ok().map(Value::Text)
+                }
+                (7, 20) => Some(Value::Bool(false)),
+                (7, 21) => Some(Value::Bool(true)),
+                _ => None,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{artifacts::ContractBytecode, ConfigurableContractArtifact};
 
     #[test]
@@ -484,4 +779,42 @@ mod tests {
         let bytecode = bytecode.unwrap();
         assert!(!bytecode.bytecode.object.is_unlinked());
     }
+
+    const IPFS_HASH: &str = "1220ef0a8a2e9c7d6ca4cb0e4b1f2e8ca4f6ee86e7c6a3ec3c2ab5ec1db0d3ee7a0f";
+
+    #[test]
+    fn parses_ipfs_metadata() {
+        // a2 { "ipfs": bytes(34), "solc": [0, 8, 17] }
+        let trailer = format!("a264697066735822{IPFS_HASH}64736f6c63430008110033");
+        let code = hex::decode(format!("6080604052{trailer}")).unwrap();
+
+        let metadata = BytecodeMetadata::from_bytecode(&code).unwrap();
+        assert_eq!(metadata.hash, Some(MetadataHash::Ipfs(hex::decode(IPFS_HASH).unwrap().into())));
+        assert_eq!(metadata.compiler_version, Some(Version::new(0, 8, 17)));
+        assert!(!metadata.experimental);
+        assert_eq!(metadata.length, trailer.len() / 2);
+        assert_eq!(BytecodeMetadata::strip(&code), hex::decode("6080604052").unwrap());
+
+        let object = BytecodeObject::Bytecode(code.into());
+        assert_eq!(object.metadata(), Some(metadata));
+    }
+
+    #[test]
+    fn parses_bzzr0_metadata() {
+        // a1 { "bzzr0": bytes(32) }
+        let hash = "ef".repeat(32);
+        let code = hex::decode(format!("6080a165627a7a72305820{hash}0029")).unwrap();
+
+        let metadata = BytecodeMetadata::from_bytecode(&code).unwrap();
+        assert_eq!(metadata.hash, Some(MetadataHash::Bzzr0(hex::decode(hash).unwrap().into())));
+        assert_eq!(metadata.compiler_version, None);
+    }
+
+    #[test]
+    fn ignores_missing_metadata() {
+        let code = hex::decode("608060405234801561001057600080fd5b50").unwrap();
+        assert_eq!(BytecodeMetadata::from_bytecode(&code), None);
+        assert_eq!(BytecodeMetadata::strip(&code), &code[..]);
+        assert_eq!(BytecodeMetadata::from_bytecode(&[]), None);
+    }
 }
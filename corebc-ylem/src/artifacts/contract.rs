@@ -1,13 +1,19 @@
 //! Contract related types
 
-use crate::artifacts::{
-    bytecode::{
-        Bytecode, BytecodeObject, CompactBytecode, CompactDeployedBytecode, DeployedBytecode,
+use crate::{
+    artifacts::{
+        bytecode::{
+            Bytecode, BytecodeObject, CompactBytecode, CompactDeployedBytecode, DeployedBytecode,
+        },
+        serde_helpers, DevDoc, Evm, Ewasm, LosslessAbi, LosslessMetadata, Offsets, StorageLayout,
+        UserDoc,
     },
-    serde_helpers, DevDoc, Evm, Ewasm, LosslessAbi, LosslessMetadata, Offsets, StorageLayout,
-    UserDoc,
+    error::YlemError,
+};
+use corebc_core::{
+    abi::Contract as Abi,
+    types::{Address, Bytes},
 };
-use corebc_core::{abi::Contract as Abi, types::Bytes};
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, collections::BTreeMap, convert::TryFrom};
 
@@ -160,6 +166,24 @@ impl CompactContractBytecode {
         }
         links
     }
+
+    /// Links the creation and runtime bytecode with all `libraries`.
+    ///
+    /// `libraries` maps library names, either fully qualified (`file.sol:Math`) or plain (`Math`),
+    /// to the addresses they are deployed at. Fails if any referenced library is missing.
+    pub fn link_libraries(
+        &mut self,
+        libraries: &BTreeMap<String, Address>,
+    ) -> Result<(), YlemError> {
+        let libraries = || libraries.iter().map(|(name, address)| (name, *address));
+        if let Some(bytecode) = self.bytecode.as_mut() {
+            bytecode.link_libraries(libraries())?;
+        }
+        if let Some(deployed) = self.deployed_bytecode.as_mut() {
+            deployed.link_libraries(libraries())?;
+        }
+        Ok(())
+    }
 }
 
 impl<'a> From<&'a CompactContractBytecode> for CompactContractBytecodeCow<'a> {
@@ -229,6 +229,28 @@ impl StandardJsonCompilerInput {
         }
         self
     }
+
+    /// Removes/adjusts values in the settings that are not compatible with the provided compiler
+    /// version, see [`Settings::sanitize()`]
+    #[must_use]
+    pub fn sanitized(mut self, version: &Version) -> Self {
+        self.settings.sanitize(version);
+        self
+    }
+
+    /// Reads the standard-json input from the json file at `path`
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, YlemError> {
+        utils::read_json_file(path)
+    }
+
+    /// Writes the standard-json input to `path`, creating its parent directories if needed
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), YlemError> {
+        let path = path.as_ref();
+        utils::create_parent_dir_all(path)?;
+        let file = fs::File::create(path).map_err(|err| YlemError::io(err, path))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
 }
 
 impl From<StandardJsonCompilerInput> for CompilerInput {
@@ -1395,6 +1417,11 @@ pub struct CompilerOutput {
     pub sources: BTreeMap<String, SourceFile>,
     #[serde(default)]
     pub contracts: Contracts,
+    /// What ylem printed to stderr on success, one entry per ylem run.
+    ///
+    /// This is not part of ylem's json output, ylem prints some warnings only there.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_warnings: Vec<String>,
 }
 
 impl CompilerOutput {
@@ -1471,6 +1498,7 @@ impl CompilerOutput {
         self.errors.extend(other.errors);
         self.contracts.extend(other.contracts);
         self.sources.extend(other.sources);
+        self.raw_warnings.extend(other.raw_warnings);
     }
 }
 
@@ -1821,6 +1849,47 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// Renders the excerpt of `source` this error points to, with carets under the offending code:
+    ///
+    /// ```text
+    ///  --> src/Contract.sol:3:17
+    ///   |
+    /// 3 |         require(address(0), "Error");
+    ///   |                 ^^^^^^^^^^
+    /// ```
+    ///
+    /// Only the first line of a location that spans multiple lines is rendered. Returns `None` if
+    /// the error has no source location or if it is out of the bounds of `source`.
+    pub fn source_snippet(&self, source: &str) -> Option<String> {
+        let location = self.source_location.as_ref()?;
+        let start = usize::try_from(location.start).ok()?;
+        let end = usize::try_from(location.end).ok()?.max(start);
+        if end > source.len() || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            return None
+        }
+
+        let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |idx| start + idx);
+        let line = source[..start].matches('\n').count() + 1;
+        let text = source[line_start..line_end].trim_end_matches('\r');
+        let column = source[line_start..start].chars().count() + 1;
+
+        // keep the tabs of the line so the carets are aligned
+        let padding = source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let carets = "^".repeat(source[start..end.min(line_end)].chars().count().max(1));
+        let gutter = " ".repeat(line.to_string().len());
+
+        Some(format!(
+            "{gutter}--> {}:{line}:{column}\n{gutter} |\n{line} | {text}\n{gutter} | {padding}{carets}",
+            location.file
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
 pub enum Severity {
     #[default]
@@ -2250,6 +2319,30 @@ mod tests {
         pretty_assertions::assert_eq!(input, &serde_json::to_string(&layout).unwrap());
     }
 
+    #[test]
+    fn can_render_source_snippet() {
+        let source = "contract A {\n\tfunction f() {}\n}\n";
+        let mut err = Error {
+            source_location: Some(SourceLocation { file: "A.sol".to_string(), start: 14, end: 22 }),
+            secondary_source_locations: vec![],
+            r#type: "SyntaxError".to_string(),
+            component: "general".to_string(),
+            severity: Severity::Error,
+            error_code: None,
+            message: "No visibility specified".to_string(),
+            formatted_message: None,
+        };
+        assert_eq!(
+            err.source_snippet(source).unwrap(),
+            " --> A.sol:2:2\n  |\n2 | \tfunction f() {}\n  | \t^^^^^^^^"
+        );
+
+        err.source_location.as_mut().unwrap().end = 100;
+        assert!(err.source_snippet(source).is_none());
+        err.source_location = None;
+        assert!(err.source_snippet(source).is_none());
+    }
+
     // <https://github.com/foundry-rs/foundry/issues/3012>
     #[test]
     fn can_parse_compiler_output_spells_0_6_12() {
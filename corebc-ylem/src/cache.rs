@@ -5,8 +5,8 @@ use crate::{
     error::{Result, YlemError},
     filter::{FilteredSource, FilteredSourceInfo, FilteredSources},
     resolver::GraphEdges,
-    utils, ArtifactFile, ArtifactOutput, Artifacts, ArtifactsMap, OutputContext, Project,
-    ProjectPathsConfig, Source,
+    utils, ArtifactFile, ArtifactOutput, ArtifactProfile, Artifacts, ArtifactsMap, OutputContext,
+    Project, ProjectPathsConfig, Source,
 };
 use semver::Version;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -413,6 +413,9 @@ pub struct CacheEntry {
     pub source_name: PathBuf,
     /// what config was set when compiling this file
     pub ylem_config: YlemConfig,
+    /// the profile of the artifacts emitted for this file
+    #[serde(default, skip_serializing_if = "ArtifactProfile::is_full")]
+    pub artifact_profile: ArtifactProfile,
     /// fully resolved imports of the file
     ///
     /// all paths start relative from the project's root: `src/importedFile.sol`
@@ -624,6 +627,7 @@ impl<'a, T: ArtifactOutput> ArtifactsCacheInner<'a, T> {
             content_hash: source.content_hash(),
             source_name: utils::source_name(file, self.project.root()).into(),
             ylem_config: self.project.ylem_config.clone(),
+            artifact_profile: self.project.artifacts.profile(),
             imports,
             version_requirement: self.edges.version_requirement(file).map(|v| v.to_string()),
             // artifacts remain empty until we received the compiler output
@@ -742,6 +746,13 @@ impl<'a, T: ArtifactOutput> ArtifactsCacheInner<'a, T> {
                     tracing::trace!("changed ylem config for source file \"{}\"", file.display());
                     return true
                 }
+                if self.project.artifacts.profile() != entry.artifact_profile {
+                    tracing::trace!(
+                        "changed artifact profile for source file \"{}\"",
+                        file.display()
+                    );
+                    return true
+                }
 
                 // only check artifact's existence if the file generated artifacts.
                 // e.g. a solidity file consisting only of import statements (like interfaces that
@@ -932,12 +943,27 @@ impl<'a, T: ArtifactOutput> ArtifactsCache<'a, T> {
                     mut cached_artifacts,
                     mut dirty_source_files,
                     filtered,
+                    edges,
                     project,
                     ..
                 } = cache;
 
+                // files outside of the compiled dependency graph were not part of this run, e.g.
+                // when only a single file and its imports were compiled, so their entries are
+                // still valid
+                let untouched = cache
+                    .files
+                    .iter()
+                    .filter(|(file, _)| !edges.contains(file))
+                    .map(|(file, entry)| (file.clone(), entry.clone()))
+                    .collect::<Vec<_>>();
+
                 // keep only those files that were previously filtered (not dirty, reused)
                 cache.retain(filtered.iter().map(|(p, (_, v))| (p.as_path(), v)));
+                cache.extend(untouched);
+
+                // only return the cached artifacts of the files that were part of the graph
+                cached_artifacts.0.retain(|file, _| edges.contains(file));
 
                 // add the written artifacts to the cache entries, this way we can keep a mapping
                 // from solidity file to its artifacts
@@ -0,0 +1,132 @@
+//! Where the ylem processes of a [`Project`](crate::Project) run
+
+use crate::Ylem;
+use semver::Version;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The docker executable used by default
+pub const DOCKER: &str = "docker";
+
+/// How a [`Project`](crate::Project) runs the ylem versions it detected for its sources, see
+/// [`ProjectBuilder::compiler_backend`](crate::ProjectBuilder::compiler_backend)
+///
+/// A [`Project`](crate::Project) with auto detection disabled always runs its configured
+/// [`Ylem`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CompilerBackend {
+    /// Runs the ylem binaries installed on the host, missing versions are installed with yvm
+    #[default]
+    Native,
+    /// Runs ylem in docker containers, nothing is installed on the host
+    Docker(DockerBackend),
+}
+
+impl CompilerBackend {
+    /// Whether ylem runs in docker containers
+    pub fn is_docker(&self) -> bool {
+        matches!(self, CompilerBackend::Docker(_))
+    }
+}
+
+impl From<DockerBackend> for CompilerBackend {
+    fn from(docker: DockerBackend) -> Self {
+        CompilerBackend::Docker(docker)
+    }
+}
+
+/// Runs ylem in a container of an image whose entrypoint is ylem, for builds that do not depend on
+/// the binaries on the host.
+///
+/// The container of version `x.y.z` is created from `<image>:x.y.z`, or from `<image>@<digest>`
+/// if a digest was [pinned](Self::pin) for the version, which makes the build byte-reproducible.
+/// Containers have no network access and the project's root, allowed and include paths are
+/// mounted read-only at the same paths as on the host.
+///
+/// # Example
+///
+/// ```
+/// use corebc_ylem::{DockerBackend, Project};
+///
+/// let docker = DockerBackend::new("example/ylem").pin(
+///     "1.0.1".parse().unwrap(),
+///     "sha256:2a1c5d1a2f8e1f0dd7cd7bc4d4f3b8ef5d5e7cbcc22d6e5a2c1f08fa30bb8f51",
+/// );
+/// let project = Project::builder().compiler_backend(docker).build().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DockerBackend {
+    /// The docker executable
+    pub docker: PathBuf,
+    /// The name of the image, without tag
+    pub image: String,
+    /// The digests of the images of pinned ylem versions
+    pub digests: BTreeMap<Version, String>,
+}
+
+impl DockerBackend {
+    /// Runs ylem in containers of `image`
+    pub fn new(image: impl Into<String>) -> Self {
+        Self { docker: DOCKER.into(), image: image.into(), digests: Default::default() }
+    }
+
+    /// Sets the docker executable, e.g. `podman`
+    #[must_use]
+    pub fn docker(mut self, docker: impl Into<PathBuf>) -> Self {
+        self.docker = docker.into();
+        self
+    }
+
+    /// Creates the containers of ylem `version` from the image with `digest`
+    #[must_use]
+    pub fn pin(mut self, version: Version, digest: impl Into<String>) -> Self {
+        self.digests.insert(version, digest.into());
+        self
+    }
+
+    /// Returns the image reference of ylem `version`
+    pub fn image_ref(&self, version: &Version) -> String {
+        match self.digests.get(version) {
+            Some(digest) => format!("{}@{}", self.image, digest),
+            None => format!("{}:{}", self.image, version),
+        }
+    }
+
+    /// Returns a [`Ylem`] that runs ylem `version` in a container with the `mounts`
+    ///
+    /// Arguments added to the returned [`Ylem`] are passed to ylem.
+    pub fn ylem<'a>(&self, version: &Version, mounts: impl IntoIterator<Item = &'a Path>) -> Ylem {
+        let mut ylem = Ylem::new(&self.docker).args(["run", "--rm", "-i", "--network", "none"]);
+        for path in mounts {
+            ylem = ylem.arg("-v").arg(format!("{0}:{0}:ro", path.display()));
+        }
+        ylem.arg(self.image_ref(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_ylem_in_docker() {
+        let version = Version::new(1, 0, 1);
+        let docker = DockerBackend::new("example/ylem");
+        assert_eq!(docker.image_ref(&version), "example/ylem:1.0.1");
+
+        let docker = docker.docker("podman").pin(version.clone(), "sha256:00");
+        assert_eq!(docker.image_ref(&version), "example/ylem@sha256:00");
+        assert_eq!(docker.image_ref(&Version::new(1, 0, 0)), "example/ylem:1.0.0");
+
+        let ylem = docker.ylem(&version, [Path::new("/project"), Path::new("/libs")]);
+        assert_eq!(
+            ylem.to_string(),
+            "podman run --rm -i --network none -v /project:/project:ro -v /libs:/libs:ro \
+             example/ylem@sha256:00"
+        );
+        assert!(CompilerBackend::from(docker).is_docker());
+        assert!(!CompilerBackend::default().is_docker());
+    }
+}
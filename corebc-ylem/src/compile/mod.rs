@@ -12,9 +12,11 @@ use std::{
     process::{Command, Output, Stdio},
     str::FromStr,
 };
+pub mod backend;
+pub use backend::{CompilerBackend, DockerBackend};
 pub mod many;
 pub mod output;
-pub use output::{contracts, info, sources};
+pub use output::{contracts, diagnostics, info, size, sources};
 pub mod project;
 
 /// The name of the `ylem` binary on the system
@@ -65,6 +67,73 @@ pub static RELEASES: once_cell::sync::Lazy<(yvm::Releases, Vec<Version>, bool)>
         }
     });
 
+/// Configures how [yvm](https://github.com/roynalnaruto/yvm-rs) installs missing `ylem` versions.
+///
+/// The initial configuration is read from the environment, see [`YvmConfig::from_env()`], and can
+/// be changed with [`Ylem::set_yvm_config()`].
+#[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct YvmConfig {
+    /// Only use installed versions, installing a missing version is an error
+    pub offline: bool,
+    /// Base URL to download `ylem` binaries from instead of the default release server.
+    ///
+    /// The mirror is expected to have the same layout as the local yvm directory, the binary of
+    /// version `1.1.2` is downloaded from `<mirror>/1.1.2/ylem-1.1.2`.
+    pub mirror: Option<String>,
+    /// Accept binaries whose checksum can't be verified because the release list could not be
+    /// read, instead of failing with [`YlemError::ReleasesUnavailable`]
+    pub allow_unverified: bool,
+}
+
+#[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+impl YvmConfig {
+    /// Reads the configuration from the `YLEM_OFFLINE`, `YLEM_MIRROR_URL` and
+    /// `YLEM_ALLOW_UNVERIFIED` environment variables
+    ///
+    /// The flags are enabled if their variable is set to anything but `false` or `0`.
+    pub fn from_env() -> Self {
+        let flag = |name: &str| {
+            std::env::var(name)
+                .map(|val| !matches!(val.trim(), "" | "0" | "false"))
+                .unwrap_or_default()
+        };
+        let mirror = std::env::var("YLEM_MIRROR_URL").ok().filter(|url| !url.trim().is_empty());
+        Self {
+            offline: flag("YLEM_OFFLINE"),
+            mirror,
+            allow_unverified: flag("YLEM_ALLOW_UNVERIFIED"),
+        }
+    }
+
+    /// Returns the URL the binary of `version` is downloaded from, if a mirror is configured
+    pub fn mirror_url(&self, version: &Version) -> Option<String> {
+        self.mirror
+            .as_ref()
+            .map(|mirror| format!("{}/{version}/ylem-{version}", mirror.trim_end_matches('/')))
+    }
+
+    /// Called when the checksum of `version` can't be verified, fails unless unverified binaries
+    /// are allowed
+    fn ensure_unverified_allowed(&self, version: &Version) -> Result<()> {
+        if !self.allow_unverified {
+            return Err(YlemError::ReleasesUnavailable(version.clone()))
+        }
+        tracing::warn!(target: "ylem", "the release list could not be read, skipping checksum verification for {}", version);
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+static YVM_CONFIG: once_cell::sync::Lazy<std::sync::RwLock<YvmConfig>> =
+    once_cell::sync::Lazy::new(|| std::sync::RwLock::new(YvmConfig::from_env()));
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+thread_local! {
+    /// Replaces the yvm home of the tests running on this thread, so they don't touch `~/.yvm`
+    static TEST_YVM_HOME: std::cell::RefCell<Option<PathBuf>> = Default::default();
+}
+
 /// A `Ylem` version is either installed (available locally) or can be downloaded, from the remote
 /// endpoint
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
@@ -190,9 +259,35 @@ impl Ylem {
     /// This will be `~/.yvm` on unix
     #[cfg(not(target_arch = "wasm32"))]
     pub fn yvm_home() -> Option<PathBuf> {
+        #[cfg(test)]
+        if let Some(home) = TEST_YVM_HOME.with(|home| home.borrow().clone()) {
+            return Some(home)
+        }
         home::home_dir().map(|dir| dir.join(".yvm"))
     }
 
+    /// Returns the current [`YvmConfig`] used to install missing versions
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    pub fn yvm_config() -> YvmConfig {
+        YVM_CONFIG.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Sets the [`YvmConfig`] used to install missing versions, for the whole process
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_ylem::{Ylem, YvmConfig};
+    /// Ylem::set_yvm_config(YvmConfig {
+    ///     mirror: Some("https://mirror.example.com/ylem".to_string()),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    pub fn set_yvm_config(config: YvmConfig) {
+        *YVM_CONFIG.write().unwrap_or_else(|err| err.into_inner()) = config;
+    }
+
     /// Returns the `semver::Version` [yvm](https://github.com/roynalnaruto/yvm-rs)'s `.global_version` is currently set to.
     ///  `global_version` is configured with (`yvm use <version>`)
     ///
@@ -291,6 +386,61 @@ impl Ylem {
         }
     }
 
+    /// Removes the [yvm](https://github.com/roynalnaruto/yvm-rs) installation of `version`.
+    ///
+    /// Does nothing if the version is not installed. If the version is yvm's global version, the
+    /// global version is unset as well.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn uninstall(version: &Version) -> Result<()> {
+        let home = Self::yvm_home().ok_or_else(|| YlemError::ylem("yvm home dir not found"))?;
+        let dir = home.join(version.to_string());
+        if dir.is_dir() {
+            tracing::trace!("uninstalling ylem version \"{}\"", version);
+            std::fs::remove_dir_all(&dir).map_err(|err| YlemError::io(err, &dir))?;
+        }
+        if Self::yvm_global_version().as_ref() == Some(version) {
+            let global = home.join(".global_version");
+            std::fs::remove_file(&global).map_err(|err| YlemError::io(err, &global))?;
+        }
+        Ok(())
+    }
+
+    /// Sets yvm's global version, see [`Self::yvm_global_version()`], to the installed `version`
+    ///
+    /// Fails if the version is not installed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_global_version(version: &Version) -> Result<()> {
+        if Self::find_yvm_installed_version(version.to_string())?.is_none() {
+            return Err(YlemError::ylem(format!("ylem {version} is not installed")))
+        }
+        let global = Self::yvm_home()
+            .ok_or_else(|| YlemError::ylem("yvm home dir not found"))?
+            .join(".global_version");
+        std::fs::write(&global, version.to_string()).map_err(|err| YlemError::io(err, &global))
+    }
+
+    /// Returns the yvm installation of exactly `version` and makes sure the binary's checksum
+    /// matches the published one.
+    ///
+    /// The version is installed if it is missing, and installed again if the checksum of the
+    /// installed binary does not match.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "yvm-ylem"))]
+    pub fn ensure_exact(version: &Version) -> Result<Self> {
+        if let Some(ylem) = Self::find_yvm_installed_version(version.to_string())? {
+            match Self::verify_binary_checksum(version, &ylem.ylem) {
+                Ok(()) => return Ok(ylem),
+                Err(YlemError::ChecksumMismatch { .. }) => {
+                    tracing::warn!(target: "ylem", "reinstalling ylem {} with a corrupted binary", version);
+                    Self::uninstall(version)?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        let ylem = Self::blocking_install(version)?;
+        Self::verify_binary_checksum(version, &ylem.ylem)?;
+        Ok(ylem)
+    }
+
     /// Assuming the `versions` array is sorted, it returns the first element which satisfies
     /// the provided [`VersionReq`]
     pub fn find_matching_installation(
@@ -326,11 +476,12 @@ impl Ylem {
 
         let local_versions = Self::find_matching_installation(&versions, sol_version);
         let remote_versions = Self::find_matching_installation(&RELEASES.1, sol_version);
+        let offline = Self::yvm_config().offline;
         // if there's a better upstream version than the one we have, install it
         Ok(match (local_versions, remote_versions) {
             (Some(local), None) => local,
             (Some(local), Some(remote)) => {
-                if remote > local {
+                if remote > local && !offline {
                     Self::blocking_install(&remote)?;
                     remote
                 } else {
@@ -373,6 +524,11 @@ impl Ylem {
     /// Installs the provided version of Ylem in the machine under the yvm dir and returns the
     /// [Ylem] instance pointing to the installation.
     ///
+    /// The binary is downloaded from the mirror of the [`YvmConfig`] if one is configured. Fails
+    /// with [`YlemError::OfflineInstall`] in offline mode.
+    ///
+    /// Download failures of `yvm` are returned as [`YlemError::YvmError`].
+    ///
     /// # Example
     /// ```no_run
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
@@ -382,30 +538,17 @@ impl Ylem {
     /// # }
     /// ```
     #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
-    pub async fn install(version: &Version) -> std::result::Result<Self, yvm::YlemVmError> {
-        tracing::trace!("installing ylem version \"{}\"", version);
-        crate::report::ylem_installation_start(version);
-        let result = yvm::install(version).await;
-        crate::report::ylem_installation_success(version);
-        result.map(Ylem::new)
-    }
-
-    /// Blocking version of `Self::install`
-    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
-    pub fn blocking_install(version: &Version) -> std::result::Result<Self, yvm::YlemVmError> {
-        use crate::utils::RuntimeOrHandle;
+    pub async fn install(version: &Version) -> Result<Self> {
+        let config = Self::yvm_config();
+        if config.offline {
+            return Err(YlemError::OfflineInstall(version.clone()))
+        }
 
-        tracing::trace!("blocking installing ylem version \"{}\"", version);
+        tracing::trace!("installing ylem version \"{}\"", version);
         crate::report::ylem_installation_start(version);
-        // the async version `yvm::install` is used instead of `yvm::blocking_intsall`
-        // because the underlying `reqwest::blocking::Client` does not behave well
-        // in tokio rt. see https://github.com/seanmonstar/reqwest/issues/1017
-        cfg_if::cfg_if! {
-            if #[cfg(target_arch = "wasm32")] {
-                let installation = yvm::blocking_install(version);
-            } else {
-                let installation = RuntimeOrHandle::new().block_on(yvm::install(version));
-            }
+        let installation = match config.mirror_url(version) {
+            Some(url) => Self::install_from_url(version, &url).await,
+            None => yvm::install(version).await.map_err(YlemError::from),
         };
         match installation {
             Ok(path) => {
@@ -419,37 +562,115 @@ impl Ylem {
         }
     }
 
+    /// Blocking version of `Self::install`
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    pub fn blocking_install(version: &Version) -> Result<Self> {
+        use crate::utils::RuntimeOrHandle;
+
+        tracing::trace!("blocking installing ylem version \"{}\"", version);
+        // the async version `yvm::install` is used instead of `yvm::blocking_intsall`
+        // because the underlying `reqwest::blocking::Client` does not behave well
+        // in tokio rt. see https://github.com/seanmonstar/reqwest/issues/1017
+        RuntimeOrHandle::new().block_on(Self::install(version))
+    }
+
+    /// Downloads the binary of `version` from `url` into the yvm dir and verifies its checksum
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    async fn install_from_url(version: &Version, url: &str) -> Result<PathBuf> {
+        let download_err =
+            |err: reqwest::Error| YlemError::Download(url.to_string(), err.to_string());
+        let mut res =
+            reqwest::get(url).await.and_then(|res| res.error_for_status()).map_err(download_err)?;
+        let total = res.content_length();
+        let mut binary = Vec::with_capacity(total.unwrap_or_default() as usize);
+        while let Some(chunk) = res.chunk().await.map_err(download_err)? {
+            binary.extend_from_slice(&chunk);
+            crate::report::ylem_installation_progress(version, binary.len() as u64, total);
+        }
+
+        let dir = yvm::version_path(version.to_string().as_str());
+        let path = dir.join(format!("ylem-{version}"));
+        // verify the download before anything lands in the yvm dir
+        Self::check_sha256(version, &binary, &path)?;
+
+        std::fs::create_dir_all(&dir).map_err(|err| YlemError::io(err, &dir))?;
+        // write to a temporary file first so that a concurrent or interrupted install never
+        // leaves a partial binary at `path`
+        let tmp = dir.join(format!(".ylem-{version}.{}.tmp", std::process::id()));
+        if let Err(err) = Self::write_binary(&tmp, &binary) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(err)
+        }
+        std::fs::rename(&tmp, &path).map_err(|err| {
+            let _ = std::fs::remove_file(&tmp);
+            YlemError::io(err, &path)
+        })?;
+        Ok(path)
+    }
+
+    /// Writes `binary` to `path` and makes it executable
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    fn write_binary(path: &Path, binary: &[u8]) -> Result<()> {
+        std::fs::write(path, binary).map_err(|err| YlemError::io(err, path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+                .map_err(|err| YlemError::io(err, path))?;
+        }
+        Ok(())
+    }
+
     /// Verify that the checksum for this version of ylem is correct. We check against the SHA256
     /// checksum from the build information published by [binaries.soliditylang.org](https://binaries.soliditylang.org/)
+    ///
+    /// Returns [`YlemError::ChecksumNotFound`] if the release list has no checksum for the version,
+    /// and [`YlemError::ReleasesUnavailable`] if the release list could not be read.
     #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
     pub fn verify_checksum(&self) -> Result<()> {
         let version = self.version_short()?;
         let mut version_path = yvm::version_path(version.to_string().as_str());
         version_path.push(format!("ylem-{}", version.to_string().as_str()));
         tracing::trace!(target:"ylem", "reading ylem binary for checksum {:?}", version_path);
-        let content =
-            std::fs::read(&version_path).map_err(|err| YlemError::io(err, version_path.clone()))?;
+        Self::verify_binary_checksum(&version, &version_path)
+    }
 
+    /// Compares the SHA256 checksum of the binary at `path` with the one published for `version`
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    fn verify_binary_checksum(version: &Version, path: &Path) -> Result<()> {
+        let content = std::fs::read(path).map_err(|err| YlemError::io(err, path))?;
+        Self::check_sha256(version, &content, path)
+    }
+
+    /// Compares the SHA256 checksum of the `content` of the binary at `file` with the one
+    /// published for `version`
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    fn check_sha256(version: &Version, content: &[u8], file: &Path) -> Result<()> {
         if !RELEASES.2 {
-            // we skip checksum verification because the underlying request to fetch release info
-            // failed so we have nothing to compare against
-            return Ok(())
+            return Self::yvm_config().ensure_unverified_allowed(version)
         }
+        let checksum_found = RELEASES
+            .0
+            .get_checksum(version)
+            .ok_or_else(|| YlemError::ChecksumNotFound(version.clone()))?;
 
         use sha2::Digest;
         let mut hasher = sha2::Sha256::new();
         hasher.update(content);
         let checksum_calc = &hasher.finalize()[..];
 
-        let checksum_found = &RELEASES.0.get_checksum(&version).expect("checksum not found");
-
-        if checksum_calc == checksum_found {
+        if checksum_calc == &checksum_found[..] {
             Ok(())
         } else {
             let expected = hex::encode(checksum_found);
             let detected = hex::encode(checksum_calc);
-            tracing:: warn!(target : "ylem", "checksum mismatch for {:?}, expected {}, but found {} for file {:?}", version, expected, detected, version_path);
-            Err(YlemError::ChecksumMismatch { version, expected, detected, file: version_path })
+            tracing:: warn!(target : "ylem", "checksum mismatch for {:?}, expected {}, but found {} for file {:?}", version, expected, detected, file);
+            Err(YlemError::ChecksumMismatch {
+                version: version.clone(),
+                expected,
+                detected,
+                file: file.to_path_buf(),
+            })
         }
     }
 
@@ -490,6 +711,8 @@ impl Ylem {
     /// Run `ylem --stand-json` and return the `ylem`'s output as
     /// `CompilerOutput`
     ///
+    /// Anything `ylem` printed to stderr is kept in [`CompilerOutput::raw_warnings`].
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -502,7 +725,7 @@ impl Ylem {
     /// # }
     /// ```
     pub fn compile<T: Serialize>(&self, input: &T) -> Result<CompilerOutput> {
-        self.compile_as(input)
+        compiler_output(self.run_compile(input)?)
     }
 
     /// Run `ylem --standart-json` and return the `ylem`'s output as the given json
@@ -513,7 +736,13 @@ impl Ylem {
     }
 
     pub fn compile_output<T: Serialize>(&self, input: &T) -> Result<Vec<u8>> {
+        compile_output(self.run_compile(input)?)
+    }
+
+    /// Runs `ylem --standard-json` with the `input` and waits for it to exit
+    fn run_compile<T: Serialize>(&self, input: &T) -> Result<Output> {
         let mut cmd = Command::new(&self.ylem);
+        cmd.args(&self.args);
 
         if let Some(ref base_path) = self.base_path {
             cmd.current_dir(base_path);
@@ -531,8 +760,7 @@ impl Ylem {
         let stdin = child.stdin.take().expect("Stdin exists.");
 
         serde_json::to_writer(stdin, input)?;
-        let output = child.wait_with_output().map_err(|err| YlemError::io(err, &self.ylem))?;
-        compile_output(output)
+        child.wait_with_output().map_err(|err| YlemError::io(err, &self.ylem))
     }
 
     pub fn version_short(&self) -> Result<Version> {
@@ -565,7 +793,7 @@ impl Ylem {
     /// Run `ylem --stand-json` and return the `ylem`'s output as
     /// `CompilerOutput`
     pub async fn async_compile<T: Serialize>(&self, input: &T) -> Result<CompilerOutput> {
-        self.async_compile_as(input).await
+        compiler_output(self.async_run_compile(input).await?)
     }
 
     /// Run `ylem --stand-json` and return the `ylem`'s output as the given json
@@ -579,6 +807,11 @@ impl Ylem {
     }
 
     pub async fn async_compile_output<T: Serialize>(&self, input: &T) -> Result<Vec<u8>> {
+        compile_output(self.async_run_compile(input).await?)
+    }
+
+    /// Runs `ylem --standard-json` with the `input` and waits for it to exit
+    async fn async_run_compile<T: Serialize>(&self, input: &T) -> Result<Output> {
         use tokio::io::AsyncWriteExt;
         let content = serde_json::to_vec(input)?;
         let mut cmd = tokio::process::Command::new(&self.ylem);
@@ -596,9 +829,7 @@ impl Ylem {
         let stdin = child.stdin.as_mut().unwrap();
         stdin.write_all(&content).await.map_err(|err| YlemError::io(err, &self.ylem))?;
         stdin.flush().await.map_err(|err| YlemError::io(err, &self.ylem))?;
-        compile_output(
-            child.wait_with_output().await.map_err(|err| YlemError::io(err, &self.ylem))?,
-        )
+        child.wait_with_output().await.map_err(|err| YlemError::io(err, &self.ylem))
     }
 
     pub async fn async_version(&self) -> Result<Version> {
@@ -663,6 +894,17 @@ fn compile_output(output: Output) -> Result<Vec<u8>> {
     }
 }
 
+/// Parses the `CompilerOutput` of a successful ylem run and keeps what ylem printed to stderr,
+/// which can contain warnings that are not part of the json output
+fn compiler_output(output: Output) -> Result<CompilerOutput> {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let mut output: CompilerOutput = serde_json::from_slice(&compile_output(output)?)?;
+    if !stderr.is_empty() {
+        output.raw_warnings.push(stderr);
+    }
+    Ok(output)
+}
+
 fn version_from_output(output: Output) -> Result<Version> {
     if output.status.success() {
         let version = output
@@ -705,6 +947,26 @@ mod tests {
         ylem().version().unwrap();
     }
 
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn manages_missing_installations() {
+        let home = tempfile::tempdir().unwrap();
+        TEST_YVM_HOME.with(|test_home| *test_home.borrow_mut() = Some(home.path().to_path_buf()));
+
+        let version = Version::new(99, 99, 99);
+        let dir = home.path().join(version.to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("ylem-{version}")), "").unwrap();
+        Ylem::set_global_version(&version).unwrap();
+        assert_eq!(Ylem::yvm_global_version(), Some(version.clone()));
+
+        Ylem::uninstall(&version).unwrap();
+        assert!(!dir.exists());
+        assert_eq!(Ylem::yvm_global_version(), None);
+        let err = Ylem::set_global_version(&version).unwrap_err();
+        assert!(err.to_string().contains("99.99.99 is not installed"), "{err}");
+    }
+
     #[test]
     fn can_parse_version_metadata() {
         let _version = Version::from_str("0.6.6+commit.6c089d02.Linux.gcc").unwrap();
@@ -836,6 +1098,33 @@ mod tests {
         assert!(res.is_none());
     }
 
+    #[test]
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    fn can_build_mirror_url() {
+        let version = Version::new(1, 1, 2);
+        assert_eq!(YvmConfig::default().mirror_url(&version), None);
+
+        let config = YvmConfig {
+            mirror: Some("https://mirror.example.com/ylem/".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.mirror_url(&version).unwrap(),
+            "https://mirror.example.com/ylem/1.1.2/ylem-1.1.2"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
+    fn fails_closed_without_release_list() {
+        let version = Version::new(1, 1, 2);
+        let err = YvmConfig::default().ensure_unverified_allowed(&version).unwrap_err();
+        assert!(matches!(err, YlemError::ReleasesUnavailable(v) if v == version));
+
+        let config = YvmConfig { allow_unverified: true, ..Default::default() };
+        config.ensure_unverified_allowed(&version).unwrap();
+    }
+
     ///// helpers
 
     fn source(version: &str) -> Source {
@@ -0,0 +1,231 @@
+//! Machine-readable compiler diagnostics
+
+use crate::artifacts::{Error, Severity};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+/// A compiler error or warning, classified according to the project's settings
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// The error as reported by ylem
+    #[serde(flatten)]
+    pub error: Error,
+    /// Whether the error code is one of the project's `ignored_error_codes`
+    pub ignored: bool,
+    /// Whether this diagnostic fails the build with the project's severity settings
+    pub is_failure: bool,
+    /// The excerpt of the source file the diagnostic points to, see [`Error::source_snippet()`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl Diagnostic {
+    /// Classifies the `error` with the given settings
+    pub fn new(
+        error: Error,
+        ignored_error_codes: &[u64],
+        compiler_severity_filter: &Severity,
+    ) -> Self {
+        // errors can't be ignored
+        let ignored = !error.severity.is_error() &&
+            error.error_code.map_or(false, |code| ignored_error_codes.contains(&code));
+        let is_failure = !ignored && compiler_severity_filter.ge(&error.severity);
+        Self { error, ignored, is_failure, snippet: None }
+    }
+
+    /// The error code of the diagnostic, if any
+    pub fn code(&self) -> Option<u64> {
+        self.error.error_code
+    }
+
+    /// The severity of the diagnostic
+    pub fn severity(&self) -> &Severity {
+        &self.error.severity
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)?;
+        // the formatted message of ylem already contains the source excerpt
+        if let (None, Some(snippet)) = (&self.error.formatted_message, &self.snippet) {
+            writeln!(f, "{snippet}")?;
+        }
+        Ok(())
+    }
+}
+
+/// All the diagnostics of a compiler run.
+///
+/// This can be serialized to JSON, or queried by error code, for example to fail a CI build on
+/// specific classes of warnings:
+///
+/// ```no_run
+/// use corebc_ylem::Project;
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let project = Project::builder().build()?;
+/// let diagnostics = project.compile()?.diagnostics();
+/// // unreachable code and shadowing
+/// if diagnostics.has_any_code(&[5740, 2519]) {
+///     eprintln!("{}", serde_json::to_string_pretty(&diagnostics)?);
+///     std::process::exit(1);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl Diagnostics {
+    /// Classifies all `errors` with the given settings
+    pub fn new<'a>(
+        errors: impl IntoIterator<Item = &'a Error>,
+        ignored_error_codes: &[u64],
+        compiler_severity_filter: &Severity,
+    ) -> Self {
+        Self(
+            errors
+                .into_iter()
+                .map(|err| {
+                    Diagnostic::new(err.clone(), ignored_error_codes, compiler_severity_filter)
+                })
+                .collect(),
+        )
+    }
+
+    /// Renders the source excerpt of every diagnostic that has a source location.
+    ///
+    /// Relative source locations are resolved against `root`, files that can't be read are
+    /// skipped.
+    #[must_use]
+    pub fn with_snippets(mut self, root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        let mut sources = BTreeMap::new();
+        for diagnostic in self.0.iter_mut() {
+            let file = match &diagnostic.error.source_location {
+                Some(location) => location.file.clone(),
+                None => continue,
+            };
+            let source = sources
+                .entry(file)
+                .or_insert_with_key(|file| fs::read_to_string(root.join(file)).ok());
+            if let Some(source) = source {
+                diagnostic.snippet = diagnostic.error.source_snippet(source);
+            }
+        }
+        self
+    }
+
+    /// Returns an iterator over all diagnostics
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0.iter()
+    }
+
+    /// Returns the number of diagnostics
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no diagnostics
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns all diagnostics that fail the build
+    pub fn failures(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.iter().filter(|d| d.is_failure)
+    }
+
+    /// Returns all warnings that are not ignored
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.iter().filter(|d| d.severity().is_warning() && !d.ignored)
+    }
+
+    /// Returns all diagnostics with the given error code, including ignored ones
+    pub fn with_code(&self, code: u64) -> impl Iterator<Item = &Diagnostic> {
+        self.iter().filter(move |d| d.code() == Some(code))
+    }
+
+    /// Whether any diagnostic has one of the given error codes
+    pub fn has_any_code(&self, codes: &[u64]) -> bool {
+        self.iter().any(|d| d.code().map_or(false, |code| codes.contains(&code)))
+    }
+
+    /// Whether any diagnostic fails the build
+    pub fn is_failure(&self) -> bool {
+        self.failures().next().is_some()
+    }
+
+    /// Returns the number of diagnostics for each error code
+    pub fn count_by_code(&self) -> BTreeMap<u64, usize> {
+        let mut counts = BTreeMap::new();
+        for code in self.iter().filter_map(Diagnostic::code) {
+            *counts.entry(code).or_default() += 1;
+        }
+        counts
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in self.iter().filter(|d| !d.ignored) {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::SourceLocation;
+
+    fn error(severity: Severity, code: u64) -> Error {
+        Error {
+            source_location: Some(SourceLocation { file: "A.sol".to_string(), start: 30, end: 40 }),
+            secondary_source_locations: vec![],
+            r#type: "Warning".to_string(),
+            component: "general".to_string(),
+            severity,
+            error_code: Some(code),
+            message: "unreachable code".to_string(),
+            formatted_message: None,
+        }
+    }
+
+    #[test]
+    fn classifies_diagnostics() {
+        let errors = [
+            error(Severity::Error, 7576),
+            error(Severity::Warning, 5740),
+            error(Severity::Warning, 1878),
+        ];
+
+        let diagnostics = Diagnostics::new(&errors, &[1878], &Severity::Error);
+        assert_eq!(diagnostics.failures().count(), 1);
+        assert_eq!(diagnostics.warnings().map(Diagnostic::code).collect::<Vec<_>>(), [Some(5740)]);
+        assert!(diagnostics.with_code(1878).all(|d| d.ignored));
+
+        // deny warnings
+        let diagnostics = Diagnostics::new(&errors[1..], &[1878], &Severity::Warning);
+        assert!(diagnostics.is_failure());
+        assert!(diagnostics.has_any_code(&[5740, 2519]));
+        assert_eq!(diagnostics.count_by_code(), BTreeMap::from([(1878, 1), (5740, 1)]));
+
+        let json = serde_json::to_value(&diagnostics).unwrap();
+        assert_eq!(json[0]["errorCode"], "5740");
+        assert_eq!(json[0]["isFailure"], true);
+        assert_eq!(json[1]["ignored"], true);
+    }
+}
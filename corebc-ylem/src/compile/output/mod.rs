@@ -11,12 +11,21 @@ use crate::{
     ArtifactId, ArtifactOutput, Artifacts, CompilerOutput, ConfigurableArtifacts, YlemIoError,
 };
 use contracts::{VersionedContract, VersionedContracts};
+use corebc_core::types::Network;
+use diagnostics::Diagnostics;
 use semver::Version;
-use std::{collections::BTreeMap, fmt, path::Path};
+use size::{ContractSize, SizeReport};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
 use tracing::trace;
 
 pub mod contracts;
+pub mod diagnostics;
 pub mod info;
+pub mod size;
 pub mod sources;
 
 /// Contains a mixture of already compiled/cached artifacts and the input set of sources that still
@@ -33,6 +42,12 @@ pub struct ProjectCompileOutput<T: ArtifactOutput = ConfigurableArtifacts> {
     pub(crate) ignored_error_codes: Vec<u64>,
     /// set minimum level of severity that is treated as an error
     pub(crate) compiler_severity_filter: Severity,
+    /// the code size limit contracts are checked against, the one of mainnet if not set
+    pub(crate) code_size_limit: Option<usize>,
+    /// whether contracts exceeding the code size limit are treated as errors
+    pub(crate) deny_oversized_contracts: bool,
+    /// the root of the project, the source locations of errors are resolved against it
+    pub(crate) root: PathBuf,
 }
 
 impl<T: ArtifactOutput> ProjectCompileOutput<T> {
@@ -198,8 +213,12 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T> {
     }
 
     /// Whether there were errors
+    ///
+    /// If the project denies oversized contracts, contracts exceeding the code size limit are
+    /// errors as well, see [`Self::size_report()`]
     pub fn has_compiler_errors(&self) -> bool {
-        self.compiler_output.has_error(&self.ignored_error_codes, &self.compiler_severity_filter)
+        self.compiler_output.has_error(&self.ignored_error_codes, &self.compiler_severity_filter) ||
+            (self.deny_oversized_contracts && self.size_report().has_oversized())
     }
 
     /// Whether there were warnings
@@ -207,6 +226,49 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T> {
         self.compiler_output.has_warning(&self.ignored_error_codes)
     }
 
+    /// Returns all errors and warnings of the compiler output, classified according to the
+    /// project's `ignored_error_codes`, `compiler_severity_filter` and `deny_warnings` settings.
+    ///
+    /// The source excerpts of the diagnostics are read from the project's source files, see
+    /// [`Diagnostics::with_snippets()`].
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics::new(
+            &self.compiler_output.errors,
+            &self.ignored_error_codes,
+            &self.compiler_severity_filter,
+        )
+        .with_snippets(&self.root)
+    }
+
+    /// Returns the init and runtime code sizes of all cached and compiled contracts, checked
+    /// against the project's code size limit
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_ylem::Project;
+    ///
+    /// let project = Project::builder().build().unwrap();
+    /// let report = project.compile().unwrap().size_report();
+    /// for contract in report.oversized() {
+    ///     println!("{} is too large to deploy: {} bytes", contract.name, contract.runtime_size);
+    /// }
+    /// ```
+    pub fn size_report(&self) -> SizeReport {
+        let mut contracts = Vec::new();
+        for (file, artifacts) in self.cached_artifacts.iter().chain(self.compiled_artifacts.iter())
+        {
+            for (name, artifacts) in artifacts {
+                contracts.extend(artifacts.iter().filter_map(|artifact| {
+                    ContractSize::new(file, name, artifact.version.clone(), &artifact.artifact)
+                }));
+            }
+        }
+        contracts.sort_by(|a, b| (&a.file, &a.name).cmp(&(&b.file, &b.name)));
+        let limit = self.code_size_limit.unwrap_or_else(|| Network::Mainnet.max_code_size());
+        SizeReport { limit, contracts }
+    }
+
     /// Returns the set of `Artifacts` that were cached and got reused during
     /// [`crate::Project::compile()`]
     pub fn cached_artifacts(&self) -> &Artifacts<T::Artifact> {
@@ -398,12 +460,23 @@ impl ProjectCompileOutput<ConfigurableArtifacts> {
 impl<T: ArtifactOutput> fmt::Display for ProjectCompileOutput<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.compiler_output.is_unchanged() {
-            f.write_str("Nothing to compile")
+            f.write_str("Nothing to compile")?;
         } else {
             self.compiler_output
                 .diagnostics(&self.ignored_error_codes, self.compiler_severity_filter.clone())
-                .fmt(f)
+                .fmt(f)?;
         }
+        if self.deny_oversized_contracts {
+            let report = self.size_report();
+            for contract in report.oversized() {
+                writeln!(
+                    f,
+                    "\nError: {} ({}) exceeds the code size limit of {} bytes: {} bytes",
+                    contract.name, contract.file, report.limit, contract.runtime_size
+                )?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -420,6 +493,8 @@ pub struct AggregatedCompilerOutput {
     pub contracts: VersionedContracts,
     // All the `BuildInfo`s of ylem invocations.
     pub build_infos: BTreeMap<Version, RawBuildInfo>,
+    /// What ylem printed to stderr on success, see [`CompilerOutput::raw_warnings`]
+    pub raw_warnings: Vec<String>,
 }
 
 impl AggregatedCompilerOutput {
@@ -484,8 +559,9 @@ impl AggregatedCompilerOutput {
 
     /// adds a new `CompilerOutput` to the aggregated output
     pub fn extend(&mut self, version: Version, output: CompilerOutput) {
-        let CompilerOutput { errors, sources, contracts } = output;
+        let CompilerOutput { errors, sources, contracts, raw_warnings } = output;
         self.errors.extend(errors);
+        self.raw_warnings.extend(raw_warnings);
 
         for (path, source_file) in sources {
             let sources = self.sources.as_mut().entry(path).or_default();
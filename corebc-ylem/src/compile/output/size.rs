@@ -0,0 +1,136 @@
+//! Contract size analysis of the compile output
+
+use crate::{artifacts::BytecodeObject, Artifact};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The init and runtime code sizes of a compiled contract
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractSize {
+    /// The source file of the contract
+    pub file: String,
+    /// The name of the contract
+    pub name: String,
+    /// The ylem version the contract was compiled with
+    pub version: Version,
+    /// The size in bytes of the creation code
+    pub init_size: usize,
+    /// The size in bytes of the deployed code
+    pub runtime_size: usize,
+}
+
+impl ContractSize {
+    /// Reads the code sizes of the `artifact`, returns `None` if it has no bytecode
+    pub fn new(
+        file: impl Into<String>,
+        name: impl Into<String>,
+        version: Version,
+        artifact: &impl Artifact,
+    ) -> Option<Self> {
+        let init_size = code_size(&*artifact.get_bytecode_object()?);
+        let runtime_size =
+            artifact.get_deployed_bytecode_object().map(|obj| code_size(&obj)).unwrap_or_default();
+        Some(Self { file: file.into(), name: name.into(), version, init_size, runtime_size })
+    }
+
+    /// Whether the runtime code is larger than `limit`
+    pub fn exceeds(&self, limit: usize) -> bool {
+        self.runtime_size > limit
+    }
+}
+
+/// The code sizes of all contracts of a compile output, see
+/// [`ProjectCompileOutput::size_report()`](crate::ProjectCompileOutput::size_report)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    /// The code size limit the contracts were checked against, see
+    /// [`Network::max_code_size`](corebc_core::types::Network::max_code_size)
+    pub limit: usize,
+    /// The sizes of all contracts, ordered by file and name
+    pub contracts: Vec<ContractSize>,
+}
+
+impl SizeReport {
+    /// Returns all contracts whose runtime code exceeds the limit
+    pub fn oversized(&self) -> impl Iterator<Item = &ContractSize> {
+        self.contracts.iter().filter(|c| c.exceeds(self.limit))
+    }
+
+    /// Whether any contract exceeds the limit
+    pub fn has_oversized(&self) -> bool {
+        self.oversized().next().is_some()
+    }
+
+    /// Returns the first contract with the given name
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&ContractSize> {
+        let name = name.as_ref();
+        self.contracts.iter().find(|c| c.name == name)
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.contracts.iter().map(|c| c.name.len()).max().unwrap_or_default();
+        for contract in &self.contracts {
+            write!(
+                f,
+                "{:width$}  runtime {:>6} B  init {:>6} B",
+                contract.name, contract.runtime_size, contract.init_size
+            )?;
+            if contract.exceeds(self.limit) {
+                write!(
+                    f,
+                    "  exceeds the limit of {} B by {} B",
+                    self.limit,
+                    contract.runtime_size - self.limit
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the size of the code, unlinked library placeholders take up as much space as the
+/// addresses that replace them
+fn code_size(object: &BytecodeObject) -> usize {
+    match object {
+        BytecodeObject::Bytecode(code) => code.len(),
+        BytecodeObject::Unlinked(code) => code.strip_prefix("0x").unwrap_or(code).len() / 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use corebc_core::types::Network;
+
+    #[test]
+    fn flags_oversized_contracts() {
+        let size = |name: &str, runtime_size| ContractSize {
+            file: "src/A.sol".to_string(),
+            name: name.to_string(),
+            version: Version::new(1, 0, 0),
+            init_size: runtime_size + 100,
+            runtime_size,
+        };
+        let limit = Network::Mainnet.max_code_size();
+        let report =
+            SizeReport { limit, contracts: vec![size("Small", 100), size("Large", limit + 1)] };
+
+        assert!(report.has_oversized());
+        assert_eq!(report.oversized().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["Large"]);
+        assert_eq!(report.get("Small").unwrap().init_size, 200);
+        assert!(report.to_string().contains("exceeds the limit of 24576 B by 1 B"));
+    }
+
+    #[test]
+    fn measures_unlinked_code() {
+        let code = BytecodeObject::Unlinked(format!("0x6080{}6040", "_".repeat(44)));
+        assert_eq!(code_size(&code), 26);
+        assert_eq!(code_size(&BytecodeObject::Bytecode(vec![0u8; 3].into())), 3);
+    }
+}
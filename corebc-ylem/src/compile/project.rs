@@ -111,7 +111,7 @@ use crate::{
     report,
     resolver::GraphEdges,
     ArtifactOutput, CompilerInput, Graph, Project, ProjectCompileOutput, ProjectPathsConfig,
-    Sources, Ylem,
+    Source, Sources, Ylem,
 };
 use rayon::prelude::*;
 use std::{collections::btree_map::BTreeMap, path::PathBuf, time::Instant};
@@ -142,7 +142,7 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
     /// ```
     #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
     pub fn new(project: &'a Project<T>) -> Result<Self> {
-        Self::with_sources(project, project.paths.read_input_files()?)
+        Self::with_sources(project, Source::read_all_files(project.input_files())?)
     }
 
     /// Bootstraps the compilation process by resolving the dependency graph of all sources and the
@@ -215,6 +215,7 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
     /// ```
     pub fn compile(self) -> Result<ProjectCompileOutput<T>> {
         let slash_paths = self.project.slash_paths;
+        let start = Instant::now();
 
         // drive the compiler statemachine to completion
         let mut output = self.preprocess()?.compile()?.write_artifacts()?.write_cache()?;
@@ -224,6 +225,7 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
             output.slash_paths();
         }
 
+        report::compile_finished(start.elapsed());
         Ok(output)
     }
 
@@ -266,6 +268,7 @@ impl<'a, T: ArtifactOutput> PreprocessedState<'a, T> {
         trace!("compiling");
         let PreprocessedState { sources, cache, sparse_output } = self;
         let project = cache.project();
+        report::compile_started(&sources.dirty_files());
         let mut output = sources.compile(
             &project.ylem_config.settings,
             &project.paths,
@@ -312,8 +315,7 @@ impl<'a, T: ArtifactOutput> CompiledState<'a, T> {
                 ctx,
                 &project.paths,
             )
-        } else if output.has_error(&project.ignored_error_codes, &project.compiler_severity_filter)
-        {
+        } else if output.has_error(&project.ignored_error_codes, &project.severity_filter()) {
             trace!("skip writing cache file due to ylem errors: {:?}", output.errors);
             project.artifacts_handler().output_to_artifacts(
                 &output.contracts,
@@ -361,7 +363,7 @@ impl<'a, T: ArtifactOutput> ArtifactsState<'a, T> {
         let ArtifactsState { output, cache, compiled_artifacts } = self;
         let project = cache.project();
         let ignored_error_codes = project.ignored_error_codes.clone();
-        let compiler_severity_filter = project.compiler_severity_filter.clone();
+        let compiler_severity_filter = project.severity_filter();
         let has_error = output.has_error(&ignored_error_codes, &compiler_severity_filter);
         let skip_write_to_disk = project.no_artifacts || has_error;
         trace!(has_error, project.no_artifacts, skip_write_to_disk, cache_path=?project.cache_path(),"prepare writing cache file");
@@ -373,6 +375,9 @@ impl<'a, T: ArtifactOutput> ArtifactsState<'a, T> {
             cached_artifacts,
             ignored_error_codes,
             compiler_severity_filter,
+            code_size_limit: project.code_size_limit,
+            deny_oversized_contracts: project.deny_oversized_contracts,
+            root: project.root().clone(),
         })
     }
 }
@@ -429,7 +434,13 @@ impl CompilerSources {
                 .into_iter()
                 .map(|(ylem, (version, sources))| {
                     trace!("Filtering {} sources for {}", sources.len(), version);
+                    let files = sources.keys().cloned().collect::<Vec<_>>();
                     let sources = cache.filter(sources, &version);
+                    let cached = files
+                        .into_iter()
+                        .filter(|file| sources.0.get(file).map_or(true, |s| !s.is_dirty()))
+                        .collect::<Vec<_>>();
+                    report::cache_hit(&version, &cached);
                     trace!(
                         "Detected {} dirty sources {:?}",
                         sources.dirty().count(),
@@ -462,6 +473,15 @@ enum FilteredCompilerSources {
 }
 
 impl FilteredCompilerSources {
+    /// Returns all files that need to be compiled
+    fn dirty_files(&self) -> Vec<PathBuf> {
+        let sources = match self {
+            FilteredCompilerSources::Sequential(s) => s,
+            FilteredCompilerSources::Parallel(s, _) => s,
+        };
+        sources.values().flat_map(|(_, sources)| sources.dirty_files().cloned()).collect()
+    }
+
     /// Compiles all the files with `Ylem`
     fn compile(
         self,
@@ -557,7 +577,9 @@ fn compile_sequential(
             let start = Instant::now();
             report::ylem_spawn(&ylem, &version, &input, &actually_dirty);
             let output = ylem.compile(&input)?;
-            report::ylem_success(&ylem, &version, &output, &start.elapsed());
+            let duration = start.elapsed();
+            report::ylem_success(&ylem, &version, &output, &duration);
+            report::files_compiled(&version, &actually_dirty, &duration);
             trace!("compiled input, output has error: {}", output.has_error());
             trace!("received compiler output: {:?}", output.contracts.keys());
 
@@ -655,7 +677,9 @@ fn compile_parallel(
                 let start = Instant::now();
                 report::ylem_spawn(&ylem, &version, &input, &actually_dirty);
                 ylem.compile(&input).map(move |output| {
-                    report::ylem_success(&ylem, &version, &output, &start.elapsed());
+                    let duration = start.elapsed();
+                    report::ylem_success(&ylem, &version, &output, &duration);
+                    report::files_compiled(&version, &actually_dirty, &duration);
                     (version, input, output)
                 })
             })
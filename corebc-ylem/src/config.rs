@@ -378,6 +378,45 @@ impl ProjectPathsConfig {
         vec![utils::find_fave_or_alt_path(root, "lib", "node_modules")]
     }
 
+    /// Returns all existing dependency directories of the given root path, `<root>/lib` and
+    /// `<root>/node_modules`, or [`Self::find_libs()`] if neither of them exists
+    pub fn find_all_libs(root: impl AsRef<Path>) -> Vec<PathBuf> {
+        let root = root.as_ref();
+        let libs: Vec<_> = ["lib", "node_modules"]
+            .iter()
+            .map(|lib| root.join(lib))
+            .filter(|lib| lib.is_dir())
+            .collect();
+        if libs.is_empty() {
+            return Self::find_libs(root)
+        }
+        libs
+    }
+
+    /// Attempts to autodetect the remappings of all dependencies in the `lib` and `node_modules`
+    /// directories of the given root path, see [`Remapping::find_in_project()`]
+    pub fn find_remappings(root: impl AsRef<Path>) -> Vec<Remapping> {
+        Remapping::find_in_project(root)
+    }
+
+    /// Adds the autodetected remappings of the project's dependencies, see
+    /// [`Self::find_remappings()`], and their dependency directories to the libraries.
+    ///
+    /// This allows compiling projects set up with foundry or hardhat without a remappings file.
+    /// Remappings with a name that is configured already are not replaced.
+    pub fn auto_detect_remappings(&mut self) {
+        for lib in Self::find_all_libs(&self.root) {
+            if lib.is_dir() && !self.libraries.contains(&lib) {
+                self.libraries.push(lib);
+            }
+        }
+        for remapping in Self::find_remappings(&self.root) {
+            if !self.remappings.iter().any(|r| r.name == remapping.name) {
+                self.remappings.push(remapping);
+            }
+        }
+    }
+
     /// Flattens all file imports into a single string
     pub fn flatten(&self, target: &Path) -> Result<String> {
         tracing::trace!("flattening file");
@@ -623,6 +662,7 @@ pub struct ProjectPathsConfigBuilder {
     scripts: Option<PathBuf>,
     libraries: Option<Vec<PathBuf>>,
     remappings: Option<Vec<Remapping>>,
+    auto_detect_remappings: bool,
 }
 
 impl ProjectPathsConfigBuilder {
@@ -693,6 +733,13 @@ impl ProjectPathsConfigBuilder {
         self
     }
 
+    /// Adds the autodetected remappings of all dependencies in `lib` and `node_modules` on build,
+    /// see [`ProjectPathsConfig::auto_detect_remappings()`]
+    pub fn auto_detect_remappings(mut self) -> Self {
+        self.auto_detect_remappings = true;
+        self
+    }
+
     pub fn build_with_root(self, root: impl Into<PathBuf>) -> ProjectPathsConfig {
         let root = utils::canonicalized(root);
 
@@ -700,7 +747,7 @@ impl ProjectPathsConfigBuilder {
         let artifacts =
             self.artifacts.unwrap_or_else(|| ProjectPathsConfig::find_artifacts_dir(&root));
 
-        ProjectPathsConfig {
+        let mut config = ProjectPathsConfig {
             cache: self
                 .cache
                 .unwrap_or_else(|| root.join("cache").join(SOLIDITY_FILES_CACHE_FILENAME)),
@@ -714,7 +761,11 @@ impl ProjectPathsConfigBuilder {
                 .unwrap_or_else(|| libraries.iter().flat_map(Remapping::find_many).collect()),
             libraries,
             root,
+        };
+        if self.auto_detect_remappings {
+            config.auto_detect_remappings();
         }
+        config
     }
 
     pub fn build(self) -> std::result::Result<ProjectPathsConfig, YlemIoError> {
@@ -970,6 +1021,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_autodetect_remappings() {
+        let root = utils::tempdir("root").unwrap();
+        let root = root.path();
+        for file in ["lib/ds-test/src/test.sol", "node_modules/@ensdomains/ens/contracts/ENS.sol"] {
+            let file = root.join(file);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, "").unwrap();
+        }
+        let root = utils::canonicalized(root);
+
+        assert_eq!(
+            ProjectPathsConfig::find_all_libs(&root),
+            vec![root.join("lib"), root.join("node_modules")]
+        );
+
+        // only the preferred `lib` dir is used by default
+        let config = ProjectPathsConfig::builder().build_with_root(&root);
+        assert_eq!(config.remappings.len(), 1);
+
+        let config = ProjectPathsConfig::builder()
+            .remapping("ds-test/=lib/forge-std/lib/ds-test/src/".parse().unwrap())
+            .auto_detect_remappings()
+            .build_with_root(&root);
+        assert_eq!(config.libraries, vec![root.join("lib"), root.join("node_modules")]);
+        let remappings = config.remappings.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            remappings,
+            vec![
+                "ds-test/=lib/forge-std/lib/ds-test/src/".to_string(),
+                format!("@ensdomains/={}/", root.join("node_modules/@ensdomains").display()),
+            ]
+        );
+    }
+
     #[test]
     fn can_have_sane_build_info_default() {
         let root = utils::tempdir("root").unwrap();
@@ -19,6 +19,18 @@ pub enum YlemError {
     VersionNotFound,
     #[error("Checksum mismatch for {file}: expected {expected} found {detected} for {version}")]
     ChecksumMismatch { version: Version, expected: String, detected: String, file: PathBuf },
+    #[error("No checksum published for ylem {0}")]
+    ChecksumNotFound(Version),
+    /// Thrown when the checksum of ylem can't be verified because the release list could not be
+    /// read, see `YvmConfig::allow_unverified`
+    #[error("Failed to verify the checksum of ylem {0}, the release list could not be read")]
+    ReleasesUnavailable(Version),
+    /// Thrown when a missing version would have to be installed in offline mode
+    #[error("ylem {0} is not installed and installing is disabled in offline mode")]
+    OfflineInstall(Version),
+    /// Thrown when downloading ylem from a mirror fails
+    #[error("Failed to download ylem from {0}: {1}")]
+    Download(String, String),
     #[error(transparent)]
     SemverError(#[from] semver::Error),
     /// Deserialization error
@@ -54,6 +66,13 @@ pub enum YlemError {
     #[error("No artifact found for `{}:{}`", .0.display(), .1)]
     ArtifactNotFound(PathBuf, String),
 
+    /// Thrown when a bytecode references libraries that were not linked
+    #[error("Unresolved library references: {}", .0.join(", "))]
+    UnresolvedLibraries(Vec<String>),
+    /// Thrown when the bytecode of the artifact `1` could not be linked
+    #[error("Failed to link {1}: {0}")]
+    FailedLink(Box<YlemError>, String),
+
     #[cfg(feature = "project-util")]
     #[error(transparent)]
     FsExtra(#[from] fs_extra::error::Error),
@@ -27,6 +27,9 @@ pub use compile::{
 mod config;
 pub use config::{AllowedLibPaths, PathStyle, ProjectPaths, ProjectPathsConfig, YlemConfig};
 
+mod profile;
+pub use profile::{CompileProfile, ProfileSources};
+
 pub mod remappings;
 use crate::artifacts::{Source, SourceFile, StandardJsonCompilerInput};
 
@@ -34,6 +37,8 @@ pub mod error;
 mod filter;
 pub mod report;
 pub mod utils;
+#[cfg(feature = "async")]
+pub mod verify;
 pub use filter::{FileFilter, TestFileFilter};
 
 use crate::{
@@ -43,8 +48,9 @@ use crate::{
     error::{YlemError, YlemIoError},
     sources::{VersionedSourceFile, VersionedSourceFiles},
 };
-use artifacts::{contract::Contract, Severity};
+use artifacts::{contract::Contract, CompactContractBytecode, Severity};
 use compile::output::contracts::VersionedContracts;
+use corebc_core::types::{Address, Network};
 use error::Result;
 use semver::Version;
 use std::path::{Path, PathBuf};
@@ -54,7 +60,7 @@ use std::path::{Path, PathBuf};
 pub mod project_util;
 
 /// Represents a project workspace and handles `ylem` compiling of all contracts in that workspace.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Project<T: ArtifactOutput = ConfigurableArtifacts> {
     /// The layout of the project
     pub paths: ProjectPathsConfig,
@@ -76,10 +82,21 @@ pub struct Project<T: ArtifactOutput = ConfigurableArtifacts> {
     pub ignored_error_codes: Vec<u64>,
     /// The minimum severity level that is treated as a compiler error
     pub compiler_severity_filter: Severity,
+    /// Whether warnings that are not ignored are treated as compiler errors
+    pub deny_warnings: bool,
+    /// The code size limit of the target network, the [`Network::max_code_size`] of mainnet if not
+    /// set
+    pub code_size_limit: Option<usize>,
+    /// Whether contracts exceeding the code size limit are treated as compiler errors
+    pub deny_oversized_contracts: bool,
     /// The paths which will be allowed for library inclusion
     pub allowed_paths: AllowedLibPaths,
     /// The paths which will be used with ylem's `--include-path` attribute
     pub include_paths: IncludePaths,
+    /// Where the detected ylem versions run
+    pub compiler_backend: CompilerBackend,
+    /// The profile the project is compiled with, see [`Project::with_profile`]
+    pub profile: CompileProfile,
     /// Maximum number of `ylem` processes to run simultaneously.
     ylem_jobs: usize,
     /// Offline mode, if set, network access (download ylem) is disallowed
@@ -146,6 +163,16 @@ impl<T: ArtifactOutput> Project<T> {
         &self.paths.root
     }
 
+    /// Returns the minimum severity level that fails the build, this is at least
+    /// [`Severity::Warning`] if [`Self::deny_warnings`] is set
+    pub fn severity_filter(&self) -> Severity {
+        if self.deny_warnings {
+            self.compiler_severity_filter.clone().max(Severity::Warning)
+        } else {
+            self.compiler_severity_filter.clone()
+        }
+    }
+
     /// Returns the handler that takes care of processing all artifacts
     pub fn artifacts_handler(&self) -> &T {
         &self.artifacts
@@ -205,6 +232,39 @@ impl<T: ArtifactOutput> Project<T> {
         ylem
     }
 
+    /// Compiles the project with `profile`: applies its optimizer settings, only compiles its
+    /// input files and, for profiles other than the default one, moves the artifacts, build infos
+    /// and cache to the profile's paths.
+    ///
+    /// This is meant to be applied once, to a project without profile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use corebc_ylem::{CompileProfile, Project};
+    /// # fn demo(project: Project) {
+    /// let project = Project::builder().build().unwrap().with_profile(CompileProfile::test());
+    /// let output = project.compile().unwrap();
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_profile(mut self, profile: CompileProfile) -> Self {
+        if let Some(ref optimizer) = profile.optimizer {
+            self.ylem_config.settings.optimizer = optimizer.clone();
+        }
+        profile.apply_paths(&mut self.paths);
+        self.profile = profile;
+        self
+    }
+
+    /// Returns the input files of the project that its [profile](Self::with_profile) compiles
+    pub fn input_files(&self) -> Vec<PathBuf> {
+        self.paths
+            .input_files_iter()
+            .filter(|file| self.profile.sources.is_included(&self.paths, file))
+            .collect()
+    }
+
     /// Sets the maximum number of parallel `ylem` processes to run simultaneously.
     ///
     /// # Panics
@@ -265,7 +325,7 @@ impl<T: ArtifactOutput> Project<T> {
     /// ```
     #[tracing::instrument(skip_all, name = "compile")]
     pub fn compile(&self) -> Result<ProjectCompileOutput<T>> {
-        let sources = self.paths.read_input_files()?;
+        let sources = Source::read_all_files(self.input_files())?;
         tracing::trace!("found {} sources to compile: {:?}", sources.len(), sources.keys());
 
         #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
@@ -306,7 +366,10 @@ impl<T: ArtifactOutput> Project<T> {
     }
 
     /// Convenience function to compile a single solidity file with the project's settings.
-    /// Same as [`Self::yvm_compile()`] but with the given `file` as input.
+    /// Same as [`Self::compile_files()`] but with the given `file` as input.
+    ///
+    /// Only the file and the files it imports, directly or transitively, are passed to ylem. The
+    /// cache entries of the other files of the project are left untouched.
     ///
     /// # Example
     ///
@@ -317,16 +380,16 @@ impl<T: ArtifactOutput> Project<T> {
     /// let output = project.compile_file("example/Greeter.sol").unwrap();
     /// # }
     /// ```
-    #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
     pub fn compile_file(&self, file: impl Into<PathBuf>) -> Result<ProjectCompileOutput<T>> {
-        let file = file.into();
-        let source = Source::read(&file)?;
-        project::ProjectCompiler::with_sources(self, Sources::from([(file, source)]))?.compile()
+        self.compile_files([file])
     }
 
     /// Convenience function to compile a series of solidity files with the project's settings.
     /// Same as [`Self::compile()`] but with the given `files` as input.
     ///
+    /// The compiler input is pruned to the dependency closure of `files`: files of the project
+    /// that are not (transitively) imported by any of them are not compiled.
+    ///
     /// # Example
     ///
     /// ```
@@ -390,7 +453,7 @@ impl<T: ArtifactOutput> Project<T> {
         filter: F,
     ) -> Result<ProjectCompileOutput<T>> {
         let sources =
-            Source::read_all(self.paths.input_files().into_iter().filter(|p| filter.is_match(p)))?;
+            Source::read_all(self.input_files().into_iter().filter(|p| filter.is_match(p)))?;
         let filter: Box<dyn FileFilter> = Box::new(filter);
 
         #[cfg(all(feature = "yvm-ylem", not(target_arch = "wasm32")))]
@@ -547,6 +610,62 @@ impl<T: ArtifactOutput> Project<T> {
 
         Ok(input)
     }
+
+    /// Returns the standard-json-input the target contract is compiled with by the given compiler
+    /// `version`.
+    ///
+    /// Unlike [`Self::standard_json_input()`] the settings are normalized and sanitized for the
+    /// `version` exactly like they are when the project is compiled, so the input can be archived
+    /// and compiled again later to reproduce the same bytecode, e.g. for verification.
+    pub fn standard_json_input_for_version(
+        &self,
+        target: impl AsRef<Path>,
+        version: &Version,
+    ) -> Result<StandardJsonCompilerInput> {
+        Ok(self.standard_json_input(target)?.normalize_evm_version(version).sanitized(version))
+    }
+
+    /// Compiles the project and links the bytecode of all contracts with the given `libraries`.
+    ///
+    /// `libraries` maps library names, either fully qualified (`src/Math.sol:Math`) or plain
+    /// (`Math`), to the addresses the libraries are deployed at. Fails with
+    /// [`YlemError::FailedLink`] if a contract references a library that is not in `libraries`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_ylem::Project;
+    /// use std::collections::BTreeMap;
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let project = Project::builder().build()?;
+    /// let libraries = BTreeMap::from([(
+    ///     "Math".to_string(),
+    ///     "cb270000000000000000000000000000000000000000".parse()?,
+    /// )]);
+    /// let contracts = project.link_libraries(&libraries)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn link_libraries(
+        &self,
+        libraries: &BTreeMap<String, Address>,
+    ) -> Result<BTreeMap<ArtifactId, CompactContractBytecode>> {
+        let output = self.compile()?;
+        if output.has_compiler_errors() {
+            return Err(YlemError::msg(output.to_string()))
+        }
+
+        output
+            .into_artifacts()
+            .map(|(id, artifact)| {
+                let mut contract = artifact.into_contract_bytecode();
+                contract
+                    .link_libraries(libraries)
+                    .map_err(|err| YlemError::FailedLink(Box::new(err), id.identifier()))?;
+                Ok((id, contract))
+            })
+            .collect()
+    }
 }
 
 pub struct ProjectBuilder<T: ArtifactOutput = ConfigurableArtifacts> {
@@ -574,10 +693,18 @@ pub struct ProjectBuilder<T: ArtifactOutput = ConfigurableArtifacts> {
     pub ignored_error_codes: Vec<u64>,
     /// The minimum severity level that is treated as a compiler error
     compiler_severity_filter: Severity,
+    /// Whether to treat warnings as errors
+    deny_warnings: bool,
+    /// The code size limit of the target network
+    code_size_limit: Option<usize>,
+    /// Whether to treat oversized contracts as errors
+    deny_oversized_contracts: bool,
     /// All allowed paths for ylem's `--allowed-paths`
     allowed_paths: AllowedLibPaths,
     /// Paths to use for ylem's `--include-path`
     include_paths: IncludePaths,
+    /// Where the detected ylem versions run
+    compiler_backend: CompilerBackend,
     ylem_jobs: Option<usize>,
 }
 
@@ -597,8 +724,12 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             artifacts,
             ignored_error_codes: Vec::new(),
             compiler_severity_filter: Severity::Error,
+            deny_warnings: false,
+            code_size_limit: None,
+            deny_oversized_contracts: false,
             allowed_paths: Default::default(),
             include_paths: Default::default(),
+            compiler_backend: Default::default(),
             ylem_jobs: None,
         }
     }
@@ -641,6 +772,47 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
         self
     }
 
+    /// Treats all warnings that are not ignored as compiler errors
+    #[must_use]
+    pub fn deny_warnings(self) -> Self {
+        self.set_deny_warnings(true)
+    }
+
+    /// Sets whether warnings that are not ignored are treated as compiler errors
+    #[must_use]
+    pub fn set_deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// Sets the code size limit contracts are checked against in
+    /// [`ProjectCompileOutput::size_report()`]
+    #[must_use]
+    pub fn code_size_limit(mut self, limit: usize) -> Self {
+        self.code_size_limit = Some(limit);
+        self
+    }
+
+    /// Checks contracts against the code size limit of the `network`, see
+    /// [`Self::code_size_limit()`]
+    #[must_use]
+    pub fn code_size_limit_of(self, network: Network) -> Self {
+        self.code_size_limit(network.max_code_size())
+    }
+
+    /// Treats contracts whose runtime code exceeds the code size limit as compiler errors
+    #[must_use]
+    pub fn deny_oversized_contracts(self) -> Self {
+        self.set_deny_oversized_contracts(true)
+    }
+
+    /// Sets whether contracts exceeding the code size limit are treated as compiler errors
+    #[must_use]
+    pub fn set_deny_oversized_contracts(mut self, deny: bool) -> Self {
+        self.deny_oversized_contracts = deny;
+        self
+    }
+
     /// Disables cached builds
     #[must_use]
     pub fn ephemeral(self) -> Self {
@@ -723,6 +895,14 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
         self
     }
 
+    /// Sets where the ylem versions detected for the sources run, e.g. in docker containers with
+    /// [`DockerBackend`]
+    #[must_use]
+    pub fn compiler_backend(mut self, backend: impl Into<CompilerBackend>) -> Self {
+        self.compiler_backend = backend.into();
+        self
+    }
+
     /// Sets the number of parallel `ylem` processes to `1`, no parallelization
     #[must_use]
     pub fn single_ylem_jobs(self) -> Self {
@@ -740,8 +920,12 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             auto_detect,
             ignored_error_codes,
             compiler_severity_filter,
+            deny_warnings,
+            code_size_limit,
+            deny_oversized_contracts,
             allowed_paths,
             include_paths,
+            compiler_backend,
             ylem_jobs,
             offline,
             build_info,
@@ -760,8 +944,12 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             artifacts,
             ignored_error_codes,
             compiler_severity_filter,
+            deny_warnings,
+            code_size_limit,
+            deny_oversized_contracts,
             allowed_paths,
             include_paths,
+            compiler_backend,
             ylem_jobs,
             build_info,
         }
@@ -818,8 +1006,12 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             artifacts,
             ignored_error_codes,
             compiler_severity_filter,
+            deny_warnings,
+            code_size_limit,
+            deny_oversized_contracts,
             mut allowed_paths,
             include_paths,
+            compiler_backend,
             ylem_jobs,
             offline,
             build_info,
@@ -850,8 +1042,13 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             artifacts,
             ignored_error_codes,
             compiler_severity_filter,
+            deny_warnings,
+            code_size_limit,
+            deny_oversized_contracts,
             allowed_paths,
             include_paths,
+            compiler_backend,
+            profile: Default::default(),
             ylem_jobs: ylem_jobs.unwrap_or_else(num_cpus::get),
             offline,
             slash_paths,
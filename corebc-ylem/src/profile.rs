@@ -0,0 +1,146 @@
+//! Compile profiles, see [`Project::with_profile`](crate::Project::with_profile)
+
+use crate::{artifacts::Optimizer, FileFilter, ProjectPathsConfig, TestFileFilter};
+use std::path::{Path, PathBuf};
+
+/// Which input files of a project a [`CompileProfile`] compiles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfileSources {
+    /// The sources, tests and scripts
+    #[default]
+    All,
+    /// Excludes the files in the tests dir and all `.t.sol` files
+    ExcludeTests,
+}
+
+impl ProfileSources {
+    /// Whether `file` of the project with `paths` is compiled
+    pub fn is_included(&self, paths: &ProjectPathsConfig, file: &Path) -> bool {
+        match self {
+            ProfileSources::All => true,
+            ProfileSources::ExcludeTests => {
+                !file.starts_with(&paths.tests) && !TestFileFilter::default().is_match(file)
+            }
+        }
+    }
+}
+
+/// The optimizer settings and input files a project is compiled with.
+///
+/// Profiles other than [`CompileProfile::default()`] write their artifacts and cache to their own
+/// paths, so switching profiles does not invalidate the artifacts of another profile.
+///
+/// # Example
+///
+/// ```
+/// use corebc_ylem::{CompileProfile, Project};
+///
+/// let project = Project::builder().build().unwrap().with_profile(CompileProfile::production());
+/// assert!(project.paths.artifacts.ends_with("production"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompileProfile {
+    /// The name of the profile
+    pub name: String,
+    /// The optimizer settings, `None` keeps the settings of the project
+    pub optimizer: Option<Optimizer>,
+    /// The input files that are compiled
+    pub sources: ProfileSources,
+}
+
+impl CompileProfile {
+    /// The name of the default profile
+    pub const DEFAULT: &'static str = "default";
+    /// The name of the test profile
+    pub const TEST: &'static str = "test";
+    /// The name of the production profile
+    pub const PRODUCTION: &'static str = "production";
+
+    /// Creates a profile that compiles all input files with the settings of the project
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), optimizer: None, sources: ProfileSources::All }
+    }
+
+    /// The test profile, compiles all input files without optimizer for fast builds
+    pub fn test() -> Self {
+        Self::new(Self::TEST).optimizer(Optimizer { enabled: Some(false), ..Default::default() })
+    }
+
+    /// The production profile, compiles the input files except tests with the optimizer enabled
+    pub fn production() -> Self {
+        Self::new(Self::PRODUCTION)
+            .optimizer(Optimizer { enabled: Some(true), ..Default::default() })
+            .sources(ProfileSources::ExcludeTests)
+    }
+
+    /// Sets the optimizer settings
+    #[must_use]
+    pub fn optimizer(mut self, optimizer: Optimizer) -> Self {
+        self.optimizer = Some(optimizer);
+        self
+    }
+
+    /// Sets the input files that are compiled
+    #[must_use]
+    pub fn sources(mut self, sources: ProfileSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Whether this is the default profile
+    pub fn is_default(&self) -> bool {
+        self.name == Self::DEFAULT
+    }
+
+    /// Moves the artifacts, build infos and cache of `paths` to the paths of this profile
+    pub(crate) fn apply_paths(&self, paths: &mut ProjectPathsConfig) {
+        if self.is_default() {
+            return
+        }
+        paths.artifacts = paths.artifacts.join(&self.name);
+        paths.build_infos = paths.build_infos.join(&self.name);
+        paths.cache = self.cache_file(&paths.cache);
+    }
+
+    /// Returns the cache file of this profile, `<stem>-<name>.<ext>` next to `cache`
+    fn cache_file(&self, cache: &Path) -> PathBuf {
+        let stem = cache.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let mut file = format!("{stem}-{}", self.name);
+        if let Some(ext) = cache.extension() {
+            file = format!("{file}.{}", ext.to_string_lossy());
+        }
+        cache.with_file_name(file)
+    }
+}
+
+impl Default for CompileProfile {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_profile_paths() {
+        let mut paths = ProjectPathsConfig::builder().build_with_root("/project");
+        let default = paths.clone();
+        CompileProfile::default().apply_paths(&mut paths);
+        assert_eq!(paths.artifacts, default.artifacts);
+        assert_eq!(paths.cache, default.cache);
+
+        let production = CompileProfile::production();
+        production.apply_paths(&mut paths);
+        assert_eq!(paths.artifacts, default.artifacts.join("production"));
+        assert_eq!(paths.build_infos, default.build_infos.join("production"));
+        assert_eq!(paths.cache.parent(), default.cache.parent());
+        assert!(paths.cache.ends_with("solidity-files-cache-production.json"));
+
+        assert!(production.sources.is_included(&default, &default.sources.join("Token.sol")));
+        assert!(!production.sources.is_included(&default, &default.tests.join("Token.sol")));
+        assert!(!production.sources.is_included(&default, &default.sources.join("Token.t.sol")));
+        assert!(ProfileSources::All.is_included(&default, &default.tests.join("Token.sol")));
+    }
+}
@@ -2,7 +2,7 @@ use crate::utils;
 
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap},
     fmt,
     path::{Path, PathBuf},
     str::FromStr,
@@ -230,6 +230,23 @@ impl Remapping {
             .collect()
     }
 
+    /// Attempts to autodetect the remappings of all dependency layouts of the project at the given
+    /// root path, see [`Self::find_many()`]: dependencies installed in `<root>/lib`, as done by
+    /// dapptools and foundry, and packages installed in `<root>/node_modules` by npm.
+    ///
+    /// If both layouts contain a dependency with the same remapping name, the one in `lib` takes
+    /// precedence. The remappings are sorted by name.
+    pub fn find_in_project(root: impl AsRef<Path>) -> Vec<Remapping> {
+        let root = root.as_ref();
+        let mut remappings = BTreeMap::new();
+        for lib in [DAPPTOOLS_LIB_DIR, JS_LIB_DIR] {
+            for remapping in Self::find_many(root.join(lib)) {
+                remappings.entry(remapping.name.clone()).or_insert(remapping);
+            }
+        }
+        remappings.into_values().collect()
+    }
+
     /// Converts any `\\` separators in the `path` to `/`
     pub fn slash_path(&mut self) {
         #[cfg(windows)]
@@ -820,6 +837,31 @@ mod tests {
         assert_eq!(remappings[0].path, format!("{path}/src/"));
     }
 
+    #[test]
+    fn find_remappings_in_project() {
+        let tmp_dir = tempdir("project").unwrap();
+        let paths = [
+            "lib/ds-test/src/test.sol",
+            "lib/solmate/src/tokens/CBC20.sol",
+            "node_modules/solmate/src/tokens/CBC20.sol",
+            "node_modules/@openzeppelin/contracts/token/CBC20/CBC20.sol",
+        ];
+        mkdir_or_touch(tmp_dir.path(), &paths[..]);
+
+        let lib = tmp_dir.path().join("lib");
+        let node_modules = tmp_dir.path().join("node_modules");
+        let remappings = Remapping::find_in_project(tmp_dir.path());
+        let expected = vec![
+            Remapping {
+                name: "@openzeppelin/".to_string(),
+                path: to_str(node_modules.join("@openzeppelin")),
+            },
+            Remapping { name: "ds-test/".to_string(), path: to_str(lib.join("ds-test/src")) },
+            Remapping { name: "solmate/".to_string(), path: to_str(lib.join("solmate/src")) },
+        ];
+        pretty_assertions::assert_eq!(remappings, expected);
+    }
+
     #[test]
     fn can_resolve_contract_dir_combinations() {
         let tmp_dir = tempdir("demo").unwrap();
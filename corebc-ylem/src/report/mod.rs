@@ -138,6 +138,13 @@ pub trait Reporter: 'static + std::fmt::Debug {
     /// list of all import paths and the file they occurred in: `(import stmt, file)`
     fn on_unresolved_imports(&self, _imports: &[(&Path, &Path)], _remappings: &[Remapping]) {}
 
+    /// Invoked with every [`ReportEvent`] of the compiler pipeline.
+    ///
+    /// This is invoked in addition to the dedicated callbacks above and receives events that have
+    /// no dedicated callback, like cache hits, installation progress and per-file compile times,
+    /// which makes it the single entry point for progress bars and IDE integrations.
+    fn on_event(&self, _event: &ReportEvent<'_>) {}
+
     /// If `self` is the same type as the provided `TypeId`, returns an untyped
     /// [`NonNull`] pointer to that type. Otherwise, returns `None`.
     ///
@@ -165,6 +172,39 @@ pub trait Reporter: 'static + std::fmt::Debug {
     }
 }
 
+/// A structured event of the compiler pipeline, see [`Reporter::on_event()`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReportEvent<'a> {
+    /// The project started compiling, `dirty_files` are all files that changed since the last
+    /// compilation, and the files that import them
+    CompileStarted { dirty_files: &'a [PathBuf] },
+    /// The `files` are unchanged since they were compiled with `version`, their artifacts are
+    /// read from the cache
+    CacheHit { version: &'a Version, files: &'a [PathBuf] },
+    /// A ylem process was spawned to compile the dirty `files` with `version`
+    YlemSpawn { version: &'a Version, files: &'a [PathBuf] },
+    /// A successful ylem run with `version` printed `warnings` to stderr, see
+    /// [`CompilerOutput::raw_warnings`]
+    YlemWarnings { version: &'a Version, warnings: &'a str },
+    /// The `file` was compiled with `version`.
+    ///
+    /// Files are compiled in batches, `duration` is the time the ylem process that compiled the
+    /// file took.
+    FileCompiled { version: &'a Version, file: &'a Path, duration: Duration },
+    /// The project finished compiling after `duration`
+    CompileFinished { duration: Duration },
+    /// The installation of ylem `version` started
+    YlemInstallationStart { version: &'a Version },
+    /// `downloaded` bytes of the ylem `version` binary were downloaded, `total` is the size of
+    /// the binary if the server reported it
+    YlemInstallationProgress { version: &'a Version, downloaded: u64, total: Option<u64> },
+    /// The ylem `version` was installed
+    YlemInstallationSuccess { version: &'a Version },
+    /// The installation of the ylem `version` failed
+    YlemInstallationError { version: &'a Version, error: &'a str },
+}
+
 impl dyn Reporter {
     /// Returns `true` if this `Reporter` is the same type as `T`.
     pub fn is<T: Any>(&self) -> bool {
@@ -187,7 +227,10 @@ pub(crate) fn ylem_spawn(
     input: &CompilerInput,
     dirty_files: &[PathBuf],
 ) {
-    get_default(|r| r.reporter.on_ylem_spawn(ylem, version, input, dirty_files));
+    get_default(|r| {
+        r.reporter.on_ylem_spawn(ylem, version, input, dirty_files);
+        r.reporter.on_event(&ReportEvent::YlemSpawn { version, files: dirty_files });
+    });
 }
 
 pub(crate) fn ylem_success(
@@ -196,22 +239,66 @@ pub(crate) fn ylem_success(
     output: &CompilerOutput,
     duration: &Duration,
 ) {
-    get_default(|r| r.reporter.on_ylem_success(ylem, version, output, duration));
+    get_default(|r| {
+        r.reporter.on_ylem_success(ylem, version, output, duration);
+        for warnings in &output.raw_warnings {
+            r.reporter.on_event(&ReportEvent::YlemWarnings { version, warnings });
+        }
+    });
+}
+
+pub(crate) fn files_compiled(version: &Version, files: &[PathBuf], duration: &Duration) {
+    get_default(|r| {
+        for file in files {
+            r.reporter.on_event(&ReportEvent::FileCompiled { version, file, duration: *duration });
+        }
+    });
+}
+
+pub(crate) fn compile_started(dirty_files: &[PathBuf]) {
+    get_default(|r| r.reporter.on_event(&ReportEvent::CompileStarted { dirty_files }));
+}
+
+pub(crate) fn compile_finished(duration: Duration) {
+    get_default(|r| r.reporter.on_event(&ReportEvent::CompileFinished { duration }));
+}
+
+pub(crate) fn cache_hit(version: &Version, files: &[PathBuf]) {
+    if files.is_empty() {
+        return
+    }
+    get_default(|r| r.reporter.on_event(&ReportEvent::CacheHit { version, files }));
 }
 
 #[allow(unused)]
 pub(crate) fn ylem_installation_start(version: &Version) {
-    get_default(|r| r.reporter.on_ylem_installation_start(version));
+    get_default(|r| {
+        r.reporter.on_ylem_installation_start(version);
+        r.reporter.on_event(&ReportEvent::YlemInstallationStart { version });
+    });
+}
+
+#[allow(unused)]
+pub(crate) fn ylem_installation_progress(version: &Version, downloaded: u64, total: Option<u64>) {
+    get_default(|r| {
+        r.reporter.on_event(&ReportEvent::YlemInstallationProgress { version, downloaded, total })
+    });
 }
 
 #[allow(unused)]
 pub(crate) fn ylem_installation_success(version: &Version) {
-    get_default(|r| r.reporter.on_ylem_installation_success(version));
+    get_default(|r| {
+        r.reporter.on_ylem_installation_success(version);
+        r.reporter.on_event(&ReportEvent::YlemInstallationSuccess { version });
+    });
 }
 
 #[allow(unused)]
 pub(crate) fn ylem_installation_error(version: &Version, error: &str) {
-    get_default(|r| r.reporter.on_ylem_installation_error(version, error));
+    get_default(|r| {
+        r.reporter.on_ylem_installation_error(version, error);
+        r.reporter.on_event(&ReportEvent::YlemInstallationError { version, error });
+    });
 }
 
 pub(crate) fn unresolved_imports(imports: &[(&Path, &Path)], remappings: &[Remapping]) {
@@ -373,6 +460,9 @@ impl Reporter for BasicStdoutReporter {
             "Ylem {}.{}.{} finished in {duration:.2?}",
             version.major, version.minor, version.patch
         );
+        for warnings in &output.raw_warnings {
+            println!("{warnings}");
+        }
     }
 
     /// Invoked before a new [`Ylem`] bin is installed
@@ -518,6 +608,61 @@ mod tests {
         get_default(|reporter| assert!(reporter.is::<BasicStdoutReporter>()))
     }
 
+    #[test]
+    fn reports_structured_events() {
+        #[derive(Debug, Default)]
+        struct EventReporter(std::sync::Mutex<Vec<String>>);
+        impl Reporter for EventReporter {
+            fn on_event(&self, event: &ReportEvent<'_>) {
+                let event = match event {
+                    ReportEvent::CacheHit { files, .. } => format!("cached {}", files.len()),
+                    ReportEvent::FileCompiled { file, .. } => {
+                        format!("compiled {}", file.display())
+                    }
+                    ReportEvent::YlemInstallationProgress { downloaded, total, .. } => {
+                        format!("downloaded {downloaded}/{}", total.unwrap_or_default())
+                    }
+                    event => format!("{event:?}"),
+                };
+                self.0.lock().unwrap().push(event);
+            }
+        }
+
+        let report = Report::new(EventReporter::default());
+        let version = Version::new(1, 1, 2);
+        let files = [PathBuf::from("src/A.sol"), PathBuf::from("src/B.sol")];
+        with_scoped(&report, || {
+            compile_started(&files);
+            cache_hit(&version, &files[..1]);
+            cache_hit(&version, &[]);
+            ylem_installation_progress(&version, 512, Some(1024));
+            files_compiled(&version, &files[1..], &Duration::from_millis(20));
+            let output = CompilerOutput {
+                raw_warnings: vec!["Warning: unreachable code".to_string()],
+                ..Default::default()
+            };
+            ylem_success(&Ylem::new("ylem"), &version, &output, &Duration::from_millis(20));
+        });
+
+        let reporter = <dyn Reporter>::downcast_ref::<EventReporter>(&*report.reporter).unwrap();
+        assert_eq!(
+            *reporter.0.lock().unwrap(),
+            [
+                format!("{:?}", ReportEvent::CompileStarted { dirty_files: &files }),
+                "cached 1".to_string(),
+                "downloaded 512/1024".to_string(),
+                "compiled src/B.sol".to_string(),
+                format!(
+                    "{:?}",
+                    ReportEvent::YlemWarnings {
+                        version: &version,
+                        warnings: "Warning: unreachable code"
+                    }
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_unresolved_message() {
         let unresolved = vec![(Path::new("./src/Import.sol"), Path::new("src/File.col"))];
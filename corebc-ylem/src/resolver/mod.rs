@@ -159,6 +159,11 @@ impl GraphEdges {
         &self.rev_indices[&id]
     }
 
+    /// Returns true if the `file` is part of the graph, either as input file or as resolved import
+    pub fn contains(&self, file: impl AsRef<Path>) -> bool {
+        self.indices.contains_key(file.as_ref())
+    }
+
     /// Returns true if the `file` was originally included when the graph was first created and not
     /// added when all `imports` were resolved
     pub fn is_input_file(&self, file: impl AsRef<Path>) -> bool {
@@ -447,6 +452,9 @@ impl Graph {
     /// First we determine the compatible version for each input file (from sources and test folder,
     /// see `Self::resolve`) and then we add all resolved library imports.
     pub fn into_sources_by_version(self, offline: bool) -> Result<(VersionedSources, GraphEdges)> {
+        // the process wide yvm configuration can enable offline mode as well, e.g. in CI
+        let offline = offline || crate::Ylem::yvm_config().offline;
+
         /// insert the imports of the given node into the sources map
         /// There can be following graph:
         /// `A(<=0.8.10) imports C(>0.4.0)` and `B(0.8.11) imports C(>0.4.0)`
@@ -795,6 +803,25 @@ impl VersionedSources {
 
         let mut sources_by_version = std::collections::BTreeMap::new();
         for (version, sources) in self.inner {
+            if let crate::CompilerBackend::Docker(docker) = &project.compiler_backend {
+                // nothing to install or verify on the host, the image provides ylem
+                let version = semver::Version::from(version);
+                let mounts = project
+                    .allowed_paths
+                    .paths()
+                    .chain(project.include_paths.paths())
+                    .chain(self.resolved_ylem_include_paths.paths())
+                    .map(PathBuf::as_path)
+                    .collect::<std::collections::BTreeSet<_>>();
+                let ylem = project.configure_ylem_with_version(
+                    docker.ylem(&version, mounts),
+                    Some(version.clone()),
+                    self.resolved_ylem_include_paths.clone(),
+                );
+                sources_by_version.insert(ylem, (version, sources));
+                continue
+            }
+
             let ylem = if !version.is_installed() {
                 if self.offline {
                     return Err(YlemError::msg(format!(
@@ -818,10 +845,21 @@ impl VersionedSources {
                 );
             } else {
                 tracing::trace!("verifying ylem checksum for {}", ylem.ylem.display());
-                if ylem.verify_checksum().is_err() {
-                    tracing::trace!("corrupted ylem version, redownloading  \"{}\"", version);
-                    Ylem::blocking_install(version.as_ref())?;
-                    tracing::trace!("reinstalled ylem: \"{}\"", version);
+                match ylem.verify_checksum() {
+                    Ok(()) => {}
+                    Err(YlemError::ChecksumNotFound(version)) => {
+                        tracing::warn!(
+                            "no published checksum for ylem {}, skipping verification",
+                            version
+                        );
+                    }
+                    // redownloading can't be verified either
+                    Err(err @ YlemError::ReleasesUnavailable(_)) => return Err(err),
+                    Err(_) => {
+                        tracing::trace!("corrupted ylem version, redownloading  \"{}\"", version);
+                        Ylem::blocking_install(version.as_ref())?;
+                        tracing::trace!("reinstalled ylem: \"{}\"", version);
+                    }
                 }
             }
 
@@ -0,0 +1,177 @@
+//! End-to-end verification of deployed contracts against a block explorer
+//!
+//! See [`Project::verify_contract`](crate::Project::verify_contract)
+
+use crate::{
+    artifacts::{BytecodeMetadata, StandardJsonCompilerInput},
+    Artifact, ArtifactId, ArtifactOutput, Project, YlemError,
+};
+use corebc_core::types::{Address, Bytes};
+use path_slash::PathExt;
+use semver::Version;
+use thiserror::Error;
+
+pub use futures_util::future::BoxFuture;
+
+/// A block explorer that can verify source code, e.g. Blockindex
+pub trait ExplorerClient {
+    /// The error returned by the explorer
+    type Error: std::error::Error;
+
+    /// Returns the runtime bytecode deployed at `address`
+    fn deployed_code(&self, address: Address) -> BoxFuture<'_, Result<Bytes, Self::Error>>;
+
+    /// Submits the `request` for verification and returns the receipt (GUID) of the submission
+    fn submit_verification(
+        &self,
+        request: VerificationRequest,
+    ) -> BoxFuture<'_, Result<String, Self::Error>>;
+}
+
+/// Everything an explorer needs to verify a contract from its standard-json input
+#[derive(Clone, Debug)]
+pub struct VerificationRequest {
+    /// The address of the deployed contract
+    pub address: Address,
+    /// The `<path>:<name>` identifier of the contract in the standard-json input
+    pub contract_name: String,
+    /// The version of ylem the contract was compiled with
+    pub compiler_version: Version,
+    /// The standard-json input that reproduces the deployed bytecode
+    pub input: StandardJsonCompilerInput,
+}
+
+/// The result of a successful [`Project::verify_contract`] call
+#[derive(Clone, Debug)]
+pub struct VerificationReceipt {
+    /// The artifact that matched the deployed bytecode
+    pub artifact: ArtifactId,
+    /// The receipt (GUID) returned by the explorer, used to check the verification status
+    pub guid: String,
+}
+
+/// The step of the verification workflow that failed
+#[derive(Debug, Error)]
+pub enum VerifyError<E> {
+    /// The explorer failed to return the deployed code
+    #[error("failed to fetch the code deployed at {0:?}: {1}")]
+    FetchCode(Address, E),
+    /// There is no contract at the address
+    #[error("no contract is deployed at {0:?}")]
+    NoCode(Address),
+    /// The project failed to compile
+    #[error("failed to compile the project: {0}")]
+    Compile(YlemError),
+    /// None of the compiled artifacts matches the deployed code
+    #[error("no artifact matches the code deployed at {0:?}")]
+    NoMatchingArtifact(Address),
+    /// The standard-json input for the matching artifact could not be generated
+    #[error("failed to generate the standard-json input for {0}: {1}")]
+    StandardJsonInput(String, YlemError),
+    /// The explorer rejected the verification request
+    #[error("failed to submit the verification of {0}: {1}")]
+    Submit(String, E),
+}
+
+/// Returns true if both bytecodes are identical once their metadata trailers are stripped.
+///
+/// The metadata hash changes with anything that affects the metadata file, like comments or the
+/// absolute paths of the sources, without changing the executable code.
+pub fn bytecode_matches_ignoring_metadata(deployed: &[u8], compiled: &[u8]) -> bool {
+    BytecodeMetadata::strip(deployed) == BytecodeMetadata::strip(compiled)
+}
+
+impl<T: ArtifactOutput + Clone + Send + 'static> Project<T> {
+    /// Verifies the contract deployed at `address` on the block explorer of `client`.
+    ///
+    /// This compiles the project on a blocking thread, looks for the artifact whose deployed
+    /// bytecode matches the code at `address` (ignoring the metadata hash), regenerates the
+    /// standard-json input of its source file and submits it to the explorer. Each step reports
+    /// its own [`VerifyError`].
+    ///
+    /// Note that contracts with immutables are not matched, as their deployed code differs from
+    /// the compiled one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use corebc_ylem::{verify::ExplorerClient, Project};
+    /// # async fn demo<C: ExplorerClient>(client: C) -> Result<(), Box<dyn std::error::Error>>
+    /// # where C::Error: 'static {
+    /// let project = Project::builder().build()?;
+    /// let address = "cb270000000000000000000000000000000000000000".parse()?;
+    /// let receipt = project.verify_contract(address, &client).await?;
+    /// println!("verifying {} with receipt {}", receipt.artifact.identifier(), receipt.guid);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn verify_contract<C: ExplorerClient>(
+        &self,
+        address: Address,
+        client: &C,
+    ) -> Result<VerificationReceipt, VerifyError<C::Error>> {
+        let deployed = client
+            .deployed_code(address)
+            .await
+            .map_err(|err| VerifyError::FetchCode(address, err))?;
+        if deployed.is_empty() {
+            return Err(VerifyError::NoCode(address))
+        }
+
+        // compiling blocks on the ylem processes, keep it off the async runtime
+        let project = self.clone();
+        let output = tokio::task::spawn_blocking(move || project.compile())
+            .await
+            .map_err(|err| VerifyError::Compile(YlemError::msg(err.to_string())))?
+            .map_err(VerifyError::Compile)?;
+        if output.has_compiler_errors() {
+            return Err(VerifyError::Compile(YlemError::msg(output.to_string())))
+        }
+
+        let (artifact, _) = output
+            .into_artifacts()
+            .find(|(_, artifact)| {
+                artifact
+                    .get_deployed_bytecode_bytes()
+                    .map_or(false, |code| bytecode_matches_ignoring_metadata(&deployed, &code))
+            })
+            .ok_or(VerifyError::NoMatchingArtifact(address))?;
+        tracing::trace!("code at {:?} matches {}", address, artifact.identifier());
+
+        let input = self
+            .standard_json_input_for_version(&artifact.source, &artifact.version)
+            .map_err(|err| VerifyError::StandardJsonInput(artifact.identifier(), err))?;
+
+        // the standard-json input identifies sources relative to the project root
+        let source = artifact.source.strip_prefix(self.root()).unwrap_or(&artifact.source);
+        let request = VerificationRequest {
+            address,
+            contract_name: format!("{}:{}", source.to_slash_lossy(), artifact.name),
+            compiler_version: artifact.version.clone(),
+            input,
+        };
+
+        let guid = client
+            .submit_verification(request)
+            .await
+            .map_err(|err| VerifyError::Submit(artifact.identifier(), err))?;
+
+        Ok(VerificationReceipt { artifact, guid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_metadata_when_matching() {
+        let trailer = |hash: &str| format!("a165627a7a72305820{}0029", hash.repeat(32));
+        let deployed = hex::decode(format!("6080604052{}", trailer("aa"))).unwrap();
+        let compiled = hex::decode(format!("6080604052{}", trailer("bb"))).unwrap();
+        let other = hex::decode(format!("6080604053{}", trailer("aa"))).unwrap();
+
+        assert!(bytecode_matches_ignoring_metadata(&deployed, &compiled));
+        assert!(!bytecode_matches_ignoring_metadata(&deployed, &other));
+    }
+}
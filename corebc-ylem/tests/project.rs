@@ -1,10 +1,10 @@
 //! project tests
 
-use corebc_core::types::Address;
+use corebc_core::types::{Address, Network};
 use corebc_ylem::{
     artifacts::{
         BytecodeHash, DevDoc, ErrorDoc, EventDoc, Libraries, MethodDoc, ModelCheckerEngine::CHC,
-        ModelCheckerSettings, UserDoc, UserDocNotice,
+        ModelCheckerSettings, StandardJsonCompilerInput, UserDoc, UserDocNotice,
     },
     buildinfo::BuildInfo,
     cache::{SolFilesCache, SOLIDITY_FILES_CACHE_FILENAME},
@@ -12,8 +12,8 @@ use corebc_ylem::{
     info::ContractInfo,
     project_util::*,
     remappings::Remapping,
-    Artifact, CompilerInput, ConfigurableArtifacts, ExtraOutputValues, Graph, Project,
-    ProjectCompileOutput, ProjectPathsConfig, TestFileFilter, Ylem,
+    Artifact, ArtifactProfile, CompilerInput, ConfigurableArtifacts, ExtraOutputValues, Graph,
+    Project, ProjectCompileOutput, ProjectPathsConfig, TestFileFilter, Ylem,
 };
 use pretty_assertions::assert_eq;
 use semver::Version;
@@ -130,6 +130,52 @@ fn can_compile_configured() {
     assert!(artifact.opcodes.is_some());
 }
 
+#[test]
+fn can_compile_with_artifact_profile() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/dapp-sample");
+    let paths = ProjectPathsConfig::builder().sources(root.join("src")).lib(root.join("lib"));
+
+    let handler = ConfigurableArtifacts::default().with_profile(ArtifactProfile::AbiOnly);
+    let mut project = TempProject::with_artifacts(paths, handler).unwrap();
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    let artifact = compiled.find_first("Dapp").unwrap();
+    assert!(artifact.abi.is_some());
+    assert!(artifact.bytecode.is_none());
+    assert!(artifact.ast.is_none());
+    project.assert_no_errors_recompile_unchanged();
+
+    // changing the profile rewrites the artifacts
+    project.project_mut().artifacts = ConfigurableArtifacts::default();
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.is_unchanged());
+    let artifact = compiled.find_first("Dapp").unwrap();
+    assert!(artifact.bytecode.is_some());
+    assert!(artifact.method_identifiers.is_some());
+}
+
+#[test]
+fn can_report_contract_sizes() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/dapp-sample");
+    let paths = ProjectPathsConfig::builder().sources(root.join("src")).lib(root.join("lib"));
+    let mut project = TempProject::<ConfigurableArtifacts>::new(paths).unwrap();
+
+    let compiled = project.compile().unwrap();
+    let report = compiled.size_report();
+    assert_eq!(report.limit, Network::Mainnet.max_code_size());
+    assert!(!report.has_oversized());
+    let dapp = report.get("Dapp").unwrap();
+    assert!(dapp.runtime_size > 0);
+    assert!(dapp.init_size > dapp.runtime_size);
+
+    // a limit below the size of `Dapp` fails the build
+    project.project_mut().code_size_limit = Some(dapp.runtime_size - 1);
+    project.project_mut().deny_oversized_contracts = true;
+    let compiled = project.compile().unwrap();
+    assert!(compiled.has_compiler_errors());
+    assert!(compiled.size_report().oversized().any(|c| c.name == "Dapp"));
+}
+
 #[test]
 fn can_compile_dapp_detect_changes_in_libs() {
     let mut project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
@@ -958,6 +1004,11 @@ fn can_detect_type_error() {
 
     let compiled = project.compile().unwrap();
     assert!(compiled.has_compiler_errors());
+
+    let diagnostics = compiled.diagnostics();
+    let snippet = diagnostics.failures().find_map(|d| d.snippet.as_deref()).unwrap();
+    assert!(snippet.contains(r#"require(address(0), "Error");"#));
+    assert!(snippet.contains("^^^^^^^^^^"));
 }
 
 #[test]
@@ -996,6 +1047,56 @@ fn can_compile_single_files() {
     assert!(compiled.find_first("Bar").is_some());
 }
 
+#[test]
+fn compile_file_only_compiles_dependency_closure() {
+    let tmp = TempProject::dapptools().unwrap();
+
+    let a = tmp
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^1.1.0;
+    import "./B.sol";
+    contract A is B {}
+   "#,
+        )
+        .unwrap();
+    tmp.add_source(
+        "B",
+        r#"
+    pragma solidity ^1.1.0;
+    contract B {}
+   "#,
+    )
+    .unwrap();
+    tmp.add_source(
+        "C",
+        r#"
+    pragma solidity ^1.1.0;
+    contract C {}
+   "#,
+    )
+    .unwrap();
+
+    let compiled = tmp.project().compile_file(a.clone()).unwrap();
+    assert!(!compiled.has_compiler_errors());
+    assert!(compiled.find_first("A").is_some());
+    assert!(compiled.find_first("B").is_some());
+    assert!(compiled.find_first("C").is_none());
+
+    let compiled = tmp.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    assert!(compiled.find_first("C").is_some());
+
+    // compiling a single file does not invalidate the cache of the rest of the project
+    let compiled = tmp.project().compile_file(a).unwrap();
+    assert!(compiled.is_unchanged());
+    assert!(compiled.find_first("C").is_none());
+    let compiled = tmp.compile().unwrap();
+    assert!(compiled.is_unchanged());
+    assert!(compiled.find_first("C").is_some());
+}
+
 #[test]
 fn consistent_bytecode() {
     let tmp = TempProject::dapptools().unwrap();
@@ -1032,6 +1133,53 @@ contract LinkTest {
     assert_eq!(bytecode.clone(), serde_json::from_str(&s).unwrap());
 }
 
+#[test]
+fn can_link_libraries_after_compile() {
+    let tmp = TempProject::dapptools().unwrap();
+
+    tmp.add_source(
+        "LinkTest",
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^1.1.0;
+import "./MyLib.sol";
+contract LinkTest {
+    function foo() public returns (uint256) {
+        return MyLib.foobar(1);
+    }
+}
+"#,
+    )
+    .unwrap();
+    tmp.add_source(
+        "MyLib",
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^1.1.0;
+library MyLib {
+    function foobar(uint256 a) public view returns (uint256) {
+    	return a * 100;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    // the library is missing
+    let err = tmp.project().link_libraries(&BTreeMap::new()).unwrap_err();
+    assert!(err.to_string().contains("MyLib.sol:MyLib"), "{err}");
+
+    let address: Address = "cb270000000000000000000000000000000000000001".parse().unwrap();
+    let contracts =
+        tmp.project().link_libraries(&BTreeMap::from([("MyLib".to_string(), address)])).unwrap();
+    let (_, contract) = contracts.iter().find(|(id, _)| id.name == "LinkTest").unwrap();
+    let bytecode = contract.bytecode.as_ref().unwrap();
+    assert!(!bytecode.object.is_unlinked());
+    assert!(bytecode.unresolved_libraries().is_empty());
+    let code = hex::encode(bytecode.object.as_bytes().unwrap());
+    assert!(code.contains(&hex::encode(address)));
+}
+
 #[test]
 fn can_apply_libraries() {
     let mut tmp = TempProject::dapptools().unwrap();
@@ -1581,6 +1729,36 @@ fn can_compile_std_json_input() {
     }
 }
 
+#[test]
+fn can_archive_std_json_input() {
+    let tmp = TempProject::dapptools_init().unwrap();
+    let source = tmp.list_source_files().into_iter().find(|p| p.ends_with("Dapp.t.sol")).unwrap();
+    let version = Version::new(1, 1, 2);
+    let input = tmp.project().standard_json_input_for_version(&source, &version).unwrap();
+
+    let path = tmp.root().join("verification").join("Dapp.t.json");
+    input.write(&path).unwrap();
+    let archived = StandardJsonCompilerInput::read(&path).unwrap();
+    assert_eq!(archived.settings, input.settings);
+    assert_eq!(
+        archived.sources.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        input.sources.iter().map(|(path, _)| path).collect::<Vec<_>>()
+    );
+
+    // should be installed
+    if let Some(ylem) = Ylem::find_yvm_installed_version("1.1.2").ok().flatten() {
+        let compiled = tmp.compile().unwrap();
+        let artifact = compiled.find_first("DappTest").unwrap().clone();
+        let out = ylem.compile(&CompilerInput::from(archived)).unwrap();
+        assert!(!out.has_error());
+        let contract = &out.contracts["src/Dapp.t.sol"]["DappTest"];
+        assert_eq!(
+            contract.get_deployed_bytecode_bytes().unwrap(),
+            artifact.get_deployed_bytecode_bytes().unwrap()
+        );
+    }
+}
+
 #[test]
 fn can_compile_model_checker_sample() {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/model-checker-sample");
@@ -1635,6 +1813,39 @@ fn test_compiler_severity_filter() {
     assert!(compiled.has_compiler_errors());
 }
 
+#[test]
+fn can_deny_warnings() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test-data/test-contract-warnings/LicenseWarning.sol");
+    let paths = ProjectPathsConfig::builder().sources(root).build().unwrap();
+    let missing_license_error_code = 1878;
+
+    let project = Project::builder()
+        .no_artifacts()
+        .paths(paths.clone())
+        .ephemeral()
+        .deny_warnings()
+        .build()
+        .unwrap();
+    let compiled = project.compile().unwrap();
+    assert!(compiled.has_compiler_errors());
+    let diagnostics = compiled.diagnostics();
+    assert!(diagnostics.is_failure());
+    assert!(diagnostics.has_any_code(&[missing_license_error_code]));
+
+    let project = Project::builder()
+        .no_artifacts()
+        .paths(paths)
+        .ephemeral()
+        .deny_warnings()
+        .ignore_error_code(missing_license_error_code)
+        .build()
+        .unwrap();
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    assert!(!compiled.diagnostics().is_failure());
+}
+
 #[test]
 fn test_compiler_severity_filter_and_ignored_error_codes() {
     fn gen_test_data_licensing_warning() -> ProjectPathsConfig {
@@ -1,5 +1,5 @@
 use corebc::{
-    core::types::{Address, Filter, H176, H256, U256},
+    core::types::{Address, AddressExt, Filter, H256, U256},
     providers::{Http, Middleware, Provider},
 };
 use eyre::Result;
@@ -20,9 +20,9 @@ async fn main() -> Result<()> {
     let provider = Provider::<Http>::try_from(HTTP_URL)?;
     let client = Arc::new(provider);
     let token_topics = vec![
-        H256::from(USDC_ADDRESS.parse::<H176>()?),
-        H256::from(USDT_ADDRESS.parse::<H176>()?),
-        H256::from(DAI_ADDRESS.parse::<H176>()?),
+        USDC_ADDRESS.parse::<Address>()?.to_h256(),
+        USDT_ADDRESS.parse::<Address>()?.to_h256(),
+        DAI_ADDRESS.parse::<Address>()?.to_h256(),
     ];
     let filter = Filter::new()
         .address(V3FACTORY_ADDRESS.parse::<Address>()?)
@@ -33,11 +33,11 @@ async fn main() -> Result<()> {
     let logs = client.get_logs(&filter).await?;
     println!("{} pools found!", logs.iter().len());
     for log in logs.iter() {
-        let token0 = Address::from(log.topics[1]);
-        let token1 = Address::from(log.topics[2]);
+        let token0 = Address::try_from_h256(log.topics[1])?;
+        let token1 = Address::try_from_h256(log.topics[2])?;
         let fee_tier = U256::from_big_endian(&log.topics[3].as_bytes()[29..32]);
         let tick_spacing = U256::from_big_endian(&log.data[29..32]);
-        let pool = Address::from(&log.data[44..64].try_into()?);
+        let pool = Address::try_from_h256(H256::from_slice(&log.data[32..64]))?;
         println!(
             "pool = {pool}, token0 = {token0}, token1 = {token1}, fee = {fee_tier}, spacing = {tick_spacing}"
         );
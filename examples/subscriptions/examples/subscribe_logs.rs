@@ -1,7 +1,7 @@
 use corebc::{
     core::{
         abi::AbiDecode,
-        types::{Address, BlockNumber, Filter, U256},
+        types::{Address, AddressExt, BlockNumber, Filter, U256},
     },
     providers::{Middleware, Provider, StreamExt, Ws},
 };
@@ -29,8 +29,8 @@ async fn main() -> Result<()> {
             log.block_number,
             log.transaction_hash,
             log.address,
-            Address::from(log.topics[1]),
-            Address::from(log.topics[2]),
+            Address::try_from_h256(log.topics[1])?,
+            Address::try_from_h256(log.topics[2])?,
             U256::decode(log.data)
         );
     }